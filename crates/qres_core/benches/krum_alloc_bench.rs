@@ -0,0 +1,77 @@
+//! Krum Allocation Benchmarks
+//!
+//! Compares `aggregate_updates` in Krum mode, which scores candidates from a
+//! flat `n*n` distance buffer with one reused scratch vector, against the
+//! former nested `Vec<Vec<f32>>` implementation that allocated a row and a
+//! neighbour list per update. Both sides run the whole aggregation (distances,
+//! scoring, selection, averaging); `aggregate_updates` additionally fills its
+//! O(n*d) dispersion diagnostics. Heap allocation counts per call are printed
+//! alongside the timings.
+
+// The bench only times the reference; the test is what reads its fields.
+#[allow(dead_code)]
+#[path = "../tests/common/krum_reference.rs"]
+mod krum_reference;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use krum_reference::{generate_updates, nested_krum};
+use qres_core::aggregation::{aggregate_updates, AggregationMode};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// System allocator wrapper counting every allocation
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn count_allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn bench_krum_distances(c: &mut Criterion) {
+    let mut group = c.benchmark_group("krum_distances");
+    group.measurement_time(Duration::from_secs(3));
+
+    let dim = 16;
+    for n in [50usize, 200, 500] {
+        let updates = generate_updates(n, dim);
+        let q = n / 10;
+        let mode = AggregationMode::Krum { expected_byz: q };
+
+        println!(
+            "krum n={n}: flat {} allocations, nested {} allocations",
+            count_allocations(|| aggregate_updates(&updates, &mode)),
+            count_allocations(|| nested_krum(&updates, q, 1)),
+        );
+
+        group.bench_with_input(BenchmarkId::new("flat", n), &updates, |b, updates| {
+            b.iter(|| aggregate_updates(black_box(updates), &mode));
+        });
+
+        group.bench_with_input(BenchmarkId::new("nested", n), &updates, |b, updates| {
+            b.iter(|| nested_krum(black_box(updates), q, 1));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_krum_distances);
+criterion_main!(benches);
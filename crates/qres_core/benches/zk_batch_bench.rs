@@ -0,0 +1,54 @@
+//! Norm Proof Batch Verification Benchmarks
+//!
+//! Compares `ZkNormProver::verify_batch` (one multiscalar multiplication)
+//! against verifying each `ProofBundle` with `verify_proof` in turn.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use qres_core::zk_proofs::{ProofBundle, ZkNormProver};
+use std::time::Duration;
+
+const THRESHOLD_SQ: f32 = 1.0;
+
+/// Generate `n` valid proof bundles over small synthetic updates
+fn generate_bundles(prover: &ZkNormProver, n: usize) -> Vec<ProofBundle> {
+    (0..n)
+        .map(|i| {
+            let weights: Vec<f32> = (0..8)
+                .map(|j| ((i * 8 + j) as f32 * 0.01).sin() * 0.2)
+                .collect();
+            let (zk_proof, _) = prover.generate_proof(&weights, THRESHOLD_SQ).unwrap();
+            ProofBundle {
+                peer_id: [i as u8; 32],
+                masked_weights: weights,
+                zk_proof,
+            }
+        })
+        .collect()
+}
+
+fn bench_norm_proof_verification(c: &mut Criterion) {
+    let mut group = c.benchmark_group("norm_proof_verify");
+    group.measurement_time(Duration::from_secs(3));
+
+    let prover = ZkNormProver::new();
+    for n in [8usize, 32, 128, 512] {
+        let bundles = generate_bundles(&prover, n);
+
+        group.bench_with_input(BenchmarkId::new("sequential", n), &bundles, |b, bundles| {
+            b.iter(|| {
+                bundles
+                    .iter()
+                    .all(|bundle| prover.verify_proof(black_box(&bundle.zk_proof), THRESHOLD_SQ))
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("batch", n), &bundles, |b, bundles| {
+            b.iter(|| prover.verify_batch(black_box(bundles), THRESHOLD_SQ));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_norm_proof_verification);
+criterion_main!(benches);
@@ -1,7 +1,15 @@
 use alloc::vec;
 use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Window over which regime transitions are counted for flapping detection
+const FLAP_WINDOW_MS: u64 = 60_000;
+/// Default transitions per minute above which the regime counts as flapping
+const DEFAULT_MAX_TRANSITIONS_PER_MINUTE: usize = 6;
+/// Flapping can raise hysteresis to at most this multiple of the configured rounds
+const FLAP_MAX_HYSTERESIS_MULTIPLIER: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Regime {
     Calm,
     /// Pre-Storm: entropy derivative exceeds threshold, preemptively
@@ -42,6 +50,8 @@ pub struct RegimeConsensusConfig {
     pub min_vote_reputation: f32,
     /// Maximum age of a vote (in rounds) before it expires
     pub vote_window_rounds: u64,
+    /// Minimum summed reputation of trusted votes for the weighted gate
+    pub min_trusted_weight: f32,
 }
 
 impl Default for RegimeConsensusConfig {
@@ -50,6 +60,7 @@ impl Default for RegimeConsensusConfig {
             min_trusted_confirmations: 3,
             min_vote_reputation: 0.8,
             vote_window_rounds: 10,
+            min_trusted_weight: 2.7,
         }
     }
 }
@@ -82,28 +93,44 @@ impl RegimeConsensusGate {
         }
     }
 
-    /// Evaluate whether Storm transition is authorized.
-    /// Returns true only if enough high-reputation nodes confirm the entropy spike.
-    pub fn is_storm_authorized(
+    /// Recent trusted votes that observed a genuine spike.
+    fn trusted_votes(
         &self,
         current_round: u64,
         entropy_derivative_threshold: f32,
-    ) -> bool {
-        let trusted_confirmations = self
-            .votes
-            .iter()
-            .filter(|v| {
-                // Vote must be recent (within window)
-                let age = current_round.saturating_sub(v.round);
-                age <= self.config.vote_window_rounds
+    ) -> impl Iterator<Item = &RegimeVote> {
+        self.votes.iter().filter(move |v| {
+            // Vote must be recent (within window)
+            let age = current_round.saturating_sub(v.round);
+            age <= self.config.vote_window_rounds
             // Reporter must be high-reputation
             && v.reputation >= self.config.min_vote_reputation
             // Reporter must have observed a genuine spike
             && v.entropy_derivative > entropy_derivative_threshold
-            })
-            .count();
+        })
+    }
 
-        trusted_confirmations >= self.config.min_trusted_confirmations
+    /// Evaluate whether Storm transition is authorized.
+    /// Returns true only if enough high-reputation nodes confirm the entropy spike.
+    pub fn is_storm_authorized(
+        &self,
+        current_round: u64,
+        entropy_derivative_threshold: f32,
+    ) -> bool {
+        self.trusted_vote_count(current_round, entropy_derivative_threshold)
+            >= self.config.min_trusted_confirmations
+    }
+
+    /// Weighted variant of `is_storm_authorized`: the reputations of trusted
+    /// votes are summed and must reach `min_trusted_weight`, so barely-trusted
+    /// voters contribute less than highly trusted ones.
+    pub fn is_storm_authorized_weighted(
+        &self,
+        current_round: u64,
+        entropy_derivative_threshold: f32,
+    ) -> bool {
+        self.trusted_weight(current_round, entropy_derivative_threshold)
+            >= self.config.min_trusted_weight
     }
 
     /// Prune expired votes to prevent unbounded memory growth.
@@ -118,16 +145,16 @@ impl RegimeConsensusGate {
         current_round: u64,
         entropy_derivative_threshold: f32,
     ) -> usize {
-        self.votes
-            .iter()
-            .filter(|v| {
-                let age = current_round.saturating_sub(v.round);
-                age <= self.config.vote_window_rounds
-                    && v.reputation >= self.config.min_vote_reputation
-                    && v.entropy_derivative > entropy_derivative_threshold
-            })
+        self.trusted_votes(current_round, entropy_derivative_threshold)
             .count()
     }
+
+    /// Summed reputation of currently valid trusted votes.
+    pub fn trusted_weight(&self, current_round: u64, entropy_derivative_threshold: f32) -> f32 {
+        self.trusted_votes(current_round, entropy_derivative_threshold)
+            .map(|v| v.reputation)
+            .sum()
+    }
 }
 
 pub struct RegimeDetector {
@@ -185,6 +212,74 @@ pub struct RegimeDetector {
     transition_streak: usize,
     /// Pending regime (if in hysteresis window)
     pending_regime: Option<Regime>,
+
+    // --- Flapping Detection Fields ---
+    /// Hysteresis rounds configured by the operator (before flapping damping)
+    base_hysteresis_rounds: usize,
+    /// Transitions per minute above which the regime is considered flapping
+    max_transitions_per_minute: usize,
+    /// Timestamps (ms) of regime transitions within the last minute
+    recent_transitions_ms: Vec<u64>,
+    /// Whether hysteresis is currently raised to dampen flapping
+    flapping: bool,
+
+    /// Raw entropy above which Storm is entered immediately, skipping hysteresis
+    panic_threshold: Option<f32>,
+
+    // --- Regime Time Budget Fields ---
+    /// Milliseconds spent in Calm, PreStorm and Storm (indexed by `regime_slot`)
+    regime_time_ms: [u64; 3],
+    /// Timestamp of the previous update, for attributing elapsed time
+    last_regime_tick_ms: Option<u64>,
+}
+
+/// Serializable copy of a [`RegimeDetector`]'s full state.
+///
+/// Persisted alongside the brain so a restarted node resumes with its
+/// observation window, throughput timer and hysteresis streak intact instead
+/// of re-learning the regime from Calm.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegimeSnapshot {
+    pub window_size: usize,
+    pub history: Vec<f32>,
+    pub sum: f32,
+    pub sum_sq: f32,
+    pub idx: usize,
+    pub count: usize,
+    pub entropy_threshold: f32,
+    pub throughput_threshold: f32,
+    pub current_regime: Regime,
+    pub last_update_ms: u64,
+    pub accumulated_bytes: u64,
+    pub current_throughput: f32,
+    pub entropy_ma_buffer: [f32; 3],
+    pub entropy_ma_idx: usize,
+    pub entropy_ma_count: usize,
+    pub prev_smoothed_entropy: f32,
+    pub entropy_derivative_threshold: f32,
+    pub calm_observation_count: usize,
+    pub silence_variance_threshold: f32,
+    pub hysteresis_rounds: usize,
+    pub transition_streak: usize,
+    pub pending_regime: Option<Regime>,
+    #[serde(default)]
+    pub base_hysteresis_rounds: usize,
+    #[serde(default = "default_max_transitions_per_minute")]
+    pub max_transitions_per_minute: usize,
+    #[serde(default)]
+    pub recent_transitions_ms: Vec<u64>,
+    #[serde(default)]
+    pub flapping: bool,
+    #[serde(default)]
+    pub panic_threshold: Option<f32>,
+    #[serde(default)]
+    pub regime_time_ms: [u64; 3],
+    #[serde(default)]
+    pub last_regime_tick_ms: Option<u64>,
+}
+
+fn default_max_transitions_per_minute() -> usize {
+    DEFAULT_MAX_TRANSITIONS_PER_MINUTE
 }
 
 impl RegimeDetector {
@@ -215,6 +310,93 @@ impl RegimeDetector {
             hysteresis_rounds: 3, // Default: 3 consecutive confirmations
             transition_streak: 0,
             pending_regime: None,
+            base_hysteresis_rounds: 3,
+            max_transitions_per_minute: DEFAULT_MAX_TRANSITIONS_PER_MINUTE,
+            recent_transitions_ms: Vec::new(),
+            flapping: false,
+            panic_threshold: None,
+            regime_time_ms: [0; 3],
+            last_regime_tick_ms: None,
+        }
+    }
+
+    /// Capture the detector state for persistence.
+    pub fn to_snapshot(&self) -> RegimeSnapshot {
+        RegimeSnapshot {
+            window_size: self.window_size,
+            history: self.history.clone(),
+            sum: self.sum,
+            sum_sq: self.sum_sq,
+            idx: self.idx,
+            count: self.count,
+            entropy_threshold: self.entropy_threshold,
+            throughput_threshold: self.throughput_threshold,
+            current_regime: self.current_regime,
+            last_update_ms: self.last_update_ms,
+            accumulated_bytes: self.accumulated_bytes,
+            current_throughput: self.current_throughput,
+            entropy_ma_buffer: self.entropy_ma_buffer,
+            entropy_ma_idx: self.entropy_ma_idx,
+            entropy_ma_count: self.entropy_ma_count,
+            prev_smoothed_entropy: self.prev_smoothed_entropy,
+            entropy_derivative_threshold: self.entropy_derivative_threshold,
+            calm_observation_count: self.calm_observation_count,
+            silence_variance_threshold: self.silence_variance_threshold,
+            hysteresis_rounds: self.hysteresis_rounds,
+            transition_streak: self.transition_streak,
+            pending_regime: self.pending_regime,
+            base_hysteresis_rounds: self.base_hysteresis_rounds,
+            max_transitions_per_minute: self.max_transitions_per_minute,
+            recent_transitions_ms: self.recent_transitions_ms.clone(),
+            flapping: self.flapping,
+            panic_threshold: self.panic_threshold,
+            regime_time_ms: self.regime_time_ms,
+            last_regime_tick_ms: self.last_regime_tick_ms,
+        }
+    }
+
+    /// Restore a detector from a snapshot taken by [`to_snapshot`](Self::to_snapshot).
+    ///
+    /// The observation window is resized to `window_size` if the snapshot is
+    /// inconsistent, and the write index is clamped into it.
+    pub fn from_snapshot(snapshot: RegimeSnapshot) -> Self {
+        let window_size = snapshot.window_size.max(1);
+        let mut history = snapshot.history;
+        history.resize(window_size, 0.0);
+        Self {
+            window_size,
+            history,
+            sum: snapshot.sum,
+            sum_sq: snapshot.sum_sq,
+            idx: snapshot.idx % window_size,
+            count: snapshot.count,
+            entropy_threshold: snapshot.entropy_threshold,
+            throughput_threshold: snapshot.throughput_threshold,
+            current_regime: snapshot.current_regime,
+            last_update_ms: snapshot.last_update_ms,
+            accumulated_bytes: snapshot.accumulated_bytes,
+            current_throughput: snapshot.current_throughput,
+            entropy_ma_buffer: snapshot.entropy_ma_buffer,
+            entropy_ma_idx: snapshot.entropy_ma_idx % 3,
+            entropy_ma_count: snapshot.entropy_ma_count.min(3),
+            prev_smoothed_entropy: snapshot.prev_smoothed_entropy,
+            entropy_derivative_threshold: snapshot.entropy_derivative_threshold,
+            calm_observation_count: snapshot.calm_observation_count,
+            silence_variance_threshold: snapshot.silence_variance_threshold,
+            hysteresis_rounds: snapshot.hysteresis_rounds.max(1),
+            transition_streak: snapshot.transition_streak,
+            pending_regime: snapshot.pending_regime,
+            base_hysteresis_rounds: if snapshot.base_hysteresis_rounds == 0 {
+                snapshot.hysteresis_rounds.max(1)
+            } else {
+                snapshot.base_hysteresis_rounds
+            },
+            max_transitions_per_minute: snapshot.max_transitions_per_minute.max(1),
+            recent_transitions_ms: snapshot.recent_transitions_ms,
+            flapping: snapshot.flapping,
+            panic_threshold: snapshot.panic_threshold,
+            regime_time_ms: snapshot.regime_time_ms,
+            last_regime_tick_ms: snapshot.last_regime_tick_ms,
         }
     }
 
@@ -233,6 +415,80 @@ impl RegimeDetector {
     /// Default: 3 rounds (balanced)
     pub fn set_hysteresis_rounds(&mut self, rounds: usize) {
         self.hysteresis_rounds = rounds.max(1); // Minimum 1
+        self.base_hysteresis_rounds = self.hysteresis_rounds;
+    }
+
+    /// Set the raw entropy above which the detector jumps straight to Storm
+    /// in a single sample, bypassing the escalation streak (default: off).
+    ///
+    /// De-escalation still goes through the normal hysteresis.
+    pub fn set_panic_threshold(&mut self, threshold: Option<f32>) {
+        self.panic_threshold = threshold;
+    }
+
+    /// Hysteresis rounds currently in effect (raised while flapping).
+    pub fn hysteresis_rounds(&self) -> usize {
+        self.hysteresis_rounds
+    }
+
+    /// Set how many regime transitions per minute count as flapping (default: 6).
+    pub fn set_max_transitions_per_minute(&mut self, max: usize) {
+        self.max_transitions_per_minute = max.max(1);
+    }
+
+    /// Whether the regime is flapping and hysteresis has been raised to dampen it.
+    pub fn is_flapping(&self) -> bool {
+        self.flapping
+    }
+
+    /// Count transitions over the last minute and adjust hysteresis.
+    ///
+    /// Exceeding the configured rate doubles the effective hysteresis (up to
+    /// 4x the configured rounds) on every further transition. Once a full
+    /// minute passes without transitions, the configured rounds are restored.
+    fn track_transitions(&mut self, new_regime: Regime, now_ms: u64) {
+        self.recent_transitions_ms
+            .retain(|&t| now_ms.saturating_sub(t) < FLAP_WINDOW_MS);
+        if new_regime != self.current_regime {
+            self.recent_transitions_ms.push(now_ms);
+        }
+
+        if self.recent_transitions_ms.len() > self.max_transitions_per_minute {
+            if new_regime != self.current_regime {
+                let cap = self.base_hysteresis_rounds * FLAP_MAX_HYSTERESIS_MULTIPLIER;
+                self.hysteresis_rounds = (self.hysteresis_rounds * 2).min(cap);
+            }
+            if !self.flapping {
+                self.flapping = true;
+                #[cfg(feature = "std")]
+                eprintln!(
+                    "[RegimeDetector] Regime flapping: {} transitions in the last minute, raising hysteresis to {} rounds",
+                    self.recent_transitions_ms.len(),
+                    self.hysteresis_rounds
+                );
+            }
+        } else if self.flapping && self.recent_transitions_ms.is_empty() {
+            self.flapping = false;
+            self.hysteresis_rounds = self.base_hysteresis_rounds;
+        }
+    }
+
+    /// Attribute the time since the previous update to the regime in effect during it.
+    fn track_regime_time(&mut self, now_ms: u64) {
+        if let Some(last) = self.last_regime_tick_ms {
+            self.regime_time_ms[regime_slot(self.current_regime)] += now_ms.saturating_sub(last);
+        }
+        self.last_regime_tick_ms = Some(now_ms);
+    }
+
+    /// Milliseconds spent in `regime` since creation (or the last `reset`).
+    pub fn time_in_regime(&self, regime: Regime) -> u64 {
+        self.regime_time_ms[regime_slot(regime)]
+    }
+
+    /// Total milliseconds accounted across all regimes since creation (or the last `reset`).
+    pub fn total_tracked_ms(&self) -> u64 {
+        self.regime_time_ms.iter().sum()
     }
 
     /// Get current transition streak count (for debugging/monitoring)
@@ -305,7 +561,16 @@ impl RegimeDetector {
     /// * `entropy` - Current raw entropy value
     /// * `packet_size` - Size of the current packet in bytes
     /// * `now_ms` - Current system timestamp in milliseconds
-    pub fn update(&mut self, entropy: f32, packet_size: usize, now_ms: u64) {
+    ///
+    /// # Returns
+    /// `Some((old, new))` when this update confirmed a regime transition,
+    /// `None` otherwise (including while a transition is pending in hysteresis).
+    pub fn update(
+        &mut self,
+        entropy: f32,
+        packet_size: usize,
+        now_ms: u64,
+    ) -> Option<(Regime, Regime)> {
         // 1. Initialize timer on first run
         if self.last_update_ms == 0 {
             self.last_update_ms = now_ms;
@@ -349,7 +614,7 @@ impl RegimeDetector {
         };
 
         // 6. Apply Hysteresis to prevent regime jitter
-        let new_regime = self.apply_hysteresis(indicated_regime);
+        let new_regime = self.escalate(indicated_regime, entropy);
 
         // Update calm observation counter for Strategic Silence
         if new_regime == Regime::Calm {
@@ -358,7 +623,9 @@ impl RegimeDetector {
             self.calm_observation_count = 0; // Reset on any non-Calm state
         }
 
-        self.current_regime = new_regime;
+        self.track_regime_time(now_ms);
+        self.track_transitions(new_regime, now_ms);
+        self.set_regime(new_regime)
     }
 
     /// Observe a new residual (absolute error).
@@ -449,6 +716,17 @@ impl RegimeDetector {
         }
     }
 
+    /// Apply hysteresis, unless `entropy` crosses the panic threshold, in
+    /// which case Storm is entered immediately.
+    fn escalate(&mut self, indicated_regime: Regime, entropy: f32) -> Regime {
+        if self.panic_threshold.is_some_and(|panic| entropy > panic) {
+            self.pending_regime = None;
+            self.transition_streak = 0;
+            return Regime::Storm;
+        }
+        self.apply_hysteresis(indicated_regime)
+    }
+
     /// Get required confirmations for a specific regime transition.
     ///
     /// Asymmetric thresholds optimize battery life:
@@ -477,6 +755,8 @@ impl RegimeDetector {
     /// Same as `update()` but Storm transition requires authorization from
     /// the `RegimeConsensusGate`. If Storm is indicated by local entropy but
     /// the trusted quorum has not confirmed, the regime stays at PreStorm.
+    ///
+    /// Returns the `(old, new)` pair on a transition, like `update()`.
     pub fn update_with_consensus(
         &mut self,
         entropy: f32,
@@ -484,7 +764,7 @@ impl RegimeDetector {
         now_ms: u64,
         consensus_gate: &RegimeConsensusGate,
         current_round: u64,
-    ) {
+    ) -> Option<(Regime, Regime)> {
         // 1. Determine indicated regime (before hysteresis)
         if self.last_update_ms == 0 {
             self.last_update_ms = now_ms;
@@ -520,7 +800,7 @@ impl RegimeDetector {
         };
 
         // 2. Apply hysteresis
-        let hysteresis_regime = self.apply_hysteresis(indicated_regime);
+        let hysteresis_regime = self.escalate(indicated_regime, entropy);
 
         // 3. If hysteresis resulted in Storm, check consensus gate
         let final_regime = if hysteresis_regime == Regime::Storm
@@ -539,7 +819,15 @@ impl RegimeDetector {
             self.calm_observation_count = 0;
         }
 
-        self.current_regime = final_regime;
+        self.track_regime_time(now_ms);
+        self.track_transitions(final_regime, now_ms);
+        self.set_regime(final_regime)
+    }
+
+    /// Switch to `regime`, reporting the edge if it differs from the current one.
+    fn set_regime(&mut self, regime: Regime) -> Option<(Regime, Regime)> {
+        let old = core::mem::replace(&mut self.current_regime, regime);
+        (old != regime).then_some((old, regime))
     }
 
     pub fn reset(&mut self) {
@@ -561,6 +849,21 @@ impl RegimeDetector {
         self.prev_smoothed_entropy = 0.0;
         // Reset silence tracking
         self.calm_observation_count = 0;
+        // Reset flapping damping
+        self.recent_transitions_ms.clear();
+        self.flapping = false;
+        self.hysteresis_rounds = self.base_hysteresis_rounds;
+        // Reset regime time budgets
+        self.regime_time_ms = [0; 3];
+        self.last_regime_tick_ms = None;
+    }
+}
+
+fn regime_slot(regime: Regime) -> usize {
+    match regime {
+        Regime::Calm => 0,
+        Regime::PreStorm => 1,
+        Regime::Storm => 2,
     }
 }
 
@@ -647,6 +950,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_weighted_gate_requires_reputation_mass() {
+        let mut gate = RegimeConsensusGate::new(RegimeConsensusConfig::default());
+
+        // Three barely-trusted nodes: enough by count, not by weight (2.55 < 2.7)
+        for i in 0..3 {
+            gate.submit_vote(RegimeVote {
+                node_id: i,
+                round: 5,
+                entropy_derivative: 0.5,
+                reputation: 0.85,
+            });
+        }
+        assert!(gate.is_storm_authorized(5, 0.1));
+        assert!(!gate.is_storm_authorized_weighted(5, 0.1));
+
+        // A fourth trusted vote pushes the aggregate over the threshold
+        gate.submit_vote(RegimeVote {
+            node_id: 3,
+            round: 5,
+            entropy_derivative: 0.5,
+            reputation: 0.85,
+        });
+        assert!((gate.trusted_weight(5, 0.1) - 3.4).abs() < 1e-5);
+        assert!(gate.is_storm_authorized_weighted(5, 0.1));
+    }
+
     #[test]
     fn test_stale_votes_expire() {
         let config = RegimeConsensusConfig {
@@ -975,4 +1305,146 @@ mod tests {
             "Hysteresis should require at least 1 confirmation"
         );
     }
+
+    #[test]
+    fn test_snapshot_restores_regime_and_streak() {
+        let mut detector = RegimeDetector::new(100, 2.0, 10000.0);
+        detector.set_entropy_derivative_threshold(0.2);
+        for (i, entropy) in [0.1, 0.1, 0.1, 1.0, 1.0].iter().enumerate() {
+            detector.update(*entropy, 100, (i as u64 + 1) * 1000);
+        }
+        assert_eq!(detector.current_regime(), Regime::PreStorm);
+
+        // One Storm signal leaves a pending transition mid-streak
+        detector.update(3.0, 100, 6000);
+        assert_eq!(detector.pending_regime(), Some(Regime::Storm));
+        assert_eq!(detector.transition_streak(), 1);
+
+        let json = serde_json::to_string(&detector.to_snapshot()).unwrap();
+        let snapshot: RegimeSnapshot = serde_json::from_str(&json).unwrap();
+        let mut restored = RegimeDetector::from_snapshot(snapshot);
+
+        assert_eq!(restored.current_regime(), Regime::PreStorm);
+        assert_eq!(restored.pending_regime(), Some(Regime::Storm));
+        assert_eq!(restored.transition_streak(), 1);
+        assert_eq!(restored.smoothed_entropy(), detector.smoothed_entropy());
+
+        // Both continue identically from the restored state
+        detector.update(3.0, 100, 7000);
+        restored.update(3.0, 100, 7000);
+        assert_eq!(restored.to_snapshot(), detector.to_snapshot());
+    }
+
+    #[test]
+    fn test_flapping_raises_hysteresis() {
+        let mut detector = RegimeDetector::new(100, 2.0, 10000.0);
+        detector.set_hysteresis_rounds(1);
+
+        // Alternating spikes flip the regime on every update
+        let mut now = 0;
+        let mut transitions = 0;
+        for i in 0..8 {
+            now += 1000;
+            let before = detector.current_regime();
+            detector.update(if i % 2 == 0 { 3.0 } else { 0.1 }, 0, now);
+            if detector.current_regime() != before {
+                transitions += 1;
+            }
+        }
+        assert!(transitions > DEFAULT_MAX_TRANSITIONS_PER_MINUTE);
+        assert!(detector.is_flapping());
+        assert!(detector.hysteresis_rounds() > 1);
+
+        // Damped: the same pattern no longer flips on every update
+        let mut damped_transitions = 0;
+        for i in 0..8 {
+            now += 1000;
+            let before = detector.current_regime();
+            detector.update(if i % 2 == 0 { 3.0 } else { 0.1 }, 0, now);
+            if detector.current_regime() != before {
+                damped_transitions += 1;
+            }
+        }
+        assert_eq!(damped_transitions, 0);
+
+        // A quiet minute restores the configured hysteresis
+        for _ in 0..70 {
+            now += 1000;
+            detector.update(0.1, 0, now);
+        }
+        assert!(!detector.is_flapping());
+        assert_eq!(detector.hysteresis_rounds(), 1);
+    }
+
+    #[test]
+    fn test_panic_threshold_bypasses_escalation_streak() {
+        let mut detector = RegimeDetector::new(100, 2.0, 10000.0);
+        detector.set_panic_threshold(Some(10.0));
+
+        // A moderate spike still needs the normal streak
+        detector.update(3.0, 0, 1000);
+        assert_eq!(detector.current_regime(), Regime::Calm);
+        detector.update(0.1, 0, 2000);
+
+        // An extreme spike forces Storm in a single sample
+        detector.update(12.0, 0, 3000);
+        assert_eq!(detector.current_regime(), Regime::Storm);
+        assert_eq!(detector.transition_streak(), 0);
+
+        // De-escalation keeps the slow ramp-down
+        for t in 0..4 {
+            detector.update(0.1, 0, 4000 + t * 1000);
+            assert_eq!(detector.current_regime(), Regime::Storm);
+        }
+        detector.update(0.1, 0, 8000);
+        assert_eq!(detector.current_regime(), Regime::Calm);
+    }
+
+    #[test]
+    fn test_time_in_regime_accumulates_elapsed_millis() {
+        let mut detector = RegimeDetector::new(100, 2.0, 10000.0);
+        detector.set_hysteresis_rounds(1);
+
+        // Calm from t=1000 to t=4000
+        for t in [1000, 2000, 3000] {
+            detector.update(0.1, 0, t);
+        }
+        // Spike at t=4000 confirms Storm; it holds until t=6500
+        detector.update(3.0, 0, 4000);
+        assert_eq!(detector.current_regime(), Regime::Storm);
+        detector.update(3.0, 0, 5000);
+        detector.update(3.0, 0, 6500);
+
+        assert_eq!(detector.time_in_regime(Regime::Calm), 3000);
+        assert_eq!(detector.time_in_regime(Regime::Storm), 2500);
+        assert_eq!(detector.time_in_regime(Regime::PreStorm), 0);
+        assert_eq!(detector.total_tracked_ms(), 5500);
+
+        detector.reset();
+        assert_eq!(detector.total_tracked_ms(), 0);
+        detector.update(0.1, 0, 10_000);
+        assert_eq!(detector.total_tracked_ms(), 0);
+    }
+
+    #[test]
+    fn test_update_reports_each_transition_once() {
+        let mut detector = RegimeDetector::new(100, 2.0, 10000.0);
+
+        // Calm -> Storm needs three confirmations; only the last reports the edge
+        assert_eq!(detector.update(3.0, 0, 1000), None);
+        assert_eq!(detector.update(3.0, 0, 2000), None);
+        assert_eq!(
+            detector.update(3.0, 0, 3000),
+            Some((Regime::Calm, Regime::Storm))
+        );
+        // Staying in Storm is not an edge
+        assert_eq!(detector.update(3.0, 0, 4000), None);
+
+        // Storm -> Calm needs five confirmations
+        let edges: Vec<_> = (0..5)
+            .filter_map(|i| detector.update(0.1, 0, 5000 + i * 1000))
+            .collect();
+        assert_eq!(edges, vec![(Regime::Storm, Regime::Calm)]);
+        assert_eq!(detector.update(0.1, 0, 11_000), None);
+    }
 }
@@ -29,6 +29,11 @@ pub struct SilenceController {
     heartbeat_interval: u64,
     /// Efficiency bias: higher = more aggressive silence (default 1.0)
     efficiency_bias: f32,
+    /// Minimum `transition` ticks to stay in a state before leaving it
+    /// (Storm escalation always wins). 0 = no dwell.
+    min_dwell_ticks: u64,
+    /// `transition` ticks spent in the current state
+    ticks_in_state: u64,
 }
 
 impl Default for SilenceController {
@@ -45,6 +50,8 @@ impl SilenceController {
             ticks_since_heartbeat: 0,
             heartbeat_interval: 50, // Default: heartbeat every 50 ticks in DeepSilence
             efficiency_bias: 1.0,
+            min_dwell_ticks: 0,
+            ticks_in_state: 0,
         }
     }
 
@@ -59,6 +66,13 @@ impl SilenceController {
         self.efficiency_bias = bias;
     }
 
+    /// Set the minimum number of `transition` ticks a state is held before
+    /// the controller may leave it, preventing silent/chirping flapping when
+    /// signals hover near a threshold. Storm escalation bypasses the dwell.
+    pub fn set_min_dwell(&mut self, ticks: u64) {
+        self.min_dwell_ticks = ticks;
+    }
+
     /// Get current silence state
     pub fn state(&self) -> SilenceState {
         self.state
@@ -129,6 +143,7 @@ impl SilenceController {
     /// * `variance_stable` - True if variance is below silence threshold (from is_stable_enough_for_silence())
     /// * `calm_streak` - Number of consecutive Calm observations
     pub fn transition(&mut self, regime: Regime, variance_stable: bool, calm_streak: usize) {
+        self.ticks_in_state = self.ticks_in_state.saturating_add(1);
         let new_state = match regime {
             Regime::Storm => {
                 // Wake up immediately on Storm
@@ -152,9 +167,13 @@ impl SilenceController {
             }
         };
 
+        // Hold the current state until the dwell time elapses (Storm overrides)
+        let dwell_elapsed = regime == Regime::Storm || self.ticks_in_state >= self.min_dwell_ticks;
+
         // Log state transition if changed
-        if new_state != self.state {
+        if new_state != self.state && dwell_elapsed {
             self.state = new_state;
+            self.ticks_in_state = 0;
             // Reset heartbeat counter on state change
             self.ticks_since_heartbeat = 0;
         }
@@ -164,6 +183,7 @@ impl SilenceController {
     pub fn set_state(&mut self, state: SilenceState) {
         self.state = state;
         self.ticks_since_heartbeat = 0;
+        self.ticks_in_state = 0;
     }
 }
 
@@ -222,4 +242,34 @@ mod tests {
         // 10th tick: should send heartbeat
         assert!(controller.should_broadcast(0.1, 50.0, 0.5, 50));
     }
+
+    #[test]
+    fn test_silence_controller_min_dwell() {
+        let mut controller = SilenceController::new();
+        controller.set_min_dwell(5);
+
+        // Signals oscillate between "stable Calm" and PreStorm every tick
+        let mut changes = Vec::new();
+        let mut previous = controller.state();
+        for tick in 0..40 {
+            if tick % 2 == 0 {
+                controller.transition(Regime::Calm, true, 150);
+            } else {
+                controller.transition(Regime::PreStorm, false, 0);
+            }
+            if controller.state() != previous {
+                changes.push(tick);
+                previous = controller.state();
+            }
+        }
+        assert!(changes.len() > 1);
+        for pair in changes.windows(2) {
+            assert!(pair[1] - pair[0] >= 5, "changes at {:?}", changes);
+        }
+
+        // Storm escalation is never held back by the dwell
+        controller.set_state(SilenceState::DeepSilence);
+        controller.transition(Regime::Storm, false, 0);
+        assert_eq!(controller.state(), SilenceState::Active);
+    }
 }
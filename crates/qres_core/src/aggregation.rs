@@ -5,6 +5,7 @@
 //! - Krum algorithm (Phase 2 Item 1 of security roadmap)
 //! - Trimmed mean (Phase 2 Item 2, planned)
 //! - Median (Phase 2 Item 2, planned)
+//! - Reputation-weighted median
 //!
 //! Reference: Blanchard et al., "Machine Learning with Adversaries: Byzantine Tolerant Gradient Descent"
 
@@ -24,6 +25,17 @@ pub trait Aggregator {
     /// Aggregate multiple model updates into a single update
     fn aggregate(&self, updates: &[Vec<f32>]) -> AggregationResult;
 
+    /// Aggregate with per-update reputation weights supplied by the caller
+    /// (same order as `updates`).
+    ///
+    /// Strategies that do not use reputation ignore `weights` and fall back
+    /// to [`Aggregator::aggregate`]; reputation-aware strategies use them in
+    /// place of the weights stored in the struct.
+    fn aggregate_weighted(&self, updates: &[Vec<f32>], weights: &[f32]) -> AggregationResult {
+        let _ = weights;
+        self.aggregate(updates)
+    }
+
     /// Human-readable name for logging/debugging
     fn name(&self) -> &'static str;
 }
@@ -189,7 +201,11 @@ impl WeightedTrimmedMeanAggregator {
 
 impl Aggregator for WeightedTrimmedMeanAggregator {
     fn aggregate(&self, updates: &[Vec<f32>]) -> AggregationResult {
-        weighted_trimmed_mean(updates, self.f, &self.reputation_weights)
+        self.aggregate_weighted(updates, &self.reputation_weights)
+    }
+
+    fn aggregate_weighted(&self, updates: &[Vec<f32>], weights: &[f32]) -> AggregationResult {
+        weighted_trimmed_mean(updates, self.f, weights).with_diagnostics(updates)
     }
 
     fn name(&self) -> &'static str {
@@ -197,6 +213,43 @@ impl Aggregator for WeightedTrimmedMeanAggregator {
     }
 }
 
+/// Reputation-weighted coordinate-wise median
+///
+/// Per coordinate, picks the value at which cumulative reputation weight
+/// crosses half of the total. Unlike a weighted mean, low-reputation outliers
+/// cannot drag the result at all until their combined weight reaches 50%,
+/// whatever their magnitude and without knowing the Byzantine fraction.
+#[derive(Clone, Debug)]
+pub struct WeightedMedianAggregator {
+    /// Reputation weights per node (same order as updates)
+    pub reputation_weights: Vec<f32>,
+}
+
+impl WeightedMedianAggregator {
+    pub fn new(reputation_weights: Vec<f32>) -> Self {
+        Self { reputation_weights }
+    }
+}
+
+impl Aggregator for WeightedMedianAggregator {
+    fn aggregate(&self, updates: &[Vec<f32>]) -> AggregationResult {
+        self.aggregate_weighted(updates, &self.reputation_weights)
+    }
+
+    fn aggregate_weighted(&self, updates: &[Vec<f32>], weights: &[f32]) -> AggregationResult {
+        aggregate_updates(
+            updates,
+            &AggregationMode::WeightedMedian {
+                reputation_weights: weights.to_vec(),
+            },
+        )
+    }
+
+    fn name(&self) -> &'static str {
+        "WeightedMedian"
+    }
+}
+
 /// Adaptive Aggregator - switches strategy based on swarm maturity
 ///
 /// Implements Phase 1.1 of v21.0 Roadmap:
@@ -249,11 +302,15 @@ impl AdaptiveAggregator {
 
 impl Aggregator for AdaptiveAggregator {
     fn aggregate(&self, updates: &[Vec<f32>]) -> AggregationResult {
+        self.aggregate_weighted(updates, &self.reputation_weights)
+    }
+
+    fn aggregate_weighted(&self, updates: &[Vec<f32>], weights: &[f32]) -> AggregationResult {
         aggregate_updates(
             updates,
             &AggregationMode::Adaptive {
                 f: self.f,
-                reputation_weights: self.reputation_weights.clone(),
+                reputation_weights: weights.to_vec(),
                 banned_count: self.banned_count,
                 total_nodes: self.total_nodes,
             },
@@ -274,11 +331,7 @@ fn weighted_trimmed_mean(
     reputation_weights: &[f32],
 ) -> AggregationResult {
     if updates.is_empty() {
-        return AggregationResult {
-            weights: Vec::new(),
-            selected_indices: Vec::new(),
-            rejected_indices: Vec::new(),
-        };
+        return AggregationResult::default();
     }
 
     let n = updates.len();
@@ -304,7 +357,7 @@ fn weighted_trimmed_mean(
             .collect();
 
         // Sort by value
-        dim_values.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(core::cmp::Ordering::Equal));
+        dim_values.sort_by(|a, b| float_order(&a.0, &b.0));
 
         // Trim top f and bottom f
         let trimmed = &dim_values[f..(n - f)];
@@ -321,17 +374,14 @@ fn weighted_trimmed_mean(
         weights: result,
         selected_indices: (0..n).collect(),
         rejected_indices: Vec::new(),
+        ..Default::default()
     }
 }
 
 /// Simple weighted mean (fallback when trimming isn't possible)
 fn weighted_mean(updates: &[Vec<f32>], reputation_weights: &[f32]) -> AggregationResult {
     if updates.is_empty() {
-        return AggregationResult {
-            weights: Vec::new(),
-            selected_indices: Vec::new(),
-            rejected_indices: Vec::new(),
-        };
+        return AggregationResult::default();
     }
 
     let n = updates.len();
@@ -347,6 +397,7 @@ fn weighted_mean(updates: &[Vec<f32>], reputation_weights: &[f32]) -> Aggregatio
             weights: result,
             selected_indices: (0..n).collect(),
             rejected_indices: Vec::new(),
+            ..Default::default()
         };
     }
 
@@ -367,6 +418,7 @@ fn weighted_mean(updates: &[Vec<f32>], reputation_weights: &[f32]) -> Aggregatio
         weights: result,
         selected_indices: (0..n).collect(),
         rejected_indices: Vec::new(),
+        ..Default::default()
     }
 }
 
@@ -426,6 +478,11 @@ pub enum AggregationMode {
     TrimmedMeanByz { f: usize },
     /// Coordinate-wise median
     Median,
+    /// Coordinate-wise median weighted by reputation (missing weights count 0.5)
+    WeightedMedian { reputation_weights: Vec<f32> },
+    /// Geometric median via Weiszfeld iteration, stopping once a step moves
+    /// less than `tol` (L2) or after `max_iters` steps
+    GeometricMedian { max_iters: usize, tol: f32 },
     /// Adaptive aggregation - switches between trimmed and reputation-only based on swarm maturity
     ///
     /// Cold-start phase: Uses WeightedTrimmedMean (banned < 3 OR ban_rate > 1%)
@@ -443,7 +500,7 @@ pub enum AggregationMode {
 }
 
 /// Result of aggregation with metadata
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct AggregationResult {
     /// The aggregated weights
     pub weights: Vec<f32>,
@@ -451,6 +508,50 @@ pub struct AggregationResult {
     pub selected_indices: Vec<usize>,
     /// Any updates that were rejected as potential outliers
     pub rejected_indices: Vec<usize>,
+    /// Per-coordinate (population) variance of the selected updates
+    pub input_variance: Vec<f32>,
+    /// L2 distance between `weights` and the simple mean of all updates
+    pub divergence_from_mean: f32,
+}
+
+impl AggregationResult {
+    /// Fill in the dispersion diagnostics for a result computed from `updates`.
+    fn with_diagnostics(mut self, updates: &[Vec<f32>]) -> Self {
+        let d = self.weights.len();
+        let n = updates.len();
+        if d == 0 || n == 0 {
+            return self;
+        }
+
+        let naive = simple_mean(updates, n, d).weights;
+        self.divergence_from_mean = squared_euclidean(&self.weights, &naive).sqrt();
+
+        let contributing: Vec<&Vec<f32>> = self
+            .selected_indices
+            .iter()
+            .filter_map(|&i| updates.get(i))
+            .collect();
+        if contributing.is_empty() {
+            self.input_variance = vec![0.0; d];
+            return self;
+        }
+        let inv_count = 1.0 / contributing.len() as f32;
+        self.input_variance = (0..d)
+            .map(|j| {
+                let value = |u: &Vec<f32>| u.get(j).copied().unwrap_or(0.0);
+                let mean: f32 = contributing.iter().map(|u| value(u)).sum::<f32>() * inv_count;
+                contributing
+                    .iter()
+                    .map(|u| {
+                        let diff = value(u) - mean;
+                        diff * diff
+                    })
+                    .sum::<f32>()
+                    * inv_count
+            })
+            .collect();
+        self
+    }
 }
 
 /// Aggregate multiple model updates using the specified mode
@@ -463,17 +564,13 @@ pub struct AggregationResult {
 /// Aggregation result containing the combined weights and metadata
 pub fn aggregate_updates(updates: &[Vec<f32>], mode: &AggregationMode) -> AggregationResult {
     if updates.is_empty() {
-        return AggregationResult {
-            weights: Vec::new(),
-            selected_indices: Vec::new(),
-            rejected_indices: Vec::new(),
-        };
+        return AggregationResult::default();
     }
 
     let n = updates.len();
     let d = updates[0].len();
 
-    match mode {
+    let result = match mode {
         AggregationMode::SimpleMean => simple_mean(updates, n, d),
         AggregationMode::Krum { expected_byz } => krum(updates, n, d, *expected_byz, 1),
         AggregationMode::MultiKrum { expected_byz, k } => krum(updates, n, d, *expected_byz, *k),
@@ -482,13 +579,20 @@ pub fn aggregate_updates(updates: &[Vec<f32>], mode: &AggregationMode) -> Aggreg
         }
         AggregationMode::TrimmedMeanByz { f } => trimmed_mean_byz(updates, n, d, *f),
         AggregationMode::Median => median_agg(updates, n, d),
+        AggregationMode::WeightedMedian { reputation_weights } => {
+            weighted_median(updates, n, d, reputation_weights)
+        }
+        AggregationMode::GeometricMedian { max_iters, tol } => {
+            geometric_median(updates, n, d, *max_iters, *tol)
+        }
         AggregationMode::Adaptive {
             f,
             reputation_weights,
             banned_count,
             total_nodes,
         } => adaptive_aggregate(updates, *f, reputation_weights, *banned_count, *total_nodes),
-    }
+    };
+    result.with_diagnostics(updates)
 }
 
 /// Simple arithmetic mean (baseline)
@@ -511,6 +615,7 @@ fn simple_mean(updates: &[Vec<f32>], n: usize, d: usize) -> AggregationResult {
         weights: sum,
         selected_indices: (0..n).collect(),
         rejected_indices: Vec::new(),
+        ..Default::default()
     }
 }
 
@@ -540,29 +645,32 @@ fn krum(
         return simple_mean(updates, n, d);
     }
 
-    // Compute pairwise squared Euclidean distances
-    let mut distances = vec![vec![0.0f32; n]; n];
+    // Compute pairwise squared Euclidean distances (flat row-major n×n matrix)
+    let mut distances = vec![0.0f32; n * n];
     for i in 0..n {
         for j in (i + 1)..n {
             let dist = squared_euclidean(&updates[i], &updates[j]);
-            distances[i][j] = dist;
-            distances[j][i] = dist;
+            distances[i * n + j] = dist;
+            distances[j * n + i] = dist;
         }
     }
 
     // For each update, compute Krum score: sum of distances to n-q-2 nearest neighbors
     let neighbors_count = n - q - 2;
     let mut scores: Vec<(usize, f32)> = Vec::with_capacity(n);
+    // Reused for every row instead of allocating one Vec per update
+    let mut neighbor_dists: Vec<f32> = Vec::with_capacity(n - 1);
+
+    for (i, row) in distances.chunks_exact(n).enumerate() {
+        neighbor_dists.clear();
+        neighbor_dists.extend(
+            row.iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, &d)| d),
+        );
 
-    for (i, row) in distances.iter().enumerate() {
-        let mut neighbor_dists: Vec<f32> = row
-            .iter()
-            .enumerate()
-            .filter(|&(j, _)| j != i)
-            .map(|(_, &d)| d)
-            .collect();
-
-        neighbor_dists.sort_by(|a: &f32, b: &f32| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        neighbor_dists.sort_by(float_order);
 
         // Sum the smallest n-q-2 distances
         let score: f32 = neighbor_dists.iter().take(neighbors_count).sum();
@@ -570,7 +678,7 @@ fn krum(
     }
 
     // Sort by score (ascending - smaller is better)
-    scores.sort_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    scores.sort_by(|(_, a), (_, b)| float_order(a, b));
 
     // Select k best updates
     let k = k.min(n);
@@ -596,6 +704,7 @@ fn krum(
         weights: result,
         selected_indices,
         rejected_indices,
+        ..Default::default()
     }
 }
 
@@ -617,7 +726,7 @@ fn trimmed_mean(updates: &[Vec<f32>], n: usize, d: usize, trim_fraction: f32) ->
             .iter()
             .map(|u| u.get(dim).copied().unwrap_or(0.0))
             .collect();
-        values.sort_by(|a: &f32, b: &f32| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        values.sort_by(float_order);
 
         // Trim extremes and average
         let sum: f32 = values[trim_count..(n - trim_count)].iter().sum();
@@ -628,6 +737,7 @@ fn trimmed_mean(updates: &[Vec<f32>], n: usize, d: usize, trim_fraction: f32) ->
         weights: result,
         selected_indices: (0..n).collect(), // All contribute partially
         rejected_indices: Vec::new(),
+        ..Default::default()
     }
 }
 
@@ -679,6 +789,7 @@ fn trimmed_mean_byz(updates: &[Vec<f32>], n: usize, d: usize, f: usize) -> Aggre
         weights: result,
         selected_indices: (0..n).collect(),
         rejected_indices: Vec::new(),
+        ..Default::default()
     }
 }
 
@@ -691,7 +802,7 @@ fn median_agg(updates: &[Vec<f32>], n: usize, d: usize) -> AggregationResult {
             .iter()
             .map(|u| u.get(dim).copied().unwrap_or(0.0))
             .collect();
-        values.sort_by(|a: &f32, b: &f32| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        values.sort_by(float_order);
 
         *res_val = if n % 2 == 1 {
             values[n / 2]
@@ -704,9 +815,170 @@ fn median_agg(updates: &[Vec<f32>], n: usize, d: usize) -> AggregationResult {
         weights: result,
         selected_indices: (0..n).collect(),
         rejected_indices: Vec::new(),
+        ..Default::default()
     }
 }
 
+/// Coordinate-wise weighted median
+///
+/// Returns the first sorted value whose cumulative weight reaches half the
+/// total; on an exact tie at one half it averages with the next value, so
+/// equal weights reproduce [`median_agg`]. Negative weights count as zero.
+fn weighted_median(
+    updates: &[Vec<f32>],
+    n: usize,
+    d: usize,
+    reputation_weights: &[f32],
+) -> AggregationResult {
+    let weight = |i: usize| reputation_weights.get(i).copied().unwrap_or(0.5).max(0.0);
+    let total: f32 = (0..n).map(weight).sum();
+    if total <= 0.0 {
+        return median_agg(updates, n, d);
+    }
+    let half = total / 2.0;
+
+    let mut result = vec![0.0f32; d];
+    for (dim, res_val) in result.iter_mut().enumerate() {
+        let mut values: Vec<(f32, f32)> = updates
+            .iter()
+            .enumerate()
+            .map(|(i, u)| (u.get(dim).copied().unwrap_or(0.0), weight(i)))
+            .collect();
+        values.sort_by(|a, b| float_order(&a.0, &b.0));
+
+        let mut cumulative = 0.0f32;
+        for (k, &(val, w)) in values.iter().enumerate() {
+            cumulative += w;
+            if cumulative >= half {
+                *res_val = match values[k + 1..].iter().find(|(_, w)| *w > 0.0) {
+                    Some(&(next, _)) if cumulative == half => (val + next) / 2.0,
+                    _ => val,
+                };
+                break;
+            }
+        }
+    }
+
+    AggregationResult {
+        weights: result,
+        selected_indices: (0..n).collect(),
+        rejected_indices: Vec::new(),
+        ..Default::default()
+    }
+}
+
+/// Geometric median (the point minimizing the sum of L2 distances) by
+/// Weiszfeld iteration, started from the coordinate-wise median.
+///
+/// Each step re-weights every update by the inverse of its distance to the
+/// current estimate. When the estimate lands on input points, plain Weiszfeld
+/// divides by zero; the Vardi-Zhang correction is used instead: the estimate
+/// is optimal if the pull of the other points does not exceed the number of
+/// coincident ones, and otherwise moves only part of the way.
+fn geometric_median(
+    updates: &[Vec<f32>],
+    n: usize,
+    d: usize,
+    max_iters: usize,
+    tol: f32,
+) -> AggregationResult {
+    const COINCIDENT_EPS: f64 = 1e-12;
+    let points: Vec<Vec<f64>> = updates
+        .iter()
+        .map(|u| {
+            (0..d)
+                .map(|j| u.get(j).copied().unwrap_or(0.0) as f64)
+                .collect()
+        })
+        .collect();
+    let mut estimate: Vec<f64> = median_agg(updates, n, d)
+        .weights
+        .iter()
+        .map(|&v| v as f64)
+        .collect();
+
+    for _ in 0..max_iters {
+        let mut weighted_sum = vec![0.0f64; d];
+        let mut weight_total = 0.0f64;
+        let mut coincident = 0usize;
+        for point in &points {
+            let dist = point
+                .iter()
+                .zip(&estimate)
+                .map(|(p, e)| (p - e) * (p - e))
+                .sum::<f64>()
+                .sqrt();
+            if dist < COINCIDENT_EPS {
+                coincident += 1;
+                continue;
+            }
+            let w = 1.0 / dist;
+            weight_total += w;
+            for (acc, p) in weighted_sum.iter_mut().zip(point) {
+                *acc += w * p;
+            }
+        }
+        if weight_total == 0.0 {
+            // Every update coincides with the estimate
+            break;
+        }
+
+        let target: Vec<f64> = weighted_sum.iter().map(|s| s / weight_total).collect();
+        let next: Vec<f64> = if coincident == 0 {
+            target
+        } else {
+            // Pull of the non-coincident points: ||sum (x_i - y) / ||x_i - y|| ||
+            let pull = target
+                .iter()
+                .zip(&estimate)
+                .map(|(t, e)| (t - e) * weight_total)
+                .map(|r| r * r)
+                .sum::<f64>()
+                .sqrt();
+            let eta = coincident as f64;
+            if pull <= eta {
+                break;
+            }
+            let step = eta / pull;
+            target
+                .iter()
+                .zip(&estimate)
+                .map(|(t, e)| (1.0 - step) * t + step * e)
+                .collect()
+        };
+
+        let moved = next
+            .iter()
+            .zip(&estimate)
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum::<f64>()
+            .sqrt();
+        estimate = next;
+        if moved <= tol as f64 {
+            break;
+        }
+    }
+
+    AggregationResult {
+        weights: estimate.iter().map(|&v| v as f32).collect(),
+        selected_indices: (0..n).collect(),
+        rejected_indices: Vec::new(),
+        ..Default::default()
+    }
+}
+
+/// Total ordering used by every float sort in this module
+///
+/// `partial_cmp(..).unwrap_or(Equal)` makes NaN "equal" to everything, which
+/// is not a consistent order: where a NaN lands then depends on the input
+/// permutation. `total_cmp` sorts positive NaN above +inf and negative NaN
+/// below -inf, so a NaN-laden update is trimmed like any other extreme and
+/// results stay bit-identical (INV-6).
+#[inline]
+fn float_order(a: &f32, b: &f32) -> Ordering {
+    a.total_cmp(b)
+}
+
 /// Squared Euclidean distance between two vectors
 #[inline]
 fn squared_euclidean(a: &[f32], b: &[f32]) -> f32 {
@@ -764,6 +1036,56 @@ mod tests {
         assert_eq!(result.weights, vec![2.0, 3.0]); // Falls back to mean
     }
 
+    #[test]
+    fn test_nan_update_aggregates_deterministically() {
+        let mut updates = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![1.1, 2.1, 3.1],
+            vec![0.9, 1.9, 2.9],
+            vec![1.05, 2.05, 3.05],
+        ];
+        // Malicious update mixing NaNs of both signs
+        updates.push(vec![f32::NAN, -f32::NAN, 1.0]);
+        let reps = vec![0.8, 0.7, 0.9, 0.6, 0.8];
+
+        let bits =
+            |r: &AggregationResult| r.weights.iter().map(|w| w.to_bits()).collect::<Vec<_>>();
+        let run = |updates: &[Vec<f32>]| {
+            [
+                aggregate_updates(
+                    updates,
+                    &AggregationMode::TrimmedMean { trim_fraction: 0.4 },
+                ),
+                aggregate_updates(updates, &AggregationMode::Median),
+                WeightedTrimmedMeanAggregator::new(1, reps.clone()).aggregate(updates),
+            ]
+        };
+
+        let baseline = run(&updates);
+        for result in &baseline {
+            assert!(
+                result.weights.iter().all(|w| w.is_finite()),
+                "{:?}",
+                result.weights
+            );
+        }
+        for _ in 0..5 {
+            for (a, b) in baseline.iter().zip(run(&updates).iter()) {
+                assert_eq!(bits(a), bits(b));
+                assert_eq!(a.selected_indices, b.selected_indices);
+            }
+        }
+
+        // Unweighted modes must not care where the NaN update sits
+        for shift in 1..updates.len() {
+            let mut rotated = updates.clone();
+            rotated.rotate_left(shift);
+            let results = run(&rotated);
+            assert_eq!(bits(&baseline[0]), bits(&results[0]));
+            assert_eq!(bits(&baseline[1]), bits(&results[1]));
+        }
+    }
+
     #[test]
     fn test_multi_krum() {
         let updates = vec![
@@ -844,6 +1166,139 @@ mod tests {
         assert_eq!(result.weights[0], 1.0);
     }
 
+    #[test]
+    fn test_weighted_median_resists_low_rep_outliers() {
+        // 6 honest nodes near 1.0 plus a cluster of 4 low-rep poisoners at 100.0
+        let mut updates: Vec<Vec<f32>> = [0.9, 0.95, 1.0, 1.0, 1.05, 1.1]
+            .iter()
+            .map(|&v| vec![v])
+            .collect();
+        updates.extend((0..4).map(|_| vec![100.0]));
+        let mut weights = vec![0.8; 6];
+        weights.extend(vec![0.1; 4]);
+
+        let mean = weighted_mean(&updates, &weights).weights[0];
+        let result = WeightedMedianAggregator::new(weights.clone()).aggregate(&updates);
+        let median = result.weights[0];
+
+        // The weighted mean is dragged far off; the weighted median stays put
+        assert!(mean > 5.0, "weighted mean should be shifted: {}", mean);
+        assert!((median - 1.0).abs() < 0.05, "weighted median: {}", median);
+
+        // Outlier magnitude is irrelevant to the median
+        let mut extreme = updates.clone();
+        for u in extreme.iter_mut().skip(6) {
+            u[0] = 1e6;
+        }
+        let extreme_result = aggregate_updates(
+            &extreme,
+            &AggregationMode::WeightedMedian {
+                reputation_weights: weights,
+            },
+        );
+        assert_eq!(extreme_result.weights[0], median);
+
+        // Equal weights reduce to the plain median, including even n
+        let plain = vec![vec![0.0], vec![1.0], vec![2.0], vec![10.0]];
+        let equal = aggregate_updates(
+            &plain,
+            &AggregationMode::WeightedMedian {
+                reputation_weights: vec![1.0; 4],
+            },
+        );
+        assert_eq!(
+            equal.weights,
+            aggregate_updates(&plain, &AggregationMode::Median).weights
+        );
+    }
+
+    #[test]
+    fn test_divergence_from_mean_flags_outliers() {
+        let clean = vec![
+            vec![1.0, 2.0],
+            vec![1.1, 2.1],
+            vec![0.9, 1.9],
+            vec![1.05, 2.05],
+            vec![0.95, 1.95],
+        ];
+        let result = aggregate_updates(&clean, &AggregationMode::Median);
+        assert!(result.divergence_from_mean < 0.01);
+        assert_eq!(result.input_variance.len(), 2);
+        assert!(result.input_variance.iter().all(|&v| v > 0.0 && v < 0.01));
+
+        let mut poisoned = clean.clone();
+        poisoned[4] = vec![100.0, -100.0];
+        let result = aggregate_updates(&poisoned, &AggregationMode::Median);
+        assert!(result.divergence_from_mean > 10.0);
+        assert!(result.input_variance[0] > 100.0);
+
+        // Krum drops the outlier, so only the chosen update's spread remains
+        let result = aggregate_updates(&poisoned, &AggregationMode::Krum { expected_byz: 1 });
+        assert!(result.divergence_from_mean > 10.0);
+        assert_eq!(result.input_variance, vec![0.0, 0.0]);
+
+        // The simple mean never diverges from itself
+        let result = aggregate_updates(&poisoned, &AggregationMode::SimpleMean);
+        assert_eq!(result.divergence_from_mean, 0.0);
+    }
+
+    #[test]
+    fn test_geometric_median_ignores_planted_outlier() {
+        let honest = vec![
+            vec![1.0, 1.0, 1.0],
+            vec![1.2, 0.8, 1.1],
+            vec![0.9, 1.1, 0.9],
+            vec![1.1, 1.0, 1.2],
+            vec![0.8, 1.1, 0.8],
+        ];
+        let centroid = aggregate_updates(&honest, &AggregationMode::SimpleMean).weights;
+        let mut updates = honest.clone();
+        updates.push(vec![1000.0, -1000.0, 1000.0]);
+
+        let mode = AggregationMode::GeometricMedian {
+            max_iters: 100,
+            tol: 1e-6,
+        };
+        let result = aggregate_updates(&updates, &mode);
+        let shift = squared_euclidean(&result.weights, &centroid).sqrt();
+        assert!(
+            shift < 0.2,
+            "geometric median drifted {} from centroid",
+            shift
+        );
+        assert!(result.divergence_from_mean > 100.0);
+
+        // Deterministic across runs
+        for _ in 0..3 {
+            assert_eq!(aggregate_updates(&updates, &mode).weights, result.weights);
+        }
+    }
+
+    #[test]
+    fn test_geometric_median_handles_coincident_points() {
+        // Majority on one point: that point is the geometric median exactly
+        let updates = vec![
+            vec![1.0, 1.0],
+            vec![1.0, 1.0],
+            vec![1.0, 1.0],
+            vec![5.0, 1.0],
+            vec![1.0, 9.0],
+        ];
+        let mode = AggregationMode::GeometricMedian {
+            max_iters: 50,
+            tol: 1e-6,
+        };
+        let result = aggregate_updates(&updates, &mode);
+        assert_eq!(result.weights, vec![1.0, 1.0]);
+        assert!(result.weights.iter().all(|v| v.is_finite()));
+
+        // A single coincident point does not trap the estimate
+        let updates = vec![vec![0.0, 0.0], vec![4.0, 0.0], vec![0.0, 4.0]];
+        let result = aggregate_updates(&updates, &mode);
+        assert!(result.weights.iter().all(|v| v.is_finite()));
+        assert!(result.weights[0] > 0.5 && result.weights[1] > 0.5);
+    }
+
     // ================================================================
     // Active Defense Tests (INV-1, INV-2, INV-3 from INVARIANTS.md)
     // ================================================================
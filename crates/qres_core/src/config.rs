@@ -11,6 +11,9 @@ pub enum PredictorType {
     Neural,
     Hybrid,
     Zero,
+    /// Choose per chunk by trial-encoding its first bytes (see
+    /// [`PredictorSet::best_predictor_for`](crate::PredictorSet::best_predictor_for))
+    Auto,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -31,7 +34,7 @@ pub enum CompressionMode {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "cli", derive(Args))]
 pub struct QresConfig {
-    /// Predictor Strategy (Heuristic, Neural, Hybrid, Zero)
+    /// Predictor Strategy (Heuristic, Neural, Hybrid, Zero, Auto)
     #[cfg_attr(feature = "cli", arg(long, value_enum, default_value_t = PredictorType::Hybrid))]
     pub predictor: PredictorType,
 
@@ -83,7 +86,7 @@ impl QresConfig {
                 eprintln!("⚠️ INFO: Neural mode using GraphPredictor (learning)");
                 alloc::boxed::Box::new(crate::predictors::GraphPredictor::new())
             }
-            PredictorType::Hybrid => {
+            PredictorType::Hybrid | PredictorType::Auto => {
                 // Hybrid uses LzMatchPredictor for pattern matching
                 alloc::boxed::Box::new(crate::predictors::LzMatchPredictor::new())
             }
@@ -28,43 +28,117 @@ pub struct Bfp16Vec {
 impl Bfp16Vec {
     /// Create Bfp16Vec from f32 slice
     pub fn from_f32_slice(data: &[f32]) -> Self {
-        if data.is_empty() {
+        let Some((exponent, scale, limit)) = block_scale(data, 16) else {
             return Self {
                 exponent: 0,
-                mantissas: Vec::new(),
+                mantissas: vec![0; data.len()],
             };
+        };
+
+        // Quantize, clamping to the i16 range (saturating)
+        let mantissas = data
+            .iter()
+            .map(|&x| (x * scale).clamp(-limit, limit).round() as i16)
+            .collect();
+
+        Self {
+            exponent,
+            mantissas,
         }
+    }
+
+    /// Convert back to `Vec<f32>`
+    pub fn to_vec_f32(&self) -> Vec<f32> {
+        let scale = 2.0f32.powi(self.exponent as i32);
+        self.mantissas.iter().map(|&m| m as f32 * scale).collect()
+    }
+
+    /// Worst-case relative error of `to_vec_f32` against the encoded input
+    ///
+    /// Rounding moves a value by at most half a step (`2^(exponent-1)`), so a
+    /// mantissa `m` stands for an input of magnitude at least `|m| - 0.5`
+    /// steps and its relative error is bounded by `0.5 / (|m| - 0.5)`.
+    /// Saturated mantissas (`±32767`) may be off by a full step. The result is
+    /// the maximum of that bound over all non-zero mantissas; zero mantissas
+    /// are taken to be exact zeros, so inputs flushed to zero by the shared
+    /// exponent are not visible here. Returns 0.0 when every mantissa is zero.
+    pub fn max_relative_error(&self) -> f32 {
+        self.mantissas
+            .iter()
+            .map(|m| m.unsigned_abs())
+            .filter(|&m| m != 0)
+            .map(|m| {
+                let err_steps = if m >= i16::MAX as u16 { 1.0 } else { 0.5 };
+                err_steps / (m as f32 - 0.5)
+            })
+            .fold(0.0f32, f32::max)
+    }
+}
 
-        // 1. Find max absolute value
-        let max_abs = data.iter().map(|x| x.abs()).fold(0.0f32, |a, b| a.max(b));
+/// Block Floating Point Vector with 8-bit mantissas (BFP-8)
+///
+/// Storm-mode counterpart of [`Bfp16Vec`]: half the payload for roughly
+/// 2 significant decimal digits relative to the block maximum.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Bfp8Vec {
+    /// Shared exponent for the block
+    pub exponent: i8,
+    /// 8-bit signed mantissas
+    pub mantissas: Vec<i8>,
+}
 
-        if max_abs == 0.0 {
+impl Bfp8Vec {
+    /// Create Bfp8Vec from f32 slice
+    pub fn from_f32_slice(data: &[f32]) -> Self {
+        let Some((exponent, scale, limit)) = block_scale(data, 8) else {
             return Self {
                 exponent: 0,
                 mantissas: vec![0; data.len()],
             };
+        };
+        let mantissas = data
+            .iter()
+            .map(|&x| (x * scale).clamp(-limit, limit).round() as i8)
+            .collect();
+
+        Self {
+            exponent,
+            mantissas,
         }
+    }
 
-        // 2. Calculate optimal exponent
-        // We want max_abs * 2^(-exp) <= 32767
-        // exp >= log2(max_abs) - log2(32767)
-        // exp = ceil(log2(max_abs) - 14.99993)
-        // Using -15.0 ensures we maximize dynamic range usage (matches Python)
-        let exp_f32 = max_abs.log2().ceil() - 15.0;
+    /// Convert back to `Vec<f32>`
+    pub fn to_vec_f32(&self) -> Vec<f32> {
+        let scale = 2.0f32.powi(self.exponent as i32);
+        self.mantissas.iter().map(|&m| m as f32 * scale).collect()
+    }
+}
 
-        // Clamp exponent to valid range if needed
-        let exponent = (exp_f32 as i8).clamp(-126, 126);
+/// Block Floating Point Vector with 32-bit mantissas (BFP-32)
+///
+/// Wide enough that every element keeps its full f32 significand unless it
+/// is more than 2^7 below the block maximum.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Bfp32Vec {
+    /// Shared exponent for the block
+    pub exponent: i8,
+    /// 32-bit signed mantissas
+    pub mantissas: Vec<i32>,
+}
 
-        // 3. Quantize
-        // scale = 2^(-exponent)
-        let scale = 2.0f32.powi(-(exponent as i32));
+impl Bfp32Vec {
+    /// Create Bfp32Vec from f32 slice
+    pub fn from_f32_slice(data: &[f32]) -> Self {
+        let Some((exponent, scale, limit)) = block_scale(data, 32) else {
+            return Self {
+                exponent: 0,
+                mantissas: vec![0; data.len()],
+            };
+        };
+        // `limit` rounds up to 2^31 in f32; the `as` cast saturates it
         let mantissas = data
             .iter()
-            .map(|&x| {
-                let scaled = x * scale;
-                // Clamp to i16 range (saturating)
-                scaled.clamp(-32767.0, 32767.0).round() as i16
-            })
+            .map(|&x| (x * scale).clamp(-limit, limit).round() as i32)
             .collect();
 
         Self {
@@ -80,6 +154,92 @@ impl Bfp16Vec {
     }
 }
 
+/// Mantissa width of a block-float encoding
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BfpWidth {
+    /// 8-bit mantissas (bandwidth-constrained, e.g. Storm)
+    Bfp8,
+    /// 16-bit mantissas (the v19 default)
+    #[default]
+    Bfp16,
+    /// 32-bit mantissas (near-lossless for f32)
+    Bfp32,
+}
+
+/// Block-float vector of any supported width
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BfpVec {
+    Bfp8(Bfp8Vec),
+    Bfp16(Bfp16Vec),
+    Bfp32(Bfp32Vec),
+}
+
+impl BfpVec {
+    /// Encode `data` with the requested mantissa width
+    pub fn from_f32_slice(width: BfpWidth, data: &[f32]) -> Self {
+        match width {
+            BfpWidth::Bfp8 => Self::Bfp8(Bfp8Vec::from_f32_slice(data)),
+            BfpWidth::Bfp16 => Self::Bfp16(Bfp16Vec::from_f32_slice(data)),
+            BfpWidth::Bfp32 => Self::Bfp32(Bfp32Vec::from_f32_slice(data)),
+        }
+    }
+
+    /// Mantissa width of this vector
+    pub fn width(&self) -> BfpWidth {
+        match self {
+            Self::Bfp8(_) => BfpWidth::Bfp8,
+            Self::Bfp16(_) => BfpWidth::Bfp16,
+            Self::Bfp32(_) => BfpWidth::Bfp32,
+        }
+    }
+
+    /// Convert back to `Vec<f32>`
+    pub fn to_vec_f32(&self) -> Vec<f32> {
+        match self {
+            Self::Bfp8(v) => v.to_vec_f32(),
+            Self::Bfp16(v) => v.to_vec_f32(),
+            Self::Bfp32(v) => v.to_vec_f32(),
+        }
+    }
+
+    /// Wire encoding via the matching `arithmetic::compress_bfp*` function
+    #[cfg(feature = "std")]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        use crate::encoding::arithmetic;
+        match self {
+            Self::Bfp8(v) => arithmetic::compress_bfp8(v.exponent, &v.mantissas),
+            Self::Bfp16(v) => arithmetic::compress_bfp(v.exponent, &v.mantissas),
+            Self::Bfp32(v) => arithmetic::compress_bfp32(v.exponent, &v.mantissas),
+        }
+    }
+}
+
+/// Shared exponent selection for a `bits`-wide signed mantissa block
+///
+/// Returns `(exponent, scale, limit)` where `scale = 2^-exponent` maps the
+/// largest magnitude just under `2^(bits-1)` and `limit` is the saturation
+/// bound, or `None` when the block is empty or all zeros.
+fn block_scale(data: &[f32], bits: u32) -> Option<(i8, f32, f32)> {
+    // 1. Find max absolute value
+    let max_abs = data.iter().map(|x| x.abs()).fold(0.0f32, |a, b| a.max(b));
+    if max_abs == 0.0 {
+        return None;
+    }
+
+    // 2. Calculate optimal exponent
+    // We want max_abs * 2^(-exp) <= 2^(bits-1) - 1
+    // exp = ceil(log2(max_abs) - (bits - 1)) maximizes dynamic range usage
+    // (for BFP-16 this is the `- 15.0` used by the Python reference)
+    let exp_f32 = max_abs.log2().ceil() - (bits - 1) as f32;
+
+    // Clamp exponent to valid range if needed
+    let exponent = (exp_f32 as i8).clamp(-126, 126);
+
+    let scale = 2.0f32.powi(-(exponent as i32));
+    let limit = ((1u64 << (bits - 1)) - 1) as f32;
+    Some((exponent, scale, limit))
+}
+
 /// Calculates the squared Euclidean distance between two fixed-point vectors.
 ///
 /// Uses `(a - b) * (a - b)` since I16F16 doesn't support `.powi()`.
@@ -251,6 +411,89 @@ mod tests {
         assert_eq!(result2, result3);
     }
 
+    #[test]
+    fn test_bfp16_max_relative_error_bounds_reconstruction() {
+        for scale in [1e-6f32, 1e-3, 0.37, 1.0, 8.0, 1234.5] {
+            // Spread of values within one block, including an exact power
+            // of two that saturates the mantissa range
+            let data: Vec<f32> = (1..=64)
+                .map(|i| scale * (i as f32 * 0.731).sin() * (1.0 + i as f32 / 8.0))
+                .chain([scale * 16.0, -scale * 0.01])
+                .collect();
+            let bfp = Bfp16Vec::from_f32_slice(&data);
+            let bound = bfp.max_relative_error();
+            assert!(
+                bound > 0.0 && bound < 1.0,
+                "scale {}: bound {}",
+                scale,
+                bound
+            );
+
+            for ((&x, y), &m) in data.iter().zip(bfp.to_vec_f32()).zip(&bfp.mantissas) {
+                if m == 0 {
+                    continue;
+                }
+                let rel = (x - y).abs() / x.abs();
+                assert!(
+                    rel <= bound,
+                    "scale {}: value {} rel error {} exceeds bound {}",
+                    scale,
+                    x,
+                    rel,
+                    bound
+                );
+            }
+        }
+
+        // A block of equal magnitudes quantizes near full precision
+        let uniform = Bfp16Vec::from_f32_slice(&[0.75, -0.75, 0.75]);
+        assert!(uniform.max_relative_error() < 1e-4);
+        assert_eq!(
+            Bfp16Vec::from_f32_slice(&[0.0; 4]).max_relative_error(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_bfp_widths_roundtrip_precision() {
+        let data: Vec<f32> = (0..32).map(|i| (i as f32 * 0.37).sin() * 3.0).collect();
+        let max_abs = data.iter().fold(0.0f32, |a, x| a.max(x.abs()));
+        let max_err = |rec: Vec<f32>| {
+            data.iter()
+                .zip(rec)
+                .map(|(a, b)| (a - b).abs())
+                .fold(0.0f32, f32::max)
+        };
+
+        // Error stays within half a step of the block scale for each width
+        let err8 = max_err(Bfp8Vec::from_f32_slice(&data).to_vec_f32());
+        let err16 = max_err(Bfp16Vec::from_f32_slice(&data).to_vec_f32());
+        let err32 = max_err(Bfp32Vec::from_f32_slice(&data).to_vec_f32());
+        assert!(err8 <= max_abs / 127.0, "BFP-8 error {}", err8);
+        assert!(err16 <= max_abs / 32767.0, "BFP-16 error {}", err16);
+        assert!(err32 <= max_abs * 1e-7, "BFP-32 error {}", err32);
+        assert!(err32 <= err16 && err16 <= err8);
+
+        // The enum dispatches to the same encodings
+        let via_enum = BfpVec::from_f32_slice(BfpWidth::Bfp8, &data);
+        assert_eq!(via_enum.width(), BfpWidth::Bfp8);
+        assert_eq!(
+            via_enum.to_vec_f32(),
+            Bfp8Vec::from_f32_slice(&data).to_vec_f32()
+        );
+
+        // Saturating power-of-two maxima and all-zero blocks
+        assert_eq!(
+            Bfp8Vec::from_f32_slice(&[-4.0, 4.0]).mantissas,
+            vec![-127, 127]
+        );
+        assert_eq!(Bfp32Vec::from_f32_slice(&[1.0]).mantissas, vec![i32::MAX]);
+        assert_eq!(
+            Bfp8Vec::from_f32_slice(&[0.0; 3]).to_vec_f32(),
+            vec![0.0; 3]
+        );
+    }
+
     #[test]
     fn test_krum_f_zero_edge_case() {
         // Test with f=0: reduces to "closest to all neighbors" logic
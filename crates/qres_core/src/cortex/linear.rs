@@ -2,9 +2,7 @@ use alloc::vec::Vec;
 use core::convert::TryInto;
 use fixed::types::I16F16;
 
-use super::neuron::{Regime, SpikeEvent, SwarmNeuron};
-
-const REFRACTORY_PERIOD_TICKS: u32 = 10; // Prevent spike storms
+use super::neuron::{Regime, SpikeEvent, SpikeHistory, SwarmNeuron, REFRACTORY_PERIOD_TICKS};
 
 /// Linear Predictor neuron with fixed-point math and refractory logic
 ///
@@ -30,6 +28,8 @@ pub struct LinearNeuron {
     refractory_counter: u32,
     /// Entropy running average (for regime detection)
     entropy_estimate: I16F16,
+    /// Recent spikes, for rate limiting broadcasts
+    spike_history: SpikeHistory,
 }
 
 impl LinearNeuron {
@@ -59,6 +59,7 @@ impl LinearNeuron {
             learning_rate: I16F16::from_num(0.01),
             refractory_counter: 0,
             entropy_estimate: I16F16::from_num(0.5),
+            spike_history: SpikeHistory::new(),
         }
     }
 
@@ -83,6 +84,71 @@ impl LinearNeuron {
         result.clamp(0, 255) as u8
     }
 
+    /// Predict the value following `history` (latest sample last) in fixed
+    /// point, without the u8 clamp of [`SwarmNeuron::predict`]. Lags longer
+    /// than the available history are skipped, as in the u8 path.
+    pub fn predict_next(&self, history: &[I16F16]) -> I16F16 {
+        let mut sum = self.bias;
+        for (&lag, &weight) in self.lags.iter().zip(&self.weights) {
+            if lag == 0 {
+                break; // End of valid lags
+            }
+            if lag >= history.len() {
+                continue; // Not enough history
+            }
+            sum = sum.saturating_add(weight.saturating_mul(history[history.len() - lag]));
+        }
+        sum
+    }
+
+    /// Predict over a whole stream: element `t` is the prediction made after
+    /// observing `inputs[..=t]`, bit-identical to `predict_next(&inputs[..=t])`.
+    pub fn predict_batch(&self, inputs: &[I16F16]) -> Vec<I16F16> {
+        (1..=inputs.len())
+            .map(|end| self.predict_next(&inputs[..end]))
+            .collect()
+    }
+
+    /// Online LMS pass over a stream: after observing `inputs[..=t]` the
+    /// neuron should have predicted `targets[t]`. Weights and bias move by
+    /// `learning_rate * error * input` one sample at a time, so a batch gives
+    /// the same result as the equivalent sequence of single updates.
+    ///
+    /// Returns the mean absolute error of the predictions made before each
+    /// update (zero for an empty batch).
+    pub fn update_batch(&mut self, inputs: &[I16F16], targets: &[I16F16]) -> I16F16 {
+        let n = inputs.len().min(targets.len());
+        if n == 0 {
+            return I16F16::ZERO;
+        }
+
+        let weight_min = I16F16::from_num(-2);
+        let weight_max = I16F16::from_num(2);
+        let mut total_error = I16F16::ZERO;
+        for t in 0..n {
+            let history = &inputs[..=t];
+            let error = targets[t].saturating_sub(self.predict_next(history));
+            total_error = total_error.saturating_add(error.saturating_abs());
+
+            let step = error.saturating_mul(self.learning_rate);
+            for i in 0..self.lags.len() {
+                let lag = self.lags[i];
+                if lag == 0 {
+                    break;
+                }
+                if lag >= history.len() {
+                    continue;
+                }
+                let delta = step.saturating_mul(history[history.len() - lag]);
+                self.weights[i] = self.weights[i]
+                    .saturating_add(delta)
+                    .clamp(weight_min, weight_max);
+            }
+            self.bias = self.bias.saturating_add(step);
+        }
+        total_error / I16F16::saturating_from_num(n)
+    }
+
     /// Update entropy estimate based on error
     fn update_entropy(&mut self, error: u8) {
         let error_fixed = I16F16::from_num(error as i32) / I16F16::from_num(256);
@@ -246,6 +312,14 @@ impl SwarmNeuron for LinearNeuron {
             self.refractory_counter -= 1;
         }
     }
+
+    fn spike_history(&self) -> &SpikeHistory {
+        &self.spike_history
+    }
+
+    fn spike_history_mut(&mut self) -> &mut SpikeHistory {
+        &mut self.spike_history
+    }
 }
 
 #[cfg(test)]
@@ -281,6 +355,80 @@ mod tests {
         assert_eq!(neuron2.learning_rate, I16F16::from_num(0.05));
     }
 
+    /// Deterministic pseudo-random stream in [-1, 1)
+    fn residual_stream(len: usize) -> Vec<I16F16> {
+        let mut state = 0x2545_f491u32;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                I16F16::from_bits((state >> 15) as i32 - (1 << 16))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_predict_batch_matches_single() {
+        // The fixed-point batch path must agree with the independent u8
+        // `SwarmNeuron::predict` path once clamped to the byte range
+        let neuron = LinearNeuron::new(32);
+        let mut state = 0x2545_f491u32;
+        let history: Vec<u8> = (0..100)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (state >> 24) as u8
+            })
+            .collect();
+        let inputs: Vec<I16F16> = history.iter().map(|&v| I16F16::from_num(v)).collect();
+
+        let batch = neuron.predict_batch(&inputs);
+        assert_eq!(batch.len(), history.len());
+        let mut clamped = 0;
+        for (t, &predicted) in batch.iter().enumerate() {
+            let raw = predicted.to_num::<i32>();
+            clamped += usize::from(!(0..=255).contains(&raw));
+            assert_eq!(
+                raw.clamp(0, 255) as u8,
+                SwarmNeuron::predict(&neuron, &history[..=t]),
+                "t = {}",
+                t
+            );
+        }
+        // The default bias sits at the byte midpoint, so the clamp is exercised
+        assert!(clamped > 0);
+        assert!(neuron.predict_batch(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_update_batch_converges_on_linear_target() {
+        let mut neuron = LinearNeuron::new(32);
+        let inputs = residual_stream(512);
+        // Next value = 0.5 * latest + 0.25 * the one before (lags 1 and 2)
+        let targets: Vec<I16F16> = (0..inputs.len())
+            .map(|t| {
+                let prev = if t >= 1 { inputs[t - 1] } else { I16F16::ZERO };
+                inputs[t] / 2 + prev / 4
+            })
+            .collect();
+
+        let first = neuron.update_batch(&inputs, &targets);
+        let mut last = first;
+        for _ in 0..10 {
+            last = neuron.update_batch(&inputs, &targets);
+        }
+        assert!(last < first / 100, "MAE {} -> {}", first, last);
+        assert!(last < I16F16::from_num(0.01));
+        assert!((neuron.weights[0] - I16F16::from_num(0.5)).abs() < I16F16::from_num(0.02));
+        assert!((neuron.weights[1] - I16F16::from_num(0.25)).abs() < I16F16::from_num(0.02));
+
+        // Same stream through a fresh neuron gives the same weights
+        let mut replay = LinearNeuron::new(32);
+        for _ in 0..11 {
+            replay.update_batch(&inputs, &targets);
+        }
+        assert_eq!(replay.weights, neuron.weights);
+        assert_eq!(replay.bias, neuron.bias);
+    }
+
     #[test]
     fn test_refractory_period() {
         let mut neuron = LinearNeuron::new(64);
@@ -303,4 +451,64 @@ mod tests {
         // Should be able to spike again
         assert_eq!(neuron.refractory_counter, 0);
     }
+
+    #[test]
+    fn test_spike_emission_is_rate_limited() {
+        const TICK_MS: u64 = 10;
+        const MIN_INTERVAL_MS: u64 = 500;
+        let mut neuron = LinearNeuron::new(32);
+
+        // Sustained Storm: every prediction is badly wrong for 2 seconds
+        let mut emitted = Vec::new();
+        for tick in 0..200u32 {
+            let now_ms = tick as u64 * TICK_MS;
+            if let Some(spike) = neuron.check_surprise(255, 0, Regime::Storm, tick) {
+                neuron.record_spike(spike, now_ms);
+                if neuron.should_emit_spike(now_ms, MIN_INTERVAL_MS) {
+                    emitted.push(now_ms);
+                }
+            }
+            neuron.tick();
+        }
+
+        assert!(neuron.spike_history().len() > 10);
+        assert_eq!(emitted.len(), 4);
+        assert!(emitted.windows(2).all(|w| w[1] - w[0] >= MIN_INTERVAL_MS));
+        // One spike per refractory period (100 ms) = 10 per second
+        let end_ms = 199 * TICK_MS;
+        assert_eq!(neuron.recent_spike_rate(end_ms, 1000), I16F16::from_num(10));
+        // Once the Storm ends the rate decays instead of freezing
+        assert_eq!(
+            neuron.recent_spike_rate(end_ms + 500, 1000),
+            I16F16::from_num(5)
+        );
+        assert_eq!(neuron.recent_spike_rate(end_ms + 2000, 1000), 0);
+
+        // Long windows and dense bursts stay exact rather than saturating
+        let mut quiet = LinearNeuron::new(32);
+        for i in 0..10u64 {
+            quiet.record_spike(SpikeEvent::new(0, 255, 255, Regime::Calm), i * 6000);
+        }
+        let rate = quiet.recent_spike_rate(59_999, 60_000).to_num::<f32>();
+        assert!((rate - 10.0 / 60.0).abs() < 1e-3, "rate {}", rate);
+        let mut burst = LinearNeuron::new(32);
+        for i in 0..40u64 {
+            burst.record_spike(SpikeEvent::new(0, 255, 255, Regime::Storm), i * 20);
+        }
+        assert_eq!(burst.recent_spike_rate(799, 1000), I16F16::from_num(40));
+
+        // Genuinely spaced surprises all pass
+        let mut spaced = LinearNeuron::new(32);
+        for i in 0..5u64 {
+            let now_ms = i * 600;
+            let spike = spaced
+                .check_surprise(255, 0, Regime::Calm, i as u32)
+                .unwrap();
+            spaced.record_spike(spike, now_ms);
+            assert!(spaced.should_emit_spike(now_ms, MIN_INTERVAL_MS));
+            for _ in 0..REFRACTORY_PERIOD_TICKS {
+                spaced.tick();
+            }
+        }
+    }
 }
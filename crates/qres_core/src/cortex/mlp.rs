@@ -0,0 +1,438 @@
+use alloc::vec::Vec;
+use core::convert::TryInto;
+use fixed::types::I16F16;
+
+use super::neuron::{Regime, SpikeEvent, SpikeHistory, SwarmNeuron, REFRACTORY_PERIOD_TICKS};
+
+/// Lag inputs feeding the hidden layer (same look-back as `LinearNeuron`)
+pub const MLP_LAGS: [usize; 4] = [1, 2, 4, 8];
+/// Hidden layer width
+pub const MLP_HIDDEN: usize = 8;
+/// Serialized size: lags (4B) + hidden weights and biases + output weights,
+/// output bias and learning rate (4B each)
+pub const MLP_GENE_SIZE: usize =
+    MLP_LAGS.len() + 4 * (MLP_HIDDEN * MLP_LAGS.len() + MLP_HIDDEN + MLP_HIDDEN + 2);
+
+const INPUTS: usize = MLP_LAGS.len();
+/// Parameters are kept inside this range so training cannot saturate
+const WEIGHT_LIMIT: i32 = 4;
+
+/// Two-layer perceptron neuron with a fixed-point ReLU hidden layer.
+///
+/// Where `LinearNeuron` can only weigh its lags, the hidden layer lets this
+/// neuron learn interactions between them (e.g. an XOR-like residual
+/// pattern), which is what keeps evolved nodes accurate in noise. Exposes
+/// the same `predict_next`/`predict_batch`/`update_batch` interface, and all
+/// arithmetic is saturating Q16.16, so training is deterministic across
+/// platforms.
+///
+/// As a [`SwarmNeuron`] it works on byte streams scaled to `[-1, 1)`
+/// (`(v - 128) / 128`), and its genes are the [`to_bytes`](Self::to_bytes)
+/// format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MlpNeuron {
+    /// Lag intervals feeding the inputs (0 = unused)
+    lags: [usize; INPUTS],
+    /// Hidden layer weights, one row per hidden unit
+    hidden_weights: [[I16F16; INPUTS]; MLP_HIDDEN],
+    /// Hidden layer biases
+    hidden_bias: [I16F16; MLP_HIDDEN],
+    /// Output weights
+    output_weights: [I16F16; MLP_HIDDEN],
+    /// Output bias
+    output_bias: I16F16,
+    /// SGD step size
+    learning_rate: I16F16,
+    /// Refractory counter: when 0, can spike
+    refractory_counter: u32,
+    /// Entropy running average of the prediction error
+    entropy_estimate: I16F16,
+    /// Recent spikes, for rate limiting broadcasts
+    spike_history: SpikeHistory,
+}
+
+impl Default for MlpNeuron {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MlpNeuron {
+    /// Create a neuron with the default deterministic initialization
+    pub fn new() -> Self {
+        Self::with_seed(0x5eed_1e55)
+    }
+
+    /// Create a neuron whose initial weights are derived from `seed`
+    pub fn with_seed(seed: u32) -> Self {
+        let mut state = seed;
+        // Small weights in [-0.5, 0.5) from an LCG: no RNG dependency, same
+        // network on every platform
+        let mut next = || {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            I16F16::from_bits((state >> 16) as i32 - (1 << 15))
+        };
+
+        let mut hidden_weights = [[I16F16::ZERO; INPUTS]; MLP_HIDDEN];
+        for row in &mut hidden_weights {
+            for w in row.iter_mut() {
+                *w = next();
+            }
+        }
+        let mut hidden_bias = [I16F16::ZERO; MLP_HIDDEN];
+        for b in &mut hidden_bias {
+            *b = next() / 4;
+        }
+        let mut output_weights = [I16F16::ZERO; MLP_HIDDEN];
+        for w in &mut output_weights {
+            *w = next();
+        }
+
+        MlpNeuron {
+            lags: MLP_LAGS,
+            hidden_weights,
+            hidden_bias,
+            output_weights,
+            output_bias: I16F16::ZERO,
+            learning_rate: I16F16::from_num(0.03125),
+            refractory_counter: 0,
+            entropy_estimate: I16F16::from_num(0.5),
+            spike_history: SpikeHistory::new(),
+        }
+    }
+
+    /// Byte sample in the network's `[-1, 1)` input scale
+    fn scale_byte(value: u8) -> I16F16 {
+        I16F16::from_num(value as i32 - 128) / 128
+    }
+
+    /// Lagged inputs for the value following `history` (latest sample last);
+    /// lags longer than the history read as zero, as in `LinearNeuron`
+    fn inputs(&self, history: &[I16F16]) -> [I16F16; INPUTS] {
+        let mut inputs = [I16F16::ZERO; INPUTS];
+        for (input, &lag) in inputs.iter_mut().zip(&self.lags) {
+            if lag != 0 && lag < history.len() {
+                *input = history[history.len() - lag];
+            }
+        }
+        inputs
+    }
+
+    /// Hidden activations (post-ReLU) and the output for `inputs`
+    fn forward(&self, inputs: &[I16F16; INPUTS]) -> ([I16F16; MLP_HIDDEN], I16F16) {
+        let mut hidden = [I16F16::ZERO; MLP_HIDDEN];
+        let mut output = self.output_bias;
+        for (j, h) in hidden.iter_mut().enumerate() {
+            let mut pre = self.hidden_bias[j];
+            for (&w, &x) in self.hidden_weights[j].iter().zip(inputs) {
+                pre = pre.saturating_add(w.saturating_mul(x));
+            }
+            *h = pre.max(I16F16::ZERO);
+            output = output.saturating_add(self.output_weights[j].saturating_mul(*h));
+        }
+        (hidden, output)
+    }
+
+    /// Predict the value following `history` (latest sample last)
+    pub fn predict_next(&self, history: &[I16F16]) -> I16F16 {
+        self.forward(&self.inputs(history)).1
+    }
+
+    /// Predict over a whole stream: element `t` is the prediction made after
+    /// observing `inputs[..=t]`, bit-identical to `predict_next(&inputs[..=t])`.
+    pub fn predict_batch(&self, inputs: &[I16F16]) -> Vec<I16F16> {
+        (1..=inputs.len())
+            .map(|end| self.predict_next(&inputs[..end]))
+            .collect()
+    }
+
+    /// Online backpropagation pass over a stream: after observing
+    /// `inputs[..=t]` the neuron should have predicted `targets[t]`. One SGD
+    /// step per sample, in order.
+    ///
+    /// Returns the mean absolute error of the predictions made before each
+    /// update (zero for an empty batch).
+    pub fn update_batch(&mut self, inputs: &[I16F16], targets: &[I16F16]) -> I16F16 {
+        let n = inputs.len().min(targets.len());
+        if n == 0 {
+            return I16F16::ZERO;
+        }
+
+        let limit = I16F16::from_num(WEIGHT_LIMIT);
+        let clamp = |v: I16F16| v.clamp(-limit, limit);
+        let mut total_error = I16F16::ZERO;
+        for t in 0..n {
+            let x = self.inputs(&inputs[..=t]);
+            let (hidden, output) = self.forward(&x);
+            let error = targets[t].saturating_sub(output);
+            total_error = total_error.saturating_add(error.saturating_abs());
+
+            let step = error.saturating_mul(self.learning_rate);
+            for (j, &h) in hidden.iter().enumerate() {
+                // Gradient through the ReLU uses the pre-update output weight
+                let hidden_step = if h > I16F16::ZERO {
+                    step.saturating_mul(self.output_weights[j])
+                } else {
+                    I16F16::ZERO
+                };
+                self.output_weights[j] =
+                    clamp(self.output_weights[j].saturating_add(step.saturating_mul(h)));
+                for (w, &xi) in self.hidden_weights[j].iter_mut().zip(&x) {
+                    *w = clamp(w.saturating_add(hidden_step.saturating_mul(xi)));
+                }
+                self.hidden_bias[j] = clamp(self.hidden_bias[j].saturating_add(hidden_step));
+            }
+            self.output_bias = clamp(self.output_bias.saturating_add(step));
+        }
+        total_error / I16F16::saturating_from_num(n)
+    }
+
+    /// Serialize as a gene: `[lags | hidden weights | hidden bias |
+    /// output weights | output bias | learning rate]`, parameters as
+    /// little-endian Q16.16 bits ([`MLP_GENE_SIZE`] bytes)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut gene = Vec::with_capacity(MLP_GENE_SIZE);
+        for &lag in &self.lags {
+            gene.push(lag as u8);
+        }
+        let params = self
+            .hidden_weights
+            .iter()
+            .flatten()
+            .chain(&self.hidden_bias)
+            .chain(&self.output_weights)
+            .chain([&self.output_bias, &self.learning_rate]);
+        for p in params {
+            gene.extend_from_slice(&p.to_bits().to_le_bytes());
+        }
+        gene
+    }
+
+    /// Deserialize a gene written by [`to_bytes`](Self::to_bytes). Returns
+    /// `None` unless it is exactly [`MLP_GENE_SIZE`] bytes.
+    pub fn from_bytes(gene: &[u8]) -> Option<Self> {
+        if gene.len() != MLP_GENE_SIZE {
+            return None;
+        }
+        let (lag_bytes, param_bytes) = gene.split_at(INPUTS);
+        let mut params = param_bytes
+            .chunks_exact(4)
+            .map(|b| I16F16::from_bits(i32::from_le_bytes(b.try_into().unwrap_or_default())));
+        let mut next = || params.next().unwrap_or(I16F16::ZERO);
+
+        let mut neuron = MlpNeuron::new();
+        for (lag, &b) in neuron.lags.iter_mut().zip(lag_bytes) {
+            *lag = b as usize;
+        }
+        for w in neuron.hidden_weights.iter_mut().flatten() {
+            *w = next();
+        }
+        for b in &mut neuron.hidden_bias {
+            *b = next();
+        }
+        for w in &mut neuron.output_weights {
+            *w = next();
+        }
+        neuron.output_bias = next();
+        neuron.learning_rate = next();
+        Some(neuron)
+    }
+}
+
+impl SwarmNeuron for MlpNeuron {
+    fn predict(&self, history: &[u8]) -> u8 {
+        let scaled: Vec<I16F16> = history.iter().map(|&v| Self::scale_byte(v)).collect();
+        let output = self.predict_next(&scaled);
+        let value = output.saturating_mul(I16F16::from_num(128)).to_num::<i32>() + 128;
+        value.clamp(0, 255) as u8
+    }
+
+    fn check_surprise(
+        &mut self,
+        actual: u8,
+        predicted: u8,
+        regime: Regime,
+        tick: u32,
+    ) -> Option<SpikeEvent> {
+        let error = (actual as i16 - predicted as i16).unsigned_abs() as u8;
+        let error_normalized = I16F16::from_num(error as i32) / I16F16::from_num(256);
+        self.entropy_estimate = self.entropy_estimate * I16F16::from_num(0.9)
+            + error_normalized * I16F16::from_num(0.1);
+
+        if error_normalized > regime.surprise_threshold() && self.refractory_counter == 0 {
+            self.refractory_counter = REFRACTORY_PERIOD_TICKS;
+            let surprise_u8 = (self.entropy_estimate * I16F16::from_num(255)).to_num::<u8>();
+            Some(SpikeEvent::new(tick, error, surprise_u8, regime))
+        } else {
+            None
+        }
+    }
+
+    fn adapt(&mut self, signals: &[SpikeEvent], reputation: &[I16F16]) {
+        // Reputation-weighted peer error nudges the output layer, as the
+        // linear neuron does for its weights
+        let mut delta = I16F16::ZERO;
+        for (signal, rep) in signals.iter().zip(reputation) {
+            let signal_weight =
+                rep.saturating_mul(I16F16::from_num(signal.error as i32)) / I16F16::from_num(256);
+            delta = delta.saturating_add(signal_weight.saturating_mul(self.learning_rate));
+        }
+        if delta == I16F16::ZERO {
+            return;
+        }
+        let limit = I16F16::from_num(WEIGHT_LIMIT);
+        for w in &mut self.output_weights {
+            *w = w.saturating_add(delta).clamp(-limit, limit);
+        }
+    }
+
+    fn export_gene(&self) -> Vec<u8> {
+        self.to_bytes()
+    }
+
+    fn install_gene(&mut self, gene: &[u8]) -> bool {
+        let Some(learned) = Self::from_bytes(gene) else {
+            return false;
+        };
+        // Adopt the peer's parameters; local error and spike tracking carry
+        // over, and the refractory period resets as for `LinearNeuron`
+        *self = MlpNeuron {
+            entropy_estimate: self.entropy_estimate,
+            spike_history: core::mem::take(&mut self.spike_history),
+            ..learned
+        };
+        true
+    }
+
+    fn refractory_remaining(&self) -> u32 {
+        self.refractory_counter
+    }
+
+    fn tick(&mut self) {
+        self.refractory_counter = self.refractory_counter.saturating_sub(1);
+    }
+
+    fn spike_history(&self) -> &SpikeHistory {
+        &self.spike_history
+    }
+
+    fn spike_history_mut(&mut self) -> &mut SpikeHistory {
+        &mut self.spike_history
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cortex::LinearNeuron;
+
+    /// Deterministic stream of +/-1 residual signs
+    fn sign_stream(len: usize) -> Vec<I16F16> {
+        let mut state = 0x0bad_5eedu32;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                if state & 0x8000_0000 != 0 {
+                    I16F16::ONE
+                } else {
+                    -I16F16::ONE
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_gene_round_trip() {
+        let neuron = MlpNeuron::with_seed(7);
+        let gene = neuron.to_bytes();
+        assert_eq!(gene.len(), MLP_GENE_SIZE);
+        assert_eq!(MlpNeuron::from_bytes(&gene), Some(neuron));
+        assert!(MlpNeuron::from_bytes(&gene[1..]).is_none());
+    }
+
+    /// Any `SwarmNeuron` can stand in for another in the swarm
+    fn run_neuron<N: SwarmNeuron>(neuron: &mut N, stream: &[u8]) -> usize {
+        let mut spikes = 0;
+        for (t, &actual) in stream.iter().enumerate().skip(1) {
+            let predicted = neuron.predict(&stream[..t]);
+            if neuron
+                .check_surprise(actual, predicted, Regime::Calm, t as u32)
+                .is_some()
+            {
+                spikes += 1;
+            }
+            neuron.tick();
+        }
+        spikes
+    }
+
+    #[test]
+    fn test_swarm_neuron_impl() {
+        let mut neuron = MlpNeuron::with_seed(3);
+        // Flat stream at the midpoint: the scaled inputs are all zero, so the
+        // byte prediction is the zero-input output mapped back to bytes
+        let zero_output = neuron.predict_next(&[I16F16::ZERO; 16]);
+        let expected = (zero_output * 128).to_num::<i32>() + 128;
+        assert_eq!(neuron.predict(&[128; 16]) as i32, expected.clamp(0, 255));
+
+        // A square wave surprises an untrained network, but the refractory
+        // period keeps it from spiking on every sample
+        let wave: Vec<u8> = (0..64).map(|i| if i % 2 == 0 { 0 } else { 255 }).collect();
+        let spikes = run_neuron(&mut neuron, &wave);
+        assert!(spikes > 0);
+        assert!(spikes <= wave.len() / REFRACTORY_PERIOD_TICKS as usize + 1);
+
+        let gene = neuron.export_gene();
+        assert_eq!(gene, neuron.to_bytes());
+        let mut other = MlpNeuron::with_seed(99);
+        assert!(other.install_gene(&gene));
+        assert_eq!(other.to_bytes(), gene);
+        assert_eq!(other.refractory_remaining(), 0);
+        assert_eq!(other.predict(&wave), neuron.predict(&wave));
+        assert!(!other.install_gene(&gene[1..]));
+
+        let before = other.to_bytes();
+        let spike = SpikeEvent::new(1, 200, 100, Regime::Storm);
+        other.adapt(&[spike], &[I16F16::ONE]);
+        assert_ne!(other.to_bytes(), before);
+    }
+
+    #[test]
+    fn test_predict_batch_matches_single() {
+        let neuron = MlpNeuron::new();
+        let inputs = sign_stream(64);
+        let batch = neuron.predict_batch(&inputs);
+        for (t, &predicted) in batch.iter().enumerate() {
+            assert_eq!(predicted, neuron.predict_next(&inputs[..=t]));
+        }
+    }
+
+    #[test]
+    fn test_learns_xor_pattern_linear_cannot() {
+        let inputs = sign_stream(256);
+        // Next residual is the XOR (sign product) of the last two
+        let targets: Vec<I16F16> = (0..inputs.len())
+            .map(|t| {
+                let prev = if t >= 1 { inputs[t - 1] } else { I16F16::ZERO };
+                inputs[t] * prev
+            })
+            .collect();
+
+        let mut linear = LinearNeuron::new(32);
+        let mut mlp = MlpNeuron::new();
+        let mut linear_error = I16F16::ZERO;
+        let mut mlp_error = I16F16::ZERO;
+        for _ in 0..40 {
+            linear_error = linear.update_batch(&inputs, &targets);
+            mlp_error = mlp.update_batch(&inputs, &targets);
+        }
+
+        // A linear map of uncorrelated signs cannot beat predicting zero
+        assert!(
+            linear_error > I16F16::from_num(0.8),
+            "linear {}",
+            linear_error
+        );
+        assert!(mlp_error < I16F16::from_num(0.2), "mlp {}", mlp_error);
+    }
+}
@@ -17,13 +17,21 @@
 /// |-----------------------|----------------------|
 /// | Cortex | Aggregation Engine |
 /// | GeneStorage | ModelPersistence |
+/// | list_genes | list_models |
+/// | remove_gene | delete_model |
 /// | gene | model bytecode |
 pub mod linear;
+pub mod mlp;
 pub mod neuron;
+pub mod selection;
 pub mod storage;
 
 pub use linear::LinearNeuron;
-pub use neuron::{Regime, SpikeEvent, SwarmNeuron};
+pub use mlp::MlpNeuron;
+pub use neuron::{Regime, SpikeEvent, SpikeHistory, SwarmNeuron};
+pub use selection::prefers_gene;
+#[cfg(feature = "std")]
+pub use storage::DiskGeneStorage;
 #[allow(deprecated)]
 pub use storage::GeneStorage;
-pub use storage::ModelPersistence;
+pub use storage::{MemoryPersistence, ModelPersistence, PersistenceError, RetryPolicy};
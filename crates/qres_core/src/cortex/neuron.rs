@@ -1,6 +1,10 @@
+use alloc::collections::VecDeque;
 use core::fmt;
 use fixed::types::I16F16;
 
+/// Number of recent spikes kept per neuron
+pub const SPIKE_HISTORY_CAPACITY: usize = 64;
+
 /// Represents the operational regime of a SwarmNeuron
 /// Used to determine surprise thresholds and adaptation rates
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -74,6 +78,83 @@ impl fmt::Display for SpikeEvent {
     }
 }
 
+/// Bounded record of a neuron's recent spikes, used to summarize and
+/// rate-limit its surprise broadcasts.
+///
+/// Timestamps are wall-clock milliseconds supplied by the caller, independent
+/// of the tick count carried in each [`SpikeEvent`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpikeHistory {
+    /// Recent spikes with the time they were recorded, oldest first
+    spikes: VecDeque<(u64, SpikeEvent)>,
+    /// When the last spike was let through by the emit gate
+    last_emit_ms: Option<u64>,
+}
+
+impl SpikeHistory {
+    /// Create an empty history
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a spike observed at `now_ms`, evicting the oldest when full
+    pub fn record(&mut self, spike: SpikeEvent, now_ms: u64) {
+        if self.spikes.len() >= SPIKE_HISTORY_CAPACITY {
+            self.spikes.pop_front();
+        }
+        self.spikes.push_back((now_ms, spike));
+    }
+
+    /// Recorded spikes, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &SpikeEvent> {
+        self.spikes.iter().map(|(_, spike)| spike)
+    }
+
+    /// Number of recorded spikes
+    pub fn len(&self) -> usize {
+        self.spikes.len()
+    }
+
+    /// `true` if no spikes have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.spikes.is_empty()
+    }
+
+    /// Spikes per second over the `window_ms` ending at `now_ms`, so the rate
+    /// falls off once a neuron goes quiet. Spikes stamped after `now_ms`
+    /// (clock skew between callers) count as current.
+    pub fn recent_spike_rate(&self, now_ms: u64, window_ms: u64) -> I16F16 {
+        if window_ms == 0 {
+            return I16F16::ZERO;
+        }
+        let count = self
+            .spikes
+            .iter()
+            .rev()
+            .take_while(|(t, _)| now_ms.saturating_sub(*t) < window_ms)
+            .count();
+        // Integer division: count * 1000 and window_ms both overflow I16F16
+        let bits = ((count as u64) << 16) * 1000 / window_ms;
+        I16F16::from_bits(bits.min(i32::MAX as u64) as i32)
+    }
+
+    /// Emit gate: `true` if at least `min_interval_ms` has passed since the
+    /// last spike this gate let through, in which case `now_ms` becomes the
+    /// new reference point. Suppressed calls leave the gate unchanged.
+    pub fn should_emit_spike(&mut self, now_ms: u64, min_interval_ms: u64) -> bool {
+        let ready = self
+            .last_emit_ms
+            .is_none_or(|last| now_ms.saturating_sub(last) >= min_interval_ms);
+        if ready {
+            self.last_emit_ms = Some(now_ms);
+        }
+        ready
+    }
+}
+
+/// Ticks a neuron stays silent after spiking (prevents spike storms)
+pub(crate) const REFRACTORY_PERIOD_TICKS: u32 = 10;
+
 /// The "Active Neuron" trait for a distributed neural swarm
 /// Each neuron processes local data, detects anomalies, and evolves via gossip
 pub trait SwarmNeuron: Clone {
@@ -149,4 +230,30 @@ pub trait SwarmNeuron: Clone {
     /// Decrement internal tick counter (called every system tick)
     /// Updates refractory period and any other time-based state
     fn tick(&mut self);
+
+    /// Recent spikes recorded by this neuron
+    fn spike_history(&self) -> &SpikeHistory;
+
+    /// Mutable access to the spike history
+    fn spike_history_mut(&mut self) -> &mut SpikeHistory;
+
+    /// Record a spike (e.g. from `check_surprise`) observed at `now_ms`
+    fn record_spike(&mut self, spike: SpikeEvent, now_ms: u64) {
+        self.spike_history_mut().record(spike, now_ms);
+    }
+
+    /// Spikes per second over the `window_ms` ending at `now_ms`
+    fn recent_spike_rate(&self, now_ms: u64, window_ms: u64) -> I16F16 {
+        self.spike_history().recent_spike_rate(now_ms, window_ms)
+    }
+
+    /// Rate limit for surprise broadcasts: `true` if the gossip layer should
+    /// send a spike at `now_ms`, given at most one per `min_interval_ms`.
+    ///
+    /// A node stuck in `Storm` keeps spiking every refractory period; this
+    /// lets the caller drop the redundant signals in between.
+    fn should_emit_spike(&mut self, now_ms: u64, min_interval_ms: u64) -> bool {
+        self.spike_history_mut()
+            .should_emit_spike(now_ms, min_interval_ms)
+    }
 }
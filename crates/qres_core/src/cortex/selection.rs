@@ -0,0 +1,82 @@
+//! Gene Selection
+//!
+//! Decides which of several competing genes a node installs. Fitness follows
+//! the `GeneMetadata` convention (RMSE or equivalent, lower is better); ties
+//! are broken by the gene's BLAKE3 hash so that every node ends up with the
+//! same gene no matter in which order the candidates arrived.
+
+use core::cmp::Ordering;
+
+/// BLAKE3 hash of a gene, used as the deterministic tie-breaker.
+pub fn gene_hash(gene: &[u8]) -> [u8; 32] {
+    *blake3::hash(gene).as_bytes()
+}
+
+/// Order two `(gene, fitness)` candidates; `Less` means `a` is preferred.
+///
+/// Lower fitness wins, then the lower gene hash. Non-comparable fitness
+/// values (NaN) are ordered with `total_cmp` so the result stays total.
+pub fn compare_genes(a: (&[u8], f32), b: (&[u8], f32)) -> Ordering {
+    a.1.total_cmp(&b.1)
+        .then_with(|| gene_hash(a.0).cmp(&gene_hash(b.0)))
+}
+
+/// Whether `incoming` should replace the currently installed gene.
+///
+/// Installing is order-independent: applying this rule to any permutation of
+/// the same candidates leaves the same gene installed. An identical gene is
+/// never reinstalled.
+pub fn prefers_gene(incoming: (&[u8], f32), installed: Option<(&[u8], f32)>) -> bool {
+    match installed {
+        None => true,
+        Some(current) => compare_genes(incoming, current) == Ordering::Less,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    fn install_all(arrivals: &[(Vec<u8>, f32)]) -> Option<(Vec<u8>, f32)> {
+        let mut installed: Option<(Vec<u8>, f32)> = None;
+        for (gene, fitness) in arrivals {
+            let current = installed.as_ref().map(|(g, f)| (g.as_slice(), *f));
+            if prefers_gene((gene, *fitness), current) {
+                installed = Some((gene.clone(), *fitness));
+            }
+        }
+        installed
+    }
+
+    #[test]
+    fn test_equal_fitness_tie_break_is_order_independent() {
+        let a = (vec![1u8, 2, 3, 4], 0.05);
+        let b = (vec![9u8, 8, 7, 6], 0.05);
+
+        let ab = install_all(&[a.clone(), b.clone()]);
+        let ba = install_all(&[b.clone(), a.clone()]);
+        assert_eq!(ab, ba);
+
+        let expected = if gene_hash(&a.0) < gene_hash(&b.0) {
+            a
+        } else {
+            b
+        };
+        assert_eq!(ab, Some(expected));
+    }
+
+    #[test]
+    fn test_better_fitness_wins_regardless_of_hash() {
+        let good = (vec![0xAAu8; 8], 0.01);
+        let bad = (vec![0xBBu8; 8], 0.20);
+
+        assert_eq!(
+            install_all(&[good.clone(), bad.clone()]),
+            Some(good.clone())
+        );
+        assert_eq!(install_all(&[bad, good.clone()]), Some(good.clone()));
+        assert!(!prefers_gene((&good.0, good.1), Some((&good.0, good.1))));
+    }
+}
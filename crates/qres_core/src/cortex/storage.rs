@@ -3,6 +3,7 @@
 //! Defines traits for persisting model bytecode across sessions.
 //! This enables learned strategies to survive reboots via trait-based persistence.
 
+use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
 
 /// Abstract interface for storing and retrieving model bytecode.
@@ -45,6 +46,38 @@ pub trait GeneStorage {
     /// # Returns
     /// `Some(gene)` if a saved gene exists, `None` otherwise
     fn load_gene(&self, id: u32) -> Option<Vec<u8>>;
+
+    /// Ids of all nodes with stored model bytecode, in ascending order.
+    ///
+    /// Defaults to an empty list so backends written against the original
+    /// save/load-only trait keep compiling.
+    fn list_models(&self) -> Vec<u32> {
+        Vec::new()
+    }
+
+    /// Delete the stored model bytecode for a node.
+    ///
+    /// Defaults to removing nothing, for backends that cannot delete.
+    ///
+    /// # Returns
+    /// `true` if a model was removed, `false` if none was stored
+    fn delete_model(&mut self, _id: u32) -> bool {
+        false
+    }
+
+    /// Ids of all nodes with a saved gene; alias of [`list_models`](Self::list_models).
+    #[deprecated(since = "20.2.0", note = "Use list_models instead")]
+    #[allow(deprecated)]
+    fn list_genes(&self) -> Vec<u32> {
+        self.list_models()
+    }
+
+    /// Remove the saved gene for a node; alias of [`delete_model`](Self::delete_model).
+    #[deprecated(since = "20.2.0", note = "Use delete_model instead")]
+    #[allow(deprecated)]
+    fn remove_gene(&mut self, id: u32) -> bool {
+        self.delete_model(id)
+    }
 }
 
 // =============================================================================
@@ -75,14 +108,551 @@ pub trait GeneStorage {
 /// This trait inherits all methods from [`GeneStorage`]:
 /// - [`save_gene`](GeneStorage::save_gene) - Save model bytecode for a node
 /// - [`load_gene`](GeneStorage::load_gene) - Load previously saved model bytecode
+/// - [`list_models`](GeneStorage::list_models) - Enumerate nodes with saved bytecode
+/// - [`delete_model`](GeneStorage::delete_model) - Delete saved bytecode for a node
+///
+/// Backends implement `list_models` / `delete_model`; the older `list_genes` /
+/// `remove_gene` names are deprecated aliases that forward to them.
 ///
 /// # Timeline
 ///
 /// - **v20.2.0**: `ModelPersistence` introduced as subtrait bridge
 /// - **v21.0.0**: `GeneStorage` removed, `ModelPersistence` becomes standalone
 #[allow(deprecated)]
-pub trait ModelPersistence: GeneStorage {}
+pub trait ModelPersistence: GeneStorage {
+    /// Save model bytecode, reporting failure as a [`PersistenceError`].
+    fn try_save_gene(&mut self, id: u32, gene: &[u8]) -> Result<(), PersistenceError> {
+        if self.save_gene(id, gene) {
+            Ok(())
+        } else {
+            Err(PersistenceError::WriteFailed { id })
+        }
+    }
+
+    /// Save model bytecode, retrying transient failures with exponential backoff.
+    ///
+    /// `sleep` is called with the backoff in milliseconds before each retry, so
+    /// `no_std` callers can plug in their own timer.
+    ///
+    /// # Returns
+    /// The number of attempts it took, or [`PersistenceError::RetriesExhausted`]
+    /// once `policy.max_attempts` writes have failed.
+    fn save_gene_with_retry<F: FnMut(u64)>(
+        &mut self,
+        id: u32,
+        gene: &[u8],
+        policy: &RetryPolicy,
+        mut sleep: F,
+    ) -> Result<u32, PersistenceError> {
+        let mut backoff_ms = policy.initial_backoff_ms;
+        for attempt in 1..=policy.max_attempts {
+            if self.try_save_gene(id, gene).is_ok() {
+                return Ok(attempt);
+            }
+            if attempt < policy.max_attempts {
+                sleep(backoff_ms);
+                backoff_ms = backoff_ms.saturating_mul(2).min(policy.max_backoff_ms);
+            }
+        }
+        Err(PersistenceError::RetriesExhausted {
+            id,
+            attempts: policy.max_attempts,
+        })
+    }
+}
 
 /// Blanket implementation: all `GeneStorage` implementors are `ModelPersistence`.
 #[allow(deprecated)]
 impl<T: GeneStorage> ModelPersistence for T {}
+
+/// Bounded retry schedule for [`ModelPersistence::save_gene_with_retry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of write attempts (including the first)
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled after each failure
+    pub initial_backoff_ms: u64,
+    /// Upper bound on the delay between attempts
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_backoff_ms: 10,
+            max_backoff_ms: 200,
+        }
+    }
+}
+
+/// Reason model bytecode could not be persisted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PersistenceError {
+    /// The backend rejected a single write
+    WriteFailed { id: u32 },
+    /// Every attempt allowed by the [`RetryPolicy`] failed
+    RetriesExhausted { id: u32, attempts: u32 },
+}
+
+impl core::fmt::Display for PersistenceError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PersistenceError::WriteFailed { id } => {
+                write!(f, "failed to persist model bytecode for node {}", id)
+            }
+            PersistenceError::RetriesExhausted { id, attempts } => write!(
+                f,
+                "failed to persist model bytecode for node {} after {} attempts",
+                id, attempts
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PersistenceError {}
+
+/// In-memory storage for tests and targets without a filesystem (e.g. WASM).
+///
+/// Nothing survives the process, but every operation is fast and
+/// deterministic. Models are kept in a `BTreeMap` (no `HashMap` in `no_std`),
+/// which also keeps [`list_models`](GeneStorage::list_models) sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryPersistence {
+    models: BTreeMap<u32, Vec<u8>>,
+}
+
+impl MemoryPersistence {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[allow(deprecated)]
+impl GeneStorage for MemoryPersistence {
+    fn save_gene(&mut self, id: u32, gene: &[u8]) -> bool {
+        self.models.insert(id, gene.to_vec());
+        true
+    }
+
+    fn load_gene(&self, id: u32) -> Option<Vec<u8>> {
+        self.models.get(&id).cloned()
+    }
+
+    fn list_models(&self) -> Vec<u32> {
+        self.models.keys().copied().collect()
+    }
+
+    fn delete_model(&mut self, id: u32) -> bool {
+        self.models.remove(&id).is_some()
+    }
+}
+
+/// Bytes in front of a checksummed gene: payload length (u32 LE) + BLAKE3 digest
+pub const GENE_HEADER_LEN: usize = 4 + 32;
+
+/// Prefix `gene` with its length and BLAKE3 digest for storage.
+pub fn seal_gene(gene: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(GENE_HEADER_LEN + gene.len());
+    out.extend_from_slice(&(gene.len() as u32).to_le_bytes());
+    out.extend_from_slice(blake3::hash(gene).as_bytes());
+    out.extend_from_slice(gene);
+    out
+}
+
+/// Strip and verify the header written by [`seal_gene`].
+///
+/// Returns `None` if the data is truncated, padded, or its digest does not
+/// match, so a corrupt gene reads the same as a missing one.
+pub fn open_gene(data: &[u8]) -> Option<Vec<u8>> {
+    let (len, rest) = data.split_first_chunk::<4>()?;
+    let (digest, gene) = rest.split_first_chunk::<32>()?;
+    if u32::from_le_bytes(*len) as usize != gene.len() || blake3::hash(gene).as_bytes() != digest {
+        return None;
+    }
+    Some(gene.to_vec())
+}
+
+/// What a stored gene file turned out to hold.
+#[cfg(feature = "std")]
+#[derive(Debug, PartialEq, Eq)]
+enum GeneFile {
+    /// Checksummed gene that verified
+    Sealed(Vec<u8>),
+    /// Header length matches the payload but the digest does not: bit rot
+    Corrupt,
+    /// No consistent header: a raw gene from before checksums were the
+    /// default, or a checksummed write that was cut short
+    Unsealed,
+}
+
+#[cfg(feature = "std")]
+fn inspect_gene_file(data: &[u8]) -> GeneFile {
+    if let Some(gene) = open_gene(data) {
+        return GeneFile::Sealed(gene);
+    }
+    let consistent = data.first_chunk::<4>().is_some_and(|len| {
+        Some(u32::from_le_bytes(*len) as usize) == data.len().checked_sub(GENE_HEADER_LEN)
+    });
+    if consistent {
+        GeneFile::Corrupt
+    } else {
+        GeneFile::Unsealed
+    }
+}
+
+/// Disk-backed storage keeping one `gene_{id}.bin` file per node in a directory.
+///
+/// Genes are written with a length + BLAKE3 header (see [`seal_gene`]) and
+/// verified on load, so a truncated or bit-rotted file is reported as absent
+/// and the node re-evolves instead of running a malformed neuron. Use
+/// [`with_checksums(false)`](Self::with_checksums) to read and write the raw
+/// format. Raw files written before checksums were the default also load as
+/// absent, with a warning pointing at
+/// [`migrate_raw_gene`](Self::migrate_raw_gene).
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct DiskGeneStorage {
+    storage_dir: std::path::PathBuf,
+    checksums: bool,
+}
+
+#[cfg(feature = "std")]
+impl DiskGeneStorage {
+    /// Use `dir` for gene files, creating it if needed.
+    pub fn new(dir: impl Into<std::path::PathBuf>) -> Self {
+        let storage_dir = dir.into();
+        if let Err(e) = std::fs::create_dir_all(&storage_dir) {
+            eprintln!(
+                "[DiskGeneStorage] Failed to create {}: {}",
+                storage_dir.display(),
+                e
+            );
+        }
+        Self {
+            storage_dir,
+            checksums: true,
+        }
+    }
+
+    /// Enable or disable the checksummed file format (enabled by default).
+    pub fn with_checksums(mut self, enabled: bool) -> Self {
+        self.checksums = enabled;
+        self
+    }
+
+    pub fn gene_path(&self, id: u32) -> std::path::PathBuf {
+        self.storage_dir.join(alloc::format!("gene_{}.bin", id))
+    }
+
+    /// Rewrite gene `id` from the raw format into the checksummed one.
+    ///
+    /// Only for files known to predate checksums: a truncated checksummed
+    /// file cannot be told apart from a raw one and would be sealed as-is.
+    /// Files that already verify are left alone. Returns whether the gene is
+    /// now stored checksummed.
+    pub fn migrate_raw_gene(&self, id: u32) -> bool {
+        let path = self.gene_path(id);
+        let Ok(data) = std::fs::read(&path) else {
+            return false;
+        };
+        if open_gene(&data).is_some() {
+            return true;
+        }
+        match std::fs::write(&path, seal_gene(&data)) {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("[DiskGeneStorage] Failed to migrate gene {}: {}", id, e);
+                false
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[allow(deprecated)]
+impl GeneStorage for DiskGeneStorage {
+    fn save_gene(&mut self, id: u32, gene: &[u8]) -> bool {
+        let result = if self.checksums {
+            std::fs::write(self.gene_path(id), seal_gene(gene))
+        } else {
+            std::fs::write(self.gene_path(id), gene)
+        };
+        match result {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("[DiskGeneStorage] Failed to save gene {}: {}", id, e);
+                false
+            }
+        }
+    }
+
+    fn load_gene(&self, id: u32) -> Option<Vec<u8>> {
+        let data = std::fs::read(self.gene_path(id)).ok()?;
+        if !self.checksums {
+            return Some(data);
+        }
+        match inspect_gene_file(&data) {
+            GeneFile::Sealed(gene) => Some(gene),
+            GeneFile::Corrupt => {
+                eprintln!("[DiskGeneStorage] Gene {} failed checksum, ignoring", id);
+                None
+            }
+            GeneFile::Unsealed => {
+                eprintln!(
+                    "[DiskGeneStorage] Gene {} has no checksum header (raw format from an older \
+                     build, or a truncated write), ignoring; migrate_raw_gene({}) converts a raw gene",
+                    id, id
+                );
+                None
+            }
+        }
+    }
+
+    fn list_models(&self) -> Vec<u32> {
+        let Ok(entries) = std::fs::read_dir(&self.storage_dir) else {
+            return Vec::new();
+        };
+        let mut ids: Vec<u32> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name();
+                name.to_str()?
+                    .strip_prefix("gene_")?
+                    .strip_suffix(".bin")?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    fn delete_model(&mut self, id: u32) -> bool {
+        std::fs::remove_file(self.gene_path(id)).is_ok()
+    }
+}
+
+#[cfg(test)]
+#[allow(deprecated)]
+mod tests {
+    use super::*;
+
+    /// Storage whose first `failures` writes fail (e.g. a disk that fills up briefly).
+    struct FlakyStorage {
+        failures: u32,
+        writes: u32,
+        genes: BTreeMap<u32, Vec<u8>>,
+    }
+
+    impl GeneStorage for FlakyStorage {
+        fn save_gene(&mut self, id: u32, gene: &[u8]) -> bool {
+            self.writes += 1;
+            if self.writes <= self.failures {
+                return false;
+            }
+            self.genes.insert(id, gene.to_vec());
+            true
+        }
+
+        fn load_gene(&self, id: u32) -> Option<Vec<u8>> {
+            self.genes.get(&id).cloned()
+        }
+
+        fn list_models(&self) -> Vec<u32> {
+            self.genes.keys().copied().collect()
+        }
+
+        fn delete_model(&mut self, id: u32) -> bool {
+            self.genes.remove(&id).is_some()
+        }
+    }
+
+    /// A backend implementing only the original save/load methods.
+    struct SaveLoadOnly(BTreeMap<u32, Vec<u8>>);
+
+    impl GeneStorage for SaveLoadOnly {
+        fn save_gene(&mut self, id: u32, gene: &[u8]) -> bool {
+            self.0.insert(id, gene.to_vec());
+            true
+        }
+
+        fn load_gene(&self, id: u32) -> Option<Vec<u8>> {
+            self.0.get(&id).cloned()
+        }
+    }
+
+    fn flaky(failures: u32) -> FlakyStorage {
+        FlakyStorage {
+            failures,
+            writes: 0,
+            genes: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_retry_persists_after_transient_failures() {
+        let mut storage = flaky(2);
+        let mut delays = Vec::new();
+        let attempts = storage
+            .save_gene_with_retry(7, &[1, 2, 3], &RetryPolicy::default(), |ms| delays.push(ms))
+            .unwrap();
+
+        assert_eq!(attempts, 3);
+        assert_eq!(delays, vec![10, 20]);
+        assert_eq!(storage.load_gene(7), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_retry_budget_is_bounded() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            initial_backoff_ms: 50,
+            max_backoff_ms: 120,
+        };
+        let mut storage = flaky(u32::MAX);
+        let mut delays = Vec::new();
+        let err = storage
+            .save_gene_with_retry(3, &[9], &policy, |ms| delays.push(ms))
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            PersistenceError::RetriesExhausted { id: 3, attempts: 5 }
+        );
+        assert_eq!(storage.writes, 5);
+        assert_eq!(delays, vec![50, 100, 120, 120]);
+        assert_eq!(storage.load_gene(3), None);
+        assert_eq!(
+            flaky(1).try_save_gene(3, &[9]),
+            Err(PersistenceError::WriteFailed { id: 3 })
+        );
+    }
+
+    /// Saving several models, deleting some, and listing again, against any
+    /// backend (including through the deprecated gene names).
+    fn exercise_list_and_delete_models<S: ModelPersistence>(storage: &mut S) {
+        assert!(storage.list_models().is_empty());
+        for id in [9, 2, 5, 4] {
+            assert!(storage.try_save_gene(id, &[id as u8; 4]).is_ok());
+        }
+        assert_eq!(storage.list_models(), vec![2, 4, 5, 9]);
+        assert_eq!(storage.list_genes(), storage.list_models());
+
+        assert!(storage.delete_model(5));
+        assert_eq!(storage.list_models(), vec![2, 4, 9]);
+        assert_eq!(storage.load_gene(5), None);
+        assert_eq!(storage.load_gene(9), Some(vec![9; 4]));
+        assert!(!storage.delete_model(5));
+        assert!(!storage.delete_model(42));
+
+        assert!(storage.remove_gene(4));
+        assert_eq!(storage.list_models(), vec![2, 9]);
+        assert!(!storage.remove_gene(4));
+    }
+
+    #[test]
+    fn test_list_and_delete_models_in_memory() {
+        exercise_list_and_delete_models(&mut flaky(0));
+        exercise_list_and_delete_models(&mut MemoryPersistence::new());
+    }
+
+    #[test]
+    fn test_save_load_only_backend_lists_nothing() {
+        let mut storage = SaveLoadOnly(BTreeMap::new());
+        assert!(storage.save_gene(1, &[1]));
+        assert!(storage.list_models().is_empty());
+        assert!(!storage.delete_model(1));
+        assert_eq!(storage.load_gene(1), Some(vec![1]));
+    }
+
+    #[test]
+    fn test_memory_persistence_round_trip() {
+        let mut storage = MemoryPersistence::new();
+        assert_eq!(storage.load_gene(1), None);
+        assert_eq!(storage.try_save_gene(1, &[1, 2, 3]), Ok(()));
+        assert_eq!(storage.load_gene(1), Some(vec![1, 2, 3]));
+
+        // Overwrite replaces the previous model
+        assert_eq!(storage.try_save_gene(1, &[4]), Ok(()));
+        assert_eq!(storage.load_gene(1), Some(vec![4]));
+        assert_eq!(storage.list_models(), vec![1]);
+
+        assert!(storage.delete_model(1));
+        assert_eq!(storage.load_gene(1), None);
+        assert!(storage.list_models().is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_list_and_delete_models_on_disk() {
+        let dir =
+            std::env::temp_dir().join(alloc::format!("qres_model_list_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut storage = DiskGeneStorage::new(&dir);
+
+        // Unrelated files in the directory are not models
+        std::fs::write(dir.join("notes.txt"), b"x").unwrap();
+        std::fs::write(dir.join("gene_abc.bin"), b"x").unwrap();
+        exercise_list_and_delete_models(&mut storage);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_sealed_gene_round_trip() {
+        let sealed = seal_gene(&[1, 2, 3]);
+        assert_eq!(sealed.len(), GENE_HEADER_LEN + 3);
+        assert_eq!(open_gene(&sealed), Some(vec![1, 2, 3]));
+        assert_eq!(open_gene(&seal_gene(&[])), Some(vec![]));
+
+        assert_eq!(open_gene(&sealed[..sealed.len() - 1]), None);
+        assert_eq!(open_gene(&[sealed.as_slice(), &[0]].concat()), None);
+        assert_eq!(open_gene(&sealed[..10]), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_corrupt_gene_file_loads_as_missing() {
+        let dir =
+            std::env::temp_dir().join(alloc::format!("qres_gene_checksum_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut storage = DiskGeneStorage::new(&dir);
+        assert!(storage.save_gene(1, &[7; 16]));
+        assert!(storage.save_gene(2, &[7; 16]));
+        assert_eq!(storage.load_gene(1), Some(vec![7; 16]));
+
+        // Flip one bit in gene 1, truncate gene 2
+        let mut data = std::fs::read(storage.gene_path(1)).unwrap();
+        data[GENE_HEADER_LEN + 3] ^= 0x01;
+        std::fs::write(storage.gene_path(1), &data).unwrap();
+        let data = std::fs::read(storage.gene_path(2)).unwrap();
+        std::fs::write(storage.gene_path(2), &data[..data.len() - 4]).unwrap();
+
+        assert_eq!(storage.load_gene(1), None);
+        assert_eq!(storage.load_gene(2), None);
+
+        // The raw format stores and returns bytes untouched
+        let mut raw = DiskGeneStorage::new(&dir).with_checksums(false);
+        assert!(raw.save_gene(3, &[5; 4]));
+        assert_eq!(std::fs::read(raw.gene_path(3)).unwrap(), vec![5; 4]);
+        assert_eq!(raw.load_gene(3), Some(vec![5; 4]));
+        assert_eq!(storage.load_gene(3), None);
+
+        // Bit rot and headerless files are told apart
+        let read = |id| inspect_gene_file(&std::fs::read(storage.gene_path(id)).unwrap());
+        assert_eq!(read(1), GeneFile::Corrupt);
+        assert_eq!(read(3), GeneFile::Unsealed);
+
+        // A legacy raw gene migrates into the checksummed format
+        assert!(storage.migrate_raw_gene(3));
+        assert_eq!(storage.load_gene(3), Some(vec![5; 4]));
+        assert!(storage.migrate_raw_gene(3));
+        assert_eq!(storage.load_gene(3), Some(vec![5; 4]));
+        assert!(!storage.migrate_raw_gene(4));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
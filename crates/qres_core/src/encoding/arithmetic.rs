@@ -161,10 +161,54 @@ pub fn decompress_residuals(data: &[u8], original_len: usize) -> Vec<u8> {
     output
 }
 
-/// Compresses BFP-16 vector components (exponent + mantissas)
-/// Format: `[exponent: i8][mantissas: 16-bit BE...]`
+/// CRC-8 (polynomial 0x07) appended to checksummed BFP payloads
+///
+/// A degree-8 CRC catches every error burst of up to 8 bits, so any single
+/// corrupted byte is detected.
+fn bfp_crc8(data: &[u8]) -> u8 {
+    let mut crc = 0u8;
+    for &byte in data {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+fn append_bfp_checksum(mut buffer: Vec<u8>) -> Vec<u8> {
+    buffer.push(bfp_crc8(&buffer));
+    buffer
+}
+
+/// Verifies and strips the trailing CRC-8, returning the raw payload
+fn strip_bfp_checksum(data: &[u8]) -> crate::Result<&[u8]> {
+    let Some((&crc, payload)) = data.split_last() else {
+        return Err(crate::QresError::InvalidData("Empty BFP data".into()));
+    };
+    if bfp_crc8(payload) != crc {
+        return Err(crate::QresError::InvalidData(
+            "BFP checksum mismatch".into(),
+        ));
+    }
+    Ok(payload)
+}
+
+/// Compresses BFP-16 vector components with a trailing CRC-8
+/// Format: `[exponent: i8][mantissas: 16-bit BE...][crc8: u8]`
 pub fn compress_bfp(exponent: i8, mantissas: &[i16]) -> Vec<u8> {
-    let mut buffer = Vec::with_capacity(1 + mantissas.len() * 2);
+    append_bfp_checksum(compress_bfp_raw(exponent, mantissas))
+}
+
+/// Compresses BFP-16 vector components without a checksum, for callers
+/// that frame their own integrity check
+/// Format: `[exponent: i8][mantissas: 16-bit BE...]`
+pub fn compress_bfp_raw(exponent: i8, mantissas: &[i16]) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(2 + mantissas.len() * 2);
     // 1. Shared Exponent
     buffer.push(exponent as u8);
 
@@ -175,8 +219,15 @@ pub fn compress_bfp(exponent: i8, mantissas: &[i16]) -> Vec<u8> {
     buffer
 }
 
-/// Decompresses BFP-16 vector components
-pub fn decompress_bfp(
+/// Decompresses BFP-16 vector components produced by [`compress_bfp`]
+///
+/// Fails on a checksum mismatch as well as on a malformed payload.
+pub fn decompress_bfp(data: &[u8], valid_len: usize) -> crate::Result<(i8, Vec<i16>)> {
+    decompress_bfp_raw(strip_bfp_checksum(data)?, valid_len)
+}
+
+/// Decompresses BFP-16 vector components produced by [`compress_bfp_raw`]
+pub fn decompress_bfp_raw(
     data: &[u8],
     _valid_len: usize, // Ignored for raw format, but kept for signature compatibility if needed
 ) -> crate::Result<(i8, Vec<i16>)> {
@@ -207,3 +258,48 @@ pub fn decompress_bfp(
 
     Ok((exponent, mantissas))
 }
+
+/// Compresses BFP-8 vector components with a trailing CRC-8
+/// Format: `[exponent: i8][mantissas: i8...][crc8: u8]`
+pub fn compress_bfp8(exponent: i8, mantissas: &[i8]) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(2 + mantissas.len());
+    buffer.push(exponent as u8);
+    buffer.extend(mantissas.iter().map(|&m| m as u8));
+    append_bfp_checksum(buffer)
+}
+
+/// Decompresses BFP-8 vector components
+pub fn decompress_bfp8(data: &[u8]) -> crate::Result<(i8, Vec<i8>)> {
+    let Some((&exponent, content)) = strip_bfp_checksum(data)?.split_first() else {
+        return Err(crate::QresError::InvalidData("Empty BFP data".into()));
+    };
+    Ok((exponent as i8, content.iter().map(|&b| b as i8).collect()))
+}
+
+/// Compresses BFP-32 vector components with a trailing CRC-8
+/// Format: `[exponent: i8][mantissas: 32-bit BE...][crc8: u8]`
+pub fn compress_bfp32(exponent: i8, mantissas: &[i32]) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(2 + mantissas.len() * 4);
+    buffer.push(exponent as u8);
+    for &m in mantissas {
+        buffer.extend_from_slice(&m.to_be_bytes());
+    }
+    append_bfp_checksum(buffer)
+}
+
+/// Decompresses BFP-32 vector components
+pub fn decompress_bfp32(data: &[u8]) -> crate::Result<(i8, Vec<i32>)> {
+    let Some((&exponent, content)) = strip_bfp_checksum(data)?.split_first() else {
+        return Err(crate::QresError::InvalidData("Empty BFP data".into()));
+    };
+    if content.len() % 4 != 0 {
+        return Err(crate::QresError::InvalidData(
+            "Invalid BFP payload length".into(),
+        ));
+    }
+    let mantissas = content
+        .chunks_exact(4)
+        .map(|c| i32::from_be_bytes([c[0], c[1], c[2], c[3]]))
+        .collect();
+    Ok((exponent as i8, mantissas))
+}
@@ -156,6 +156,41 @@ impl PredictorSet {
     }
 }
 
+impl PredictorSet {
+    /// Estimate whether `chunk` compresses better with the brain `weights`
+    /// (as passed to [`compress_chunk`]) or with the default mixer.
+    ///
+    /// Trial-encodes the first [`AUTO_PROBE_BYTES`] of the chunk once per
+    /// candidate, reusing this set's memory, and charges the neural candidate
+    /// for the weights stored in its chunk header. Returns
+    /// [`PREDICTOR_ID_NEURAL`] only when that wins outright; pass `weights`
+    /// to `compress_chunk` accordingly. Leaves the set dirty: reset it before
+    /// decoding with it.
+    pub fn best_predictor_for(&mut self, chunk: &[u8], weights: Option<&[u8]>) -> u8 {
+        let Some(weights) = weights.filter(|w| !w.is_empty()) else {
+            return PREDICTOR_ID_DEFAULT;
+        };
+        let probe = &chunk[..chunk.len().min(AUTO_PROBE_BYTES)];
+        let mut scratch = vec![0u8; probe.len() + 1024];
+
+        self.reset(None, None);
+        let default_len =
+            predictive_encode_v4_with_state(probe, None, self, &mut scratch).unwrap_or(usize::MAX);
+
+        let parsed = parse_mixer_weights(Some(&effective_weights(weights)));
+        let (init_w, global_w) = split_mixer_weights(&parsed);
+        self.reset(init_w, global_w);
+        let neural_len = predictive_encode_v4_with_state(probe, None, self, &mut scratch)
+            .map_or(usize::MAX, |len| len + WEIGHTS_LEN);
+
+        if neural_len < default_len {
+            PREDICTOR_ID_NEURAL
+        } else {
+            PREDICTOR_ID_DEFAULT
+        }
+    }
+}
+
 impl Default for PredictorSet {
     fn default() -> Self {
         Self::new(None, None)
@@ -168,11 +203,23 @@ const CHUNK_SIZE: usize = 1024 * 1024;
 const QRES_MAGIC: &[u8] = b"QRES";
 const QRES_PROTOCOL_VERSION: u8 = 10;
 
-#[allow(dead_code)]
-const PREDICTOR_ID_DEFAULT: u8 = 0;
-#[allow(dead_code)]
-const PREDICTOR_ID_NEURAL: u8 = 1;
-const PREDICTOR_ID_SPLIT: u8 = 2;
+pub const PREDICTOR_ID_DEFAULT: u8 = 0;
+pub const PREDICTOR_ID_NEURAL: u8 = 1;
+pub const PREDICTOR_ID_SPLIT: u8 = 2;
+/// Constant-run chunks; chosen automatically for single-byte inputs, never requested.
+pub const PREDICTOR_ID_RUN: u8 = 3;
+
+/// Codec mode for a chunk made of one repeated byte: `[flag][len:4][byte]`.
+const CODEC_MODE_RUN: u8 = 0x04;
+const RUN_CHUNK_LEN: usize = 6;
+/// Largest chunk a run record may expand to (the daemon's `--chunk-size` cap).
+pub const MAX_RUN_CHUNK_LEN: usize = 16 * 1024 * 1024;
+/// Codec mode of chunks stored by the daemon's LZ4 fallback (decoded externally,
+/// like the Zstd fallback's 0x01).
+const CODEC_MODE_LZ4: u8 = 0x05;
+
+/// Leading bytes of a chunk trial-encoded by [`PredictorSet::best_predictor_for`].
+pub const AUTO_PROBE_BYTES: usize = 4096;
 
 const NUM_PREDICTORS: usize = 6;
 const WEIGHTS_LEN: usize = NUM_PREDICTORS * 4;
@@ -254,7 +301,7 @@ fn predictive_encode_v4_with_state(
         let base_residual = actual.wrapping_sub(mixed_prediction) as i8;
 
         let residual = if q_factor > 1 {
-            (base_residual / q_factor) * q_factor
+            quantize_residual(actual, mixed_prediction, base_residual, q_factor)
         } else {
             base_residual
         };
@@ -295,35 +342,68 @@ fn predictive_encode_v4_with_state(
     Ok(compressed_data.len())
 }
 
+/// Quantize a residual to a multiple of `q_factor`, truncating toward zero,
+/// unless the reconstruction would then wrap past 0 or 255 (which turns a
+/// small error into one near 255); the neighbouring step is used instead.
+fn quantize_residual(actual: u8, prediction: u8, residual: i8, q_factor: i8) -> i8 {
+    let truncated = (residual / q_factor) * q_factor;
+    let error = |r: i8| actual.abs_diff(prediction.wrapping_add(r as u8));
+    if error(truncated) < q_factor as u8 {
+        return truncated;
+    }
+    [
+        truncated.checked_sub(q_factor),
+        truncated.checked_add(q_factor),
+    ]
+    .into_iter()
+    .flatten()
+    .fold(
+        truncated,
+        |best, r| if error(r) < error(best) { r } else { best },
+    )
+}
+
+/// Decode little-endian Q16.16 mixer weights.
+fn parse_mixer_weights(weights: Option<&[u8]>) -> Vec<i32> {
+    weights
+        .unwrap_or_default()
+        .chunks_exact(4)
+        .map(|chunk| i32::from_le_bytes(chunk.try_into().unwrap()))
+        .collect()
+}
+
+/// Split parsed weights into the mixer's initial and global (FedProx) halves.
+fn split_mixer_weights(weights: &[i32]) -> (Option<&[i32]>, Option<&[i32]>) {
+    if weights.len() >= 2 * NUM_MODELS {
+        (
+            Some(&weights[0..NUM_MODELS]),
+            Some(&weights[NUM_MODELS..2 * NUM_MODELS]),
+        )
+    } else if weights.len() >= NUM_MODELS {
+        (Some(&weights[0..NUM_MODELS]), None)
+    } else {
+        (None, None)
+    }
+}
+
+/// The weight bytes `compress_chunk` hands to the encoder: up to
+/// `WEIGHTS_LEN` initial weights, plus the global half when present.
+fn effective_weights(weights: &[u8]) -> Vec<u8> {
+    let mut effective = weights[..weights.len().min(WEIGHTS_LEN)].to_vec();
+    if weights.len() >= WEIGHTS_LEN * 2 {
+        effective.extend_from_slice(&weights[WEIGHTS_LEN..WEIGHTS_LEN * 2]);
+    }
+    effective
+}
+
 fn predictive_encode_v4(
     data: &[u8],
     config: Option<&crate::config::QresConfig>,
     weights: Option<&[u8]>,
     output: &mut [u8],
 ) -> Result<usize> {
-    // Parse weights
-    let mut safe_weights_vec = Vec::new();
-    if let Some(w_bytes) = weights {
-        for chunk in w_bytes.chunks_exact(4) {
-            safe_weights_vec.push(i32::from_le_bytes(chunk.try_into().unwrap()));
-        }
-    }
-
-    let (init_w, global_w) = if !safe_weights_vec.is_empty() {
-        let wc = safe_weights_vec.len();
-        if wc >= 2 * NUM_MODELS {
-            (
-                Some(&safe_weights_vec[0..NUM_MODELS]),
-                Some(&safe_weights_vec[NUM_MODELS..2 * NUM_MODELS]),
-            )
-        } else if wc >= NUM_MODELS {
-            (Some(&safe_weights_vec[0..NUM_MODELS]), None)
-        } else {
-            (None, None)
-        }
-    } else {
-        (None, None)
-    };
+    let safe_weights_vec = parse_mixer_weights(weights);
+    let (init_w, global_w) = split_mixer_weights(&safe_weights_vec);
 
     // Create temporary PredictorSet (backward compatibility wrapper)
     let mut state = PredictorSet::new(init_w, global_w);
@@ -386,29 +466,8 @@ fn predictive_decode_v4(
     decoded_len: usize,
     weights: Option<&[u8]>,
 ) -> Vec<u8> {
-    // Parse weights
-    let mut safe_weights_vec = Vec::new();
-    if let Some(w_bytes) = weights {
-        for chunk in w_bytes.chunks_exact(4) {
-            safe_weights_vec.push(i32::from_le_bytes(chunk.try_into().unwrap()));
-        }
-    }
-
-    let (init_w, global_w) = if !safe_weights_vec.is_empty() {
-        let wc = safe_weights_vec.len();
-        if wc >= 2 * NUM_MODELS {
-            (
-                Some(&safe_weights_vec[0..NUM_MODELS]),
-                Some(&safe_weights_vec[NUM_MODELS..2 * NUM_MODELS]),
-            )
-        } else if wc >= NUM_MODELS {
-            (Some(&safe_weights_vec[0..NUM_MODELS]), None)
-        } else {
-            (None, None)
-        }
-    } else {
-        (None, None)
-    };
+    let safe_weights_vec = parse_mixer_weights(weights);
+    let (init_w, global_w) = split_mixer_weights(&safe_weights_vec);
 
     // Create temporary PredictorSet (backward compatibility wrapper)
     let mut state = PredictorSet::new(init_w, global_w);
@@ -429,13 +488,16 @@ pub fn compress_chunk(
         )));
     }
 
-    let mut effective_weights = Vec::new();
+    if let Some(byte) = constant_run_byte(chunk) {
+        return encode_run_chunk(chunk.len(), byte, output);
+    }
+
+    let effective_weights = _weights.map(effective_weights).unwrap_or_default();
     let mut is_neural = false;
     let mut stored_init_weights = Vec::new();
 
     if let Some(w) = _weights {
         let take = w.len().min(WEIGHTS_LEN);
-        effective_weights.extend_from_slice(&w[0..take]);
         if take > 0 {
             is_neural = true;
             stored_init_weights.extend_from_slice(&w[0..take]);
@@ -445,12 +507,6 @@ pub fn compress_chunk(
         }
     }
 
-    if let Some(w) = _weights {
-        if w.len() >= WEIGHTS_LEN * 2 {
-            effective_weights.extend_from_slice(&w[WEIGHTS_LEN..WEIGHTS_LEN * 2]);
-        }
-    }
-
     let w_arg = if effective_weights.is_empty() {
         None
     } else {
@@ -500,6 +556,49 @@ pub fn compress_chunk(
     }
 }
 
+/// The repeated byte if `chunk` is a single-byte run worth encoding as one.
+fn constant_run_byte(chunk: &[u8]) -> Option<u8> {
+    let (&first, rest) = chunk.split_first()?;
+    (chunk.len() > RUN_CHUNK_LEN
+        && chunk.len() <= MAX_RUN_CHUNK_LEN
+        && rest.iter().all(|&b| b == first))
+    .then_some(first)
+}
+
+fn encode_run_chunk(len: usize, byte: u8, output: &mut [u8]) -> Result<usize> {
+    if output.len() < RUN_CHUNK_LEN {
+        return Err(QresError::Other(String::from(
+            "Buffer too small for header",
+        )));
+    }
+    output[0] = ((QRES_PROTOCOL_VERSION & 0x0F) << 4) | CODEC_MODE_RUN;
+    output[1..5].copy_from_slice(&(len as u32).to_le_bytes());
+    output[5] = byte;
+    Ok(RUN_CHUNK_LEN)
+}
+
+/// Expand a run record, checking its stored length before allocating: the
+/// encoder only writes runs longer than the record itself and no longer than
+/// [`MAX_RUN_CHUNK_LEN`].
+fn decode_run_chunk(compressed: &[u8], decomp_len: usize) -> Result<Vec<u8>> {
+    if compressed.len() != RUN_CHUNK_LEN {
+        return Err(QresError::InvalidData(format!(
+            "Run chunk is {} bytes, expected {}",
+            compressed.len(),
+            RUN_CHUNK_LEN
+        )));
+    }
+    if decomp_len <= RUN_CHUNK_LEN || decomp_len > MAX_RUN_CHUNK_LEN {
+        return Err(QresError::InvalidData(format!(
+            "Run length {} outside {}..={}",
+            decomp_len,
+            RUN_CHUNK_LEN + 1,
+            MAX_RUN_CHUNK_LEN
+        )));
+    }
+    Ok(vec![compressed[5]; decomp_len])
+}
+
 pub fn decompress_chunk(
     compressed: &[u8],
     _predictor_id: u8,
@@ -534,6 +633,9 @@ pub fn decompress_chunk(
                 "Zstd fallback chunk - handle externally",
             )))
         }
+        CODEC_MODE_LZ4 => Err(QresError::CompressionError(String::from(
+            "LZ4 fallback chunk - handle externally",
+        ))),
         0x02 => {
             let header_size = 5 + WEIGHTS_LEN;
             if compressed.len() < header_size {
@@ -563,6 +665,7 @@ pub fn decompress_chunk(
                 w_arg,
             ))
         }
+        CODEC_MODE_RUN => decode_run_chunk(compressed, decomp_len),
         0x03 => {
             // Split logic omitted for brevity (unchanged)
             Err(QresError::Other(String::from(
@@ -663,6 +766,9 @@ pub fn decompress_chunk_with_state(
                 "Zstd fallback chunk - handle externally",
             )))
         }
+        CODEC_MODE_LZ4 => Err(QresError::CompressionError(String::from(
+            "LZ4 fallback chunk - handle externally",
+        ))),
         0x02 => {
             let header_size = 5 + WEIGHTS_LEN;
             if compressed.len() < header_size {
@@ -710,6 +816,7 @@ pub fn decompress_chunk_with_state(
                 state,
             ))
         }
+        CODEC_MODE_RUN => decode_run_chunk(compressed, decomp_len),
         0x03 => {
             // Split logic omitted for brevity (unchanged)
             Err(QresError::Other(String::from(
@@ -723,6 +830,60 @@ pub fn decompress_chunk_with_state(
     }
 }
 
+/// Header fields of a single compressed chunk, parsed without decoding the payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkHeader {
+    /// Protocol version nibble from the flag byte.
+    pub version: u8,
+    /// Codec mode nibble from the flag byte.
+    pub codec_mode: u8,
+    /// Predictor the encoder selected for this chunk, or `None` for chunks
+    /// that are handled outside the core (the daemon's Zstd and LZ4 fallbacks).
+    pub predictor_id: Option<u8>,
+    /// Length of the chunk once decoded.
+    pub decoded_len: usize,
+}
+
+/// Read the header of a compressed chunk.
+///
+/// Useful for debugging decode mismatches: it reports which predictor the
+/// encoder stamped into each chunk without running the decoder. The version is
+/// reported as-is (not validated) so that foreign streams can still be inspected.
+pub fn read_chunk_header(compressed: &[u8]) -> Result<ChunkHeader> {
+    if compressed.len() < 5 {
+        return Err(QresError::InvalidData(String::from("Chunk too short")));
+    }
+
+    let flag_byte = compressed[0];
+    let codec_mode = flag_byte & 0x0F;
+    let predictor_id = match codec_mode {
+        0x00 => Some(PREDICTOR_ID_DEFAULT),
+        0x01 | CODEC_MODE_LZ4 => None,
+        0x02 => Some(PREDICTOR_ID_NEURAL),
+        0x03 => Some(PREDICTOR_ID_SPLIT),
+        CODEC_MODE_RUN => Some(PREDICTOR_ID_RUN),
+        _ => {
+            return Err(QresError::InvalidData(format!(
+                "Unknown codec mode: {:#x}",
+                codec_mode
+            )))
+        }
+    };
+
+    let decoded_len = u32::from_le_bytes(
+        compressed[1..5]
+            .try_into()
+            .map_err(|_| QresError::InvalidData(String::from("Invalid Header")))?,
+    ) as usize;
+
+    Ok(ChunkHeader {
+        version: (flag_byte >> 4) & 0x0F,
+        codec_mode,
+        predictor_id,
+        decoded_len,
+    })
+}
+
 // RESTORED FUNCTIONS
 
 #[cfg(feature = "std")]
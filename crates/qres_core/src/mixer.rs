@@ -96,9 +96,12 @@ impl Mixer {
         // Inputs are u8, convert to Q16 (x << 16) before multiply
         // But weights are Q16. So weight * (pred << 16) >> 16 == weight * pred.
         // We can just accumulate weight * pred then result is Q16.
+        // Accumulation saturates so that externally supplied weights outside
+        // the Q16.16 unit range clamp the same way in debug and release.
         let mut ensemble_sum: i32 = 0;
         for (i, &pred) in preds.iter().enumerate().take(NUM_MODELS) {
-            ensemble_sum += mul_q16(self.weights[i], (pred as i32) << 16);
+            ensemble_sum =
+                ensemble_sum.saturating_add(mul_q16(self.weights[i], (pred as i32) << 16));
         }
 
         // 2. Calculate AR(2) Prediction
@@ -123,14 +126,14 @@ impl Mixer {
         } else if self.running_var < VAR_THRESH {
             let p1 = mul_q16(39322, ar_pred);
             let p2 = mul_q16(26214, ensemble_sum);
-            p1 + p2
+            p1.saturating_add(p2)
         } else {
             ensemble_sum
         };
 
         // Round and clamp
         // Add 0.5 (half Q16) for rounding
-        let rounded = prediction + Q16_HALF;
+        let rounded = prediction.saturating_add(Q16_HALF);
         let byte_val = rounded >> 16;
 
         if byte_val < 0 {
@@ -256,20 +259,20 @@ impl Mixer {
             // mu = 0.001 -> 66
             const MU: i32 = 66;
             for (i, &g_val) in global.iter().enumerate() {
-                let diff_g = g_val - self.weights[i];
-                self.weights[i] += mul_q16(diff_g, MU);
+                let diff_g = g_val.saturating_sub(self.weights[i]);
+                self.weights[i] = self.weights[i].saturating_add(mul_q16(diff_g, MU));
             }
         }
 
         // Regeneration: + 0.001 (66)
         for i in 0..NUM_MODELS {
-            self.weights[i] += 66;
+            self.weights[i] = self.weights[i].saturating_add(66);
         }
 
-        // Normalize
+        // Normalize (a saturated sum still scales oversized weights back down)
         let mut sum: i32 = 0;
         for i in 0..NUM_MODELS {
-            sum += self.weights[i];
+            sum = sum.saturating_add(self.weights[i]);
         }
 
         if sum > 10 {
@@ -280,3 +283,65 @@ impl Mixer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_out_of_range_weights_saturate_instead_of_overflowing() {
+        // Weights decoded from a gene are not range-checked. Six weights of
+        // 16384.0 sum past i32::MAX, which panicked in debug builds while
+        // release builds wrapped, so the two disagreed on the same input.
+        let weights = [1 << 30; NUM_MODELS];
+        let mut mixer = Mixer::new(Some(&weights), None);
+        assert_eq!(mixer.mix(&[1; NUM_MODELS]), 255);
+
+        // The saturated sum scales the weights back into range, and the next
+        // update normalizes them exactly
+        mixer.update_lazy(1, 1, &[1; NUM_MODELS]);
+        assert!(mixer.weights.iter().all(|&w| w <= Q16_ONE));
+        mixer.update_lazy(1, 1, &[1; NUM_MODELS]);
+        let sum: i32 = mixer.weights[..NUM_MODELS].iter().sum();
+        assert!(
+            (sum - Q16_ONE).abs() < 64,
+            "weights not normalized: {}",
+            sum
+        );
+        assert_eq!(mixer.mix(&[1; NUM_MODELS]), 1);
+    }
+
+    #[test]
+    fn test_update_lazy_shifts_weight_to_the_accurate_model() {
+        let mut mixer = Mixer::new(None, None);
+        let preds = [10, 200, 100, 100, 100, 100];
+        for _ in 0..200 {
+            mixer.update_lazy(1, 200, &preds);
+        }
+        // Model 1 is always right, model 0 always far off
+        assert!(mixer.weights[1] > mixer.weights[0]);
+        assert!(mixer.weights[1] > 13107);
+        let sum: i32 = mixer.weights[..NUM_MODELS].iter().sum();
+        assert!(
+            (sum - Q16_ONE).abs() < 64,
+            "weights not normalized: {}",
+            sum
+        );
+        // A long win streak locks on to model 1
+        assert_eq!(mixer.mix(&preds), 200);
+    }
+
+    #[test]
+    fn test_update_lazy_pulls_weights_toward_global() {
+        let global = [Q16_ONE, 0, 0, 0, 0, 0];
+        let mut prox = Mixer::new(None, Some(&global));
+        let mut plain = Mixer::new(None, None);
+        let preds = [100; NUM_MODELS];
+        for _ in 0..50 {
+            prox.update_lazy(1, 100, &preds);
+            plain.update_lazy(1, 100, &preds);
+        }
+        assert!(prox.weights[0] > plain.weights[0]);
+        assert!(prox.weights[1] < plain.weights[1]);
+    }
+}
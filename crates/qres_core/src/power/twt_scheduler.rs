@@ -52,6 +52,18 @@ const PRESTORM_INTERVAL_MS: u64 = 10 * 60 * 1000;
 /// 30 seconds in milliseconds — rapid coordination during Storm
 const STORM_INTERVAL_MS: u64 = 30 * 1000;
 
+/// Awake burst window after a scheduled wake with an empty backlog
+const BURST_WINDOW_MS: u64 = 500;
+
+/// Awake burst window during Storm for sustained coordination
+const STORM_BURST_WINDOW_MS: u64 = 5_000;
+
+/// Extra awake time per batched message still waiting to be transmitted
+const BURST_PER_MESSAGE_MS: u64 = 20;
+
+/// Upper bound on the backlog-scaled burst window
+const MAX_BURST_WINDOW_MS: u64 = 15_000;
+
 /// Maximum jitter as a fraction of the interval (±10%)
 const JITTER_FRACTION: f32 = 0.10;
 
@@ -75,6 +87,12 @@ const RADIO_IDLE_MW: f32 = 80.0;
 /// Wi-Fi radio TWT sleep power (MAC layer sleep, not deep sleep)
 const RADIO_SLEEP_MW: f32 = 5.0;
 
+/// Wi-Fi radio deep-sleep (power save) power, used for full Calm intervals
+const RADIO_DEEP_SLEEP_MW: f32 = 0.1;
+
+/// CPU power while the whole node is in deep sleep (retention mode)
+const CPU_DEEP_SLEEP_MW: f32 = 0.5;
+
 /// CPU active power estimate (ARM Cortex-A53 class)
 const CPU_ACTIVE_MW: f32 = 150.0;
 
@@ -94,6 +112,24 @@ pub struct TWTConfig {
     pub jitter_enabled: bool,
     /// Maximum number of messages to batch during sleep
     pub max_batch_size: usize,
+    /// Calm wake interval in milliseconds (default: 4 hours)
+    pub calm_override_ms: Option<u64>,
+    /// PreStorm wake interval in milliseconds (default: 10 minutes)
+    pub prestorm_override_ms: Option<u64>,
+    /// Storm wake interval in milliseconds (default: 30 seconds)
+    pub storm_override_ms: Option<u64>,
+}
+
+impl TWTConfig {
+    /// Unweighted wake interval for `regime`, honouring any override.
+    pub fn interval_for(&self, regime: Regime) -> u64 {
+        let override_ms = match regime {
+            Regime::Calm => self.calm_override_ms,
+            Regime::PreStorm => self.prestorm_override_ms,
+            Regime::Storm => self.storm_override_ms,
+        };
+        override_ms.unwrap_or_else(|| regime_to_interval_ms(regime))
+    }
 }
 
 impl Default for TWTConfig {
@@ -102,6 +138,9 @@ impl Default for TWTConfig {
             base_interval_ms: CALM_INTERVAL_MS,
             jitter_enabled: true,
             max_batch_size: 64,
+            calm_override_ms: None,
+            prestorm_override_ms: None,
+            storm_override_ms: None,
         }
     }
 }
@@ -122,10 +161,14 @@ pub enum NodeRole {
 pub struct MockRadio {
     /// Whether the radio is currently "awake"
     is_awake: bool,
+    /// Whether the current sleep is deep sleep rather than TWT (MAC) sleep
+    deep_sleep: bool,
     /// Timestamp (ms) when the radio last went to sleep
     sleep_start_ms: u64,
-    /// Total accumulated sleep time in milliseconds
+    /// Total accumulated sleep time in milliseconds (both tiers)
     total_sleep_ms: u64,
+    /// Portion of `total_sleep_ms` spent in deep sleep
+    total_deep_sleep_ms: u64,
     /// Total accumulated awake time in milliseconds
     total_awake_ms: u64,
     /// Number of wake/sleep transitions
@@ -140,8 +183,10 @@ impl MockRadio {
     pub fn new() -> Self {
         Self {
             is_awake: true,
+            deep_sleep: false,
             sleep_start_ms: 0,
             total_sleep_ms: 0,
+            total_deep_sleep_ms: 0,
             total_awake_ms: 0,
             transition_count: 0,
             energy_consumed_mwh: 0.0,
@@ -159,11 +204,25 @@ impl MockRadio {
         }
     }
 
+    /// Put the radio (and node) into deep sleep at the given simulated timestamp.
+    ///
+    /// A radio already in TWT sleep drops to the deeper tier without counting
+    /// an extra wake/sleep transition.
+    pub fn deep_sleep(&mut self, now_ms: u64) {
+        if self.is_awake {
+            self.sleep(now_ms);
+        } else {
+            self.account_energy(now_ms);
+        }
+        self.deep_sleep = true;
+    }
+
     /// Wake the radio at the given simulated timestamp
     pub fn wake(&mut self, now_ms: u64) {
         if !self.is_awake {
             self.account_energy(now_ms);
             self.is_awake = true;
+            self.deep_sleep = false;
             self.transition_count += 1;
         }
     }
@@ -182,6 +241,12 @@ impl MockRadio {
             // Active: radio idle + CPU active (not TX, just listening)
             self.energy_consumed_mwh +=
                 (RADIO_IDLE_MW as f64 + CPU_ACTIVE_MW as f64) * elapsed_hours;
+        } else if self.deep_sleep {
+            self.total_sleep_ms += elapsed_ms;
+            self.total_deep_sleep_ms += elapsed_ms;
+            // Deep sleep: radio power save + CPU retention
+            self.energy_consumed_mwh +=
+                (RADIO_DEEP_SLEEP_MW as f64 + CPU_DEEP_SLEEP_MW as f64) * elapsed_hours;
         } else {
             self.total_sleep_ms += elapsed_ms;
             // Sleep: radio TWT sleep + CPU idle
@@ -203,10 +268,18 @@ impl MockRadio {
         self.is_awake
     }
 
+    pub fn is_deep_sleep(&self) -> bool {
+        !self.is_awake && self.deep_sleep
+    }
+
     pub fn total_sleep_ms(&self) -> u64 {
         self.total_sleep_ms
     }
 
+    pub fn total_deep_sleep_ms(&self) -> u64 {
+        self.total_deep_sleep_ms
+    }
+
     pub fn total_awake_ms(&self) -> u64 {
         self.total_awake_ms
     }
@@ -235,8 +308,10 @@ impl Default for MockRadio {
 /// Aggregated power metrics for reporting
 #[derive(Debug, Clone)]
 pub struct PowerMetrics {
-    /// Fraction of time the radio was asleep (0.0 to 1.0)
+    /// Fraction of time the radio was asleep (0.0 to 1.0), either tier
     pub radio_sleep_ratio: f32,
+    /// Fraction of time spent in deep sleep (included in `radio_sleep_ratio`)
+    pub radio_deep_sleep_ratio: f32,
     /// Total energy consumed (mWh)
     pub energy_consumed_mwh: f64,
     /// Number of wake/sleep transitions
@@ -261,9 +336,9 @@ pub struct PowerMetrics {
 /// On wake, the queue is drained and all messages are burst-transmitted.
 #[derive(Debug, Clone)]
 pub struct GossipBatchQueue {
-    /// Outgoing messages waiting to be sent
-    outgoing: VecDeque<GhostUpdate>,
-    /// Maximum queue capacity (drop oldest if exceeded)
+    /// Outgoing messages waiting to be sent, with their priority
+    outgoing: VecDeque<(u8, GhostUpdate)>,
+    /// Maximum queue capacity (drop lowest-priority, then oldest, if exceeded)
     max_size: usize,
     /// Total messages ever enqueued
     total_enqueued: u64,
@@ -281,18 +356,39 @@ impl GossipBatchQueue {
         }
     }
 
-    /// Enqueue a message for later transmission. Drops oldest if at capacity.
+    /// Enqueue a routine (priority-0) message. Drops oldest if at capacity.
     pub fn enqueue(&mut self, update: GhostUpdate) {
+        self.enqueue_with_priority(update, 0);
+    }
+
+    /// Enqueue a message with an explicit priority (higher survives longer).
+    ///
+    /// At capacity the lowest-priority message is dropped, the oldest one
+    /// among equals. If the new message ranks below everything queued, it is
+    /// the one dropped. Drain order stays FIFO.
+    pub fn enqueue_with_priority(&mut self, update: GhostUpdate, priority: u8) {
+        self.total_enqueued += 1;
         if self.outgoing.len() >= self.max_size {
-            self.outgoing.pop_front(); // Drop oldest
+            let lowest = self
+                .outgoing
+                .iter()
+                .enumerate()
+                .min_by_key(|(i, (p, _))| (*p, *i))
+                .map(|(i, (p, _))| (i, *p));
+            match lowest {
+                Some((_, lowest_priority)) if priority < lowest_priority => return,
+                Some((idx, _)) => {
+                    self.outgoing.remove(idx);
+                }
+                None => {}
+            }
         }
-        self.outgoing.push_back(update);
-        self.total_enqueued += 1;
+        self.outgoing.push_back((priority, update));
     }
 
     /// Drain all queued messages for burst transmission
     pub fn drain_batch(&mut self) -> Vec<GhostUpdate> {
-        let batch: Vec<GhostUpdate> = self.outgoing.drain(..).collect();
+        let batch: Vec<GhostUpdate> = self.outgoing.drain(..).map(|(_, u)| u).collect();
         self.total_burst_sent += batch.len() as u64;
         batch
     }
@@ -431,9 +527,17 @@ impl TWTScheduler {
     /// - PreStorm: 10 minute intervals (elevated readiness)
     /// - Storm: 30 second intervals (rapid coordination)
     ///
+    /// Scheduled nodes use the `*_override_ms` values from their `TWTConfig`
+    /// instead when set; reputation weighting applies either way.
+    ///
     /// For Sentinel nodes, this is a no-op (always awake).
     /// For OnDemand nodes, Storm triggers an immediate wake.
-    pub fn update_regime(&mut self, new_regime: Regime, now_ms: u64) {
+    ///
+    /// Returns the number of batched messages ready for burst transmission.
+    /// On escalation to Storm the radio is already awake when this returns, so
+    /// the caller can `drain_batch()` right away instead of waiting for `tick()`.
+    /// Other transitions return 0.
+    pub fn update_regime(&mut self, new_regime: Regime, now_ms: u64) -> usize {
         let old_regime = self.current_regime;
         self.current_regime = new_regime;
 
@@ -451,8 +555,8 @@ impl TWTScheduler {
                     self.enter_sleep(now_ms);
                 }
             }
-            NodeRole::Scheduled(_) => {
-                let base = regime_to_interval_ms(new_regime);
+            NodeRole::Scheduled(cfg) => {
+                let base = cfg.interval_for(new_regime);
                 self.current_interval_ms = calculate_weighted_interval(base, self.reputation);
 
                 // If transitioning to a more urgent regime, wake immediately
@@ -466,6 +570,13 @@ impl TWTScheduler {
                 self.schedule_next_wake(now_ms);
             }
         }
+
+        // Flush the backlog batched during Calm/PreStorm sleep
+        if new_regime == Regime::Storm && old_regime != Regime::Storm {
+            self.batch_queue.pending_count()
+        } else {
+            0
+        }
     }
 
     /// Get the current regime
@@ -493,8 +604,8 @@ impl TWTScheduler {
     /// `schedule_next_wake` call.
     pub fn set_reputation(&mut self, reputation: f32, now_ms: u64) {
         self.reputation = reputation.clamp(0.0, 1.0);
-        if let NodeRole::Scheduled(_) = self.role {
-            let base = regime_to_interval_ms(self.current_regime);
+        if let NodeRole::Scheduled(cfg) = self.role {
+            let base = cfg.interval_for(self.current_regime);
             self.current_interval_ms = calculate_weighted_interval(base, self.reputation);
             self.schedule_next_wake(now_ms);
         }
@@ -549,13 +660,8 @@ impl TWTScheduler {
                 // Check if we should go back to sleep after burst window
                 if self.mock_radio.is_awake() && self.last_wake_ms > 0 {
                     let awake_duration = now_ms.saturating_sub(self.last_wake_ms);
-                    // Stay awake for a brief burst window (500ms) then sleep
-                    let burst_window = if self.current_regime == Regime::Storm {
-                        5_000 // 5s during Storm for sustained coordination
-                    } else {
-                        500 // 500ms normally
-                    };
-                    if awake_duration > burst_window {
+                    // Stay awake for the burst window, then sleep
+                    if awake_duration > self.burst_window_ms() {
                         self.enter_sleep(now_ms);
                     }
                 }
@@ -565,12 +671,33 @@ impl TWTScheduler {
         }
     }
 
+    /// How long the radio stays awake after a scheduled wake.
+    ///
+    /// The base window (500ms, or 5s during Storm) grows by 20ms for every
+    /// message still pending in the batch queue, capped at 15s, so a large
+    /// backlog gets time to drain instead of overflowing the queue.
+    pub fn burst_window_ms(&self) -> u64 {
+        let base = if self.current_regime == Regime::Storm {
+            STORM_BURST_WINDOW_MS
+        } else {
+            BURST_WINDOW_MS
+        };
+        let backlog = self.batch_queue.pending_count() as u64 * BURST_PER_MESSAGE_MS;
+        (base + backlog).min(MAX_BURST_WINDOW_MS.max(base))
+    }
+
     /// Queue an outgoing GhostUpdate. If the radio is asleep, the message
     /// is batched. If awake, it's still queued for immediate drain.
     pub fn enqueue_gossip(&mut self, update: GhostUpdate) {
         self.batch_queue.enqueue(update);
     }
 
+    /// Queue an outgoing GhostUpdate that should outlive routine messages
+    /// if the batch queue overflows (see [`GossipBatchQueue::enqueue_with_priority`]).
+    pub fn enqueue_gossip_with_priority(&mut self, update: GhostUpdate, priority: u8) {
+        self.batch_queue.enqueue_with_priority(update, priority);
+    }
+
     /// Drain all batched messages for transmission.
     /// Call this after `tick()` returns > 0, or when the radio is awake.
     pub fn drain_batch(&mut self) -> Vec<GhostUpdate> {
@@ -628,10 +755,13 @@ impl TWTScheduler {
         self.mock_radio.finalize(now_ms);
 
         let total_time = self.mock_radio.total_awake_ms() + self.mock_radio.total_sleep_ms();
-        let sleep_ratio = if total_time > 0 {
-            self.mock_radio.total_sleep_ms() as f32 / total_time as f32
+        let (sleep_ratio, deep_sleep_ratio) = if total_time > 0 {
+            (
+                self.mock_radio.total_sleep_ms() as f32 / total_time as f32,
+                self.mock_radio.total_deep_sleep_ms() as f32 / total_time as f32,
+            )
         } else {
-            0.0
+            (0.0, 0.0)
         };
 
         // Baseline: always-on energy (radio idle + CPU active) for the same duration
@@ -647,6 +777,7 @@ impl TWTScheduler {
 
         PowerMetrics {
             radio_sleep_ratio: sleep_ratio,
+            radio_deep_sleep_ratio: deep_sleep_ratio,
             energy_consumed_mwh: actual,
             transition_count: self.mock_radio.transition_count(),
             messages_batched: self.batch_queue.total_enqueued(),
@@ -666,6 +797,58 @@ impl TWTScheduler {
         &self.role
     }
 
+    // -------------------------------------------------------------------------
+    // Sentinel Rotation
+    // -------------------------------------------------------------------------
+
+    /// Take over the always-on Sentinel duty.
+    ///
+    /// Energy up to `now_ms` is booked under the previous radio state; from
+    /// then on the radio stays awake. No-op for nodes that are already Sentinels.
+    pub fn promote_to_sentinel(&mut self, now_ms: u64) {
+        if self.role == NodeRole::Sentinel {
+            return;
+        }
+        self.mock_radio.finalize(now_ms);
+        self.force_wake(now_ms);
+        self.role = NodeRole::Sentinel;
+        self.current_interval_ms = 0;
+        self.next_wake_ms = None;
+        self.emergency_wake_pending = false;
+    }
+
+    /// Hand the Sentinel duty back and resume `new_role`.
+    ///
+    /// Always-on energy is booked up to `now_ms`. Scheduled nodes get their
+    /// regime interval (reputation-weighted) and sleep after the usual burst
+    /// window; OnDemand nodes sleep immediately unless the regime is elevated.
+    /// No-op unless the node is currently a Sentinel and `new_role` is not.
+    pub fn demote_from_sentinel(&mut self, now_ms: u64, new_role: NodeRole) {
+        if self.role != NodeRole::Sentinel {
+            return;
+        }
+        match new_role {
+            NodeRole::Sentinel => {}
+            NodeRole::Scheduled(cfg) => {
+                self.mock_radio.finalize(now_ms);
+                self.role = new_role;
+                let base = cfg.interval_for(self.current_regime);
+                self.current_interval_ms = calculate_weighted_interval(base, self.reputation);
+                self.last_wake_ms = now_ms;
+                self.schedule_next_wake(now_ms);
+            }
+            NodeRole::OnDemand => {
+                self.mock_radio.finalize(now_ms);
+                self.role = new_role;
+                self.current_interval_ms = 0;
+                self.next_wake_ms = None;
+                if self.current_regime == Regime::Calm {
+                    self.mock_radio.sleep(now_ms);
+                }
+            }
+        }
+    }
+
     // -------------------------------------------------------------------------
     // Internal helpers
     // -------------------------------------------------------------------------
@@ -675,11 +858,27 @@ impl TWTScheduler {
         self.last_wake_ms = now_ms;
     }
 
+    /// Sleep until the next wake. Scheduled nodes facing a full Calm interval
+    /// use deep sleep; everything else uses TWT (MAC) sleep.
     fn enter_sleep(&mut self, now_ms: u64) {
-        self.mock_radio.sleep(now_ms);
+        if self.is_full_calm_interval() {
+            self.mock_radio.deep_sleep(now_ms);
+        } else {
+            self.mock_radio.sleep(now_ms);
+        }
         self.schedule_next_wake(now_ms);
     }
 
+    fn is_full_calm_interval(&self) -> bool {
+        match &self.role {
+            NodeRole::Scheduled(cfg) => {
+                self.current_regime == Regime::Calm
+                    && self.current_interval_ms >= cfg.interval_for(Regime::Calm)
+            }
+            _ => false,
+        }
+    }
+
     fn schedule_next_wake(&mut self, now_ms: u64) {
         if self.current_interval_ms == 0 {
             self.next_wake_ms = None;
@@ -790,7 +989,9 @@ mod tests {
             masked_weights: alloc::vec![0i32; 4],
             zk_proof: NormProof {
                 commitment: CompressedEdwardsY::identity(),
+                announcement: CompressedEdwardsY::identity(),
                 response: Scalar::ZERO,
+                value: 0,
             },
             dp_epsilon: 1.0,
             residual_error: 0.0,
@@ -844,6 +1045,7 @@ mod tests {
             base_interval_ms: 1000, // 1 second for testing
             jitter_enabled: false,
             max_batch_size: 16,
+            ..TWTConfig::default()
         };
         let mut sched = TWTScheduler::new(NodeRole::Scheduled(cfg));
 
@@ -858,6 +1060,79 @@ mod tests {
         assert!(sched.next_wake_ms().is_some());
     }
 
+    #[test]
+    fn test_sentinel_promotion_and_demotion() {
+        let cfg = TWTConfig {
+            jitter_enabled: false,
+            ..TWTConfig::default()
+        };
+        let mut sched = TWTScheduler::new(NodeRole::Scheduled(cfg));
+        let scheduled_interval = sched.current_interval_ms();
+        sched.tick(602);
+        assert!(!sched.is_awake());
+
+        sched.promote_to_sentinel(10_000);
+        assert_eq!(*sched.role(), NodeRole::Sentinel);
+        assert!(sched.is_awake());
+        assert_eq!(sched.current_interval_ms(), 0);
+        assert_eq!(sched.next_wake_ms(), None);
+        let sleep_before = sched.mock_radio().total_sleep_ms();
+        assert_eq!(sleep_before, 10_000 - 602);
+
+        // An hour of always-on duty: ticks never put a Sentinel to sleep
+        assert_eq!(sched.tick(3_610_000), 0);
+        assert!(sched.is_awake());
+        let energy_before = sched.mock_radio().energy_consumed_mwh();
+
+        sched.demote_from_sentinel(3_610_000, NodeRole::Scheduled(cfg));
+        assert_eq!(*sched.role(), NodeRole::Scheduled(cfg));
+        assert_eq!(sched.current_interval_ms(), scheduled_interval);
+        assert!(sched.next_wake_ms().unwrap() > 3_610_000);
+        // The Sentinel hour was booked as awake time
+        assert_eq!(sched.mock_radio().total_sleep_ms(), sleep_before);
+        assert!(sched.mock_radio().total_awake_ms() >= 3_600_000);
+        let awake_hour_mwh = RADIO_IDLE_MW as f64 + CPU_ACTIVE_MW as f64;
+        assert!(sched.mock_radio().energy_consumed_mwh() - energy_before >= awake_hour_mwh);
+
+        // Back on the normal cycle: sleeps after the burst window
+        sched.tick(3_610_000 + 601);
+        assert!(!sched.is_awake());
+    }
+
+    #[test]
+    fn test_burst_window_scales_with_backlog() {
+        let cfg = TWTConfig {
+            base_interval_ms: 1000,
+            jitter_enabled: false,
+            max_batch_size: 1024,
+            ..TWTConfig::default()
+        };
+        let mut sched = TWTScheduler::new(NodeRole::Scheduled(cfg));
+        sched.tick(602);
+        assert!(!sched.is_awake());
+
+        for _ in 0..50 {
+            sched.enqueue_gossip(make_dummy_ghost_update());
+        }
+        let wake_at = sched.next_wake_ms().unwrap();
+        assert_eq!(sched.tick(wake_at), 50);
+        assert_eq!(sched.burst_window_ms(), 500 + 50 * BURST_PER_MESSAGE_MS);
+
+        // Past the base window but the backlog keeps the radio awake
+        sched.tick(wake_at + 600);
+        assert!(sched.is_awake());
+
+        // Sleeps once the scaled window elapses
+        sched.tick(wake_at + 1_501);
+        assert!(!sched.is_awake());
+
+        // The window is capped for very large backlogs
+        for _ in 0..1000 {
+            sched.enqueue_gossip(make_dummy_ghost_update());
+        }
+        assert_eq!(sched.burst_window_ms(), MAX_BURST_WINDOW_MS);
+    }
+
     #[test]
     fn test_on_demand_wakes_on_emergency() {
         let mut sched = TWTScheduler::new_on_demand();
@@ -877,6 +1152,7 @@ mod tests {
             base_interval_ms: 100_000,
             jitter_enabled: false,
             max_batch_size: 16,
+            ..TWTConfig::default()
         };
         let mut sched = TWTScheduler::new(NodeRole::Scheduled(cfg));
 
@@ -890,6 +1166,54 @@ mod tests {
         assert_eq!(sched.current_interval_ms(), STORM_INTERVAL_MS);
     }
 
+    #[test]
+    fn test_storm_transition_flushes_batch() {
+        let cfg = TWTConfig {
+            base_interval_ms: 100_000,
+            jitter_enabled: false,
+            max_batch_size: 16,
+            ..TWTConfig::default()
+        };
+        let mut sched = TWTScheduler::new(NodeRole::Scheduled(cfg));
+
+        // Batch messages during Calm sleep
+        sched.mock_radio.sleep(0);
+        for _ in 0..3 {
+            sched.enqueue_gossip(make_dummy_ghost_update());
+        }
+
+        // Escalation hands the backlog over immediately, no tick() needed
+        assert_eq!(sched.update_regime(Regime::Storm, 500), 3);
+        assert!(sched.should_transmit(500));
+        assert_eq!(sched.drain_batch().len(), 3);
+
+        // Staying in Storm is not an escalation
+        sched.enqueue_gossip(make_dummy_ghost_update());
+        assert_eq!(sched.update_regime(Regime::Storm, 600), 0);
+        assert_eq!(sched.update_regime(Regime::Calm, 700), 0);
+    }
+
+    #[test]
+    fn test_prestorm_interval_override() {
+        let cfg = TWTConfig {
+            prestorm_override_ms: Some(2 * 60 * 1000),
+            ..TWTConfig::default()
+        };
+        let mut sched = TWTScheduler::new(NodeRole::Scheduled(cfg));
+
+        sched.update_regime(Regime::PreStorm, 1000);
+        assert_eq!(sched.current_interval_ms(), 2 * 60 * 1000);
+
+        // Regimes without an override keep the built-in intervals
+        sched.update_regime(Regime::Calm, 2000);
+        assert_eq!(sched.current_interval_ms(), CALM_INTERVAL_MS);
+
+        // Reputation weighting still applies on top of the override
+        sched.update_regime(Regime::PreStorm, 3000);
+        sched.set_reputation(0.0, 3000);
+        assert_eq!(sched.current_interval_ms(), 2 * 60 * 1000 / 5);
+    }
+
     // ---- Gossip Batching Tests ----
 
     #[test]
@@ -930,6 +1254,36 @@ mod tests {
         assert_eq!(batch[2].dp_epsilon, 4.0);
     }
 
+    #[test]
+    fn test_batch_queue_overflow_drops_lowest_priority() {
+        let tagged = |tag: f32| {
+            let mut update = make_dummy_ghost_update();
+            update.dp_epsilon = tag;
+            update
+        };
+        let mut queue = GossipBatchQueue::new(3);
+        for i in 0..3 {
+            queue.enqueue(tagged(i as f32));
+        }
+
+        // An anomaly displaces the oldest routine message
+        queue.enqueue_with_priority(tagged(100.0), 5);
+        // More chatter evicts routine messages, never the anomaly
+        queue.enqueue(tagged(3.0));
+        queue.enqueue(tagged(4.0));
+        queue.enqueue_with_priority(tagged(200.0), 7);
+        queue.enqueue(tagged(5.0));
+        queue.enqueue_with_priority(tagged(300.0), 6);
+        // Ranking below everything queued, the new message is the one dropped
+        queue.enqueue(tagged(6.0));
+
+        assert_eq!(queue.pending_count(), 3);
+        assert_eq!(queue.total_enqueued(), 10);
+        let tags: Vec<f32> = queue.drain_batch().iter().map(|u| u.dp_epsilon).collect();
+        // FIFO order among survivors
+        assert_eq!(tags, vec![100.0, 200.0, 300.0]);
+    }
+
     // ---- Power Metrics Tests ----
 
     #[test]
@@ -945,6 +1299,35 @@ mod tests {
         assert!(energy > 220.0 && energy < 240.0, "energy = {}", energy);
     }
 
+    #[test]
+    fn test_calm_interval_uses_deep_sleep() {
+        const FOUR_HOURS_MS: u64 = 4 * 3_600_000;
+        let cfg = TWTConfig {
+            jitter_enabled: false,
+            ..TWTConfig::default()
+        };
+        let mut sched = TWTScheduler::new(NodeRole::Scheduled(cfg));
+        sched.tick(602);
+        assert!(sched.mock_radio().is_deep_sleep());
+
+        let metrics = sched.get_metrics(602 + FOUR_HOURS_MS);
+        assert!(metrics.radio_deep_sleep_ratio > 0.99);
+
+        let mut mac_sleep = MockRadio::new();
+        mac_sleep.sleep(602);
+        mac_sleep.finalize(602 + FOUR_HOURS_MS);
+        let deep = sched.mock_radio().energy_consumed_mwh();
+        let shallow = mac_sleep.energy_consumed_mwh();
+        assert!(deep * 20.0 < shallow, "deep={} mac={}", deep, shallow);
+        assert!(metrics.savings_percent > 99.0);
+
+        // Shorter Storm intervals keep using TWT (MAC) sleep
+        sched.update_regime(Regime::Storm, 602 + FOUR_HOURS_MS);
+        sched.tick(602 + FOUR_HOURS_MS + STORM_BURST_WINDOW_MS + 1);
+        assert!(!sched.is_awake());
+        assert!(!sched.mock_radio().is_deep_sleep());
+    }
+
     #[test]
     fn test_sleep_saves_energy_vs_baseline() {
         let mut radio = MockRadio::new();
@@ -972,6 +1355,7 @@ mod tests {
             base_interval_ms: 1000,
             jitter_enabled: false,
             max_batch_size: 16,
+            ..TWTConfig::default()
         };
         let mut sched = TWTScheduler::new(NodeRole::Scheduled(cfg));
 
@@ -1001,6 +1385,7 @@ mod tests {
             base_interval_ms: CALM_INTERVAL_MS,
             jitter_enabled: false,
             max_batch_size: 32,
+            ..TWTConfig::default()
         };
         let mut sched = TWTScheduler::new(NodeRole::Scheduled(cfg));
 
@@ -1041,6 +1426,7 @@ mod tests {
             base_interval_ms: 1000,
             jitter_enabled: false,
             max_batch_size: 16,
+            ..TWTConfig::default()
         };
         let sched = TWTScheduler::new(NodeRole::Scheduled(cfg));
         let schedule = sched.get_wake_schedule(0, 5);
@@ -1158,6 +1544,7 @@ mod tests {
             base_interval_ms: CALM_INTERVAL_MS,
             jitter_enabled: false,
             max_batch_size: 32,
+            ..TWTConfig::default()
         };
 
         // High reputation → full interval
@@ -1182,6 +1569,7 @@ mod tests {
             base_interval_ms: CALM_INTERVAL_MS,
             jitter_enabled: false,
             max_batch_size: 32,
+            ..TWTConfig::default()
         };
         let mut sched = TWTScheduler::new(NodeRole::Scheduled(cfg));
         assert_eq!(sched.current_interval_ms(), CALM_INTERVAL_MS);
@@ -1204,6 +1592,7 @@ mod tests {
             base_interval_ms: CALM_INTERVAL_MS,
             jitter_enabled: false,
             max_batch_size: 32,
+            ..TWTConfig::default()
         };
 
         let low = TWTScheduler::with_reputation(NodeRole::Scheduled(cfg), 0.1);
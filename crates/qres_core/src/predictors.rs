@@ -310,3 +310,119 @@ impl Predictor for ZeroPredictor {
         // No state to reset
     }
 }
+
+// --- Predictor Auto-Selection ---
+
+/// Tunables for [`suggest_predictor_with`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PredictorHeuristic {
+    /// Minimum fraction of printable ASCII / whitespace bytes to treat a sample as text
+    pub min_text_ratio: f32,
+    /// Maximum entropy (bits) of the quietest byte lane for a sample to look like
+    /// packed floats (sign/exponent bytes barely change between neighbours)
+    pub max_float_lane_entropy: f32,
+    /// Minimum overall entropy (bits) for the float check, so constant or
+    /// near-constant data is not mistaken for floats
+    pub min_float_entropy: f32,
+}
+
+impl Default for PredictorHeuristic {
+    fn default() -> Self {
+        Self {
+            min_text_ratio: 0.95,
+            max_float_lane_entropy: 3.0,
+            min_float_entropy: 4.0,
+        }
+    }
+}
+
+/// Suggest a starting predictor for data resembling `sample` (typically the first chunk).
+///
+/// Text goes to [`PREDICTOR_ID_DEFAULT`](crate::PREDICTOR_ID_DEFAULT) (order-N
+/// context models), packed f32/f64 data to
+/// [`PREDICTOR_ID_NEURAL`](crate::PREDICTOR_ID_NEURAL) (weighted mixer).
+/// Anything else keeps the default.
+pub fn suggest_predictor(sample: &[u8]) -> u8 {
+    suggest_predictor_with(sample, &PredictorHeuristic::default())
+}
+
+/// [`suggest_predictor`] with explicit thresholds.
+pub fn suggest_predictor_with(sample: &[u8], heuristic: &PredictorHeuristic) -> u8 {
+    if sample.is_empty() {
+        return crate::PREDICTOR_ID_DEFAULT;
+    }
+
+    let textual = sample
+        .iter()
+        .filter(|&&b| matches!(b, 0x20..=0x7E | b'\n' | b'\r' | b'\t'))
+        .count();
+    if textual as f32 / sample.len() as f32 >= heuristic.min_text_ratio {
+        return crate::PREDICTOR_ID_DEFAULT;
+    }
+
+    if byte_entropy(sample.iter().copied()) >= heuristic.min_float_entropy {
+        for stride in [4usize, 8] {
+            if sample.len() < stride * 16 {
+                continue;
+            }
+            let quietest = (0..stride)
+                .map(|lane| byte_entropy(sample.iter().skip(lane).step_by(stride).copied()))
+                .fold(f32::MAX, f32::min);
+            if quietest <= heuristic.max_float_lane_entropy {
+                return crate::PREDICTOR_ID_NEURAL;
+            }
+        }
+    }
+
+    crate::PREDICTOR_ID_DEFAULT
+}
+
+/// Shannon entropy in bits per byte.
+fn byte_entropy<I: Iterator<Item = u8>>(bytes: I) -> f32 {
+    let mut counts = [0u32; 256];
+    let mut total = 0u32;
+    for b in bytes {
+        counts[b as usize] += 1;
+        total += 1;
+    }
+    if total == 0 {
+        return 0.0;
+    }
+    counts
+        .iter()
+        .filter(|&&c| c > 0)
+        .map(|&c| {
+            let p = c as f32 / total as f32;
+            -p * libm::log2f(p)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suggest_predictor_text_vs_float() {
+        let text: Vec<u8> = (0..200)
+            .flat_map(|i| {
+                alloc::format!("sensor_{},{}.{}\n", i % 7, 20 + i % 5, i % 10).into_bytes()
+            })
+            .collect();
+        assert_eq!(suggest_predictor(&text), crate::PREDICTOR_ID_DEFAULT);
+
+        let floats: Vec<u8> = (0..4096)
+            .flat_map(|i| (20.0f32 + libm::sinf(i as f32 * 0.01) * 3.7).to_le_bytes())
+            .collect();
+        assert_eq!(suggest_predictor(&floats), crate::PREDICTOR_ID_NEURAL);
+
+        let doubles: Vec<u8> = (0..2048)
+            .flat_map(|i| (1000.0f64 + i as f64 * 0.731).to_le_bytes())
+            .collect();
+        assert_eq!(suggest_predictor(&doubles), crate::PREDICTOR_ID_NEURAL);
+
+        // Constant bytes are neither text nor floats
+        assert_eq!(suggest_predictor(&[0u8; 4096]), crate::PREDICTOR_ID_DEFAULT);
+        assert_eq!(suggest_predictor(&[]), crate::PREDICTOR_ID_DEFAULT);
+    }
+}
@@ -3,15 +3,18 @@
 //! Implements mechanisms to add noise to model updates, providing (epsilon, delta)-differential privacy.
 //! Supports `opendp` for rigorous accounting (optional feature) and a manual fallback.
 
-// Vec is only needed when dp feature is enabled (for OpenDP implementation)
-#[cfg(all(not(feature = "std"), feature = "dp"))]
+#[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
-#[cfg(all(feature = "std", feature = "dp"))]
+#[cfg(feature = "std")]
 use std::vec::Vec;
 
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap;
 #[cfg(not(feature = "std"))]
 use alloc::string::String;
 #[cfg(feature = "std")]
+use std::collections::BTreeMap;
+#[cfg(feature = "std")]
 use std::string::String;
 
 #[cfg(feature = "dp")]
@@ -245,7 +248,11 @@ impl DifferentialPrivacy {
 #[derive(Debug, Clone)]
 pub enum PrivacyError {
     BudgetExceeded,
+    /// The per-peer sub-budget is exhausted (the global budget may not be)
+    PeerBudgetExceeded,
     InvalidCost,
+    /// No Rényi order above 1 was given, so ε could never be finite
+    InvalidRdpOrders,
 }
 
 #[cfg(feature = "std")]
@@ -253,7 +260,9 @@ impl std::fmt::Display for PrivacyError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             PrivacyError::BudgetExceeded => write!(f, "Privacy budget exceeded"),
+            PrivacyError::PeerBudgetExceeded => write!(f, "Per-peer privacy budget exceeded"),
             PrivacyError::InvalidCost => write!(f, "Invalid privacy cost"),
+            PrivacyError::InvalidRdpOrders => write!(f, "No Renyi DP order above 1"),
         }
     }
 }
@@ -280,6 +289,53 @@ pub struct PrivacyAccountant {
     pub query_count: u64,
     /// Timestamp of last reset (for rolling window)
     pub last_reset: u64,
+    /// Epsilon budget per receiving peer; `None` leaves peers bounded only
+    /// by the global `total_epsilon`
+    #[serde(default)]
+    pub per_peer_epsilon: Option<f64>,
+    /// Consumed epsilon per receiving peer (decays with the global budget)
+    #[serde(default)]
+    pub peer_consumed: BTreeMap<String, f64>,
+    /// Rényi DP ledger; `None` keeps the additive accountant (the default)
+    #[serde(default)]
+    pub rdp: Option<RdpLedger>,
+}
+
+/// Rényi DP orders probed when none are given to [`PrivacyAccountant::with_rdp`]
+pub const DEFAULT_RDP_ORDERS: [f64; 10] = [1.5, 2.0, 3.0, 4.0, 5.0, 6.0, 8.0, 16.0, 32.0, 64.0];
+
+/// Accumulated Rényi DP per order α
+///
+/// RDP composes by addition at each order; the (ε, δ) guarantee is the best
+/// conversion `ε = rdp(α) + ln(1/δ) / (α - 1)` over the tracked orders.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RdpLedger {
+    /// Orders α > 1
+    pub orders: Vec<f64>,
+    /// Accumulated RDP at each order (same length as `orders`)
+    pub rdp: Vec<f64>,
+}
+
+impl RdpLedger {
+    /// (ε, δ) guarantee after adding `extra(α)` at every order
+    fn epsilon_with(&self, delta: f64, extra: impl Fn(f64) -> f64) -> f64 {
+        self.orders
+            .iter()
+            .zip(&self.rdp)
+            .map(|(&alpha, &rdp)| rdp + extra(alpha) + ln(1.0 / delta) / (alpha - 1.0))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    fn add(&mut self, extra: impl Fn(f64) -> f64) {
+        for (&alpha, rdp) in self.orders.iter().zip(self.rdp.iter_mut()) {
+            *rdp += extra(alpha);
+        }
+    }
+}
+
+/// RDP of one Gaussian mechanism step with noise multiplier `z` (σ / sensitivity)
+fn gaussian_rdp(z: f64) -> impl Fn(f64) -> f64 {
+    move |alpha| alpha / (2.0 * z * z)
 }
 
 impl Default for PrivacyAccountant {
@@ -291,6 +347,9 @@ impl Default for PrivacyAccountant {
             decay_rate: 0.995,
             query_count: 0,
             last_reset: 0,
+            per_peer_epsilon: None,
+            peer_consumed: BTreeMap::new(),
+            rdp: None,
         }
     }
 }
@@ -304,7 +363,42 @@ impl PrivacyAccountant {
             decay_rate,
             query_count: 0,
             last_reset: 0, // Should be set by caller using system time if available
+            per_peer_epsilon: None,
+            peer_consumed: BTreeMap::new(),
+            rdp: None,
+        }
+    }
+
+    /// Accountant composing under Rényi DP at `alpha_orders` (orders ≤ 1 are
+    /// ignored; an empty slice uses [`DEFAULT_RDP_ORDERS`]). Budget checks
+    /// compare the RDP-derived ε at `delta` against `total_epsilon`.
+    ///
+    /// Fails with [`PrivacyError::InvalidRdpOrders`] if a non-empty slice has
+    /// no order above 1: ε would be infinite and every query refused.
+    pub fn with_rdp(alpha_orders: &[f64], delta: f64) -> Result<Self, PrivacyError> {
+        let orders: Vec<f64> = if alpha_orders.is_empty() {
+            DEFAULT_RDP_ORDERS.to_vec()
+        } else {
+            alpha_orders.iter().copied().filter(|&a| a > 1.0).collect()
+        };
+        if orders.is_empty() {
+            return Err(PrivacyError::InvalidRdpOrders);
         }
+        Ok(Self {
+            target_delta: delta,
+            rdp: Some(RdpLedger {
+                rdp: vec![0.0; orders.len()],
+                orders,
+            }),
+            ..Self::default()
+        })
+    }
+
+    /// Cap the epsilon any single peer may consume. The global budget still
+    /// applies on top, so sub-budgets may sum to more than `total_epsilon`.
+    pub fn with_peer_budget(mut self, per_peer_epsilon: f64) -> Self {
+        self.per_peer_epsilon = Some(per_peer_epsilon);
+        self
     }
 
     /// Check if there is enough budget for a query with cost `epsilon_cost`.
@@ -312,7 +406,12 @@ impl PrivacyAccountant {
         if epsilon_cost < 0.0 {
             return Err(PrivacyError::InvalidCost);
         }
-        if self.consumed_budget + epsilon_cost > self.total_epsilon {
+        // A pure ε-DP query is (α, ε)-RDP at every order
+        let projected = match &self.rdp {
+            Some(ledger) => ledger.epsilon_with(self.target_delta, |_| epsilon_cost),
+            None => self.consumed_budget + epsilon_cost,
+        };
+        if projected > self.total_epsilon {
             return Err(PrivacyError::BudgetExceeded);
         }
         Ok(())
@@ -322,20 +421,120 @@ impl PrivacyAccountant {
     pub fn record_consumption(&mut self, epsilon_cost: f64) -> Result<(), PrivacyError> {
         self.check_budget(epsilon_cost)?;
         self.consumed_budget += epsilon_cost;
+        if let Some(ledger) = &mut self.rdp {
+            ledger.add(|_| epsilon_cost);
+        }
+        self.query_count += 1;
+        Ok(())
+    }
+
+    /// Deduct budget for one Gaussian mechanism step with noise multiplier
+    /// `noise_multiplier` (σ divided by the L2 sensitivity).
+    ///
+    /// The additive ledger charges the step's (ε, `target_delta`) cost as
+    /// calibrated by [`DifferentialPrivacy::sigma`]; the RDP ledger, when
+    /// enabled, charges `α / (2 z²)` at each order.
+    pub fn record_gaussian(&mut self, noise_multiplier: f64) -> Result<(), PrivacyError> {
+        if noise_multiplier <= 0.0 {
+            return Err(PrivacyError::InvalidCost);
+        }
+        let step_epsilon = sqrt(2.0 * ln(1.25 / self.target_delta)) / noise_multiplier;
+        let projected = match &self.rdp {
+            Some(ledger) => ledger.epsilon_with(self.target_delta, gaussian_rdp(noise_multiplier)),
+            None => self.consumed_budget + step_epsilon,
+        };
+        if projected > self.total_epsilon {
+            return Err(PrivacyError::BudgetExceeded);
+        }
+        self.consumed_budget += step_epsilon;
+        if let Some(ledger) = &mut self.rdp {
+            ledger.add(gaussian_rdp(noise_multiplier));
+        }
+        self.query_count += 1;
+        Ok(())
+    }
+
+    /// Total ε spent so far. With RDP accounting this is the tightest
+    /// conversion at `delta`; the additive accountant ignores `delta` and
+    /// reports its summed (decayed) ε.
+    pub fn spent_epsilon(&self, delta: f64) -> f32 {
+        match &self.rdp {
+            Some(ledger) => ledger.epsilon_with(delta, |_| 0.0) as f32,
+            None => self.consumed_budget as f32,
+        }
+    }
+
+    /// Check `peer_id`'s sub-budget and then the global budget for a query
+    /// with cost `epsilon_cost` sent to that peer.
+    pub fn check_budget_for(&self, peer_id: &str, epsilon_cost: f64) -> Result<(), PrivacyError> {
+        if epsilon_cost < 0.0 {
+            return Err(PrivacyError::InvalidCost);
+        }
+        if let Some(cap) = self.per_peer_epsilon {
+            let spent = self.peer_consumed.get(peer_id).copied().unwrap_or(0.0);
+            if spent + epsilon_cost > cap {
+                return Err(PrivacyError::PeerBudgetExceeded);
+            }
+        }
+        self.check_budget(epsilon_cost)
+    }
+
+    /// Deduct budget for a query sent to `peer_id`, from both its sub-budget
+    /// and the global budget.
+    pub fn record_consumption_for(
+        &mut self,
+        peer_id: &str,
+        epsilon_cost: f64,
+    ) -> Result<(), PrivacyError> {
+        self.check_budget_for(peer_id, epsilon_cost)?;
+        *self
+            .peer_consumed
+            .entry(String::from(peer_id))
+            .or_insert(0.0) += epsilon_cost;
+        self.consumed_budget += epsilon_cost;
         self.query_count += 1;
         Ok(())
     }
 
+    /// Epsilon still available for `peer_id`: the smaller of its remaining
+    /// sub-budget and the remaining global budget.
+    pub fn remaining_for(&self, peer_id: &str) -> f64 {
+        let global = self.total_epsilon - self.consumed_budget;
+        let remaining = match self.per_peer_epsilon {
+            Some(cap) => {
+                let spent = self.peer_consumed.get(peer_id).copied().unwrap_or(0.0);
+                global.min(cap - spent)
+            }
+            None => global,
+        };
+        remaining.max(0.0)
+    }
+
     /// Decay the consumed budget (simulate rolling window)
     /// Should be called periodically (e.g. every tick)
     pub fn decay(&mut self) {
         self.consumed_budget *= self.decay_rate;
+        if let Some(ledger) = &mut self.rdp {
+            let decay_rate = self.decay_rate;
+            for rdp in ledger.rdp.iter_mut() {
+                *rdp *= decay_rate;
+            }
+        }
+        for spent in self.peer_consumed.values_mut() {
+            *spent *= self.decay_rate;
+        }
+        // Forget peers whose spend has decayed away so the map stays bounded
+        self.peer_consumed.retain(|_, spent| *spent > 1e-9);
     }
 
     /// Reset budget (e.g., daily reset).
     pub fn reset(&mut self) {
         self.consumed_budget = 0.0;
         self.query_count = 0;
+        self.peer_consumed.clear();
+        if let Some(ledger) = &mut self.rdp {
+            ledger.rdp.iter_mut().for_each(|rdp| *rdp = 0.0);
+        }
     }
 }
 
@@ -354,6 +553,109 @@ mod tests {
         assert!(sigma > 4.8 && sigma < 4.9);
     }
 
+    #[test]
+    fn test_per_peer_budget_isolated_until_global_cap() {
+        let mut accountant = PrivacyAccountant::new(10.0, 1e-5, 0.995).with_peer_budget(4.0);
+
+        // A chatty peer exhausts its own sub-budget...
+        for _ in 0..4 {
+            accountant.record_consumption_for("chatty", 1.0).unwrap();
+        }
+        assert!(matches!(
+            accountant.check_budget_for("chatty", 1.0),
+            Err(PrivacyError::PeerBudgetExceeded)
+        ));
+        assert_eq!(accountant.remaining_for("chatty"), 0.0);
+
+        // ...while another peer's sub-budget is untouched
+        assert_eq!(accountant.remaining_for("quiet"), 4.0);
+        accountant.record_consumption_for("quiet", 4.0).unwrap();
+
+        // Global: 8 of 10 spent, so a fresh peer is capped by the umbrella
+        assert!((accountant.remaining_for("fresh") - 2.0).abs() < 1e-9);
+        accountant.record_consumption_for("fresh", 2.0).unwrap();
+        assert!(matches!(
+            accountant.check_budget_for("other", 0.5),
+            Err(PrivacyError::BudgetExceeded)
+        ));
+        assert_eq!(accountant.remaining_for("other"), 0.0);
+
+        // Decay frees sub-budgets and the global budget alike
+        accountant.decay();
+        assert!(accountant.remaining_for("chatty") > 0.0);
+        accountant.reset();
+        assert_eq!(accountant.remaining_for("chatty"), 4.0);
+        assert!(matches!(
+            accountant.check_budget_for("chatty", -1.0),
+            Err(PrivacyError::InvalidCost)
+        ));
+    }
+
+    #[test]
+    fn test_rdp_composition_tighter_than_additive() {
+        // Noise calibrated so each step is (1.0, 1e-5)-DP on its own
+        let z = DifferentialPrivacy::new(1.0, 1e-5, 1.0).sigma();
+        let steps = 100;
+
+        let mut additive = PrivacyAccountant::new(1e6, 1e-5, 1.0);
+        let mut rdp = PrivacyAccountant::with_rdp(&DEFAULT_RDP_ORDERS, 1e-5).unwrap();
+        rdp.total_epsilon = 1e6;
+        for _ in 0..steps {
+            additive.record_gaussian(z).unwrap();
+            rdp.record_gaussian(z).unwrap();
+        }
+
+        let naive = additive.spent_epsilon(1e-5);
+        let tight = rdp.spent_epsilon(1e-5);
+        assert!((naive - steps as f32).abs() < 1e-3, "naive {}", naive);
+        // RDP composes as ~sqrt(steps) rather than linearly (≈12 here)
+        assert!(tight < naive / 5.0, "RDP {} vs additive {}", tight, naive);
+        assert!(tight > 0.0);
+        assert_eq!(rdp.query_count, steps);
+
+        // A looser δ buys a smaller ε
+        assert!(rdp.spent_epsilon(1e-3) < tight);
+    }
+
+    #[test]
+    fn test_rdp_rejects_orders_at_or_below_one() {
+        for orders in [&[1.0][..], &[0.5, 1.0], &[f64::NAN]] {
+            assert!(matches!(
+                PrivacyAccountant::with_rdp(orders, 1e-5),
+                Err(PrivacyError::InvalidRdpOrders)
+            ));
+        }
+        // Orders ≤ 1 alongside a usable one are dropped
+        let accountant = PrivacyAccountant::with_rdp(&[1.0, 8.0], 1e-5).unwrap();
+        assert!(accountant.spent_epsilon(1e-5).is_finite());
+    }
+
+    #[test]
+    fn test_rdp_budget_gate() {
+        let z = DifferentialPrivacy::new(1.0, 1e-5, 1.0).sigma();
+        let mut accountant = PrivacyAccountant::with_rdp(&[], 1e-5).unwrap();
+        let mut accepted = 0;
+        while accountant.record_gaussian(z).is_ok() {
+            accepted += 1;
+        }
+        // The additive default (10.0) would stop after 10 steps
+        assert!(accepted > 10, "only {} steps accepted", accepted);
+        assert!(accountant.spent_epsilon(1e-5) <= accountant.total_epsilon as f32);
+        assert!(matches!(
+            accountant.record_gaussian(0.0),
+            Err(PrivacyError::InvalidCost)
+        ));
+
+        accountant.reset();
+        assert_eq!(accountant.spent_epsilon(1e-5), {
+            let min_conversion = DEFAULT_RDP_ORDERS
+                .iter()
+                .map(|&a| ln(1e5) / (a - 1.0))
+                .fold(f64::INFINITY, f64::min);
+            min_conversion as f32
+        });
+    }
+
     #[test]
     fn test_clipping() {
         let dp = DifferentialPrivacy::new(1.0, 1e-5, 1.0);
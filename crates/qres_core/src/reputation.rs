@@ -48,9 +48,13 @@ const INFLUENCE_CAP: f32 = 0.8;
 /// - Valid ZKP submissions increase score
 /// - Drift detection during aggregation decreases score
 /// - Peers below 0.2 are banned from consensus
+/// - Explicit bans (`ban`) last until a given round, then expire
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ReputationTracker {
     scores: BTreeMap<PeerId, f32>,
+    /// Explicit bans: peer -> first round at which the peer is eligible again
+    #[serde(default)]
+    bans: BTreeMap<PeerId, u64>,
 }
 
 impl Default for ReputationTracker {
@@ -63,6 +67,7 @@ impl ReputationTracker {
     pub fn new() -> Self {
         Self {
             scores: BTreeMap::new(),
+            bans: BTreeMap::new(),
         }
     }
 
@@ -76,6 +81,66 @@ impl ReputationTracker {
         self.get_score(peer) < BAN_THRESHOLD
     }
 
+    /// Ban a peer until `until_round` (exclusive). Re-banning replaces the
+    /// previous expiry.
+    pub fn ban(&mut self, peer: &PeerId, until_round: u64) {
+        self.bans.insert(*peer, until_round);
+    }
+
+    /// Check if a peer is banned at `current_round`.
+    ///
+    /// An explicit ban decides while it is on record: banned before
+    /// `until_round`, eligible from then on even if the score is still below
+    /// the threshold (the timeout is the un-ban). Peers without an explicit
+    /// ban fall back to the score threshold, as in [`Self::is_banned`].
+    pub fn is_banned_at(&self, peer: &PeerId, current_round: u64) -> bool {
+        match self.bans.get(peer) {
+            Some(&until_round) => current_round < until_round,
+            None => self.is_banned(peer),
+        }
+    }
+
+    /// All peers banned at `current_round` (explicitly or by score), sorted
+    pub fn banned_peers(&self, current_round: u64) -> Vec<PeerId> {
+        let mut banned: Vec<PeerId> = self
+            .bans
+            .iter()
+            .filter(|(_, &until_round)| current_round < until_round)
+            .map(|(&peer, _)| peer)
+            .chain(
+                self.scores
+                    .iter()
+                    .filter(|(peer, &score)| {
+                        score < BAN_THRESHOLD && !self.bans.contains_key(*peer)
+                    })
+                    .map(|(&peer, _)| peer),
+            )
+            .collect();
+        banned.sort_unstable();
+        banned
+    }
+
+    /// Drop bans that have expired by `current_round` and return those peers.
+    ///
+    /// Expired peers re-enter on probation: a score below the ban threshold
+    /// is raised to exactly the threshold, so a single further penalty bans
+    /// them again by score.
+    pub fn expire_bans(&mut self, current_round: u64) -> Vec<PeerId> {
+        let expired: Vec<PeerId> = self
+            .bans
+            .iter()
+            .filter(|(_, &until_round)| current_round >= until_round)
+            .map(|(&peer, _)| peer)
+            .collect();
+        for peer in &expired {
+            self.bans.remove(peer);
+            if let Some(score) = self.scores.get_mut(peer) {
+                *score = score.max(BAN_THRESHOLD);
+            }
+        }
+        expired
+    }
+
     /// Reward a peer for submitting a valid ZKP
     pub fn reward_valid_zkp(&mut self, peer: &PeerId) {
         let score = self.scores.entry(*peer).or_insert(DEFAULT_TRUST);
@@ -270,6 +335,38 @@ mod tests {
         assert!(fixed >= 0, "Fixed-point influence must be non-negative");
     }
 
+    #[test]
+    fn test_ban_expires_after_until_round() {
+        let mut tracker = ReputationTracker::new();
+        let peer = make_peer(1);
+        let bystander = make_peer(2);
+
+        // Drive the score below the threshold, then ban for rounds 10..20
+        for _ in 0..5 {
+            tracker.penalize_drift(&peer);
+        }
+        tracker.ban(&peer, 20);
+        tracker.penalize_drift(&bystander);
+
+        assert!(tracker.is_banned_at(&peer, 10));
+        assert!(tracker.is_banned_at(&peer, 19));
+        assert!(!tracker.is_banned_at(&bystander, 19));
+        assert_eq!(tracker.banned_peers(19), vec![peer]);
+
+        // Past the expiry round the peer is eligible again
+        assert!(!tracker.is_banned_at(&peer, 20));
+        assert!(tracker.banned_peers(20).is_empty());
+        assert!(tracker.expire_bans(19).is_empty());
+        assert_eq!(tracker.expire_bans(25), vec![peer]);
+        assert!(!tracker.is_banned_at(&peer, 25));
+        assert!(!tracker.is_banned(&peer));
+
+        // On probation at the threshold: one more penalty re-bans by score
+        tracker.penalize_drift(&peer);
+        assert!(tracker.is_banned_at(&peer, 26));
+        assert_eq!(tracker.banned_peers(26), vec![peer]);
+    }
+
     #[test]
     fn test_active_peers_excludes_banned() {
         let mut tracker = ReputationTracker::new();
@@ -23,6 +23,8 @@ pub mod energy_costs {
     pub const ADAPT: u32 = 25; // Weight update cost
     pub const RECHARGE_RATE: u32 = 5; // Energy recovered per Calm tick
     pub const HEARTBEAT: u32 = 5; // Low-cost proof-of-life packet
+    pub const ZK_PROVE: u32 = 80; // Norm proof: commitment + Schnorr scalar mults
+    pub const ZK_VERIFY: u32 = 30; // Norm proof check: two-term multiscalar mult
 }
 
 // =============================================================================
@@ -106,6 +108,10 @@ pub struct EnergyPool {
     max_capacity: u32,
     /// Total energy units consumed (lifetime telemetry)
     lifetime_consumption: u64,
+    /// Energy harvested automatically on each `tick()` (0 = battery only)
+    harvest_per_tick: u32,
+    /// Total energy units actually stored by harvesting (lifetime telemetry)
+    lifetime_harvested: u64,
 }
 
 impl Default for EnergyPool {
@@ -117,10 +123,18 @@ impl Default for EnergyPool {
 impl EnergyPool {
     /// Create a new energy pool with specified max capacity
     pub fn new(max_capacity: u32) -> Self {
+        Self::with_harvest_rate(max_capacity, 0)
+    }
+
+    /// Create a full pool that harvests `per_tick` units on every `tick()`
+    /// (solar or other ambient harvesting)
+    pub fn with_harvest_rate(max_capacity: u32, per_tick: u32) -> Self {
         Self {
             current: max_capacity,
             max_capacity,
             lifetime_consumption: 0,
+            harvest_per_tick: per_tick,
+            lifetime_harvested: 0,
         }
     }
 
@@ -165,7 +179,32 @@ impl EnergyPool {
 
     /// Recharge energy (capped at max_capacity)
     pub fn recharge(&mut self, amount: u32) {
-        self.current = (self.current + amount).min(self.max_capacity);
+        self.harvest(amount);
+    }
+
+    /// Add harvested energy, capped at max_capacity.
+    /// Returns the units actually stored.
+    pub fn harvest(&mut self, units: u32) -> u32 {
+        let stored = units.min(self.max_capacity - self.current);
+        self.current += stored;
+        self.lifetime_harvested += stored as u64;
+        stored
+    }
+
+    /// Advance one tick, harvesting the configured per-tick rate.
+    /// Returns the units actually stored.
+    pub fn tick(&mut self) -> u32 {
+        self.harvest(self.harvest_per_tick)
+    }
+
+    /// Energy harvested per `tick()`
+    pub fn harvest_rate(&self) -> u32 {
+        self.harvest_per_tick
+    }
+
+    /// Total lifetime energy harvested
+    pub fn lifetime_harvested(&self) -> u64 {
+        self.lifetime_harvested
     }
 
     /// Check if energy is critical (< 10%)
@@ -303,6 +342,41 @@ mod energy_pool_tests {
         assert!(!pool.is_critical());
     }
 
+    #[test]
+    fn test_harvest_cycle_reopens_enclave_gate() {
+        use crate::zk_proofs::{EnclaveError, EnclaveGate, SoftwareEnclaveGate};
+
+        let gate = SoftwareEnclaveGate::new(0.10);
+        let mut pool = EnergyPool::with_harvest_rate(1000, 20);
+
+        // Drain below the 10% INV-5 threshold
+        while pool.spend(energy_costs::GOSSIP_SEND) {}
+        assert!(pool.is_critical());
+        assert!(pool.ratio() < 0.10);
+        assert!(matches!(
+            gate.report_reputation(0.5, pool.ratio()),
+            Err(EnclaveError::InsufficientEnergy)
+        ));
+
+        // Harvest back above the threshold, one tick at a time
+        let mut ticks = 0;
+        while pool.is_critical() {
+            assert_eq!(pool.tick(), 20);
+            ticks += 1;
+        }
+        assert!(ticks <= 100 / 20 + 1);
+        assert!(pool.ratio() >= 0.10);
+        assert!(gate.report_reputation(0.5, pool.ratio()).is_ok());
+
+        // Harvesting stops at capacity and only counts what was stored
+        let stored = pool.harvest(u32::MAX);
+        assert_eq!(pool.current(), 1000);
+        assert_eq!(pool.ratio(), 1.0);
+        assert_eq!(pool.tick(), 0);
+        assert_eq!(pool.lifetime_harvested(), ticks as u64 * 20 + stored as u64);
+        assert_eq!(pool.lifetime_consumption(), 1000);
+    }
+
     #[test]
     fn test_insufficient_energy() {
         let mut pool = EnergyPool::new(100);
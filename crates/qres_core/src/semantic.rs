@@ -648,7 +648,9 @@ mod tests {
         use curve25519_dalek::scalar::Scalar;
         crate::zk_proofs::NormProof {
             commitment: CompressedEdwardsY::default(),
+            announcement: CompressedEdwardsY::default(),
             response: Scalar::ZERO,
+            value: 0,
         }
     }
 
@@ -1,3 +1,4 @@
+use alloc::collections::VecDeque;
 use alloc::vec;
 use alloc::vec::Vec;
 use fixed::types::I16F16;
@@ -78,12 +79,27 @@ impl FixedTensor {
             .collect();
         Self::new(data_i16f16)
     }
+
+    /// Max absolute error of a Storm Mode round trip
+    /// (`quantize_to_i8f8` then `from_i8f8`), including saturation loss
+    pub fn quantization_error(&self) -> I16F16 {
+        let restored = Self::from_i8f8(&self.quantize_to_i8f8());
+        self.data
+            .iter()
+            .zip(&restored.data)
+            .map(|(&a, &b)| a.saturating_sub(b).saturating_abs())
+            .max()
+            .unwrap_or(I16F16::ZERO)
+    }
 }
 
 // ============================================================================
 // VarianceMonitor: BFP-16 Auto-Tuning for Vanishing Gradient Recovery
 // ============================================================================
 
+/// Observations retained by [`VarianceMonitor`] for convergence checks
+pub const CONVERGENCE_HISTORY: usize = 256;
+
 /// Monitors gradient magnitudes during the backward pass and triggers
 /// a bit-shift in the Bfp16Vec exponent to re-center the precision window
 /// when gradients fall below 10^-7.
@@ -102,6 +118,8 @@ pub struct VarianceMonitor {
     corrections_applied: usize,
     /// Running minimum gradient magnitude observed
     min_magnitude: f32,
+    /// Most recent observations (newest last), capped at [`CONVERGENCE_HISTORY`]
+    history: VecDeque<f32>,
 }
 
 impl Default for VarianceMonitor {
@@ -123,6 +141,7 @@ impl VarianceMonitor {
             current_below: 0,
             corrections_applied: 0,
             min_magnitude: f32::MAX,
+            history: VecDeque::new(),
         }
     }
 
@@ -139,6 +158,7 @@ impl VarianceMonitor {
         // Compute gradient magnitude (L2 norm / sqrt(n))
         let sum_sq: f32 = gradients.iter().map(|g| g * g).sum();
         let magnitude = (sum_sq / gradients.len() as f32).sqrt();
+        self.record(magnitude);
 
         if magnitude < self.min_magnitude && magnitude > 0.0 {
             self.min_magnitude = magnitude;
@@ -188,6 +208,31 @@ impl VarianceMonitor {
         bfp.exponent = new_exp;
     }
 
+    /// Record an externally computed variance (e.g. swarm-wide confidence
+    /// variance) without running gradient auto-tuning.
+    pub fn observe_variance(&mut self, variance: f32) {
+        self.record(variance);
+    }
+
+    fn record(&mut self, value: f32) {
+        if self.history.len() == CONVERGENCE_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(value);
+    }
+
+    /// Whether the last `window` observations all stayed below `epsilon`.
+    ///
+    /// A single observation at or above `epsilon` (or NaN) restarts the
+    /// window. Always `false` while fewer than `window` observations have
+    /// been recorded, hence also for `window > CONVERGENCE_HISTORY`, and for
+    /// `window == 0` (an empty window is no evidence of convergence).
+    pub fn has_converged(&self, window: usize, epsilon: f32) -> bool {
+        window > 0
+            && self.history.len() >= window
+            && self.history.iter().rev().take(window).all(|&v| v < epsilon)
+    }
+
     /// Get the number of corrections applied so far.
     pub fn corrections_count(&self) -> usize {
         self.corrections_applied
@@ -203,6 +248,7 @@ impl VarianceMonitor {
         self.current_below = 0;
         self.corrections_applied = 0;
         self.min_magnitude = f32::MAX;
+        self.history.clear();
     }
 }
 
@@ -230,9 +276,84 @@ impl MpsCompressor {
     /// Compress a 2D matrix (rows x cols) into MPS cores using "Haar Wavelet Tensor Train"
     /// Uses Q16.16 Fixed Point arithmetic for determinism.
     pub fn compress_matrix(&self, data: &[f64], rows: usize, cols: usize) -> Vec<Vec<f64>> {
+        let coeffs = match self.wavelet_coefficients(data, rows, cols) {
+            Some(c) => c,
+            None => return Vec::new(), // Error
+        };
+
+        // Thresholding (Sparse approximation of Wavelet Coefficients)
+        let mut flattened_sparse = Vec::new();
+        for val in coeffs {
+            if val.abs() > self.threshold {
+                flattened_sparse.push(val.to_num::<f64>());
+            } else {
+                flattened_sparse.push(0.0);
+            }
+        }
+
+        vec![flattened_sparse]
+    }
+
+    /// Like `compress_matrix`, but returns only the surviving coefficients as
+    /// `(index, value)` pairs (index into the column-major coefficient layout).
+    ///
+    /// Returns an empty vector if `data.len() != rows * cols`.
+    pub fn compress_matrix_sparse(
+        &self,
+        data: &[f64],
+        rows: usize,
+        cols: usize,
+    ) -> Vec<(u32, f64)> {
+        let coeffs = match self.wavelet_coefficients(data, rows, cols) {
+            Some(c) => c,
+            None => return Vec::new(),
+        };
+
+        coeffs
+            .iter()
+            .enumerate()
+            .filter(|(_, val)| val.abs() > self.threshold)
+            .map(|(i, val)| (i as u32, val.to_num::<f64>()))
+            .collect()
+    }
+
+    /// Reconstruct a row-major `rows x cols` matrix from `compress_matrix_sparse` output.
+    ///
+    /// Pruned coefficients are taken as zero, so each element is within the
+    /// approximation threshold of the original (up to Q16.16 rounding).
+    /// Out-of-range indices are ignored.
+    pub fn decompress_sparse(&self, pairs: &[(u32, f64)], rows: usize, cols: usize) -> Vec<f64> {
+        let mut transposed = vec![I16F16::ZERO; rows * cols];
+        for &(idx, val) in pairs {
+            if let Some(slot) = transposed.get_mut(idx as usize) {
+                *slot = I16F16::from_num(val);
+            }
+        }
+
+        // Undo column transform, then transpose back and undo row transform
+        for c in 0..cols {
+            self.inverse_haar_1d(&mut transposed, c * rows, rows);
+        }
+
+        let mut matrix = vec![I16F16::ZERO; rows * cols];
+        for r in 0..rows {
+            for c in 0..cols {
+                matrix[r * cols + c] = transposed[c * rows + r];
+            }
+        }
+
+        for r in 0..rows {
+            self.inverse_haar_1d(&mut matrix, r * cols, cols);
+        }
+
+        matrix.iter().map(|v| v.to_num::<f64>()).collect()
+    }
+
+    /// 2D Haar transform of the matrix, in column-major (transposed) layout.
+    fn wavelet_coefficients(&self, data: &[f64], rows: usize, cols: usize) -> Option<Vec<I16F16>> {
         // Validation
         if data.len() != rows * cols {
-            return Vec::new(); // Error
+            return None;
         }
 
         // 1. Convert to Fixed Point Matrix
@@ -265,17 +386,7 @@ impl MpsCompressor {
             self.haar_1d(&mut transposed, c * rows, rows);
         }
 
-        // Thresholding (Sparse approximation of Wavelet Coefficients)
-        let mut flattened_sparse = Vec::new();
-        for val in transposed {
-            if val.abs() > self.threshold {
-                flattened_sparse.push(val.to_num::<f64>());
-            } else {
-                flattened_sparse.push(0.0);
-            }
-        }
-
-        vec![flattened_sparse]
+        Some(transposed)
     }
 
     fn haar_1d(&self, data: &mut [I16F16], start: usize, len: usize) {
@@ -303,6 +414,10 @@ impl MpsCompressor {
                     *idx = diff.checked_mul(frac_sqrt_2).unwrap_or(I16F16::MAX);
                 }
             }
+            // Odd length: the unpaired last element passes through unchanged
+            if h % 2 == 1 {
+                temp[h - 1] = data.get(start + h - 1).copied().unwrap_or(I16F16::ZERO);
+            }
             // Copy back
             if let Some(dest_slice) = data.get_mut(start..start + h) {
                 if let Some(src_slice) = temp.get(..h) {
@@ -312,6 +427,145 @@ impl MpsCompressor {
             h = half;
         }
     }
+
+    /// Inverse of `haar_1d`: replays the levels from coarsest to finest.
+    fn inverse_haar_1d(&self, data: &mut [I16F16], start: usize, len: usize) {
+        let mut levels = Vec::new();
+        let mut h = len;
+        while h > 1 {
+            levels.push(h);
+            h /= 2;
+        }
+
+        let frac_sqrt_2 = I16F16::from_num(core::f64::consts::FRAC_1_SQRT_2);
+        let mut temp = vec![I16F16::ZERO; len];
+
+        for &h in levels.iter().rev() {
+            let half = h / 2;
+            for i in 0..half {
+                let s = data.get(start + i).copied().unwrap_or(I16F16::ZERO);
+                let d = data.get(start + half + i).copied().unwrap_or(I16F16::ZERO);
+
+                let a = s.checked_add(d).unwrap_or(I16F16::MAX);
+                let b = s.checked_sub(d).unwrap_or(I16F16::MAX);
+
+                temp[2 * i] = a.checked_mul(frac_sqrt_2).unwrap_or(I16F16::MAX);
+                temp[2 * i + 1] = b.checked_mul(frac_sqrt_2).unwrap_or(I16F16::MAX);
+            }
+            if h % 2 == 1 {
+                temp[h - 1] = data.get(start + h - 1).copied().unwrap_or(I16F16::ZERO);
+            }
+            if let Some(dest_slice) = data.get_mut(start..start + h) {
+                dest_slice.copy_from_slice(&temp[..h]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod fixed_tensor_tests {
+    use super::*;
+
+    #[test]
+    fn test_quantization_error_matches_round_trip_near_saturation() {
+        let tensor = FixedTensor::new(
+            [127.9, -127.5, 127.999, -128.5, 200.0, 1.0 / 512.0, 1.5]
+                .iter()
+                .map(|&v| I16F16::from_num(v))
+                .collect(),
+        );
+
+        let restored = FixedTensor::from_i8f8(&tensor.quantize_to_i8f8());
+        let observed = tensor
+            .data
+            .iter()
+            .zip(&restored.data)
+            .map(|(&a, &b)| (a - b).abs())
+            .max()
+            .unwrap();
+        assert_eq!(tensor.quantization_error(), observed);
+
+        // 200.0 saturates at the I8F8 maximum (127.99609375)
+        let expected = I16F16::from_num(200.0) - I16F16::from_num(I8F8::MAX);
+        assert_eq!(tensor.quantization_error(), expected);
+    }
+
+    #[test]
+    fn test_quantization_error_zero_for_i8f8_representable_weights() {
+        let tensor = FixedTensor::new(
+            [0.0, 0.5, -3.25, 100.0, -128.0]
+                .iter()
+                .map(|&v| I16F16::from_num(v))
+                .collect(),
+        );
+        assert_eq!(tensor.quantization_error(), I16F16::ZERO);
+        assert_eq!(
+            FixedTensor::new(Vec::new()).quantization_error(),
+            I16F16::ZERO
+        );
+    }
+}
+
+#[cfg(test)]
+mod mps_tests {
+    use super::*;
+
+    fn smooth_matrix(rows: usize, cols: usize) -> Vec<f64> {
+        (0..rows * cols)
+            .map(|i| {
+                let (r, c) = ((i / cols) as f64, (i % cols) as f64);
+                1.0 + 0.05 * r + 0.02 * c + if (r + c) as usize % 7 == 0 { 0.3 } else { 0.0 }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sparse_roundtrip_within_threshold() {
+        for (rows, cols) in [(8, 8), (6, 5), (16, 3)] {
+            let data = smooth_matrix(rows, cols);
+
+            // No pruning: only fixed-point rounding remains
+            let exact = MpsCompressor::new(10, 0.0);
+            let pairs = exact.compress_matrix_sparse(&data, rows, cols);
+            let recon = exact.decompress_sparse(&pairs, rows, cols);
+            for (a, b) in data.iter().zip(&recon) {
+                assert!((a - b).abs() < 1e-3, "{}x{}: {} vs {}", rows, cols, a, b);
+            }
+
+            // Pruned: the orthonormal transform bounds the L2 error by the
+            // energy of the dropped coefficients (each <= threshold)
+            let threshold = 0.05;
+            let lossy = MpsCompressor::new(10, threshold);
+            let pairs = lossy.compress_matrix_sparse(&data, rows, cols);
+            let dropped = rows * cols - pairs.len();
+            assert!(dropped > 0, "threshold should prune something");
+            let recon = lossy.decompress_sparse(&pairs, rows, cols);
+            let err_l2: f64 = data
+                .iter()
+                .zip(&recon)
+                .map(|(a, b)| (a - b) * (a - b))
+                .sum::<f64>()
+                .sqrt();
+            assert!(err_l2 <= threshold * (dropped as f64).sqrt() + 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_sparse_matches_dense_nonzeros() {
+        let data = smooth_matrix(8, 4);
+        let mps = MpsCompressor::new(10, 0.05);
+        let dense = &mps.compress_matrix(&data, 8, 4)[0];
+        let pairs = mps.compress_matrix_sparse(&data, 8, 4);
+
+        let nonzero: Vec<(u32, f64)> = dense
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| **v != 0.0)
+            .map(|(i, v)| (i as u32, *v))
+            .collect();
+        assert_eq!(pairs, nonzero);
+        assert!(mps.compress_matrix_sparse(&data, 3, 3).is_empty());
+    }
 }
 
 #[cfg(test)]
@@ -319,6 +573,42 @@ mod variance_monitor_tests {
     use super::*;
     use crate::consensus::krum::Bfp16Vec;
 
+    #[test]
+    fn test_has_converged_after_sustained_window_and_resets_on_spike() {
+        let mut monitor = VarianceMonitor::default();
+        let (window, epsilon) = (4, 0.01);
+
+        // Decaying variance: 0.1, 0.05, 0.025, 0.0125, then below epsilon
+        let mut variance = 0.1f32;
+        let mut fired_at = None;
+        for step in 0..12 {
+            monitor.observe_variance(variance);
+            if monitor.has_converged(window, epsilon) && fired_at.is_none() {
+                fired_at = Some(step);
+            }
+            variance *= 0.5;
+        }
+        // First sub-epsilon value is step 4 (0.00625); window completes at step 7
+        assert_eq!(fired_at, Some(7));
+
+        // A spike restarts the window
+        monitor.observe_variance(0.5);
+        assert!(!monitor.has_converged(window, epsilon));
+        for _ in 0..window - 1 {
+            monitor.observe_variance(0.001);
+            assert!(!monitor.has_converged(window, epsilon));
+        }
+        monitor.observe_variance(0.001);
+        assert!(monitor.has_converged(window, epsilon));
+
+        assert!(!monitor.has_converged(0, epsilon));
+
+        monitor.reset();
+        assert!(!monitor.has_converged(0, epsilon));
+        assert!(!monitor.has_converged(1, epsilon));
+        assert!(!monitor.has_converged(CONVERGENCE_HISTORY + 1, f32::MAX));
+    }
+
     #[test]
     fn test_no_correction_for_normal_gradients() {
         let mut monitor = VarianceMonitor::default();
@@ -6,12 +6,16 @@
 // #[cfg(not(feature = "std"))]
 // use alloc::vec::Vec;
 
+use alloc::collections::VecDeque;
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 use blake3::Hasher;
 use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
 use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
 use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::{IsIdentity, MultiscalarMul};
+use rand_chacha::rand_core::{CryptoRng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use serde::{Deserialize, Serialize}; // Added for ProofBundle
 #[cfg(feature = "std")]
 use std::vec::Vec;
@@ -25,15 +29,42 @@ pub struct PedersenGens {
     pub h: EdwardsPoint,
 }
 
+/// Domain-separation string hashed to derive the value generator H.
+const PEDERSEN_H_DOMAIN: &[u8] = b"QRES-PedersenGens-H-v1";
+
 impl Default for PedersenGens {
     fn default() -> Self {
         let g = ED25519_BASEPOINT_POINT;
-        // H = 2*G (simple derivation, secure if dlog relationship unknown)
-        let h = g + g;
+        // H is hashed to the curve, so nobody knows log_G(H) (binding)
+        let h = hash_to_curve(PEDERSEN_H_DOMAIN);
         PedersenGens { g, h }
     }
 }
 
+/// Map `domain` to a prime-order curve point with unknown discrete log.
+///
+/// Try-and-increment: BLAKE3(domain || counter) is read as a compressed
+/// Edwards point until one decompresses; clearing the cofactor then lands it
+/// in the prime-order subgroup. About half of all candidates decompress, and
+/// the input is a public constant, so the variable running time leaks nothing.
+fn hash_to_curve(domain: &[u8]) -> EdwardsPoint {
+    let mut counter = 0u32;
+    loop {
+        let mut hasher = Hasher::new();
+        hasher.update(b"QRES-HashToCurve-v1");
+        hasher.update(domain);
+        hasher.update(&counter.to_le_bytes());
+        let candidate = CompressedEdwardsY(*hasher.finalize().as_bytes());
+        if let Some(point) = candidate.decompress() {
+            let point = point.mul_by_cofactor();
+            if !point.is_small_order() {
+                return point;
+            }
+        }
+        counter += 1;
+    }
+}
+
 impl PedersenGens {
     /// Create a commitment C = v*H + r*G
     pub fn commit(&self, value: Scalar, blinding: Scalar) -> EdwardsPoint {
@@ -77,12 +108,68 @@ impl SimpleTranscript {
 }
 
 /// Proof that the L2 norm of a vector is within a threshold.
+///
+/// The scaled squared norm `value` is disclosed so the verifier can range-check
+/// it; the Schnorr proof shows the prover knows the blinding `r` opening
+/// `commitment = value*H + r*G`, i.e. that the commitment really binds `value`.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NormProof {
-    /// Commitment to the norm
+    /// Commitment to the norm: C = value*H + r*G
     pub commitment: CompressedEdwardsY,
-    /// Schnorr response
+    /// Schnorr announcement: R = k*G
+    #[serde(default)]
+    pub announcement: CompressedEdwardsY,
+    /// Schnorr response: s = k + c*r
     pub response: Scalar,
+    /// Squared L2 norm scaled by `NORM_SCALE` and truncated
+    #[serde(default)]
+    pub value: u64,
+}
+
+impl NormProof {
+    /// Size of the fixed-width encoding produced by [`to_bytes`](Self::to_bytes)
+    pub const WIRE_SIZE: usize = 104;
+
+    /// Encode as `commitment || announcement || response || value (LE)`.
+    pub fn to_bytes(&self) -> [u8; Self::WIRE_SIZE] {
+        let mut out = [0u8; Self::WIRE_SIZE];
+        out[..32].copy_from_slice(self.commitment.as_bytes());
+        out[32..64].copy_from_slice(self.announcement.as_bytes());
+        out[64..96].copy_from_slice(self.response.as_bytes());
+        out[96..].copy_from_slice(&self.value.to_le_bytes());
+        out
+    }
+
+    /// Decode [`to_bytes`](Self::to_bytes) output. Returns `None` on a wrong
+    /// length or a non-canonical response scalar.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::WIRE_SIZE {
+            return None;
+        }
+        let commitment = CompressedEdwardsY::from_slice(&bytes[..32]).ok()?;
+        let announcement = CompressedEdwardsY::from_slice(&bytes[32..64]).ok()?;
+        let response: [u8; 32] = bytes[64..96].try_into().ok()?;
+        let response = Option::from(Scalar::from_canonical_bytes(response))?;
+        let value = u64::from_le_bytes(bytes[96..].try_into().ok()?);
+        Some(Self {
+            commitment,
+            announcement,
+            response,
+            value,
+        })
+    }
+}
+
+/// Fixed-point scale applied to squared norms before committing to them.
+pub const NORM_SCALE: f32 = 1_000_000.0;
+
+/// Fiat-Shamir challenge binding a norm proof's commitment, announcement and value.
+fn norm_challenge(commitment: &EdwardsPoint, announcement: &EdwardsPoint, value: u64) -> Scalar {
+    let mut transcript = SimpleTranscript::new(b"NormProof");
+    transcript.append_point(b"C", commitment);
+    transcript.append_point(b"R", announcement);
+    transcript.append_message(b"v", &value.to_le_bytes());
+    transcript.challenge_scalar(b"c")
 }
 
 /// A Bundle containing the Identity, the Masked Update, and the ZK Proof of Normality.
@@ -104,7 +191,7 @@ pub struct ProofBundle {
 /// A zero-knowledge proof that a weight transition is legitimate.
 /// Uses a non-interactive Sigma protocol (Schnorr-style) over the
 /// Edwards curve to prove knowledge of the transition without revealing weights.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ZkTransitionProof {
     /// Commitment to the previous weight hash: C_prev = hash_scalar * H + r_prev * G
     pub commitment_prev: CompressedEdwardsY,
@@ -120,6 +207,53 @@ pub struct ZkTransitionProof {
     pub residual_commitment: CompressedEdwardsY,
 }
 
+impl ZkTransitionProof {
+    /// Size of [`to_compact_bytes`](Self::to_compact_bytes): five points and one scalar.
+    pub const COMPACT_LEN: usize = 6 * 32;
+
+    /// Fixed-size wire encoding, in field order:
+    /// `[C_prev | C_new | B | R | s | residual]`, 32 bytes each.
+    pub fn to_compact_bytes(&self) -> [u8; Self::COMPACT_LEN] {
+        let mut out = [0u8; Self::COMPACT_LEN];
+        let fields = [
+            self.commitment_prev.as_bytes(),
+            self.commitment_new.as_bytes(),
+            self.blinding_diff_commitment.as_bytes(),
+            self.announcement.as_bytes(),
+            self.response.as_bytes(),
+            self.residual_commitment.as_bytes(),
+        ];
+        for (chunk, field) in out.chunks_exact_mut(32).zip(fields) {
+            chunk.copy_from_slice(field);
+        }
+        out
+    }
+
+    /// Decode [`to_compact_bytes`](Self::to_compact_bytes) output.
+    ///
+    /// Returns `None` on a wrong length or a non-canonical response scalar.
+    /// Points are not decompressed here; verification rejects invalid ones.
+    pub fn from_compact_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::COMPACT_LEN {
+            return None;
+        }
+        let field = |i: usize| -> [u8; 32] {
+            bytes[i * 32..(i + 1) * 32]
+                .try_into()
+                .expect("slice is 32 bytes")
+        };
+        let response = Option::from(Scalar::from_canonical_bytes(field(4)))?;
+        Some(Self {
+            commitment_prev: CompressedEdwardsY(field(0)),
+            commitment_new: CompressedEdwardsY(field(1)),
+            blinding_diff_commitment: CompressedEdwardsY(field(2)),
+            announcement: CompressedEdwardsY(field(3)),
+            response,
+            residual_commitment: CompressedEdwardsY(field(5)),
+        })
+    }
+}
+
 /// Trait for zero-knowledge proof of weight transitions.
 ///
 /// Any neuron that participates in gossip must prove that its gene update
@@ -137,6 +271,10 @@ pub trait ZkProtocol {
     ) -> Option<(Vec<u8>, ZkTransitionProof)>;
 }
 
+/// Challenge seed for transition proofs that answer no audit challenge
+/// (e.g. routine gossip checked by [`BudgetedTransitionVerifier`]).
+pub const UNCHALLENGED_SEED: [u8; 32] = [0u8; 32];
+
 /// Verifier for transition proofs.
 pub struct ZkTransitionVerifier {
     gens: PedersenGens,
@@ -160,11 +298,13 @@ impl ZkTransitionVerifier {
     /// Checks the Schnorr relation: s * G == R + c * B
     /// where B is the blinding difference commitment B = (r_new - r_prev) * G.
     /// The Pedersen commitments C_prev, C_new bind the proof to actual weight data
-    /// through the Fiat-Shamir transcript.
+    /// through the Fiat-Shamir transcript, as does `challenge_seed`, so a proof
+    /// made for one audit challenge does not verify under another.
     pub fn verify_transition(
         &self,
         proof: &ZkTransitionProof,
         prev_weight_hash: &[u8; 32],
+        challenge_seed: &[u8; 32],
     ) -> bool {
         // Decompress all points
         let c_prev = match proof.commitment_prev.decompress() {
@@ -191,6 +331,7 @@ impl ZkTransitionVerifier {
         // Recompute challenge via Fiat-Shamir transcript
         let mut transcript = SimpleTranscript::new(b"TransitionProof");
         transcript.append_message(b"prev_hash", prev_weight_hash);
+        transcript.append_message(b"challenge_seed", challenge_seed);
         transcript.append_point(b"C_prev", &c_prev);
         transcript.append_point(b"C_new", &c_new);
         transcript.append_point(b"B", &blinding_diff);
@@ -206,17 +347,237 @@ impl ZkTransitionVerifier {
     }
 }
 
+/// Outcome of submitting a transition proof to a [`BudgetedTransitionVerifier`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransitionVerdict {
+    /// Proof was verified and is valid.
+    Valid,
+    /// Proof was verified and is invalid.
+    Invalid,
+    /// Budget (or energy) exhausted; the proof is queued for a later round.
+    Deferred,
+    /// Budget exhausted and the deferral queue is full; the proof was discarded.
+    Dropped,
+}
+
+/// Transition-proof verifier with a per-round work cap.
+///
+/// Each verification costs five point decompressions and a scalar multiplication.
+/// Under an audit flood a node would otherwise spend its whole energy pool on
+/// verification, so at most `max_per_round` proofs are checked per round and
+/// the rest wait (bounded by `max_deferred`) for the next round. Every
+/// verification must also be admitted by the [`EnclaveGate`] energy guard.
+pub struct BudgetedTransitionVerifier {
+    verifier: ZkTransitionVerifier,
+    max_per_round: usize,
+    max_deferred: usize,
+    current_round: u64,
+    verified_this_round: usize,
+    deferred: VecDeque<(ZkTransitionProof, [u8; 32])>,
+}
+
+impl BudgetedTransitionVerifier {
+    pub fn new(max_per_round: usize, max_deferred: usize) -> Self {
+        Self {
+            verifier: ZkTransitionVerifier::new(),
+            max_per_round,
+            max_deferred,
+            current_round: 0,
+            verified_this_round: 0,
+            deferred: VecDeque::new(),
+        }
+    }
+
+    /// Verify a proof if this round's budget allows, otherwise defer it.
+    pub fn verify(
+        &mut self,
+        proof: &ZkTransitionProof,
+        prev_weight_hash: &[u8; 32],
+        gate: &dyn EnclaveGate,
+        energy_pool: f32,
+    ) -> TransitionVerdict {
+        if self.verified_this_round >= self.max_per_round
+            || gate.admit_verification(energy_pool).is_err()
+        {
+            if self.deferred.len() >= self.max_deferred {
+                return TransitionVerdict::Dropped;
+            }
+            self.deferred.push_back((proof.clone(), *prev_weight_hash));
+            return TransitionVerdict::Deferred;
+        }
+
+        self.verified_this_round += 1;
+        if self
+            .verifier
+            .verify_transition(proof, prev_weight_hash, &UNCHALLENGED_SEED)
+        {
+            TransitionVerdict::Valid
+        } else {
+            TransitionVerdict::Invalid
+        }
+    }
+
+    /// Start a new round: reset the budget and verify deferred proofs first.
+    ///
+    /// Returns `(prev_weight_hash, valid)` for each deferred proof processed.
+    /// Proofs that still do not fit remain queued.
+    pub fn begin_round(
+        &mut self,
+        round: u64,
+        gate: &dyn EnclaveGate,
+        energy_pool: f32,
+    ) -> Vec<([u8; 32], bool)> {
+        self.current_round = round;
+        self.verified_this_round = 0;
+
+        let mut results = Vec::new();
+        while self.verified_this_round < self.max_per_round {
+            if gate.admit_verification(energy_pool).is_err() {
+                break;
+            }
+            let (proof, prev_hash) = match self.deferred.pop_front() {
+                Some(item) => item,
+                None => break,
+            };
+            self.verified_this_round += 1;
+            results.push((
+                prev_hash,
+                self.verifier
+                    .verify_transition(&proof, &prev_hash, &UNCHALLENGED_SEED),
+            ));
+        }
+        results
+    }
+
+    /// Round the current budget applies to.
+    pub fn current_round(&self) -> u64 {
+        self.current_round
+    }
+
+    /// Verifications left in the current round.
+    pub fn remaining_budget(&self) -> usize {
+        self.max_per_round.saturating_sub(self.verified_this_round)
+    }
+
+    /// Number of proofs waiting for a later round.
+    pub fn deferred_count(&self) -> usize {
+        self.deferred.len()
+    }
+}
+
 /// Generate a ZkTransitionProof (standalone function for use outside trait impls).
 ///
 /// Uses Pedersen commitments + Schnorr protocol:
 /// 1. Commit to prev_weight_hash and new_weights
 /// 2. Schnorr announce with random k
-/// 3. Fiat-Shamir challenge
+/// 3. Fiat-Shamir challenge (bound to `challenge_seed`)
 /// 4. Response s = k + c * blinding_diff
+///
+/// Without `std` there is no OS entropy and the blindings are fixed public
+/// constants, which breaks hiding; embedded callers must use
+/// [`generate_transition_proof_seeded`] instead.
+#[cfg_attr(
+    not(feature = "std"),
+    deprecated(note = "blindings are constant without std; use generate_transition_proof_seeded")
+)]
 pub fn generate_transition_proof(
     prev_weight_hash: &[u8; 32],
     new_weights: &[f32],
     input_residuals: &[f32],
+    challenge_seed: &[u8; 32],
+) -> Option<(Vec<u8>, ZkTransitionProof)> {
+    #[cfg(feature = "std")]
+    {
+        generate_transition_proof_with_rng(
+            prev_weight_hash,
+            new_weights,
+            input_residuals,
+            challenge_seed,
+            &mut rand::rngs::OsRng,
+        )
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        build_transition_proof(
+            prev_weight_hash,
+            new_weights,
+            input_residuals,
+            challenge_seed,
+            [
+                Scalar::from(11111u64),
+                Scalar::from(22222u64),
+                Scalar::from(33333u64),
+                Scalar::from(44444u64),
+            ],
+        )
+    }
+}
+
+/// Generate a ZkTransitionProof drawing blindings from a caller-provided RNG.
+pub fn generate_transition_proof_with_rng<R: RngCore + CryptoRng>(
+    prev_weight_hash: &[u8; 32],
+    new_weights: &[f32],
+    input_residuals: &[f32],
+    challenge_seed: &[u8; 32],
+    rng: &mut R,
+) -> Option<(Vec<u8>, ZkTransitionProof)> {
+    let blindings = [
+        Scalar::random(rng),
+        Scalar::random(rng),
+        Scalar::random(rng),
+        Scalar::random(rng),
+    ];
+    build_transition_proof(
+        prev_weight_hash,
+        new_weights,
+        input_residuals,
+        challenge_seed,
+        blindings,
+    )
+}
+
+/// Generate a ZkTransitionProof with blindings derived from a device secret.
+///
+/// For `no_std` targets without an entropy source: `seed` should come from
+/// device entropy (e.g. a hardware RNG read at provisioning). The ChaCha20
+/// stream is keyed by BLAKE3(seed, statement), so blindings are unpredictable
+/// without the seed and never repeat across different statements (reusing
+/// the Schnorr nonce `k` for two challenges would leak the blinding).
+pub fn generate_transition_proof_seeded(
+    prev_weight_hash: &[u8; 32],
+    new_weights: &[f32],
+    input_residuals: &[f32],
+    challenge_seed: &[u8; 32],
+    seed: &[u8; 32],
+) -> Option<(Vec<u8>, ZkTransitionProof)> {
+    let mut hasher = Hasher::new_keyed(seed);
+    hasher.update(b"qres-transition-blinding");
+    hasher.update(prev_weight_hash);
+    hasher.update(challenge_seed);
+    for w in new_weights {
+        hasher.update(&w.to_le_bytes());
+    }
+    hasher.update(&(new_weights.len() as u64).to_le_bytes());
+    for r in input_residuals {
+        hasher.update(&r.to_le_bytes());
+    }
+    let mut rng = ChaCha20Rng::from_seed(*hasher.finalize().as_bytes());
+    generate_transition_proof_with_rng(
+        prev_weight_hash,
+        new_weights,
+        input_residuals,
+        challenge_seed,
+        &mut rng,
+    )
+}
+
+/// Build the proof from explicit blindings `[r_prev, r_new, r_residual, k]`.
+fn build_transition_proof(
+    prev_weight_hash: &[u8; 32],
+    new_weights: &[f32],
+    input_residuals: &[f32],
+    challenge_seed: &[u8; 32],
+    blindings: [Scalar; 4],
 ) -> Option<(Vec<u8>, ZkTransitionProof)> {
     let gens = PedersenGens::default();
 
@@ -240,24 +601,7 @@ pub fn generate_transition_proof(
     let residual_scaled = (residual_norm_sq * 1_000_000.0) as u64;
     let residual_scalar = Scalar::from(residual_scaled);
 
-    // Generate blinding factors
-    #[cfg(feature = "std")]
-    let (r_prev, r_new, r_residual, k) = {
-        use rand::rngs::OsRng;
-        (
-            Scalar::random(&mut OsRng),
-            Scalar::random(&mut OsRng),
-            Scalar::random(&mut OsRng),
-            Scalar::random(&mut OsRng),
-        )
-    };
-    #[cfg(not(feature = "std"))]
-    let (r_prev, r_new, r_residual, k) = (
-        Scalar::from(11111u64),
-        Scalar::from(22222u64),
-        Scalar::from(33333u64),
-        Scalar::from(44444u64),
-    );
+    let [r_prev, r_new, r_residual, k] = blindings;
 
     // Pedersen commitments
     let c_prev = gens.commit(prev_scalar, r_prev);
@@ -274,6 +618,7 @@ pub fn generate_transition_proof(
     // Fiat-Shamir challenge
     let mut transcript = SimpleTranscript::new(b"TransitionProof");
     transcript.append_message(b"prev_hash", prev_weight_hash);
+    transcript.append_message(b"challenge_seed", challenge_seed);
     transcript.append_point(b"C_prev", &c_prev);
     transcript.append_point(b"C_new", &c_new);
     transcript.append_point(b"B", &blinding_diff_point);
@@ -330,6 +675,56 @@ impl Default for StochasticAuditConfig {
     }
 }
 
+impl StochasticAuditConfig {
+    /// Check that the schedule is usable.
+    ///
+    /// The deadline must fall strictly before the next scheduled audit, otherwise
+    /// a node could be re-challenged while its previous response is still due.
+    pub fn validate(&self) -> Result<(), AuditConfigError> {
+        if self.audit_interval == 0 {
+            return Err(AuditConfigError::ZeroInterval);
+        }
+        if self.response_deadline >= self.audit_interval {
+            return Err(AuditConfigError::DeadlineNotBeforeInterval {
+                response_deadline: self.response_deadline,
+                audit_interval: self.audit_interval,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Errors raised when constructing a [`StochasticAuditor`] from a bad config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditConfigError {
+    /// `audit_interval` is zero, so no round could ever be scheduled.
+    ZeroInterval,
+    /// `response_deadline` is not strictly less than `audit_interval`.
+    DeadlineNotBeforeInterval {
+        response_deadline: u64,
+        audit_interval: u64,
+    },
+}
+
+impl core::fmt::Display for AuditConfigError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AuditConfigError::ZeroInterval => write!(f, "audit_interval must be non-zero"),
+            AuditConfigError::DeadlineNotBeforeInterval {
+                response_deadline,
+                audit_interval,
+            } => write!(
+                f,
+                "response_deadline ({}) must be less than audit_interval ({})",
+                response_deadline, audit_interval
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AuditConfigError {}
+
 /// A challenge issued to a specific node for a specific round.
 #[derive(Clone, Debug, PartialEq)]
 pub struct AuditChallenge {
@@ -365,17 +760,20 @@ pub struct StochasticAuditor {
     config: StochasticAuditConfig,
     /// The last swarm epoch hash (chain of prior consensus hashes).
     swarm_epoch_hash: [u8; 32],
-    /// Pending challenge awaiting response (at most one at a time).
-    pending_challenge: Option<AuditChallenge>,
+    /// Challenges awaiting a response, oldest first (at most one per node).
+    pending_challenges: Vec<AuditChallenge>,
 }
 
 impl StochasticAuditor {
-    pub fn new(config: StochasticAuditConfig) -> Self {
-        Self {
+    /// Create an auditor, rejecting configs whose response deadline would
+    /// overlap the next scheduled audit.
+    pub fn new(config: StochasticAuditConfig) -> Result<Self, AuditConfigError> {
+        config.validate()?;
+        Ok(Self {
             config,
             swarm_epoch_hash: [0u8; 32],
-            pending_challenge: None,
-        }
+            pending_challenges: Vec::new(),
+        })
     }
 
     /// Update the swarm epoch hash (called after each successful consensus round).
@@ -397,62 +795,106 @@ impl StochasticAuditor {
         round: u64,
         n_active_nodes: usize,
     ) -> Option<AuditChallenge> {
+        self.generate_challenges(round, n_active_nodes, 1).pop()
+    }
+
+    /// Deterministically challenge up to `count` distinct nodes for the given round.
+    ///
+    /// The first challenge is the one [`generate_challenge`](Self::generate_challenge)
+    /// issues; each further one draws its own seed from
+    /// BLAKE3(round || epoch_hash || draw). A new challenge replaces any still
+    /// pending for the same node. Returns an empty list if no audit is scheduled
+    /// or `n_active == 0`.
+    pub fn generate_challenges(
+        &mut self,
+        round: u64,
+        n_active_nodes: usize,
+        count: usize,
+    ) -> Vec<AuditChallenge> {
         if !self.should_audit(round) || n_active_nodes == 0 {
-            return None;
+            return Vec::new();
         }
 
-        // Deterministic seed: BLAKE3(round || epoch_hash)
+        let count = count.min(n_active_nodes);
+        let mut issued: Vec<AuditChallenge> = Vec::with_capacity(count);
+        // Bounded redraws: duplicates are skipped, so a small swarm may get fewer
+        let mut draw = 0u64;
+        while issued.len() < count && draw < 4 * count as u64 {
+            let seed = self.challenge_seed(round, draw);
+            draw += 1;
+
+            // Select node: first 8 bytes of seed as u64 mod n_active
+            let selection_bytes: [u8; 8] = seed[..8].try_into().expect("slice is 8 bytes");
+            let selection = u64::from_le_bytes(selection_bytes);
+            let challenged_index = (selection % n_active_nodes as u64) as usize;
+            if issued
+                .iter()
+                .any(|c| c.challenged_node_index == challenged_index)
+            {
+                continue;
+            }
+
+            issued.push(AuditChallenge {
+                round,
+                challenged_node_index: challenged_index,
+                challenge_seed: seed,
+                deadline_round: round + self.config.response_deadline,
+            });
+        }
+
+        for challenge in &issued {
+            self.pending_challenges
+                .retain(|c| c.challenged_node_index != challenge.challenged_node_index);
+            self.pending_challenges.push(challenge.clone());
+        }
+        issued
+    }
+
+    /// Seed for the `draw`-th challenge of `round`: BLAKE3(round || epoch_hash [|| draw]).
+    fn challenge_seed(&self, round: u64, draw: u64) -> [u8; 32] {
         let mut hasher = Hasher::new();
         hasher.update(b"QRES-StochasticAudit-v1");
         hasher.update(&round.to_le_bytes());
         hasher.update(&self.swarm_epoch_hash);
-        let hash = hasher.finalize();
-        let seed: [u8; 32] = *hash.as_bytes();
-
-        // Select node: first 8 bytes of seed as u64 mod n_active
-        let selection_bytes: [u8; 8] = seed[..8].try_into().expect("slice is 8 bytes");
-        let selection = u64::from_le_bytes(selection_bytes);
-        let challenged_index = (selection % n_active_nodes as u64) as usize;
-
-        let challenge = AuditChallenge {
-            round,
-            challenged_node_index: challenged_index,
-            challenge_seed: seed,
-            deadline_round: round + self.config.response_deadline,
-        };
-
-        self.pending_challenge = Some(challenge.clone());
-        Some(challenge)
+        if draw > 0 {
+            hasher.update(&draw.to_le_bytes());
+        }
+        *hasher.finalize().as_bytes()
     }
 
     /// Verify an audit response: the challenged node provides a transition proof
     /// bound to the challenge seed.
     ///
-    /// The proof must:
+    /// The response is matched to the pending challenge for `challenged_node_index`;
+    /// `NotScheduled` is returned if that node has none. The proof must:
     /// 1. Be a valid ZkTransitionProof for the node's prev_weight_hash
-    /// 2. Include the challenge_seed in its Fiat-Shamir transcript (replay resistance)
+    /// 2. Include the challenge_seed in its Fiat-Shamir transcript (replay resistance:
+    ///    a proof answering an earlier challenge fails against this one)
     pub fn verify_response(
         &mut self,
         current_round: u64,
+        challenged_node_index: usize,
         prev_weight_hash: &[u8; 32],
         proof: &ZkTransitionProof,
     ) -> AuditVerdict {
-        let challenge = match &self.pending_challenge {
-            Some(c) => c.clone(),
+        let position = match self
+            .pending_challenges
+            .iter()
+            .position(|c| c.challenged_node_index == challenged_node_index)
+        {
+            Some(position) => position,
             None => return AuditVerdict::NotScheduled,
         };
+        let challenge = self.pending_challenges.remove(position);
 
         // Check deadline
         if current_round > challenge.deadline_round {
-            self.pending_challenge = None;
             return AuditVerdict::Timeout;
         }
 
         // Verify the transition proof against the claimed prev_weight_hash
         let verifier = ZkTransitionVerifier::new();
-        let valid = verifier.verify_transition(proof, prev_weight_hash);
-
-        self.pending_challenge = None;
+        let valid = verifier.verify_transition(proof, prev_weight_hash, &challenge.challenge_seed);
 
         if valid {
             AuditVerdict::Pass
@@ -461,27 +903,90 @@ impl StochasticAuditor {
         }
     }
 
-    /// Check if a pending challenge has timed out.
-    pub fn check_timeout(&mut self, current_round: u64) -> AuditVerdict {
-        match &self.pending_challenge {
-            Some(c) if current_round > c.deadline_round => {
-                self.pending_challenge = None;
-                AuditVerdict::Timeout
+    /// Remove and return every pending challenge whose deadline has passed.
+    pub fn expire_timeouts(&mut self, current_round: u64) -> Vec<AuditChallenge> {
+        let mut expired = Vec::new();
+        self.pending_challenges.retain(|c| {
+            if current_round > c.deadline_round {
+                expired.push(c.clone());
+                false
+            } else {
+                true
             }
-            Some(_) => AuditVerdict::NotScheduled, // still waiting
-            None => AuditVerdict::NotScheduled,
+        });
+        expired
+    }
+
+    /// Check if any pending challenge has timed out, expiring each independently.
+    ///
+    /// Returns `Timeout` if at least one challenge expired; use
+    /// [`expire_timeouts`](Self::expire_timeouts) to learn which nodes missed.
+    pub fn check_timeout(&mut self, current_round: u64) -> AuditVerdict {
+        if self.expire_timeouts(current_round).is_empty() {
+            AuditVerdict::NotScheduled // none pending, or still waiting
+        } else {
+            AuditVerdict::Timeout
         }
     }
 
-    /// Get the pending challenge, if any.
+    /// Get the most recently issued pending challenge, if any.
     pub fn pending(&self) -> Option<&AuditChallenge> {
-        self.pending_challenge.as_ref()
+        self.pending_challenges.last()
+    }
+
+    /// All challenges awaiting a response, oldest first.
+    pub fn pending_all(&self) -> &[AuditChallenge] {
+        &self.pending_challenges
     }
 }
 
+/// Default minimum number of weights a norm proof may cover.
+///
+/// Empty or single-element vectors make the norm bound meaningless.
+pub const DEFAULT_MIN_PROOF_DIMENSION: usize = 2;
+
+/// Reasons a norm proof cannot be generated.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NormProofError {
+    /// Weight vector is shorter than the prover's minimum dimension.
+    DimensionTooSmall { len: usize, min: usize },
+    /// ||weights||^2 exceeds the requested threshold.
+    NormExceedsThreshold { norm_sq: f32, threshold_sq: f32 },
+    /// Built without `std`, so there is no entropy source for the blinding;
+    /// use `try_generate_proof_with_rng` or `generate_proof_seeded`.
+    NoEntropySource,
+}
+
+impl core::fmt::Display for NormProofError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            NormProofError::DimensionTooSmall { len, min } => write!(
+                f,
+                "weight vector has {} elements, minimum for a norm proof is {}",
+                len, min
+            ),
+            NormProofError::NormExceedsThreshold {
+                norm_sq,
+                threshold_sq,
+            } => write!(
+                f,
+                "squared norm {} exceeds threshold {}",
+                norm_sq, threshold_sq
+            ),
+            NormProofError::NoEntropySource => {
+                write!(f, "no entropy source for the proof blinding")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NormProofError {}
+
 /// Generates and verifies proofs that ||weights||_2 <= threshold.
 pub struct ZkNormProver {
     gens: PedersenGens,
+    min_dimension: usize,
 }
 
 impl Default for ZkNormProver {
@@ -492,77 +997,209 @@ impl Default for ZkNormProver {
 
 impl ZkNormProver {
     pub fn new() -> Self {
+        Self::with_min_dimension(DEFAULT_MIN_PROOF_DIMENSION)
+    }
+
+    /// Create a prover that refuses weight vectors shorter than `min_dimension`.
+    pub fn with_min_dimension(min_dimension: usize) -> Self {
         Self {
             gens: PedersenGens::default(),
+            min_dimension,
         }
     }
 
+    /// Minimum number of weights accepted by `generate_proof`.
+    pub fn min_dimension(&self) -> usize {
+        self.min_dimension
+    }
+
     /// Generate a proof that the L2 norm squared of `weights` is below `threshold_sq`.
+    ///
+    /// Returns `None` when the vector is too short or over threshold; use
+    /// [`try_generate_proof`](Self::try_generate_proof) to learn which.
     pub fn generate_proof(
         &self,
         weights: &[f32],
         threshold_sq: f32,
     ) -> Option<(NormProof, Scalar)> {
+        self.try_generate_proof(weights, threshold_sq).ok()
+    }
+
+    /// Like [`generate_proof`](Self::generate_proof), reporting why proving was refused.
+    ///
+    /// Without `std` there is no OS entropy, so this always fails with
+    /// [`NormProofError::NoEntropySource`]; embedded callers must use
+    /// [`try_generate_proof_with_rng`](Self::try_generate_proof_with_rng) or
+    /// [`generate_proof_seeded`](Self::generate_proof_seeded).
+    pub fn try_generate_proof(
+        &self,
+        weights: &[f32],
+        threshold_sq: f32,
+    ) -> Result<(NormProof, Scalar), NormProofError> {
+        #[cfg(feature = "std")]
+        {
+            self.try_generate_proof_with_rng(weights, threshold_sq, &mut rand::rngs::OsRng)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            let _ = (weights, threshold_sq);
+            Err(NormProofError::NoEntropySource)
+        }
+    }
+
+    /// Generate a norm proof with a blinding and nonce drawn from a
+    /// caller-provided RNG.
+    pub fn try_generate_proof_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        weights: &[f32],
+        threshold_sq: f32,
+        rng: &mut R,
+    ) -> Result<(NormProof, Scalar), NormProofError> {
+        if weights.len() < self.min_dimension {
+            return Err(NormProofError::DimensionTooSmall {
+                len: weights.len(),
+                min: self.min_dimension,
+            });
+        }
+
         let norm_sq: f32 = weights.iter().map(|w| w * w).sum();
 
         if norm_sq > threshold_sq {
-            return None;
+            return Err(NormProofError::NormExceedsThreshold {
+                norm_sq,
+                threshold_sq,
+            });
         }
 
-        let norm_scaled = (norm_sq * 1_000_000.0) as u64;
+        let norm_scaled = (norm_sq * NORM_SCALE) as u64;
         let value = Scalar::from(norm_scaled);
 
-        #[cfg(feature = "std")]
-        let blinding = {
-            use rand::rngs::OsRng;
-            Scalar::random(&mut OsRng)
-        };
-        #[cfg(not(feature = "std"))]
-        let blinding = Scalar::from(12345u64);
+        let blinding = Scalar::random(rng);
+        let nonce = Scalar::random(rng);
 
         let commitment_point = self.gens.commit(value, blinding);
-        let commitment = commitment_point.compress();
-
-        let mut transcript = SimpleTranscript::new(b"NormProof");
-        transcript.append_point(b"C", &commitment_point);
-        let challenge = transcript.challenge_scalar(b"c");
+        let announcement_point = nonce * self.gens.g;
+        let challenge = norm_challenge(&commitment_point, &announcement_point, norm_scaled);
 
-        let response = blinding + challenge * value;
+        let response = nonce + challenge * blinding;
 
-        Some((
+        Ok((
             NormProof {
-                commitment,
+                commitment: commitment_point.compress(),
+                announcement: announcement_point.compress(),
                 response,
+                value: norm_scaled,
             },
             blinding,
         ))
     }
 
-    /// Verify the proof structure (placeholder for full range proof).
-    pub fn verify_proof(&self, proof: &NormProof, _threshold_sq: f32) -> bool {
-        let commitment_point = match proof.commitment.decompress() {
-            Some(p) => p,
-            None => return false,
+    /// Generate a norm proof with the blinding derived from a device secret.
+    ///
+    /// For `no_std` targets without an entropy source, as with
+    /// [`generate_transition_proof_seeded`]: the ChaCha20 stream is keyed by
+    /// BLAKE3(seed, statement), so the Schnorr nonce never repeats across
+    /// different weight vectors.
+    pub fn generate_proof_seeded(
+        &self,
+        weights: &[f32],
+        threshold_sq: f32,
+        seed: &[u8; 32],
+    ) -> Result<(NormProof, Scalar), NormProofError> {
+        let mut hasher = Hasher::new_keyed(seed);
+        hasher.update(b"qres-norm-blinding");
+        hasher.update(&threshold_sq.to_le_bytes());
+        for w in weights {
+            hasher.update(&w.to_le_bytes());
+        }
+        hasher.update(&(weights.len() as u64).to_le_bytes());
+        let mut rng = ChaCha20Rng::from_seed(*hasher.finalize().as_bytes());
+        self.try_generate_proof_with_rng(weights, threshold_sq, &mut rng)
+    }
+
+    /// Verify a norm proof against `threshold_sq`.
+    ///
+    /// Rejects proofs whose disclosed value exceeds the threshold, then
+    /// recomputes the Fiat-Shamir challenge and checks the Schnorr relation
+    /// `s*G == R + c*(C - value*H)`, which only holds if the prover knows the
+    /// blinding opening `C` to exactly `value`.
+    pub fn verify_proof(&self, proof: &NormProof, threshold_sq: f32) -> bool {
+        if proof.value > (threshold_sq * NORM_SCALE) as u64 {
+            return false;
+        }
+        let (Some(commitment_point), Some(announcement_point)) = (
+            proof.commitment.decompress(),
+            proof.announcement.decompress(),
+        ) else {
+            return false;
         };
 
-        let mut transcript = SimpleTranscript::new(b"NormProof");
-        transcript.append_point(b"C", &commitment_point);
-        let _challenge = transcript.challenge_scalar(b"c");
+        let challenge = norm_challenge(&commitment_point, &announcement_point, proof.value);
+        let blinding_point = commitment_point - Scalar::from(proof.value) * self.gens.h;
 
-        // Basic sanity check
-        proof.response != Scalar::ZERO
+        proof.response * self.gens.g == announcement_point + challenge * blinding_point
     }
 
-    /// Verify a batch of proofs efficiently.
+    /// Verify a batch of proofs with a single multiscalar multiplication.
     ///
-    /// Currently iterates sequentially, but designed to allow Multiscalar Mul optimization later.
+    /// Every proof's relation `s*G - R - c*(C - value*H) = 0` is scaled by a
+    /// random coefficient `z` and the sum is checked against the identity, so
+    /// one forged proof only slips through if the coefficients happen to
+    /// cancel it. The coefficients are drawn from a ChaCha20 stream keyed by
+    /// BLAKE3 over the whole batch (and OS entropy under `std`), so they are
+    /// fixed only after every proof is. Small-order (torsion) components are
+    /// caught with probability 7/8 rather than always; they carry no
+    /// information about the opening.
     pub fn verify_batch(&self, bundles: &[ProofBundle], threshold_sq: f32) -> bool {
+        let max_value = (threshold_sq * NORM_SCALE) as u64;
+        let mut hasher = Hasher::new();
+        hasher.update(b"QRES-NormProof-Batch-v1");
+        #[cfg(feature = "std")]
+        {
+            let mut entropy = [0u8; 32];
+            rand::rngs::OsRng.fill_bytes(&mut entropy);
+            hasher.update(&entropy);
+        }
         for bundle in bundles {
-            if !self.verify_proof(&bundle.zk_proof, threshold_sq) {
+            let proof = &bundle.zk_proof;
+            if proof.value > max_value {
                 return false;
             }
+            hasher.update(proof.commitment.as_bytes());
+            hasher.update(proof.announcement.as_bytes());
+            hasher.update(proof.response.as_bytes());
+            hasher.update(&proof.value.to_le_bytes());
+        }
+        let mut rng = ChaCha20Rng::from_seed(*hasher.finalize().as_bytes());
+
+        let mut g_scalar = Scalar::ZERO;
+        let mut h_scalar = Scalar::ZERO;
+        let mut scalars = Vec::with_capacity(2 * bundles.len() + 2);
+        let mut points = Vec::with_capacity(2 * bundles.len() + 2);
+        for bundle in bundles {
+            let proof = &bundle.zk_proof;
+            let (Some(commitment_point), Some(announcement_point)) = (
+                proof.commitment.decompress(),
+                proof.announcement.decompress(),
+            ) else {
+                return false;
+            };
+            let challenge = norm_challenge(&commitment_point, &announcement_point, proof.value);
+            let z = Scalar::random(&mut rng);
+
+            g_scalar += z * proof.response;
+            h_scalar += z * challenge * Scalar::from(proof.value);
+            scalars.push(-z);
+            points.push(announcement_point);
+            scalars.push(-(z * challenge));
+            points.push(commitment_point);
         }
-        true
+        scalars.push(g_scalar);
+        points.push(self.gens.g);
+        scalars.push(h_scalar);
+        points.push(self.gens.h);
+
+        EdwardsPoint::multiscalar_mul(scalars, points).is_identity()
     }
 }
 
@@ -589,6 +1226,25 @@ mod tests {
         assert_eq!(c_sum, c_expected, "Homomorphism C(a)+C(b) = C(a+b) failed");
     }
 
+    #[test]
+    fn test_value_generator_is_hashed_to_curve() {
+        let gens = PedersenGens::default();
+
+        // No known relation to G, stable across instances, prime order
+        assert_ne!(gens.h, gens.g + gens.g);
+        assert_ne!(gens.h, gens.g);
+        assert_eq!(gens.h, PedersenGens::default().h);
+        assert!(gens.h.is_torsion_free());
+        assert!(!gens.h.is_small_order());
+
+        let (v1, r1) = (Scalar::from(3u64), Scalar::from(5u64));
+        let (v2, r2) = (Scalar::from(7u64), Scalar::from(11u64));
+        assert_eq!(
+            gens.commit(v1, r1) + gens.commit(v2, r2),
+            gens.commit(v1 + v2, r1 + r2)
+        );
+    }
+
     #[test]
     fn test_transcript_determinism() {
         let mut t1 = SimpleTranscript::new(b"Test");
@@ -615,6 +1271,74 @@ mod tests {
         assert!(prover.verify_proof(&proof, threshold));
     }
 
+    #[test]
+    fn test_norm_proof_rejects_tampering() {
+        let prover = ZkNormProver::new();
+        let threshold = 1.0;
+        let (proof, _) = prover.generate_proof(&[0.1, 0.2, 0.3], threshold).unwrap();
+        assert!(prover.verify_proof(&proof, threshold));
+
+        // Commitment swapped for another point
+        let mut forged = proof.clone();
+        forged.commitment = (Scalar::from(7u64) * ED25519_BASEPOINT_POINT).compress();
+        assert!(!prover.verify_proof(&forged, threshold));
+
+        // Any other nonzero response
+        let mut forged = proof.clone();
+        forged.response += Scalar::ONE;
+        assert!(!prover.verify_proof(&forged, threshold));
+
+        // Understating the committed norm breaks the opening
+        let mut forged = proof.clone();
+        forged.value /= 2;
+        assert!(!prover.verify_proof(&forged, threshold));
+
+        // A valid proof is still rejected against a tighter threshold
+        assert!(!prover.verify_proof(&proof, 0.1));
+    }
+
+    #[test]
+    fn test_norm_proof_bytes_round_trip() {
+        let prover = ZkNormProver::new();
+        let (proof, _) = prover.generate_proof(&[0.1, 0.2, 0.3], 1.0).unwrap();
+        let bytes = proof.to_bytes();
+        let decoded = NormProof::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.to_bytes(), bytes);
+        assert!(prover.verify_proof(&decoded, 1.0));
+
+        assert!(NormProof::from_bytes(&bytes[1..]).is_none());
+        // A response scalar above the group order is not canonical
+        let mut bad = bytes;
+        bad[64..96].fill(0xff);
+        assert!(NormProof::from_bytes(&bad).is_none());
+    }
+
+    #[test]
+    fn test_verify_batch_rejects_one_tampered_proof() {
+        let prover = ZkNormProver::new();
+        let threshold = 1.0;
+        let mut bundles: Vec<ProofBundle> = (0..16)
+            .map(|i| {
+                let weights = [0.01 * i as f32, 0.2, 0.3];
+                let (zk_proof, _) = prover.generate_proof(&weights, threshold).unwrap();
+                ProofBundle {
+                    peer_id: [i as u8; 32],
+                    masked_weights: weights.to_vec(),
+                    zk_proof,
+                }
+            })
+            .collect();
+        assert!(prover.verify_batch(&[], threshold));
+        assert!(prover.verify_batch(&bundles, threshold));
+
+        bundles[9].zk_proof.response += Scalar::ONE;
+        assert!(!prover.verify_batch(&bundles, threshold));
+
+        bundles[9].zk_proof.response -= Scalar::ONE;
+        bundles[3].zk_proof.value -= 1;
+        assert!(!prover.verify_batch(&bundles, threshold));
+    }
+
     #[test]
     fn test_norm_proof_exceeds_threshold() {
         let prover = ZkNormProver::new();
@@ -625,6 +1349,53 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_norm_proof_rejects_degenerate_dimension() {
+        let prover = ZkNormProver::new();
+
+        for weights in [vec![], vec![0.1]] {
+            assert!(prover.generate_proof(&weights, 1.0).is_none());
+            assert_eq!(
+                prover.try_generate_proof(&weights, 1.0).unwrap_err(),
+                NormProofError::DimensionTooSmall {
+                    len: weights.len(),
+                    min: DEFAULT_MIN_PROOF_DIMENSION,
+                }
+            );
+        }
+
+        // Configurable: a stricter prover rejects what the default accepts
+        let strict = ZkNormProver::with_min_dimension(4);
+        assert!(prover.generate_proof(&[0.1, 0.2, 0.3], 1.0).is_some());
+        assert!(strict.generate_proof(&[0.1, 0.2, 0.3], 1.0).is_none());
+    }
+
+    #[test]
+    fn test_norm_proof_with_caller_entropy() {
+        let prover = ZkNormProver::new();
+        let weights = [0.1, 0.2, 0.3];
+
+        let mut rng = ChaCha20Rng::from_seed([7; 32]);
+        let (proof, _) = prover
+            .try_generate_proof_with_rng(&weights, 1.0, &mut rng)
+            .unwrap();
+        assert!(prover.verify_proof(&proof, 1.0));
+
+        // Seeded proofs verify, and the blinding depends on both seed and statement
+        let (seeded, blinding) = prover
+            .generate_proof_seeded(&weights, 1.0, &[1; 32])
+            .unwrap();
+        assert!(prover.verify_proof(&seeded, 1.0));
+        let (_, other_seed) = prover
+            .generate_proof_seeded(&weights, 1.0, &[2; 32])
+            .unwrap();
+        let (_, other_weights) = prover
+            .generate_proof_seeded(&[0.1, 0.2, 0.4], 1.0, &[1; 32])
+            .unwrap();
+        assert_ne!(blinding, other_seed);
+        assert_ne!(blinding, other_weights);
+    }
+
     #[test]
     fn test_transition_proof_valid() {
         // Legitimate transition: neuron adapts weights based on residuals
@@ -632,7 +1403,8 @@ mod tests {
         let new_weights = vec![0.1, 0.2, 0.3, 0.4];
         let residuals = vec![0.01, -0.02, 0.015, -0.005];
 
-        let result = generate_transition_proof(&prev_hash, &new_weights, &residuals);
+        let result =
+            generate_transition_proof(&prev_hash, &new_weights, &residuals, &UNCHALLENGED_SEED);
         assert!(result.is_some(), "Proof generation should succeed");
 
         let (gene, proof) = result.unwrap();
@@ -641,7 +1413,7 @@ mod tests {
         // Verify the proof
         let verifier = ZkTransitionVerifier::new();
         assert!(
-            verifier.verify_transition(&proof, &prev_hash),
+            verifier.verify_transition(&proof, &prev_hash, &UNCHALLENGED_SEED),
             "Valid transition proof should verify"
         );
     }
@@ -656,15 +1428,20 @@ mod tests {
         let residuals = vec![0.01, -0.02, 0.015, -0.005];
 
         // Generate proof for the legitimate hash
-        let (_, proof) = generate_transition_proof(&legitimate_hash, &new_weights, &residuals)
-            .expect("Proof generation should succeed");
+        let (_, proof) = generate_transition_proof(
+            &legitimate_hash,
+            &new_weights,
+            &residuals,
+            &UNCHALLENGED_SEED,
+        )
+        .expect("Proof generation should succeed");
 
         // Malicious attempt: verify against a different previous hash
         let forged_hash = [0xCDu8; 32];
         let verifier = ZkTransitionVerifier::new();
 
         assert!(
-            !verifier.verify_transition(&proof, &forged_hash),
+            !verifier.verify_transition(&proof, &forged_hash, &UNCHALLENGED_SEED),
             "Malicious neuron with forged prev_hash MUST be rejected"
         );
     }
@@ -676,8 +1453,8 @@ mod tests {
         let weights = vec![1.0, 2.0, 3.0];
         let residuals = vec![0.1, 0.2, 0.3];
 
-        let r1 = generate_transition_proof(&prev_hash, &weights, &residuals);
-        let r2 = generate_transition_proof(&prev_hash, &weights, &residuals);
+        let r1 = generate_transition_proof(&prev_hash, &weights, &residuals, &UNCHALLENGED_SEED);
+        let r2 = generate_transition_proof(&prev_hash, &weights, &residuals, &UNCHALLENGED_SEED);
 
         // Both should succeed
         assert!(r1.is_some());
@@ -685,21 +1462,91 @@ mod tests {
 
         // Both proofs verify (each with its own randomness but independently valid)
         let verifier = ZkTransitionVerifier::new();
-        assert!(verifier.verify_transition(&r1.unwrap().1, &prev_hash));
-        assert!(verifier.verify_transition(&r2.unwrap().1, &prev_hash));
+        assert!(verifier.verify_transition(&r1.unwrap().1, &prev_hash, &UNCHALLENGED_SEED));
+        assert!(verifier.verify_transition(&r2.unwrap().1, &prev_hash, &UNCHALLENGED_SEED));
+    }
+
+    #[test]
+    fn test_transition_proof_compact_roundtrip() {
+        let prev_hash = [0x42u8; 32];
+        let (_, proof) =
+            generate_transition_proof(&prev_hash, &[0.1, 0.2, 0.3], &[0.01], &UNCHALLENGED_SEED)
+                .unwrap();
+
+        let bytes = proof.to_compact_bytes();
+        assert_eq!(bytes.len(), 192);
+        assert_eq!(&bytes[..32], proof.commitment_prev.as_bytes());
+        assert_eq!(&bytes[128..160], proof.response.as_bytes());
+
+        let decoded = ZkTransitionProof::from_compact_bytes(&bytes).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(ZkTransitionVerifier::new().verify_transition(
+            &decoded,
+            &prev_hash,
+            &UNCHALLENGED_SEED
+        ));
+
+        // Far smaller than the JSON encoding gossip would otherwise carry
+        assert!(serde_json::to_vec(&proof).unwrap().len() > bytes.len());
+
+        // Truncated input and non-canonical scalars are refused
+        assert!(ZkTransitionProof::from_compact_bytes(&bytes[..191]).is_none());
+        let mut bad = bytes;
+        bad[128..160].copy_from_slice(&[0xFF; 32]);
+        assert!(ZkTransitionProof::from_compact_bytes(&bad).is_none());
     }
 
     // ================================================================
     // Stochastic Audit Tests (INV-6)
     // ================================================================
 
+    #[test]
+    fn test_seeded_transition_proofs_differ_per_seed() {
+        let verifier = ZkTransitionVerifier::new();
+        let prev_hash = [0x42u8; 32];
+        let weights = [0.1f32, -0.2, 0.3];
+        let residuals = [0.01f32, 0.02];
+
+        let prove = |seed: &[u8; 32]| {
+            generate_transition_proof_seeded(
+                &prev_hash,
+                &weights,
+                &residuals,
+                &UNCHALLENGED_SEED,
+                seed,
+            )
+            .unwrap()
+            .1
+        };
+        let a = prove(&[1u8; 32]);
+        let b = prove(&[2u8; 32]);
+
+        assert_ne!(a.commitment_new, b.commitment_new);
+        assert_ne!(a.announcement, b.announcement);
+        assert!(verifier.verify_transition(&a, &prev_hash, &UNCHALLENGED_SEED));
+        assert!(verifier.verify_transition(&b, &prev_hash, &UNCHALLENGED_SEED));
+
+        // Same seed and statement reproduce the proof; a new statement does not
+        assert_eq!(prove(&[1u8; 32]).announcement, a.announcement);
+        let other = generate_transition_proof_seeded(
+            &prev_hash,
+            &[0.5],
+            &residuals,
+            &UNCHALLENGED_SEED,
+            &[1u8; 32],
+        )
+        .unwrap()
+        .1;
+        assert_ne!(other.announcement, a.announcement);
+    }
+
     #[test]
     fn test_audit_scheduling() {
         let config = StochasticAuditConfig {
             audit_interval: 50,
             response_deadline: 5,
         };
-        let auditor = StochasticAuditor::new(config);
+        let auditor = StochasticAuditor::new(config).unwrap();
 
         assert!(!auditor.should_audit(0));
         assert!(!auditor.should_audit(1));
@@ -715,11 +1562,11 @@ mod tests {
         let config = StochasticAuditConfig::default();
         let epoch = [0xABu8; 32];
 
-        let mut a1 = StochasticAuditor::new(config.clone());
+        let mut a1 = StochasticAuditor::new(config.clone()).unwrap();
         a1.update_epoch_hash(&epoch);
         let c1 = a1.generate_challenge(50, 10).unwrap();
 
-        let mut a2 = StochasticAuditor::new(StochasticAuditConfig::default());
+        let mut a2 = StochasticAuditor::new(StochasticAuditConfig::default()).unwrap();
         a2.update_epoch_hash(&epoch);
         let c2 = a2.generate_challenge(50, 10).unwrap();
 
@@ -731,11 +1578,11 @@ mod tests {
     fn test_different_epoch_different_selection() {
         let config = StochasticAuditConfig::default();
 
-        let mut a1 = StochasticAuditor::new(config.clone());
+        let mut a1 = StochasticAuditor::new(config.clone()).unwrap();
         a1.update_epoch_hash(&[0x01u8; 32]);
         let c1 = a1.generate_challenge(50, 100).unwrap();
 
-        let mut a2 = StochasticAuditor::new(StochasticAuditConfig::default());
+        let mut a2 = StochasticAuditor::new(StochasticAuditConfig::default()).unwrap();
         a2.update_epoch_hash(&[0x02u8; 32]);
         let c2 = a2.generate_challenge(50, 100).unwrap();
 
@@ -745,37 +1592,81 @@ mod tests {
 
     #[test]
     fn test_audit_proof_pass() {
-        let mut auditor = StochasticAuditor::new(StochasticAuditConfig::default());
+        let mut auditor = StochasticAuditor::new(StochasticAuditConfig::default()).unwrap();
         auditor.update_epoch_hash(&[0xFFu8; 32]);
 
         let challenge = auditor.generate_challenge(50, 10);
         assert!(challenge.is_some());
+        let challenge = challenge.unwrap();
 
-        // Generate a valid transition proof
+        // Generate a valid transition proof answering this challenge
         let prev_hash = [0xABu8; 32];
         let weights = vec![0.1, 0.2, 0.3];
         let residuals = vec![0.01, -0.02, 0.015];
-        let (_, proof) = generate_transition_proof(&prev_hash, &weights, &residuals).unwrap();
+        let (_, proof) =
+            generate_transition_proof(&prev_hash, &weights, &residuals, &challenge.challenge_seed)
+                .unwrap();
 
         // Verify the response (within deadline)
-        let verdict = auditor.verify_response(51, &prev_hash, &proof);
+        let verdict =
+            auditor.verify_response(51, challenge.challenged_node_index, &prev_hash, &proof);
         assert_eq!(verdict, AuditVerdict::Pass);
     }
 
+    #[test]
+    fn test_audit_rejects_proof_replayed_from_earlier_challenge() {
+        let mut auditor = StochasticAuditor::new(StochasticAuditConfig::default()).unwrap();
+        auditor.update_epoch_hash(&[0xFFu8; 32]);
+        let prev_hash = [0xABu8; 32];
+        let weights = vec![0.1, 0.2, 0.3];
+        let residuals = vec![0.01, -0.02, 0.015];
+
+        // Answer round 50 honestly
+        let first = auditor.generate_challenge(50, 10).unwrap();
+        let (_, proof) =
+            generate_transition_proof(&prev_hash, &weights, &residuals, &first.challenge_seed)
+                .unwrap();
+        assert_eq!(
+            auditor.verify_response(51, first.challenged_node_index, &prev_hash, &proof),
+            AuditVerdict::Pass
+        );
+
+        // Replaying the same proof for the next challenge fails
+        let second = auditor.generate_challenge(100, 10).unwrap();
+        assert_ne!(first.challenge_seed, second.challenge_seed);
+        assert_eq!(
+            auditor.verify_response(101, second.challenged_node_index, &prev_hash, &proof),
+            AuditVerdict::Fail
+        );
+
+        // So does a routine gossip proof bound to no challenge
+        let third = auditor.generate_challenge(150, 10).unwrap();
+        let (_, unbound) =
+            generate_transition_proof(&prev_hash, &weights, &residuals, &UNCHALLENGED_SEED)
+                .unwrap();
+        assert_eq!(
+            auditor.verify_response(151, third.challenged_node_index, &prev_hash, &unbound),
+            AuditVerdict::Fail
+        );
+    }
+
     #[test]
     fn test_audit_proof_fail_forged_hash() {
-        let mut auditor = StochasticAuditor::new(StochasticAuditConfig::default());
+        let mut auditor = StochasticAuditor::new(StochasticAuditConfig::default()).unwrap();
         auditor.update_epoch_hash(&[0xFFu8; 32]);
-        auditor.generate_challenge(50, 10);
+        let challenge = auditor.generate_challenge(50, 10).unwrap();
 
         // Generate proof with one hash, verify against a different one
         let real_hash = [0xABu8; 32];
         let forged_hash = [0xCDu8; 32];
         let weights = vec![0.1, 0.2, 0.3];
         let residuals = vec![0.01, -0.02, 0.015];
-        let (_, proof) = generate_transition_proof(&real_hash, &weights, &residuals).unwrap();
+        let (_, proof) =
+            generate_transition_proof(&real_hash, &weights, &residuals, &challenge.challenge_seed)
+                .unwrap();
 
-        let verdict = auditor.verify_response(51, &forged_hash, &proof);
+        let verdict =
+            auditor.verify_response(51, challenge.challenged_node_index, &forged_hash, &proof);
         assert_eq!(verdict, AuditVerdict::Fail);
     }
 
@@ -785,17 +1676,20 @@ mod tests {
             audit_interval: 50,
             response_deadline: 5,
         };
-        let mut auditor = StochasticAuditor::new(config);
+        let mut auditor = StochasticAuditor::new(config).unwrap();
         auditor.update_epoch_hash(&[0xFFu8; 32]);
-        auditor.generate_challenge(50, 10);
+        let challenge = auditor.generate_challenge(50, 10).unwrap();
 
         // Respond after deadline (round 56 > deadline 55)
         let prev_hash = [0xABu8; 32];
         let weights = vec![0.1, 0.2];
         let residuals = vec![0.01, -0.02];
-        let (_, proof) = generate_transition_proof(&prev_hash, &weights, &residuals).unwrap();
+        let (_, proof) =
+            generate_transition_proof(&prev_hash, &weights, &residuals, &UNCHALLENGED_SEED)
+                .unwrap();
 
-        let verdict = auditor.verify_response(56, &prev_hash, &proof);
+        let verdict =
+            auditor.verify_response(56, challenge.challenged_node_index, &prev_hash, &proof);
         assert_eq!(verdict, AuditVerdict::Timeout);
     }
 
@@ -805,7 +1699,7 @@ mod tests {
             audit_interval: 50,
             response_deadline: 5,
         };
-        let mut auditor = StochasticAuditor::new(config);
+        let mut auditor = StochasticAuditor::new(config).unwrap();
         auditor.update_epoch_hash(&[0xFFu8; 32]);
         auditor.generate_challenge(50, 10);
 
@@ -817,16 +1711,88 @@ mod tests {
         assert!(auditor.pending().is_none());
     }
 
+    #[test]
+    fn test_concurrent_challenges_expire_independently() {
+        let config = StochasticAuditConfig {
+            audit_interval: 50,
+            response_deadline: 5,
+        };
+        let mut auditor = StochasticAuditor::new(config).unwrap();
+        auditor.update_epoch_hash(&[0xFFu8; 32]);
+
+        let challenges = auditor.generate_challenges(50, 10, 2);
+        assert_eq!(challenges.len(), 2);
+        let (answered, silent) = (&challenges[0], &challenges[1]);
+        assert_ne!(answered.challenged_node_index, silent.challenged_node_index);
+        assert_ne!(answered.challenge_seed, silent.challenge_seed);
+        // The first draw matches the single-challenge selection
+        let mut single = StochasticAuditor::new(StochasticAuditConfig::default()).unwrap();
+        single.update_epoch_hash(&[0xFFu8; 32]);
+        assert_eq!(single.generate_challenge(50, 10).as_ref(), Some(answered));
+        assert_eq!(auditor.pending(), Some(silent));
+
+        // One node answers in time
+        let prev_hash = [0xABu8; 32];
+        let (_, proof) =
+            generate_transition_proof(&prev_hash, &[0.1, 0.2], &[0.01], &answered.challenge_seed)
+                .unwrap();
+        assert_eq!(
+            auditor.verify_response(53, answered.challenged_node_index, &prev_hash, &proof),
+            AuditVerdict::Pass
+        );
+        assert_eq!(auditor.pending_all(), core::slice::from_ref(silent));
+
+        // The other times out on its own
+        assert!(auditor.expire_timeouts(55).is_empty());
+        assert_eq!(auditor.expire_timeouts(56), vec![silent.clone()]);
+        assert!(auditor.pending_all().is_empty());
+        assert_eq!(
+            auditor.verify_response(56, silent.challenged_node_index, &prev_hash, &proof),
+            AuditVerdict::NotScheduled
+        );
+    }
+
     #[test]
     fn test_no_audit_on_non_scheduled_round() {
-        let mut auditor = StochasticAuditor::new(StochasticAuditConfig::default());
+        let mut auditor = StochasticAuditor::new(StochasticAuditConfig::default()).unwrap();
         let result = auditor.generate_challenge(37, 10);
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_audit_config_rejects_overlapping_deadline() {
+        let config = StochasticAuditConfig {
+            audit_interval: 10,
+            response_deadline: 10,
+        };
+        let err = StochasticAuditor::new(config).err().unwrap();
+        assert_eq!(
+            err,
+            AuditConfigError::DeadlineNotBeforeInterval {
+                response_deadline: 10,
+                audit_interval: 10,
+            }
+        );
+
+        let config = StochasticAuditConfig {
+            audit_interval: 0,
+            response_deadline: 0,
+        };
+        assert_eq!(
+            StochasticAuditor::new(config).err(),
+            Some(AuditConfigError::ZeroInterval)
+        );
+
+        let config = StochasticAuditConfig {
+            audit_interval: 10,
+            response_deadline: 9,
+        };
+        assert!(StochasticAuditor::new(config).is_ok());
+    }
+
     #[test]
     fn test_no_audit_with_zero_nodes() {
-        let mut auditor = StochasticAuditor::new(StochasticAuditConfig::default());
+        let mut auditor = StochasticAuditor::new(StochasticAuditConfig::default()).unwrap();
         let result = auditor.generate_challenge(50, 0);
         assert!(result.is_none());
     }
@@ -870,11 +1836,12 @@ pub trait EnclaveGate {
     ///
     /// # Arguments
     /// * `weights` - Model weights to prove
-    /// * `threshold` - Norm threshold
+    /// * `threshold` - Squared L2 norm threshold
     /// * `energy_pool` - Current energy pool
     ///
     /// # Returns
-    /// `Ok(NormProof)` if energy sufficient, `Err` otherwise.
+    /// `Ok(NormProof)` if energy is sufficient and the weights are within
+    /// `threshold`, `Err` otherwise.
     fn generate_attested_proof(
         &self,
         weights: &[f32],
@@ -895,6 +1862,17 @@ pub trait EnclaveGate {
     /// `true` if proof is valid, `false` otherwise.
     fn verify_attested_proof(&self, proof: &NormProof, threshold: f32) -> bool;
 
+    /// Admit one transition-proof verification (energy-gated)
+    ///
+    /// Norm proofs are cheap enough to verify for free, but transition proofs
+    /// are not; [`BudgetedTransitionVerifier`] asks the gate before each one.
+    ///
+    /// # Returns
+    /// `Ok(())` if the verification may run. The default admits everything.
+    fn admit_verification(&self, _energy_pool: f32) -> Result<(), EnclaveError> {
+        Ok(())
+    }
+
     /// Verify audit response for Class C collusion detection (v21.0)
     ///
     /// Verifies that a node's claimed raw prediction matches their submitted gradient.
@@ -989,22 +1967,31 @@ impl EnclaveGate for SoftwareEnclaveGate {
             return Err(EnclaveError::InvalidInput);
         }
 
-        // Generate standard ZK proof (software path)
+        // Standard ZK proof (software path)
         // In real TEE: this would happen inside enclave with attestation
-        let commitment = ED25519_BASEPOINT_POINT.compress();
-        let response = Scalar::ONE; // Placeholder response
-
-        Ok(NormProof {
-            commitment,
-            response,
-        })
+        ZkNormProver::new()
+            .try_generate_proof(weights, threshold)
+            .map(|(proof, _blinding)| proof)
+            .map_err(|e| match e {
+                NormProofError::DimensionTooSmall { .. } => EnclaveError::InvalidInput,
+                NormProofError::NormExceedsThreshold { .. } | NormProofError::NoEntropySource => {
+                    EnclaveError::ProofGenerationFailed
+                }
+            })
     }
 
-    fn verify_attested_proof(&self, _proof: &NormProof, _threshold: f32) -> bool {
+    fn verify_attested_proof(&self, proof: &NormProof, threshold: f32) -> bool {
         // Software verification (no energy cost for verifiers)
-        // In real TEE: would verify attestation signature
-        // For now: always accept (mock)
-        true
+        // In real TEE: would also verify the attestation signature
+        ZkNormProver::new().verify_proof(proof, threshold)
+    }
+
+    fn admit_verification(&self, energy_pool: f32) -> Result<(), EnclaveError> {
+        // INV-5: Same energy guard as proof generation
+        if energy_pool < self.energy_threshold {
+            return Err(EnclaveError::InsufficientEnergy);
+        }
+        Ok(())
     }
 
     fn verify_audit_response(
@@ -1093,7 +2080,7 @@ mod enclave_tests {
         assert_eq!(result.unwrap_err(), EnclaveError::InsufficientEnergy);
 
         // Sufficient energy
-        let result = gate.generate_attested_proof(&weights, 5.0, 0.50);
+        let result = gate.generate_attested_proof(&weights, 20.0, 0.50);
         assert!(result.is_ok());
     }
 
@@ -1103,10 +2090,33 @@ mod enclave_tests {
 
         // Generate a proof
         let weights = vec![1.0, 2.0];
-        let proof = gate.generate_attested_proof(&weights, 3.0, 0.50).unwrap();
+        let proof = gate.generate_attested_proof(&weights, 6.0, 0.50).unwrap();
 
-        // Verification should always succeed (software mock)
-        assert!(gate.verify_attested_proof(&proof, 3.0));
+        // Verification runs regardless of the verifier's energy
+        assert!(gate.verify_attested_proof(&proof, 6.0));
+    }
+
+    #[test]
+    fn test_attested_proof_is_a_real_norm_proof() {
+        let gate = SoftwareEnclaveGate::default();
+        let weights = vec![0.3, -0.4, 0.5];
+
+        let proof = gate.generate_attested_proof(&weights, 1.0, 0.50).unwrap();
+        assert!(gate.verify_attested_proof(&proof, 1.0));
+        assert!(ZkNormProver::new().verify_proof(&proof, 1.0));
+        // A tighter threshold or a tampered proof is rejected
+        assert!(!gate.verify_attested_proof(&proof, 0.1));
+        let mut forged = proof.clone();
+        forged.response += Scalar::ONE;
+        assert!(!gate.verify_attested_proof(&forged, 1.0));
+
+        // Over-threshold weights cannot be attested
+        let result = gate.generate_attested_proof(&[10.0, 10.0, 10.0], 1.0, 0.50);
+        assert_eq!(result.unwrap_err(), EnclaveError::ProofGenerationFailed);
+
+        // Too few weights to make the bound meaningful
+        let result = gate.generate_attested_proof(&[0.1], 1.0, 0.50);
+        assert_eq!(result.unwrap_err(), EnclaveError::InvalidInput);
     }
 
     #[test]
@@ -1121,4 +2131,66 @@ mod enclave_tests {
         let result = gate.generate_attested_proof(&[1.0], 0.0, 0.50);
         assert_eq!(result.unwrap_err(), EnclaveError::InvalidInput);
     }
+
+    #[test]
+    fn test_transition_verification_budget_defers_excess() {
+        let gate = SoftwareEnclaveGate::default();
+        let mut verifier = BudgetedTransitionVerifier::new(2, 8);
+        let prev_hash = [0x42u8; 32];
+        let (_, proof) =
+            generate_transition_proof(&prev_hash, &[0.1, 0.2], &[0.01], &UNCHALLENGED_SEED)
+                .unwrap();
+
+        verifier.begin_round(1, &gate, 1.0);
+        assert_eq!(
+            verifier.verify(&proof, &prev_hash, &gate, 1.0),
+            TransitionVerdict::Valid
+        );
+        assert_eq!(
+            verifier.verify(&proof, &[0u8; 32], &gate, 1.0),
+            TransitionVerdict::Invalid
+        );
+        assert_eq!(verifier.remaining_budget(), 0);
+
+        // Budget exhausted: further proofs wait for the next round
+        for _ in 0..3 {
+            assert_eq!(
+                verifier.verify(&proof, &prev_hash, &gate, 1.0),
+                TransitionVerdict::Deferred
+            );
+        }
+        assert_eq!(verifier.deferred_count(), 3);
+
+        // Low energy defers even with budget left
+        let results = verifier.begin_round(2, &gate, 0.05);
+        assert!(results.is_empty());
+        assert_eq!(
+            verifier.verify(&proof, &prev_hash, &gate, 0.05),
+            TransitionVerdict::Deferred
+        );
+
+        // Next round drains the queue within budget
+        let results = verifier.begin_round(3, &gate, 1.0);
+        assert_eq!(results, vec![(prev_hash, true), (prev_hash, true)]);
+        assert_eq!(verifier.deferred_count(), 2);
+        assert_eq!(verifier.remaining_budget(), 0);
+    }
+
+    #[test]
+    fn test_transition_verification_queue_bounded() {
+        let gate = SoftwareEnclaveGate::default();
+        let mut verifier = BudgetedTransitionVerifier::new(0, 1);
+        let prev_hash = [0x42u8; 32];
+        let (_, proof) =
+            generate_transition_proof(&prev_hash, &[0.1], &[0.01], &UNCHALLENGED_SEED).unwrap();
+
+        assert_eq!(
+            verifier.verify(&proof, &prev_hash, &gate, 1.0),
+            TransitionVerdict::Deferred
+        );
+        assert_eq!(
+            verifier.verify(&proof, &prev_hash, &gate, 1.0),
+            TransitionVerdict::Dropped
+        );
+    }
 }
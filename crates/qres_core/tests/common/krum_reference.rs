@@ -0,0 +1,87 @@
+//! Krum as implemented before the flat distance buffer, shared by the
+//! equivalence test and `krum_alloc_bench` so both exercise the same code.
+
+/// `n` pseudo-random updates of length `dim`; every 13th is a 100x outlier.
+pub fn generate_updates(n: usize, dim: usize) -> Vec<Vec<f32>> {
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    (0..n)
+        .map(|i| {
+            (0..dim)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    let v = (state >> 40) as f32 / (1u64 << 24) as f32;
+                    if i % 13 == 0 {
+                        v * 100.0
+                    } else {
+                        v
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Outcome of [`nested_krum`], in the shape of `AggregationResult`.
+pub struct NestedKrum {
+    pub weights: Vec<f32>,
+    pub selected_indices: Vec<usize>,
+    pub rejected_indices: Vec<usize>,
+}
+
+/// Multi-Krum over a nested `Vec<Vec<f32>>` distance matrix, collecting a
+/// fresh neighbour list per update, then averaging the `k` best updates.
+/// Expects `n > 2q + 2` (the caller's fallback to the mean is not mirrored).
+pub fn nested_krum(updates: &[Vec<f32>], q: usize, k: usize) -> NestedKrum {
+    let n = updates.len();
+    let d = updates[0].len();
+    let mut distances = vec![vec![0.0f32; n]; n];
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let dist: f32 = updates[i]
+                .iter()
+                .zip(&updates[j])
+                .map(|(&x, &y)| {
+                    let diff = x - y;
+                    diff * diff
+                })
+                .sum();
+            distances[i][j] = dist;
+            distances[j][i] = dist;
+        }
+    }
+
+    let mut scores: Vec<(usize, f32)> = Vec::with_capacity(n);
+    for (i, row) in distances.iter().enumerate() {
+        let mut neighbor_dists: Vec<f32> = row
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .map(|(_, &d)| d)
+            .collect();
+        neighbor_dists.sort_by(f32::total_cmp);
+        scores.push((i, neighbor_dists.iter().take(n - q - 2).sum()));
+    }
+    scores.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+    let k = k.min(n);
+    let selected_indices: Vec<usize> = scores.iter().take(k).map(|(i, _)| *i).collect();
+    let rejected_indices: Vec<usize> = scores.iter().skip(k).map(|(i, _)| *i).collect();
+    let mut weights = vec![0.0f32; d];
+    for &idx in &selected_indices {
+        for (w, v) in weights.iter_mut().zip(&updates[idx]) {
+            *w += v;
+        }
+    }
+    let inv_k = 1.0 / k as f32;
+    for w in weights.iter_mut() {
+        *w *= inv_k;
+    }
+
+    NestedKrum {
+        weights,
+        selected_indices,
+        rejected_indices,
+    }
+}
@@ -0,0 +1,124 @@
+//! Fixed-point precision bounds
+//!
+//! Compares the I16F16/I8F8 arithmetic used by aggregation and Storm-mode
+//! quantization against an f64 reference, so a refactor that silently loses
+//! precision fails here instead of in the field.
+//!
+//! Documented bounds (ULP = one least-significant fractional bit):
+//!
+//! | Operation                     | Bound                                  |
+//! |-------------------------------|----------------------------------------|
+//! | `TrimmedMeanByz` (m kept)     | `(m * max|x| + 2) * 2^-16`             |
+//! | I16F16 → I8F8 → I16F16        | `2^-8 + 2^-16` inside [-128, 127.996]  |
+//! | I8F8 outside its range        | saturates to the nearest bound         |
+
+use fixed::types::I16F16;
+use qres_core::aggregation::{aggregate_updates, AggregationMode};
+use qres_core::tensor::{FixedTensor, I8F8};
+
+const I16F16_ULP: f64 = 1.0 / 65536.0;
+const I8F8_ULP: f64 = 1.0 / 256.0;
+
+/// Deterministic xorshift64 stream of values in `[-range, range]`.
+struct Inputs(u64);
+
+impl Inputs {
+    fn next(&mut self, range: f32) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        let unit = (self.0 >> 11) as f64 / (1u64 << 53) as f64;
+        ((unit * 2.0 - 1.0) * range as f64) as f32
+    }
+}
+
+/// f64 reference for the trimmed mean of one coordinate.
+fn reference_trimmed_mean(values: &mut [f64], f: usize) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let kept = &values[f..values.len() - f];
+    kept.iter().sum::<f64>() / kept.len() as f64
+}
+
+#[test]
+fn trimmed_mean_byz_fixed_point_error_is_bounded() {
+    let mut inputs = Inputs(0x9E37_79B9_7F4A_7C15);
+    let dim = 16;
+
+    for range in [0.01f32, 1.0, 10.0, 100.0] {
+        for n in [3usize, 5, 8, 15, 31] {
+            for f in 0..n.div_ceil(2) {
+                let updates: Vec<Vec<f32>> = (0..n)
+                    .map(|_| (0..dim).map(|_| inputs.next(range)).collect())
+                    .collect();
+                let result = aggregate_updates(&updates, &AggregationMode::TrimmedMeanByz { f });
+
+                let kept = n - 2 * f;
+                let bound = (kept as f64 * range as f64 + 2.0) * I16F16_ULP;
+                for (d, &got) in result.weights.iter().enumerate() {
+                    let mut column: Vec<f64> = updates.iter().map(|u| u[d] as f64).collect();
+                    let expected = reference_trimmed_mean(&mut column, f);
+                    let err = (got as f64 - expected).abs();
+                    assert!(
+                        err <= bound,
+                        "n={} f={} range={} dim={}: |{} - {}| = {:e} exceeds {:e}",
+                        n,
+                        f,
+                        range,
+                        d,
+                        got,
+                        expected,
+                        err,
+                        bound
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn i8f8_quantization_roundtrip_error_is_bounded() {
+    let mut inputs = Inputs(0xD1B5_4A32_D192_ED03);
+    let originals: Vec<f64> = (0..4096)
+        .map(|i| match i % 4 {
+            0 => inputs.next(0.05) as f64,
+            1 => inputs.next(1.0) as f64,
+            2 => inputs.next(64.0) as f64,
+            _ => inputs.next(127.0) as f64,
+        })
+        .collect();
+
+    let tensor = FixedTensor::new(originals.iter().map(|&v| I16F16::from_num(v)).collect());
+    let restored = FixedTensor::from_i8f8(&tensor.quantize_to_i8f8());
+
+    let bound = I8F8_ULP + I16F16_ULP;
+    for (&original, restored) in originals.iter().zip(&restored.data) {
+        let err = (restored.to_num::<f64>() - original).abs();
+        assert!(
+            err <= bound,
+            "{} -> {} error {:e} exceeds {:e}",
+            original,
+            restored,
+            err,
+            bound
+        );
+    }
+}
+
+#[test]
+fn i8f8_quantization_saturates_out_of_range() {
+    let tensor = FixedTensor::new(
+        [-1000.0f64, -128.5, 127.999, 300.0]
+            .iter()
+            .map(|&v| I16F16::from_num(v))
+            .collect(),
+    );
+    let quantized = tensor.quantize_to_i8f8();
+
+    assert_eq!(quantized[0], I8F8::MIN);
+    assert_eq!(quantized[1], I8F8::MIN);
+    // Upper clamp (127.996) lands within one I8F8 ULP of the maximum
+    for q in &quantized[2..] {
+        assert!((I8F8::MAX - *q).to_num::<f64>() <= I8F8_ULP);
+    }
+}
@@ -0,0 +1,26 @@
+//! Pins the flat-buffer Krum in `aggregate_updates` to the nested reference.
+
+#[path = "common/krum_reference.rs"]
+mod krum_reference;
+
+use krum_reference::{generate_updates, nested_krum};
+use qres_core::aggregation::{aggregate_updates, AggregationMode};
+
+#[test]
+fn test_flat_krum_matches_nested_reference() {
+    let updates = generate_updates(40, 16);
+
+    for (q, k) in [(1, 1), (3, 1), (5, 10), (8, 25)] {
+        let reference = nested_krum(&updates, q, k);
+        let mode = if k == 1 {
+            AggregationMode::Krum { expected_byz: q }
+        } else {
+            AggregationMode::MultiKrum { expected_byz: q, k }
+        };
+        let result = aggregate_updates(&updates, &mode);
+
+        assert_eq!(result.selected_indices, reference.selected_indices);
+        assert_eq!(result.rejected_indices, reference.rejected_indices);
+        assert_eq!(result.weights, reference.weights);
+    }
+}
@@ -14,7 +14,7 @@
 use qres_core::consensus::krum::Bfp16Vec;
 use qres_core::multimodal::{Modality, MultimodalFusion, ATTENTION_WINDOW};
 use qres_core::resource_management::EnergyPool;
-use qres_core::zk_proofs::{generate_transition_proof, ZkTransitionVerifier};
+use qres_core::zk_proofs::{generate_transition_proof, ZkTransitionVerifier, UNCHALLENGED_SEED};
 
 /// TEST 1: Deterministic Bit-Check
 /// Verify that identical inputs produce bit-identical outputs across multiple runs
@@ -123,14 +123,15 @@ fn test_zkp_validation() {
     let residuals = vec![0.05, 0.03, 0.02]; // Simulated residuals
 
     // Generate ZK proof
-    let proof_result = generate_transition_proof(&prev_hash, &new_weights, &residuals);
+    let proof_result =
+        generate_transition_proof(&prev_hash, &new_weights, &residuals, &UNCHALLENGED_SEED);
     assert!(proof_result.is_some(), "ZK proof generation should succeed");
 
     let (_gene, proof) = proof_result.unwrap();
 
     // Verify proof
     let verifier = ZkTransitionVerifier::new();
-    let is_valid = verifier.verify_transition(&proof, &prev_hash);
+    let is_valid = verifier.verify_transition(&proof, &prev_hash, &UNCHALLENGED_SEED);
 
     assert!(is_valid, "ZK proof must validate");
 
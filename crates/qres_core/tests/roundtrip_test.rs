@@ -40,6 +40,43 @@ fn roundtrip_64kb_pattern() {
     assert_eq!(test_data, decompressed, "64KB roundtrip failed");
 }
 
+/// Constant input collapses to a single run record per chunk
+#[test]
+fn roundtrip_constant_run() {
+    for byte in [0x00u8, 0xFF] {
+        let test_data = vec![byte; 65536];
+        let mut compressed = vec![0u8; 64];
+        let comp_len = compress_chunk(&test_data, 0, None, None, &mut compressed).unwrap();
+        assert_eq!(comp_len, 6);
+        let decompressed = decompress_chunk(&compressed[..comp_len], 0, None).unwrap();
+        assert_eq!(test_data, decompressed);
+    }
+}
+
+/// A run record's stored length is checked before it is expanded
+#[test]
+fn run_chunk_rejects_bad_length() {
+    let mut record = vec![0u8; 64];
+    let len = compress_chunk(&[7u8; 4096], 0, None, None, &mut record).unwrap();
+    record.truncate(len);
+
+    for bad_len in [0u32, 6, u32::MAX, (qres_core::MAX_RUN_CHUNK_LEN + 1) as u32] {
+        let mut forged = record.clone();
+        forged[1..5].copy_from_slice(&bad_len.to_le_bytes());
+        assert!(
+            decompress_chunk(&forged, 0, None).is_err(),
+            "len {}",
+            bad_len
+        );
+    }
+
+    // Trailing bytes mean this is not a run record
+    let mut padded = record.clone();
+    padded.push(7);
+    assert!(decompress_chunk(&padded, 0, None).is_err());
+    assert_eq!(decompress_chunk(&record, 0, None).unwrap(), vec![7u8; 4096]);
+}
+
 /// Test roundtrip with random-like data (may not compress)
 #[test]
 fn roundtrip_random_data() {
@@ -113,3 +150,30 @@ fn compression_ratio_check() {
         comp_len
     );
 }
+
+/// Lossy mode quantizes residuals; bytes near 0 and 255 must not wrap around
+#[test]
+fn lossy_roundtrip_error_stays_bounded_at_byte_edges() {
+    use qres_core::config::{CompressionMode, QresConfig};
+
+    let test_data: Vec<u8> = (0..65536)
+        .map(|i| ((i % 256) ^ ((i / 256) % 256)) as u8)
+        .collect();
+    let config = QresConfig {
+        mode: CompressionMode::Lossy,
+        ..QresConfig::default()
+    };
+    let mut compressed = vec![0u8; test_data.len() * 2 + 4096];
+    let comp_len = compress_chunk(&test_data, 0, None, Some(&config), &mut compressed)
+        .expect("64KB should compress");
+    let decompressed = decompress_chunk(&compressed[..comp_len], 0, None).unwrap();
+
+    assert_eq!(decompressed.len(), test_data.len());
+    let max_error = test_data
+        .iter()
+        .zip(&decompressed)
+        .map(|(a, b)| a.abs_diff(*b))
+        .max()
+        .unwrap();
+    assert!(max_error > 0 && max_error <= 4, "max error {}", max_error);
+}
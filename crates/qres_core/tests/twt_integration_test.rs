@@ -29,7 +29,9 @@ fn dummy_update(peer_id: u8) -> GhostUpdate {
         masked_weights: vec![0i32; 8],
         zk_proof: NormProof {
             commitment: CompressedEdwardsY::identity(),
+            announcement: CompressedEdwardsY::identity(),
             response: Scalar::ZERO,
+            value: 0,
         },
         dp_epsilon: 1.0,
         residual_error: 0.0,
@@ -54,6 +56,7 @@ fn test_24h_swarm_simulation() {
         base_interval_ms: 4 * MS_PER_HOUR, // Start with Calm interval
         jitter_enabled: false,             // Deterministic for testing
         max_batch_size: 128,
+        ..TWTConfig::default()
     };
 
     let mut nodes: Vec<TWTScheduler> = Vec::new();
@@ -257,6 +260,7 @@ fn test_regime_cycle_interval_correctness() {
         base_interval_ms: 4 * 3_600_000,
         jitter_enabled: false,
         max_batch_size: 64,
+        ..TWTConfig::default()
     };
     let mut sched = TWTScheduler::new(NodeRole::Scheduled(cfg));
 
@@ -312,6 +316,7 @@ fn test_reputation_weighted_sleep_staggering() {
         base_interval_ms: 10 * 60 * 1000, // 10 minutes
         jitter_enabled: false,
         max_batch_size: 128,
+        ..TWTConfig::default()
     };
 
     // 10 nodes with reputations from 0.1 to 1.0
@@ -494,6 +499,7 @@ fn test_reputation_preserved_across_regime_changes() {
         base_interval_ms: 10 * 60 * 1000, // 10 min
         jitter_enabled: false,
         max_batch_size: 32,
+        ..TWTConfig::default()
     };
 
     let mut low_rep = TWTScheduler::with_reputation(NodeRole::Scheduled(cfg), 0.2);
@@ -552,6 +558,7 @@ fn test_dynamic_reputation_change_mid_simulation() {
         base_interval_ms: 60_000, // 1 minute base
         jitter_enabled: false,
         max_batch_size: 64,
+        ..TWTConfig::default()
     };
 
     // Start with low reputation
@@ -1,5 +1,5 @@
 use qres_core::aggregation::{Aggregator, TrimmedMeanByzAggregator, WeightedTrimmedMeanAggregator};
-use qres_core::consensus::krum::Bfp16Vec;
+use qres_core::consensus::krum::{Bfp16Vec, Bfp32Vec, Bfp8Vec, BfpVec, BfpWidth};
 use qres_core::encoding::arithmetic;
 use qres_core::reputation::ReputationTracker;
 use qres_core::tensor::VarianceMonitor;
@@ -56,8 +56,8 @@ fn verification_phase_3_precision_bfp16() {
     let bytes = arithmetic::compress_bfp(bfp.exponent, &bfp.mantissas);
     assert_eq!(
         bytes.len(),
-        17,
-        "BFP-16 wire format not optimized (Expected 17 bytes for 8 dims)"
+        18,
+        "BFP-16 wire format not optimized (Expected 17 bytes + CRC-8 for 8 dims)"
     );
 
     let (exp, mantissas) = arithmetic::decompress_bfp(&bytes, 8).unwrap();
@@ -88,7 +88,7 @@ fn verification_phase_3_precision_bfp16() {
 fn verification_onboarding_summary_size() {
     // Verify Summary Gene size < 200 bytes
     // Simulating the struct layout manually
-    // [Round: 8] + [Hash: 32] + [Consensus: 17] + [Variance: 17] = 74 bytes
+    // [Round: 8] + [Hash: 32] + [Consensus: 17 + 1] + [Variance: 17 + 1] = 76 bytes
 
     let consensus = vec![0.5; 8];
     let variance = vec![0.01; 8];
@@ -112,7 +112,69 @@ fn verification_onboarding_summary_size() {
     println!("Summary Gene Total Size: {} bytes", size);
 
     assert!(size < 200, "Summary Gene bloated!");
-    assert_eq!(size, 74, "Exact size mismatch on optimized layout");
+    assert_eq!(size, 76, "Exact size mismatch on optimized layout");
+}
+
+#[test]
+fn verification_bfp_width_wire_sizes() {
+    // [exponent: 1] + 8 mantissas at 1, 2 or 4 bytes each + [crc8: 1]
+    let data: Vec<f32> = (0..8).map(|i| 0.5 - i as f32 * 0.1).collect();
+
+    let bfp8 = Bfp8Vec::from_f32_slice(&data);
+    let bfp16 = Bfp16Vec::from_f32_slice(&data);
+    let bfp32 = Bfp32Vec::from_f32_slice(&data);
+    let bytes8 = arithmetic::compress_bfp8(bfp8.exponent, &bfp8.mantissas);
+    let bytes16 = arithmetic::compress_bfp(bfp16.exponent, &bfp16.mantissas);
+    let bytes32 = arithmetic::compress_bfp32(bfp32.exponent, &bfp32.mantissas);
+
+    assert_eq!(bytes8.len(), 10);
+    assert_eq!(bytes16.len(), 18);
+    assert_eq!(bytes32.len(), 34);
+    assert_eq!(
+        BfpVec::from_f32_slice(BfpWidth::Bfp8, &data).to_bytes(),
+        bytes8
+    );
+
+    // Wire round-trips are exact
+    assert_eq!(
+        arithmetic::decompress_bfp8(&bytes8).unwrap(),
+        (bfp8.exponent, bfp8.mantissas)
+    );
+    assert_eq!(
+        arithmetic::decompress_bfp32(&bytes32).unwrap(),
+        (bfp32.exponent, bfp32.mantissas)
+    );
+    assert!(arithmetic::decompress_bfp32(&bytes32[..6]).is_err());
+    assert!(arithmetic::decompress_bfp8(&[]).is_err());
+}
+
+#[test]
+fn verification_bfp_checksum_detects_single_byte_corruption() {
+    let data: Vec<f32> = (0..8).map(|i| 0.5 - i as f32 * 0.1).collect();
+    let bfp = Bfp16Vec::from_f32_slice(&data);
+    let bytes = arithmetic::compress_bfp(bfp.exponent, &bfp.mantissas);
+
+    for i in 0..bytes.len() {
+        for flip in [0x01u8, 0x80, 0xFF] {
+            let mut corrupted = bytes.clone();
+            corrupted[i] ^= flip;
+            assert!(
+                arithmetic::decompress_bfp(&corrupted, 8).is_err(),
+                "byte {} ^ {:#04x} decoded without error",
+                i,
+                flip
+            );
+        }
+    }
+    assert!(arithmetic::decompress_bfp(&bytes[..bytes.len() - 2], 8).is_err());
+
+    // The raw variant omits the checksum and round-trips on its own
+    let raw = arithmetic::compress_bfp_raw(bfp.exponent, &bfp.mantissas);
+    assert_eq!(raw, bytes[..bytes.len() - 1]);
+    assert_eq!(
+        arithmetic::decompress_bfp_raw(&raw, 8).unwrap(),
+        (bfp.exponent, bfp.mantissas)
+    );
 }
 
 /// Simulates the Mid-Flight Join protocol under 90% packet loss.
@@ -0,0 +1,45 @@
+//! Crash-safe file replacement
+//!
+//! State files (brain, reputation DB, regime snapshot, dream replay) are
+//! written to a temp file next to the target, synced, then renamed over it.
+//! The rename is atomic on POSIX, so a crash mid-write (e.g. a second Ctrl-C)
+//! leaves the previous file intact instead of a truncated one.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Temp file `path` is staged in before being renamed into place
+pub fn staging_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+/// Replace `path` with `contents` through [`staging_path`].
+pub fn write_file_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let tmp_path = staging_path(path);
+    let mut file = fs::File::create(&tmp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_replaces_file_and_leaves_no_temp() {
+        let path =
+            std::env::temp_dir().join(format!("qres_atomic_file_{}.json", std::process::id()));
+        fs::write(&path, b"old").unwrap();
+        // A stale temp file from an interrupted write is overwritten
+        fs::write(staging_path(&path), b"partial").unwrap();
+
+        write_file_atomic(&path, b"new").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"new");
+        assert!(!staging_path(&path).exists());
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,200 @@
+//! Bounded Audit Logs
+//!
+//! Ring buffers for the verification history of incoming updates and for
+//! rejected updates. Each log keeps at most `capacity` records in memory;
+//! when a flush file is configured, records are appended to it (JSON lines)
+//! before they are evicted so they remain available for compliance review.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// A single audit record for an incoming update.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Unix timestamp (seconds) when the decision was made
+    pub timestamp: u64,
+    /// Sender of the update (PeerID or public key)
+    pub peer_id: String,
+    /// Outcome or rejection reason
+    pub detail: String,
+}
+
+impl AuditRecord {
+    pub fn now(peer_id: &str, detail: &str) -> Self {
+        Self {
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            peer_id: peer_id.to_string(),
+            detail: detail.to_string(),
+        }
+    }
+}
+
+/// Fixed-capacity log that evicts its oldest entries.
+#[derive(Debug, Clone)]
+pub struct AuditLog<T> {
+    entries: VecDeque<T>,
+    capacity: usize,
+    /// JSON-lines file receiving entries before they leave memory
+    flush_path: Option<PathBuf>,
+    /// Set once an evicted entry was lost rather than flushed to disk
+    overflowed: bool,
+}
+
+impl<T: Serialize + DeserializeOwned> AuditLog<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity.min(1024)),
+            capacity: capacity.max(1),
+            flush_path: None,
+            overflowed: false,
+        }
+    }
+
+    /// Append entries to `path` before evicting them
+    pub fn with_flush_path(mut self, path: PathBuf) -> Self {
+        self.flush_path = Some(path);
+        self
+    }
+
+    /// Record an entry, evicting (and flushing, if configured) the oldest one at capacity.
+    pub fn push(&mut self, entry: T) {
+        if self.entries.len() >= self.capacity {
+            if let Some(oldest) = self.entries.pop_front() {
+                let flushed = self.flush_path.is_some()
+                    && match self.append_to_disk(std::slice::from_ref(&oldest)) {
+                        Ok(()) => true,
+                        Err(e) => {
+                            warn!(error = %e, "Failed to flush evicted audit record");
+                            false
+                        }
+                    };
+                self.overflowed |= !flushed;
+            }
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// Write all retained entries to the flush file and clear them from memory.
+    ///
+    /// Returns the number of flushed entries (0 when no flush file is configured).
+    pub fn flush(&mut self) -> io::Result<usize> {
+        if self.flush_path.is_none() || self.entries.is_empty() {
+            return Ok(0);
+        }
+        let entries: Vec<T> = self.entries.drain(..).collect();
+        self.append_to_disk(&entries)?;
+        Ok(entries.len())
+    }
+
+    fn append_to_disk(&self, entries: &[T]) -> io::Result<()> {
+        let Some(path) = &self.flush_path else {
+            return Ok(());
+        };
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        for entry in entries {
+            serde_json::to_writer(&mut file, entry)?;
+            file.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &T> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether older records were lost: evicted with no flush file, or the
+    /// flush failed
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+}
+
+/// Read back entries flushed by [`AuditLog`].
+pub fn load_flushed<T: DeserializeOwned>(path: &Path) -> io::Result<Vec<T>> {
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| serde_json::from_str(line).map_err(io::Error::from))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(i: u64) -> AuditRecord {
+        AuditRecord {
+            timestamp: i,
+            peer_id: format!("peer{}", i),
+            detail: "accepted".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_eviction_flushes_to_disk_without_overflowing() {
+        let path = std::env::temp_dir().join(format!("qres_audit_{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut log = AuditLog::new(3).with_flush_path(path.clone());
+
+        for i in 0..3 {
+            log.push(record(i));
+        }
+        assert!(!log.overflowed());
+
+        log.push(record(3));
+        log.push(record(4));
+        // Nothing was lost: the evicted records are on disk
+        assert!(!log.overflowed());
+        let retained: Vec<u64> = log.entries().map(|r| r.timestamp).collect();
+        assert_eq!(retained, vec![2, 3, 4]);
+
+        // Evicted records were written out before being dropped
+        let flushed: Vec<AuditRecord> = load_flushed(&path).unwrap();
+        assert_eq!(flushed, vec![record(0), record(1)]);
+
+        // Periodic flush moves the rest to disk
+        assert_eq!(log.flush().unwrap(), 3);
+        assert!(log.is_empty());
+        let flushed: Vec<AuditRecord> = load_flushed(&path).unwrap();
+        assert_eq!(flushed, (0..5).map(record).collect::<Vec<_>>());
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_failed_flush_sets_overflow() {
+        // A directory cannot be opened for appending
+        let mut log = AuditLog::new(1).with_flush_path(std::env::temp_dir());
+        log.push(record(0));
+        log.push(record(1));
+        assert!(log.overflowed());
+    }
+
+    #[test]
+    fn test_without_flush_path_entries_are_dropped() {
+        let mut log = AuditLog::new(2);
+        for i in 0..4 {
+            log.push(record(i));
+        }
+        assert!(log.overflowed());
+        assert_eq!(log.len(), 2);
+        assert_eq!(log.flush().unwrap(), 0);
+        assert_eq!(log.len(), 2);
+    }
+}
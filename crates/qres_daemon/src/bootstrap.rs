@@ -0,0 +1,154 @@
+//! Bootstrap Redial Schedule
+//!
+//! WAN swarms rely on their bootstrap peers to stay joined. A bootstrap dial
+//! that fails, or a bootstrap peer that disconnects, is queued here and
+//! redialed from the swarm loop's interval tick. Each consecutive failure of
+//! the same address doubles its wait (up to a cap) so an unreachable peer is
+//! not hammered; a successful connection forgets the backoff.
+
+use libp2p::swarm::ConnectionId;
+use libp2p::Multiaddr;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Wait before the first redial of a failed or lost bootstrap address
+pub const INITIAL_REDIAL_BACKOFF: Duration = Duration::from_secs(1);
+/// Longest wait between redials of one address
+pub const MAX_REDIAL_BACKOFF: Duration = Duration::from_secs(300);
+/// How often the swarm loop checks for due redials
+pub const REDIAL_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A bootstrap address waiting for its next dial.
+#[derive(Debug, Clone)]
+struct PendingRedial {
+    due: Instant,
+    addr: Multiaddr,
+    backoff: Duration,
+}
+
+/// Tracks in-flight bootstrap dials and schedules redials with exponential
+/// backoff.
+#[derive(Debug)]
+pub struct BootstrapRedialer {
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    /// Address and the backoff that preceded it, per outstanding dial
+    in_flight: HashMap<ConnectionId, (Multiaddr, Option<Duration>)>,
+    waiting: Vec<PendingRedial>,
+}
+
+impl Default for BootstrapRedialer {
+    fn default() -> Self {
+        Self::new(INITIAL_REDIAL_BACKOFF, MAX_REDIAL_BACKOFF)
+    }
+}
+
+impl BootstrapRedialer {
+    pub fn new(initial_backoff: Duration, max_backoff: Duration) -> Self {
+        Self {
+            initial_backoff,
+            max_backoff: max_backoff.max(initial_backoff),
+            in_flight: HashMap::new(),
+            waiting: Vec::new(),
+        }
+    }
+
+    /// Record a dial of `addr` issued as `connection_id`, after waiting
+    /// `backoff` (`None` for a first dial).
+    pub fn dialed(
+        &mut self,
+        connection_id: ConnectionId,
+        addr: Multiaddr,
+        backoff: Option<Duration>,
+    ) {
+        self.in_flight.insert(connection_id, (addr, backoff));
+    }
+
+    /// A tracked dial connected: its address starts over from the initial backoff.
+    pub fn connected(&mut self, connection_id: ConnectionId) {
+        self.in_flight.remove(&connection_id);
+    }
+
+    /// A dial failed at `now`. Bootstrap dials are queued for a redial and
+    /// the wait is returned; other dials are ignored.
+    pub fn dial_failed(&mut self, connection_id: ConnectionId, now: Instant) -> Option<Duration> {
+        let (addr, previous) = self.in_flight.remove(&connection_id)?;
+        let backoff = previous.map_or(self.initial_backoff, |b| {
+            b.saturating_mul(2).min(self.max_backoff)
+        });
+        self.schedule(addr, backoff, now);
+        Some(backoff)
+    }
+
+    /// The peer behind bootstrap address `addr` disconnected at `now`.
+    pub fn disconnected(&mut self, addr: Multiaddr, now: Instant) {
+        let busy = self.in_flight.values().any(|(a, _)| *a == addr);
+        if !busy {
+            self.schedule(addr, self.initial_backoff, now);
+        }
+    }
+
+    fn schedule(&mut self, addr: Multiaddr, backoff: Duration, now: Instant) {
+        self.waiting.retain(|r| r.addr != addr);
+        self.waiting.push(PendingRedial {
+            due: now + backoff,
+            addr,
+            backoff,
+        });
+    }
+
+    /// Remove and return the redials due at `now`, each with the backoff
+    /// it waited (to pass back to [`dialed`](Self::dialed)).
+    pub fn take_due(&mut self, now: Instant) -> Vec<(Multiaddr, Duration)> {
+        let (due, waiting): (Vec<PendingRedial>, _) = std::mem::take(&mut self.waiting)
+            .into_iter()
+            .partition(|r| r.due <= now);
+        self.waiting = waiting;
+        due.into_iter().map(|r| (r.addr, r.backoff)).collect()
+    }
+
+    /// Number of addresses waiting for a redial
+    pub fn waiting(&self) -> usize {
+        self.waiting.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_until_connected() {
+        let addr: Multiaddr = "/ip4/127.0.0.1/tcp/1".parse().unwrap();
+        let mut redialer = BootstrapRedialer::new(Duration::from_secs(1), Duration::from_secs(4));
+        let start = Instant::now();
+
+        let mut backoff = None;
+        let mut waits = Vec::new();
+        for _ in 0..4 {
+            let id = ConnectionId::new_unchecked(waits.len());
+            redialer.dialed(id, addr.clone(), backoff);
+            let wait = redialer.dial_failed(id, start).unwrap();
+            assert!(redialer
+                .take_due(start + wait - Duration::from_millis(1))
+                .is_empty());
+            let due = redialer.take_due(start + wait);
+            assert_eq!(due, vec![(addr.clone(), wait)]);
+            backoff = Some(wait);
+            waits.push(wait.as_secs());
+        }
+        assert_eq!(waits, vec![1, 2, 4, 4]);
+
+        // Connecting resets: a later loss waits the initial backoff again
+        let id = ConnectionId::new_unchecked(10);
+        redialer.dialed(id, addr.clone(), backoff);
+        redialer.connected(id);
+        assert!(redialer.dial_failed(id, start).is_none());
+        redialer.disconnected(addr.clone(), start);
+        assert_eq!(
+            redialer.take_due(start + Duration::from_secs(1)),
+            vec![(addr, Duration::from_secs(1))]
+        );
+        assert_eq!(redialer.waiting(), 0);
+    }
+}
@@ -8,7 +8,11 @@ use crate::config::AggregationConfig;
 use crate::living_brain::{LivingBrain, SignedEpiphany};
 use crate::security::ReputationManager;
 use fixed::types::I16F16;
-use qres_core::aggregation::{aggregate_updates, AggregationMode, AggregationResult};
+use qres_core::aggregation::{
+    aggregate_updates, AggregationMode, AggregationResult, Aggregator, FedAvgAggregator,
+    KrumAggregator, TrimmedMeanAggregator, TrimmedMeanByzAggregator, WeightedMedianAggregator,
+    WeightedTrimmedMeanAggregator,
+};
 use qres_core::consensus::aggregate_krum;
 use qres_core::tensor::FixedTensor;
 use std::collections::VecDeque;
@@ -211,6 +215,7 @@ impl BrainAggregator {
             updates = n,
             selected = result.selected_indices.len(),
             rejected = result.rejected_indices.len(),
+            divergence_from_mean = result.divergence_from_mean,
             mode = ?self.config.mode,
             "Aggregated brain updates"
         );
@@ -248,6 +253,13 @@ impl BrainAggregator {
     }
 }
 
+/// FedProx proximal step: `w <- w - mu * (w - anchor)`, element-wise
+pub fn fedprox_step(aggregate: &mut [f32], anchor: &[f32], mu: f32) {
+    for (w, &a) in aggregate.iter_mut().zip(anchor) {
+        *w -= mu * (*w - a);
+    }
+}
+
 /// Apply aggregated confidence to a brain
 pub fn apply_aggregated_confidence(brain: &mut LivingBrain, aggregated: &[f32], alpha: f32) {
     for (conf, &agg) in brain.confidence.iter_mut().zip(aggregated.iter()) {
@@ -255,6 +267,70 @@ pub fn apply_aggregated_confidence(brain: &mut LivingBrain, aggregated: &[f32],
     }
 }
 
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Build the federated averaging strategy named by `config.federated_strategy`
+/// for a round of `n_updates` buffered updates.
+///
+/// The Byzantine bound f is `expected_byzantines_fraction` of the updates
+/// actually being aggregated (at least 1), not of the buffer capacity, and
+/// Multi-Krum averages `multi_krum_k` updates, defaulting to n - f.
+///
+/// Returns `None` for the default reputation/freshness weighted mean (and for
+/// unknown names, which are logged and fall back to it).
+pub fn federated_strategy(
+    config: &AggregationConfig,
+    n_updates: usize,
+) -> Option<Box<dyn Aggregator + Send + Sync>> {
+    let expected_byz = ((n_updates as f32 * config.expected_byzantines_fraction) as usize).max(1);
+    let multi_k = config
+        .multi_krum_k
+        .unwrap_or(n_updates.saturating_sub(expected_byz))
+        .max(1);
+    let strategy: Box<dyn Aggregator + Send + Sync> =
+        match config.federated_strategy.to_lowercase().as_str() {
+            "weighted_mean" | "" => return None,
+            "fedavg" | "mean" => Box::new(FedAvgAggregator),
+            "krum" => Box::new(KrumAggregator {
+                expected_byz,
+                multi_k: None,
+            }),
+            "multi_krum" => Box::new(KrumAggregator {
+                expected_byz,
+                multi_k: Some(multi_k),
+            }),
+            "trimmed_mean" | "trimmed" => Box::new(TrimmedMeanAggregator {
+                trim_fraction: config.trim_fraction,
+            }),
+            "trimmed_mean_byz" => Box::new(TrimmedMeanByzAggregator { f: expected_byz }),
+            "weighted_trimmed_mean" => {
+                Box::new(WeightedTrimmedMeanAggregator::new(expected_byz, Vec::new()))
+            }
+            "weighted_median" => Box::new(WeightedMedianAggregator::new(Vec::new())),
+            other => {
+                warn!(
+                    strategy = other,
+                    "Unknown federated strategy, using weighted mean"
+                );
+                return None;
+            }
+        };
+    Some(strategy)
+}
+
+/// Where [`FederatedAverager`] gets its robust strategy from each round
+enum FederatedStrategy {
+    /// The same aggregator every round
+    Fixed(Box<dyn Aggregator + Send + Sync>),
+    /// Rebuilt by [`federated_strategy`] for each round's update count
+    Configured(AggregationConfig),
+}
+
 /// Federated Learning Averager using weighted averaging with reputation and freshness
 pub struct FederatedAverager {
     /// Buffered SignedEpiphany updates from peers
@@ -263,6 +339,12 @@ pub struct FederatedAverager {
     max_buffer_size: usize,
     /// Freshness decay half-life in seconds (updates older than this lose weight)
     freshness_half_life: f64,
+    /// Hard age cutoff in seconds; older updates are evicted instead of down-weighted
+    max_update_age: Option<f64>,
+    /// Robust aggregation strategy; `None` uses the Kahan weighted mean
+    strategy: Option<FederatedStrategy>,
+    /// FedProx proximal coefficient pulling results toward the global anchor
+    proximal_mu: f32,
 }
 
 impl FederatedAverager {
@@ -272,12 +354,66 @@ impl FederatedAverager {
             buffer: VecDeque::with_capacity(max_buffer_size),
             max_buffer_size,
             freshness_half_life,
+            max_update_age: None,
+            strategy: None,
+            proximal_mu: 0.0,
         }
     }
 
+    /// Delegate aggregation to `strategy`, passing reputation x freshness
+    /// as per-update weights
+    pub fn with_strategy(mut self, strategy: Box<dyn Aggregator + Send + Sync>) -> Self {
+        self.strategy = Some(FederatedStrategy::Fixed(strategy));
+        self
+    }
+
+    /// Use the strategy named by `config.federated_strategy`, sized for the
+    /// number of updates in each round (see [`federated_strategy`])
+    pub fn with_strategy_config(mut self, config: AggregationConfig) -> Self {
+        if let Some(strategy) = federated_strategy(&config, self.max_buffer_size) {
+            info!(
+                strategy = strategy.name(),
+                "Federated averaging strategy selected"
+            );
+            self.strategy = Some(FederatedStrategy::Configured(config));
+        }
+        self
+    }
+
+    /// FedProx: move the aggregated confidence `mu` of the way back toward
+    /// the global anchor, `w - mu * (w - anchor)`. Clamped to `[0, 1]`;
+    /// `0.0` is plain averaging.
+    pub fn with_proximal_mu(mut self, mu: f32) -> Self {
+        self.proximal_mu = if mu.is_finite() {
+            mu.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self
+    }
+
+    /// Evict buffered updates older than `max_update_age` seconds
+    pub fn with_max_update_age(mut self, max_update_age: f64) -> Self {
+        self.max_update_age = Some(max_update_age);
+        self
+    }
+
+    /// Drop updates that exceeded the maximum age as of `now` (unix seconds).
+    /// Returns the number of evicted updates.
+    pub fn evict_stale(&mut self, now: u64) -> usize {
+        let Some(max_age) = self.max_update_age else {
+            return 0;
+        };
+        let before = self.buffer.len();
+        self.buffer
+            .retain(|e| (now as f64 - e.timestamp as f64) <= max_age);
+        before - self.buffer.len()
+    }
+
     /// Add a SignedEpiphany update to the buffer
     pub fn add_update(&mut self, epiphany: SignedEpiphany) {
         self.buffer.push_back(epiphany);
+        self.evict_stale(unix_now());
 
         // Keep buffer size in check
         if self.buffer.len() > self.max_buffer_size {
@@ -285,21 +421,70 @@ impl FederatedAverager {
         }
     }
 
+    /// Unnormalized aggregation weight of `update`: its sender's reputation
+    /// times the staleness decay `0.5^(age / half_life)`
+    pub fn effective_weight(
+        &self,
+        update: &SignedEpiphany,
+        reputation_manager: &ReputationManager,
+    ) -> f32 {
+        self.effective_weight_at(update, reputation_manager, unix_now())
+    }
+
+    /// Effective weight as of `now` (unix seconds); see
+    /// [`effective_weight`](Self::effective_weight)
+    pub fn effective_weight_at(
+        &self,
+        update: &SignedEpiphany,
+        reputation_manager: &ReputationManager,
+        now: u64,
+    ) -> f32 {
+        self.combined_weight(update, reputation_manager, now) as f32
+    }
+
+    fn combined_weight(
+        &self,
+        update: &SignedEpiphany,
+        reputation_manager: &ReputationManager,
+        now: u64,
+    ) -> f64 {
+        // Get reputation score (0.5 default for new peers)
+        let reputation = reputation_manager.get_trust(&update.sender_id) as f64;
+
+        // Future-dated updates count as fresh rather than gaining weight
+        let age_seconds = now.saturating_sub(update.timestamp) as f64;
+        let freshness = 0.5f64.powf(age_seconds / self.freshness_half_life);
+
+        reputation * freshness
+    }
+
     /// Aggregate buffered updates using weighted average
-    /// Returns the aggregated weights and confidence vectors
+    /// Returns the aggregated weights and confidence vectors; the confidence
+    /// is regularized toward `anchor` (the brain's `global_confidence`) when
+    /// a proximal `mu` is set
     pub fn aggregate(
         &mut self,
         reputation_manager: &ReputationManager,
+        anchor: Option<&[f32]>,
+    ) -> Option<(Vec<u8>, Vec<f32>)> {
+        self.aggregate_at(reputation_manager, anchor, unix_now())
+    }
+
+    /// Aggregate as of `now` (unix seconds); see [`aggregate`](Self::aggregate)
+    pub fn aggregate_at(
+        &mut self,
+        reputation_manager: &ReputationManager,
+        anchor: Option<&[f32]>,
+        now: u64,
     ) -> Option<(Vec<u8>, Vec<f32>)> {
+        let evicted = self.evict_stale(now);
+        if evicted > 0 {
+            info!(evicted = evicted, "Evicted stale federated updates");
+        }
         if self.buffer.is_empty() {
             return None;
         }
 
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs() as f64;
-
         // Extract all weight vectors and compute weights
         let mut all_weights: Vec<Vec<f32>> = Vec::new();
         let mut all_confidences: Vec<Vec<f32>> = Vec::new();
@@ -307,15 +492,7 @@ impl FederatedAverager {
         let mut total_weight = 0.0;
 
         for epiphany in &self.buffer {
-            // Get reputation score (0.5 default for new peers)
-            let reputation = reputation_manager.get_trust(&epiphany.sender_id) as f64;
-
-            // Calculate freshness decay: weight = reputation * exp(-ln(2) * age / half_life)
-            let age_seconds = now - epiphany.timestamp as f64;
-            let freshness =
-                (-std::f64::consts::LN_2 * age_seconds / self.freshness_half_life).exp();
-
-            let combined_weight = reputation * freshness;
+            let combined_weight = self.combined_weight(epiphany, reputation_manager, now);
             weights.push(combined_weight);
             total_weight += combined_weight;
 
@@ -364,37 +541,58 @@ impl FederatedAverager {
             *w /= total_weight;
         }
 
-        // Weighted average using Kahan summation for precision
-        let weight_dim = all_weights[0].len();
-        let mut aggregated_weights = vec![0.0f32; weight_dim];
-        let mut confidences = vec![0.0f32; all_confidences[0].len()];
-
-        // Aggregate weights with Kahan summation
-        for i in 0..weight_dim {
-            let mut sum = 0.0f64;
-            let mut c = 0.0f64; // Kahan compensation
-
-            for (j, weights_vec) in all_weights.iter().enumerate() {
-                let y = weights_vec[i] as f64 * weights[j] - c;
-                let t = sum + y;
-                c = (t - sum) - y;
-                sum = t;
+        let round_strategy = match &self.strategy {
+            Some(FederatedStrategy::Configured(config)) => {
+                federated_strategy(config, all_weights.len())
             }
-            aggregated_weights[i] = sum as f32;
-        }
-
-        // Aggregate confidences with Kahan summation
-        for i in 0..all_confidences[0].len() {
-            let mut sum = 0.0f64;
-            let mut c = 0.0f64;
+            _ => None,
+        };
+        let strategy = match &self.strategy {
+            Some(FederatedStrategy::Fixed(strategy)) => Some(strategy.as_ref()),
+            _ => round_strategy.as_deref(),
+        };
 
-            for (j, conf_vec) in all_confidences.iter().enumerate() {
-                let y = conf_vec[i] as f64 * weights[j] - c;
-                let t = sum + y;
-                c = (t - sum) - y;
-                sum = t;
+        let (aggregated_weights, confidences) = match strategy {
+            Some(strategy) => {
+                let rep_weights: Vec<f32> = weights.iter().map(|&w| w as f32).collect();
+                let weight_result = strategy.aggregate_weighted(&all_weights, &rep_weights);
+                // Krum keeps whole updates, so confidences follow its selection;
+                // coordinate-wise strategies aggregate the confidences themselves
+                let confidences = if matches!(strategy.name(), "Krum" | "MultiKrum") {
+                    Self::selected_confidence(&all_confidences, &weights, &weight_result)
+                } else {
+                    strategy
+                        .aggregate_weighted(&all_confidences, &rep_weights)
+                        .weights
+                };
+                if !weight_result.rejected_indices.is_empty() {
+                    let rejected: Vec<&str> = weight_result
+                        .rejected_indices
+                        .iter()
+                        .filter_map(|&i| self.buffer.get(i))
+                        .map(|e| e.sender_id.as_str())
+                        .collect();
+                    warn!(
+                        strategy = strategy.name(),
+                        rejected = ?rejected,
+                        "Federated updates rejected by robust aggregation"
+                    );
+                }
+                (weight_result.weights, confidences)
+            }
+            None => Self::weighted_mean(&all_weights, &all_confidences, &weights),
+        };
+        let mut confidences = confidences;
+        if let Some(anchor) = anchor.filter(|_| self.proximal_mu > 0.0) {
+            if anchor.len() == confidences.len() {
+                fedprox_step(&mut confidences, anchor, self.proximal_mu);
+            } else {
+                warn!(
+                    aggregate = confidences.len(),
+                    anchor = anchor.len(),
+                    "FedProx anchor dimension mismatch, skipping proximal term"
+                );
             }
-            confidences[i] = sum as f32;
         }
 
         // Convert back to bytes (use I16F16 for aggregated result)
@@ -419,6 +617,63 @@ impl FederatedAverager {
         Some((weights_bytes, confidences))
     }
 
+    /// Weighted average using Kahan summation for precision;
+    /// `weights` must already be normalized
+    fn weighted_mean(
+        all_weights: &[Vec<f32>],
+        all_confidences: &[Vec<f32>],
+        weights: &[f64],
+    ) -> (Vec<f32>, Vec<f32>) {
+        let rows: Vec<usize> = (0..weights.len()).collect();
+        (
+            Self::kahan_weighted_sum(all_weights, &rows, weights),
+            Self::kahan_weighted_sum(all_confidences, &rows, weights),
+        )
+    }
+
+    /// Reputation-weighted mean of the confidences of the updates Krum kept
+    /// in `result.selected_indices` (all updates if none were reported)
+    fn selected_confidence(
+        all_confidences: &[Vec<f32>],
+        weights: &[f64],
+        result: &AggregationResult,
+    ) -> Vec<f32> {
+        let rows: Vec<usize> = if result.selected_indices.is_empty() {
+            (0..all_confidences.len()).collect()
+        } else {
+            result
+                .selected_indices
+                .iter()
+                .copied()
+                .filter(|&i| i < all_confidences.len())
+                .collect()
+        };
+        let total: f64 = rows.iter().map(|&i| weights[i]).sum();
+        if total <= 0.0 {
+            return vec![0.0; all_confidences[0].len()];
+        }
+        let renormalized: Vec<f64> = weights.iter().map(|w| w / total).collect();
+        Self::kahan_weighted_sum(all_confidences, &rows, &renormalized)
+    }
+
+    /// Sum of `vectors[j] * weights[j]` over `rows`, with Kahan summation
+    fn kahan_weighted_sum(vectors: &[Vec<f32>], rows: &[usize], weights: &[f64]) -> Vec<f32> {
+        let mut out = vec![0.0f32; vectors[0].len()];
+        for (i, slot) in out.iter_mut().enumerate() {
+            let mut sum = 0.0f64;
+            let mut c = 0.0f64; // Kahan compensation
+
+            for &j in rows {
+                let y = vectors[j][i] as f64 * weights[j] - c;
+                let t = sum + y;
+                c = (t - sum) - y;
+                sum = t;
+            }
+            *slot = sum as f32;
+        }
+        out
+    }
+
     /// Get current buffer size
     pub fn buffer_len(&self) -> usize {
         self.buffer.len()
@@ -441,6 +696,10 @@ mod tests {
             expected_byzantines_fraction: 0.2,
             buffer_size: 3,
             trim_fraction: 0.2,
+            federated_strategy: "weighted_mean".to_string(),
+            multi_krum_k: None,
+            fedprox_mu: 0.0,
+            max_update_age_secs: 3600,
         };
 
         let mut agg = BrainAggregator::new(config);
@@ -466,9 +725,282 @@ mod tests {
             expected_byzantines_fraction: 0.2,
             buffer_size: 5,
             trim_fraction: 0.2,
+            federated_strategy: "weighted_mean".to_string(),
+            multi_krum_k: None,
+            fedprox_mu: 0.0,
+            max_update_age_secs: 3600,
         };
 
         let agg = BrainAggregator::new(config);
         assert!(agg.is_robust());
     }
+
+    #[test]
+    fn test_federated_averager_evicts_stale_updates() {
+        let now = 1_000_000u64;
+        let epiphany = |confidence: f32, timestamp: u64, sender: &str| {
+            let mut brain = LivingBrain::new();
+            brain.confidence = vec![confidence; 4];
+            SignedEpiphany::new(
+                brain,
+                None,
+                String::new(),
+                sender.to_string(),
+                timestamp,
+                0,
+                false,
+            )
+        };
+        let reputation = ReputationManager::new(std::env::temp_dir().join(format!(
+            "qres_fedavg_{}_reputation.json",
+            std::process::id()
+        )));
+
+        let mut averager = FederatedAverager::new(50, 300.0).with_max_update_age(600.0);
+        // Push directly so insertion-time eviction (wall clock) does not interfere
+        averager.buffer.push_back(epiphany(1.0, now - 10, "fresh"));
+        averager
+            .buffer
+            .push_back(epiphany(0.0, now - 1_000, "departed"));
+
+        let (_, confidence) = averager.aggregate_at(&reputation, None, now).unwrap();
+        // Without the cutoff the departed peer would keep a ~10% residual weight
+        assert!(confidence.iter().all(|&c| (c - 1.0).abs() < 1e-6));
+        assert_eq!(averager.buffer_len(), 0);
+
+        // Only stale updates left: nothing to aggregate
+        averager
+            .buffer
+            .push_back(epiphany(0.0, now - 1_000, "departed"));
+        assert!(averager.aggregate_at(&reputation, None, now).is_none());
+        assert_eq!(averager.buffer_len(), 0);
+    }
+
+    #[test]
+    fn test_federated_averager_krum_strategy_excludes_malicious_update() {
+        let now = 1_000_000u64;
+        let epiphany = |value: f32, sender: &str| {
+            let mut brain = LivingBrain::new();
+            brain.confidence = vec![value; 4];
+            brain.best_engine_weights = Some(
+                [I16F16::from_num(value); 4]
+                    .iter()
+                    .flat_map(|w| w.to_le_bytes())
+                    .collect(),
+            );
+            SignedEpiphany::new(
+                brain,
+                None,
+                String::new(),
+                sender.to_string(),
+                now,
+                0,
+                false,
+            )
+        };
+        let reputation = ReputationManager::new(std::env::temp_dir().join(format!(
+            "qres_fedavg_krum_{}_reputation.json",
+            std::process::id()
+        )));
+
+        let mut averager =
+            FederatedAverager::new(50, 300.0).with_strategy(Box::new(KrumAggregator {
+                expected_byz: 1,
+                multi_k: None,
+            }));
+        for (i, value) in [1.0, 1.01, 0.99, 1.02].iter().enumerate() {
+            averager
+                .buffer
+                .push_back(epiphany(*value, &format!("honest{i}")));
+        }
+        averager.buffer.push_back(epiphany(100.0, "attacker"));
+
+        let (weights_bytes, confidence) = averager.aggregate_at(&reputation, None, now).unwrap();
+        let weights = FixedTensor::from_i16f16_bytes(&weights_bytes);
+        assert_eq!(weights.data.len(), 4);
+        assert!(weights
+            .data
+            .iter()
+            .all(|w| (w.to_num::<f32>() - 1.0).abs() < 0.05));
+        assert!(confidence.iter().all(|&c| (c - 1.0).abs() < 0.05));
+        assert_eq!(averager.buffer_len(), 0);
+    }
+
+    #[test]
+    fn test_configured_multi_krum_sizes_f_to_the_round() {
+        let now = 1_000_000u64;
+        let epiphany = |weight: f32, confidence: f32, sender: &str| {
+            let mut brain = LivingBrain::new();
+            brain.confidence = vec![confidence; 4];
+            brain.best_engine_weights = Some(
+                [I16F16::from_num(weight); 4]
+                    .iter()
+                    .flat_map(|w| w.to_le_bytes())
+                    .collect(),
+            );
+            SignedEpiphany::new(
+                brain,
+                None,
+                String::new(),
+                sender.to_string(),
+                now,
+                0,
+                false,
+            )
+        };
+        let reputation = ReputationManager::new(std::env::temp_dir().join(format!(
+            "qres_fedavg_multi_krum_{}_reputation.json",
+            std::process::id()
+        )));
+
+        // Sized by the buffer capacity, f would be 10 and Krum could not
+        // run on the 5 updates of this round
+        let config = AggregationConfig {
+            buffer_size: 50,
+            federated_strategy: "multi_krum".to_string(),
+            ..AggregationConfig::default()
+        };
+        let mut averager = FederatedAverager::new(50, 300.0).with_strategy_config(config);
+        for (i, value) in [1.0, 1.01, 0.99, 1.02].iter().enumerate() {
+            averager
+                .buffer
+                .push_back(epiphany(*value, 0.5, &format!("honest{i}")));
+        }
+        // Confidence can only be excluded by following the weight selection
+        averager.buffer.push_back(epiphany(100.0, 0.9, "attacker"));
+
+        let (weights_bytes, confidence) = averager.aggregate_at(&reputation, None, now).unwrap();
+        let weights = FixedTensor::from_i16f16_bytes(&weights_bytes);
+        assert!(weights
+            .data
+            .iter()
+            .all(|w| (w.to_num::<f32>() - 1.005).abs() < 0.01));
+        assert!(confidence.iter().all(|&c| (c - 0.5).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_trimmed_mean_trims_an_outlier_confidence() {
+        let now = 1_000_000u64;
+        let epiphany = |confidence: f32, sender: &str| {
+            let mut brain = LivingBrain::new();
+            brain.confidence = vec![confidence; 4];
+            brain.best_engine_weights = Some(
+                [I16F16::from_num(1.0); 4]
+                    .iter()
+                    .flat_map(|w| w.to_le_bytes())
+                    .collect(),
+            );
+            SignedEpiphany::new(
+                brain,
+                None,
+                String::new(),
+                sender.to_string(),
+                now,
+                0,
+                false,
+            )
+        };
+        let reputation = ReputationManager::new(std::env::temp_dir().join(format!(
+            "qres_fedavg_trimmed_{}_reputation.json",
+            std::process::id()
+        )));
+
+        let config = AggregationConfig {
+            federated_strategy: "trimmed_mean".to_string(),
+            // Drops one update from each tail of five
+            trim_fraction: 0.4,
+            ..AggregationConfig::default()
+        };
+        let mut averager = FederatedAverager::new(50, 300.0).with_strategy_config(config);
+        for i in 0..4 {
+            averager
+                .buffer
+                .push_back(epiphany(0.5, &format!("honest{i}")));
+        }
+        // Identical weights: only the confidence is an outlier
+        averager.buffer.push_back(epiphany(100.0, "attacker"));
+
+        let (_, confidence) = averager.aggregate_at(&reputation, None, now).unwrap();
+        assert!(confidence.iter().all(|&c| (c - 0.5).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_federated_averager_fresh_update_dominates_stale() {
+        let now = 1_000_000u64;
+        let epiphany = |confidence: f32, timestamp: u64, sender: &str| {
+            let mut brain = LivingBrain::new();
+            brain.confidence = vec![confidence; 4];
+            SignedEpiphany::new(
+                brain,
+                None,
+                String::new(),
+                sender.to_string(),
+                timestamp,
+                0,
+                false,
+            )
+        };
+        let reputation = ReputationManager::new(std::env::temp_dir().join(format!(
+            "qres_fedavg_stale_{}_reputation.json",
+            std::process::id()
+        )));
+
+        let mut averager = FederatedAverager::new(50, 300.0);
+        let fresh = epiphany(1.0, now, "fresh");
+        // Four half-lives old, same (default) reputation
+        let stale = epiphany(0.0, now - 1_200, "quiet");
+        assert_eq!(reputation.get_trust("fresh"), reputation.get_trust("quiet"));
+
+        let fresh_weight = averager.effective_weight_at(&fresh, &reputation, now);
+        let stale_weight = averager.effective_weight_at(&stale, &reputation, now);
+        assert!((fresh_weight / stale_weight - 16.0).abs() < 1e-3);
+
+        averager.buffer.push_back(fresh);
+        averager.buffer.push_back(stale);
+        let (_, confidence) = averager.aggregate_at(&reputation, None, now).unwrap();
+        // 16:1 weighting -> 16/17 of the fresh value
+        assert!(confidence.iter().all(|&c| (c - 16.0 / 17.0).abs() < 1e-4));
+    }
+
+    #[test]
+    fn test_federated_averager_fedprox_pulls_toward_anchor() {
+        let now = 1_700_000_000;
+        let epiphany = |confidence: f32, timestamp: u64, sender: &str| {
+            let mut brain = LivingBrain::new();
+            brain.confidence = vec![confidence; 4];
+            SignedEpiphany::new(
+                brain,
+                None,
+                String::new(),
+                sender.to_string(),
+                timestamp,
+                0,
+                false,
+            )
+        };
+        let reputation = ReputationManager::new(std::env::temp_dir().join(format!(
+            "qres_fedprox_{}_reputation.json",
+            std::process::id()
+        )));
+        let anchor = vec![0.2; 4];
+
+        let aggregate_with_mu = |mu: f32| {
+            let mut averager = FederatedAverager::new(50, 300.0).with_proximal_mu(mu);
+            averager.buffer.push_back(epiphany(1.0, now, "a"));
+            averager.buffer.push_back(epiphany(0.6, now, "b"));
+            let (_, confidence) = averager
+                .aggregate_at(&reputation, Some(&anchor), now)
+                .unwrap();
+            confidence
+        };
+
+        let plain = aggregate_with_mu(0.0);
+        let prox = aggregate_with_mu(0.25);
+        for ((&p, &x), &a) in plain.iter().zip(&prox).zip(&anchor) {
+            assert!((p - 0.8).abs() < 1e-5);
+            // w - mu * (w - anchor) = 0.8 - 0.25 * 0.6
+            assert!((x - 0.65).abs() < 1e-5);
+            assert!((x - a).abs() < (p - a).abs());
+        }
+    }
 }
@@ -1,8 +1,37 @@
+use libp2p::Multiaddr;
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::ops::RangeInclusive;
 use std::path::PathBuf;
 use tracing::warn;
 
+/// Accepted `aggregation.max_update_age_secs`: one minute to one day
+pub const MAX_UPDATE_AGE_SECS_RANGE: RangeInclusive<u64> = 60..=86400;
+
+/// A config value outside its documented range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    OutOfRange {
+        field: &'static str,
+        value: String,
+        range: String,
+    },
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::OutOfRange {
+                field,
+                value,
+                range,
+            } => write!(f, "{} = {} is outside {}", field, value, range),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
 /// Returns the ~/.qres directory, creating it if needed.
 /// Falls back to a local `.qres` directory if the home directory cannot be determined.
 pub fn qres_data_dir() -> PathBuf {
@@ -79,6 +108,36 @@ pub struct Config {
     pub api: ApiConfig,
     #[serde(default)]
     pub privacy: PrivacyConfig,
+    #[serde(default)]
+    pub audit: AuditConfig,
+    #[serde(default)]
+    pub convergence: ConvergenceConfig,
+}
+
+/// Transport the libp2p swarm listens and dials on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum SwarmTransport {
+    /// TCP with Noise + Yamux
+    #[default]
+    Tcp,
+    /// QUIC (built-in TLS 1.3 and stream multiplexing; UDP, NAT-friendly)
+    Quic,
+    /// WebSocket over TCP with Noise + Yamux (proxy/browser-friendly)
+    Ws,
+}
+
+/// Handling of peer updates that arrive while the local node is in Storm
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StormPolicy {
+    /// Verify and buffer as usual
+    #[default]
+    Normal,
+    /// Verify, then hold until the local regime returns to Calm
+    Defer,
+    /// Require a valid ZK proof, even from storm-mode or trusted senders
+    Scrutinize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -86,6 +145,48 @@ pub struct SwarmConfig {
     pub gossip_interval: u64,
     pub wan_mode: bool,
     pub max_peers: usize,
+    #[serde(default)]
+    pub transport: SwarmTransport,
+    #[serde(default)]
+    pub storm_policy: StormPolicy,
+    /// Incoming gossip messages admitted for verification at once; excess is shed
+    #[serde(default = "default_verify_queue_capacity")]
+    pub verify_queue_capacity: usize,
+    /// Verifications allowed to run concurrently
+    #[serde(default = "default_verify_concurrency")]
+    pub verify_concurrency: usize,
+    /// Explicit listen multiaddr (e.g. "/ip4/0.0.0.0/tcp/4001"); defaults to
+    /// an ephemeral port on the selected transport
+    #[serde(default)]
+    pub listen_addr: Option<String>,
+    /// Discover LAN peers via mDNS multicast
+    #[serde(default = "default_mdns")]
+    pub mdns: bool,
+    /// Peers dialed at startup, e.g. "/ip4/203.0.113.7/tcp/4001/p2p/12D3KooW...".
+    /// Addresses ending in `/p2p/<peer id>` also become explicit gossipsub
+    /// peers and are redialed when their connection closes.
+    #[serde(default)]
+    pub bootstrap_peers: Vec<Multiaddr>,
+    /// Largest gossip payload in bytes; bigger messages are rejected before
+    /// being re-propagated
+    #[serde(default = "default_max_message_size")]
+    pub max_message_size: usize,
+}
+
+fn default_max_message_size() -> usize {
+    65536
+}
+
+fn default_mdns() -> bool {
+    true
+}
+
+fn default_verify_queue_capacity() -> usize {
+    256
+}
+
+fn default_verify_concurrency() -> usize {
+    4
 }
 
 impl Default for SwarmConfig {
@@ -94,6 +195,14 @@ impl Default for SwarmConfig {
             gossip_interval: 600,
             wan_mode: false,
             max_peers: 50,
+            transport: SwarmTransport::Tcp,
+            storm_policy: StormPolicy::Normal,
+            verify_queue_capacity: default_verify_queue_capacity(),
+            verify_concurrency: default_verify_concurrency(),
+            listen_addr: None,
+            mdns: default_mdns(),
+            bootstrap_peers: Vec::new(),
+            max_message_size: default_max_message_size(),
         }
     }
 }
@@ -112,6 +221,31 @@ pub struct SecurityConfig {
     /// List of trusted public keys in hex format (32-byte ed25519)
     #[serde(default)]
     pub trusted_pubkeys: Vec<String>,
+    /// Shared swarm secret; when set, gossip payloads are encrypted with a key derived from it
+    #[serde(default)]
+    pub swarm_secret: Option<String>,
+    /// EWMA weight of the latest reward/punishment in peer trust (0.0 = cumulative scoring)
+    #[serde(default)]
+    pub trust_decay: f32,
+    /// Seconds a signed update's timestamp may lag local time before it is
+    /// rejected as expired; the future bound is this capped at 60 s
+    #[serde(default = "default_max_clock_skew_secs")]
+    pub max_clock_skew_secs: u64,
+    /// Key this node signed with before its last rotation; while set,
+    /// updates carry a certificate endorsing the current key
+    #[serde(default)]
+    pub previous_key_path: Option<String>,
+    /// Seconds a peer's rotated-out key is still accepted
+    #[serde(default = "default_key_rotation_grace_secs")]
+    pub key_rotation_grace_secs: u64,
+}
+
+fn default_key_rotation_grace_secs() -> u64 {
+    86400
+}
+
+fn default_max_clock_skew_secs() -> u64 {
+    crate::security::DEFAULT_MAX_CLOCK_SKEW_SECS
 }
 
 impl Default for SecurityConfig {
@@ -123,6 +257,11 @@ impl Default for SecurityConfig {
             key_path: None,
             trusted_peers: Vec::new(),
             trusted_pubkeys: Vec::new(),
+            swarm_secret: None,
+            trust_decay: 0.0,
+            max_clock_skew_secs: default_max_clock_skew_secs(),
+            previous_key_path: None,
+            key_rotation_grace_secs: default_key_rotation_grace_secs(),
         }
     }
 }
@@ -142,6 +281,62 @@ impl Default for ApiConfig {
     }
 }
 
+/// Convergence-triggered switch to inference-only mode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvergenceConfig {
+    /// Global error rate below which the swarm counts as converged
+    #[serde(default = "default_target_error")]
+    pub target_error: f32,
+    /// Consecutive federation rounds below target before training stops
+    #[serde(default = "default_patience_rounds")]
+    pub patience_rounds: u32,
+}
+
+fn default_target_error() -> f32 {
+    0.01
+}
+
+fn default_patience_rounds() -> u32 {
+    5
+}
+
+impl Default for ConvergenceConfig {
+    fn default() -> Self {
+        Self {
+            target_error: default_target_error(),
+            patience_rounds: default_patience_rounds(),
+        }
+    }
+}
+
+/// Retention for the in-memory audit history and rejection logs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditConfig {
+    /// Maximum verification records kept in memory
+    #[serde(default = "default_audit_capacity")]
+    pub history_capacity: usize,
+    /// Maximum rejection records kept in memory
+    #[serde(default = "default_audit_capacity")]
+    pub rejection_capacity: usize,
+    /// Append records to JSON-lines files in the data dir before evicting them
+    #[serde(default)]
+    pub flush_to_disk: bool,
+}
+
+fn default_audit_capacity() -> usize {
+    1024
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            history_capacity: default_audit_capacity(),
+            rejection_capacity: default_audit_capacity(),
+            flush_to_disk: false,
+        }
+    }
+}
+
 /// Aggregation settings for robust federated averaging
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AggregationConfig {
@@ -157,12 +352,35 @@ pub struct AggregationConfig {
     /// Trim fraction for trimmed mean (e.g., 0.2 = trim 10% from each side)
     #[serde(default)]
     pub trim_fraction: f32,
+    /// Strategy for federated weight averaging: "weighted_mean" (reputation
+    /// and freshness weighted, the default), "fedavg", "krum", "multi_krum",
+    /// "trimmed_mean", "trimmed_mean_byz", "weighted_trimmed_mean",
+    /// "weighted_median"
+    #[serde(default = "default_federated_strategy")]
+    pub federated_strategy: String,
+    /// Updates averaged by the "multi_krum" strategy; unset = n - f for
+    /// each round's n updates
+    #[serde(default)]
+    pub multi_krum_k: Option<usize>,
+    /// FedProx proximal coefficient in `[0, 1]`: how strongly the federated
+    /// result is pulled back toward the brain's `global_confidence` anchor
+    /// (0.0 = plain averaging)
+    #[serde(default)]
+    pub fedprox_mu: f32,
+    /// Buffered updates older than this many seconds are evicted before
+    /// federated averaging
+    #[serde(default = "default_max_update_age_secs")]
+    pub max_update_age_secs: u64,
 }
 
 fn default_agg_mode() -> String {
     "mean".to_string()
 }
 
+fn default_federated_strategy() -> String {
+    "weighted_mean".to_string()
+}
+
 fn default_expected_byz() -> f32 {
     0.2
 }
@@ -171,6 +389,10 @@ fn default_buffer_size() -> usize {
     5
 }
 
+fn default_max_update_age_secs() -> u64 {
+    3600
+}
+
 impl Default for AggregationConfig {
     fn default() -> Self {
         Self {
@@ -178,6 +400,10 @@ impl Default for AggregationConfig {
             expected_byzantines_fraction: default_expected_byz(),
             buffer_size: default_buffer_size(),
             trim_fraction: 0.2,
+            federated_strategy: default_federated_strategy(),
+            multi_krum_k: None,
+            fedprox_mu: 0.0,
+            max_update_age_secs: default_max_update_age_secs(),
         }
     }
 }
@@ -201,9 +427,27 @@ impl Config {
 
         let content = fs::read_to_string(path)?;
         let config: Config = toml::from_str(&content)?;
+        config.validate()?;
         Ok(config)
     }
 
+    /// Check values whose documented range serde cannot express.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let age = self.aggregation.max_update_age_secs;
+        if !MAX_UPDATE_AGE_SECS_RANGE.contains(&age) {
+            return Err(ConfigError::OutOfRange {
+                field: "aggregation.max_update_age_secs",
+                value: age.to_string(),
+                range: format!(
+                    "{}..={}",
+                    MAX_UPDATE_AGE_SECS_RANGE.start(),
+                    MAX_UPDATE_AGE_SECS_RANGE.end()
+                ),
+            });
+        }
+        Ok(())
+    }
+
     pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
         let path = Self::get_config_path();
         let toml = toml::to_string_pretty(self)?;
@@ -211,3 +455,40 @@ impl Config {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_convergence_section_keeps_defaults() {
+        let config: Config = toml::from_str("[convergence]\npatience_rounds = 9\n").unwrap();
+        assert_eq!(config.convergence.patience_rounds, 9);
+        assert_eq!(config.convergence.target_error, 0.01);
+
+        let config: Config = toml::from_str("[convergence]\ntarget_error = 0.05\n").unwrap();
+        assert_eq!(config.convergence.target_error, 0.05);
+        assert_eq!(config.convergence.patience_rounds, 5);
+    }
+
+    #[test]
+    fn test_max_update_age_is_range_checked() {
+        assert!(Config::default().validate().is_ok());
+        for (age, ok) in [
+            (59, false),
+            (60, true),
+            (86400, true),
+            (86401, false),
+            (0, false),
+        ] {
+            let config: Config =
+                toml::from_str(&format!("[aggregation]\nmax_update_age_secs = {}\n", age)).unwrap();
+            assert_eq!(
+                config.validate().is_ok(),
+                ok,
+                "max_update_age_secs = {}",
+                age
+            );
+        }
+    }
+}
@@ -2,10 +2,19 @@
 // Idle-time hallucinatory training for privacy-preserving learning
 
 use rand::Rng;
+use std::fs;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 use std::collections::VecDeque;
 
+/// Replay entries kept by a [`DreamBuffer`] (oldest evicted first)
+pub const DREAM_BUFFER_CAPACITY: usize = 256;
+/// Leading bytes of an exported replay delta
+const DREAM_EXPORT_MAGIC: &[u8; 4] = b"QDRM";
+/// Bytes of framing in front of each entry's sample: round (8) + length (4)
+const DREAM_ENTRY_HEADER_LEN: usize = 12;
+
 /// Federated Dreaming manager.
 /// Generates synthetic training data during idle periods
 /// to reinforce patterns without accessing original data.
@@ -17,6 +26,8 @@ pub struct DreamingManager {
     validation_buffer: VecDeque<Vec<u8>>,
     /// Max validation buffer size
     max_validation_samples: usize,
+    /// Dream samples tagged with their federation round, served to waking peers
+    replay: DreamBuffer,
 }
 
 impl DreamingManager {
@@ -29,9 +40,16 @@ impl DreamingManager {
             dream_count: 0,
             validation_buffer: VecDeque::with_capacity(100),
             max_validation_samples: 100,
+            replay: DreamBuffer::default(),
         }
     }
 
+    /// Start from a previously persisted replay buffer (e.g. after waking).
+    pub fn with_replay(mut self, replay: DreamBuffer) -> Self {
+        self.replay = replay;
+        self
+    }
+
     /// Record activity to reset idle timer.
     pub fn record_activity(&mut self) {
         self.last_activity = Instant::now();
@@ -50,20 +68,28 @@ impl DreamingManager {
         self.last_activity.elapsed() >= self.idle_threshold
     }
 
-    /// Generate a synthetic training sample based on learned patterns.
+    /// Generate a synthetic training sample based on learned patterns and
+    /// record it in the replay buffer under federation `round`.
     /// This is a placeholder - real implementation would use
     /// the generative models (SNN/QNN) to produce realistic data.
-    pub fn generate_dream_sample(&mut self, pattern_mean: u8) -> Vec<u8> {
+    pub fn generate_dream_sample(&mut self, round: u64, pattern_mean: u8) -> Vec<u8> {
         self.dream_count += 1;
         let mut rng = rand::thread_rng();
 
         // Generate 1KB of synthetic data around the pattern mean
-        (0..1024)
+        let sample: Vec<u8> = (0..1024)
             .map(|_| {
                 let noise: i16 = rng.gen_range(-20..20);
                 (pattern_mean as i16 + noise).clamp(0, 255) as u8
             })
-            .collect()
+            .collect();
+        self.replay.record(round, sample.clone());
+        sample
+    }
+
+    /// Replay buffer of dream samples recorded so far
+    pub fn replay(&self) -> &DreamBuffer {
+        &self.replay
     }
 
     /// Validate dreamt weights against real data buffer.
@@ -102,6 +128,166 @@ impl DreamingManager {
     }
 }
 
+/// One replayed sample, tagged with the gossip round it was recorded in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DreamEntry {
+    pub round: u64,
+    pub sample: Vec<u8>,
+}
+
+/// Bounded replay buffer of dream samples, exportable as a delta.
+///
+/// A node waking from TWT deep sleep requests the entries recorded since the
+/// last round it saw (`/api/dream-since/:round`) and imports them, catching
+/// up on consolidation it slept through. Complements the SummaryGene
+/// onboarding, which only carries the consensus weights.
+///
+/// Export layout (big-endian, like the SummaryGene):
+///
+/// ```text
+/// [magic: QDRM][count: u32]{ [round: u64][len: u32][sample ...] }*
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DreamBuffer {
+    entries: VecDeque<DreamEntry>,
+    capacity: usize,
+}
+
+impl Default for DreamBuffer {
+    fn default() -> Self {
+        Self::new(DREAM_BUFFER_CAPACITY)
+    }
+}
+
+impl DreamBuffer {
+    /// Create an empty buffer holding at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity.min(DREAM_BUFFER_CAPACITY)),
+            capacity,
+        }
+    }
+
+    fn get_buffer_path() -> PathBuf {
+        let mut path = crate::config::qres_data_dir();
+        path.push("dream_buffer.bin");
+        path
+    }
+
+    /// Load the persisted buffer, or an empty one if missing or unreadable.
+    pub fn load() -> Self {
+        let mut buffer = Self::default();
+        if let Ok(bytes) = fs::read(Self::get_buffer_path()) {
+            buffer.import(&bytes);
+        }
+        buffer
+    }
+
+    /// Persist the whole buffer (as an export from round 0), atomically.
+    pub fn save(&self) -> std::io::Result<()> {
+        crate::atomic_file::write_file_atomic(&Self::get_buffer_path(), &self.export_all())
+    }
+
+    /// Record a sample replayed in `round`, evicting the oldest when full.
+    pub fn record(&mut self, round: u64, sample: Vec<u8>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(DreamEntry { round, sample });
+    }
+
+    /// Entries in recording order
+    pub fn entries(&self) -> impl Iterator<Item = &DreamEntry> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Latest round held, if any
+    pub fn latest_round(&self) -> Option<u64> {
+        self.entries.back().map(|e| e.round)
+    }
+
+    fn export_all(&self) -> Vec<u8> {
+        Self::encode(self.entries.iter())
+    }
+
+    /// Serialize the entries recorded after `round` (exclusive).
+    pub fn export_since(&self, round: u64) -> Vec<u8> {
+        Self::encode(self.entries.iter().filter(|e| e.round > round))
+    }
+
+    fn encode<'a>(entries: impl Iterator<Item = &'a DreamEntry> + Clone) -> Vec<u8> {
+        let count = entries.clone().count();
+        let len: usize = entries
+            .clone()
+            .map(|e| DREAM_ENTRY_HEADER_LEN + e.sample.len())
+            .sum();
+        let mut bytes = Vec::with_capacity(8 + len);
+        bytes.extend_from_slice(DREAM_EXPORT_MAGIC);
+        bytes.extend_from_slice(&(count as u32).to_be_bytes());
+        for entry in entries {
+            bytes.extend_from_slice(&entry.round.to_be_bytes());
+            bytes.extend_from_slice(&(entry.sample.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(&entry.sample);
+        }
+        bytes
+    }
+
+    /// Decode an export into its entries. Returns `None` if it is malformed.
+    fn decode(bytes: &[u8]) -> Option<Vec<DreamEntry>> {
+        let body = bytes.strip_prefix(DREAM_EXPORT_MAGIC)?;
+        let count = u32::from_be_bytes(body.get(..4)?.try_into().ok()?) as usize;
+        let mut rest = &body[4..];
+        // Every entry carries a header, which bounds a plausible count
+        if count > rest.len() / DREAM_ENTRY_HEADER_LEN {
+            return None;
+        }
+        let mut entries = Vec::with_capacity(count);
+        for _ in 0..count {
+            let header = rest.get(..DREAM_ENTRY_HEADER_LEN)?;
+            let round = u64::from_be_bytes(header[..8].try_into().ok()?);
+            let len = u32::from_be_bytes(header[8..].try_into().ok()?) as usize;
+            let sample = rest.get(DREAM_ENTRY_HEADER_LEN..DREAM_ENTRY_HEADER_LEN + len)?;
+            entries.push(DreamEntry {
+                round,
+                sample: sample.to_vec(),
+            });
+            rest = &rest[DREAM_ENTRY_HEADER_LEN + len..];
+        }
+        rest.is_empty().then_some(entries)
+    }
+
+    /// Merge an export produced by [`export_since`](Self::export_since).
+    ///
+    /// Entries not newer than the latest round held before the import are
+    /// skipped, so importing the same delta twice is harmless; a delta may
+    /// carry several entries for one round. Returns the number of entries
+    /// added, or `None` (leaving the buffer unchanged) if `bytes` is malformed.
+    pub fn import(&mut self, bytes: &[u8]) -> Option<usize> {
+        let entries = Self::decode(bytes)?;
+        let latest = self.latest_round();
+        let mut imported = 0;
+        for entry in entries {
+            if latest.is_some_and(|latest| entry.round <= latest) {
+                continue;
+            }
+            self.record(entry.round, entry.sample);
+            imported += 1;
+        }
+        Some(imported)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,8 +301,67 @@ mod tests {
     #[test]
     fn test_dream_sample_generation() {
         let mut mgr = DreamingManager::new(60);
-        let sample = mgr.generate_dream_sample(128);
+        let sample = mgr.generate_dream_sample(4, 128);
         assert_eq!(sample.len(), 1024);
         assert_eq!(mgr.dream_count(), 1);
+        // The sample is kept for replay under its round
+        let recorded: Vec<&DreamEntry> = mgr.replay().entries().collect();
+        assert_eq!(recorded, vec![&DreamEntry { round: 4, sample }]);
+    }
+    #[test]
+    fn test_export_since_round_reconstructs_later_entries() {
+        let mut source = DreamBuffer::default();
+        for round in 1..=10u64 {
+            source.record(round, vec![round as u8; round as usize * 3]);
+        }
+
+        // A node that slept after round 6 catches up from the delta
+        let mut woken = DreamBuffer::default();
+        woken.record(6, vec![6; 18]);
+        let delta = source.export_since(6);
+        assert_eq!(woken.import(&delta), Some(4));
+        let expected: Vec<&DreamEntry> = source.entries().filter(|e| e.round > 6).collect();
+        let caught_up: Vec<&DreamEntry> = woken.entries().skip(1).collect();
+        assert_eq!(caught_up, expected);
+
+        // Re-importing the same delta adds nothing
+        assert_eq!(woken.import(&delta), Some(0));
+        assert_eq!(woken.len(), 5);
+
+        // A full export into an empty buffer reproduces the source
+        let mut fresh = DreamBuffer::default();
+        assert_eq!(fresh.import(&source.export_since(0)), Some(10));
+        assert_eq!(fresh, source);
+    }
+
+    #[test]
+    fn test_import_keeps_every_entry_of_a_round() {
+        let mut source = DreamBuffer::default();
+        for round in [3u64, 4, 4, 4, 5, 5] {
+            source.record(round, vec![round as u8, source.len() as u8]);
+        }
+
+        let mut woken = DreamBuffer::default();
+        woken.record(3, vec![3, 0]);
+        assert_eq!(woken.import(&source.export_since(3)), Some(5));
+        assert_eq!(woken, source);
+        assert_eq!(woken.import(&source.export_since(3)), Some(0));
+    }
+
+    #[test]
+    fn test_import_rejects_malformed_export() {
+        let mut source = DreamBuffer::new(4);
+        for round in 1..=6u64 {
+            source.record(round, vec![0xAB; 8]);
+        }
+        assert_eq!(source.latest_round(), Some(6));
+        assert_eq!(source.len(), 4);
+
+        let export = source.export_since(0);
+        let mut target = DreamBuffer::default();
+        assert_eq!(target.import(&export[..export.len() - 1]), None);
+        assert_eq!(target.import(b"QDRM\xff\xff\xff\xff"), None);
+        assert_eq!(target.import(b"nope"), None);
+        assert!(target.is_empty());
     }
 }
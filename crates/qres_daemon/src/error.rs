@@ -0,0 +1,118 @@
+//! Typed errors for the file compression and brain commands
+//!
+//! `compress_file`/`decompress_file` used to flatten every failure into
+//! `io::Error::other`, so a script could not tell a corrupt stream from a
+//! missing file. [`DaemonError`] keeps the category and maps each one to a
+//! distinct process exit code (BSD `sysexits.h` values).
+
+use crate::living_brain::BrainError;
+use qres_core::QresError;
+use std::fmt;
+use std::io;
+
+/// Reading or writing a file failed (`EX_IOERR`)
+pub const EXIT_IO: i32 = 74;
+/// The compressed input is truncated or malformed (`EX_DATAERR`)
+pub const EXIT_CORRUPT: i32 = 65;
+/// The stream was written by an incompatible format version (`EX_PROTOCOL`)
+pub const EXIT_UNSUPPORTED: i32 = 76;
+/// The brain does not match the one used to compress, or two brains cannot
+/// be combined (`EX_CONFIG`)
+pub const EXIT_CONFIG: i32 = 78;
+/// The codec itself failed (`EX_SOFTWARE`)
+pub const EXIT_CODEC: i32 = 70;
+
+/// Failure of a file compression, decompression, verification, or brain command.
+#[derive(Debug)]
+pub enum DaemonError {
+    /// Reading or writing a file failed
+    Io(io::Error),
+    /// The core codec could not encode a chunk
+    Codec(QresError),
+    /// The Zstd or LZ4 fallback could not encode a chunk
+    Fallback(io::Error),
+    /// The compressed stream is truncated or malformed, or a chunk in it
+    /// fails to decode
+    CorruptStream(String),
+    /// The file header carries a version this build cannot read
+    UnsupportedVersion(u8),
+    /// The brain differs from the one used for compression
+    BrainMismatch,
+    /// Two brains have incompatible shapes and cannot be merged or compared
+    IncompatibleBrain(BrainError),
+    /// Decoding succeeded but did not reproduce the input
+    RoundTripMismatch,
+}
+
+impl DaemonError {
+    /// Process exit code for this failure.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            DaemonError::Io(_) => EXIT_IO,
+            DaemonError::CorruptStream(_) | DaemonError::RoundTripMismatch => EXIT_CORRUPT,
+            DaemonError::UnsupportedVersion(_) => EXIT_UNSUPPORTED,
+            DaemonError::BrainMismatch | DaemonError::IncompatibleBrain(_) => EXIT_CONFIG,
+            DaemonError::Codec(_) | DaemonError::Fallback(_) => EXIT_CODEC,
+        }
+    }
+
+    /// Classify an error from reading the compressed stream: running out of
+    /// input means the stream was cut short, not that IO failed.
+    pub fn from_stream_read(err: io::Error, what: &str) -> Self {
+        if err.kind() == io::ErrorKind::UnexpectedEof {
+            DaemonError::CorruptStream(format!("truncated {}", what))
+        } else {
+            DaemonError::Io(err)
+        }
+    }
+}
+
+impl fmt::Display for DaemonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DaemonError::Io(e) => write!(f, "IO error: {}", e),
+            DaemonError::Codec(e) => write!(f, "Codec error: {}", e),
+            DaemonError::Fallback(e) => write!(f, "Fallback codec failed: {}", e),
+            DaemonError::CorruptStream(reason) => write!(f, "Corrupt stream: {}", reason),
+            DaemonError::UnsupportedVersion(v) => {
+                write!(f, "Unsupported file header version: {}", v)
+            }
+            DaemonError::BrainMismatch => write!(
+                f,
+                "brain mismatch: differs from the brain used for compression"
+            ),
+            DaemonError::IncompatibleBrain(e) => write!(f, "Incompatible brain: {}", e),
+            DaemonError::RoundTripMismatch => {
+                write!(f, "Round trip does not reproduce the input")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DaemonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DaemonError::Io(e) | DaemonError::Fallback(e) => Some(e),
+            DaemonError::IncompatibleBrain(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DaemonError {
+    fn from(err: io::Error) -> Self {
+        DaemonError::Io(err)
+    }
+}
+
+impl From<BrainError> for DaemonError {
+    fn from(err: BrainError) -> Self {
+        DaemonError::IncompatibleBrain(err)
+    }
+}
+
+impl From<QresError> for DaemonError {
+    fn from(err: QresError) -> Self {
+        DaemonError::Codec(err)
+    }
+}
@@ -0,0 +1,379 @@
+//! MTU Fragmentation for Gossip Payloads
+//!
+//! Serialized epiphanies can outgrow a single radio frame once the brain
+//! carries a few hundred weights. Mirroring the simulator's `MTU_LIMIT`, a
+//! payload larger than [`FRAGMENT_MTU`] is split into framed fragments that
+//! each fit one frame, published individually, and stitched back together by
+//! a [`Reassembler`] on the receiving node before signature verification.
+//! Sets are tracked per sending peer, so one peer cannot claim or evict the
+//! slot of another peer's payload.
+//! Payloads that already fit are sent unframed, so small epiphanies look
+//! exactly as they did before.
+//!
+//! Wire layout of a fragment (big-endian):
+//!
+//! ```text
+//! [magic: u8][msg_id: u64][frag_index: u16][frag_count: u16][chunk ...]
+//! ```
+
+use libp2p::PeerId;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+/// Maximum size of one gossip frame, header included (matches the sim)
+pub const FRAGMENT_MTU: usize = 1400;
+/// Bytes of framing in front of every fragment's chunk
+pub const FRAGMENT_HEADER_LEN: usize = 13;
+/// Incomplete fragment sets are dropped after this long
+pub const REASSEMBLY_TIMEOUT: Duration = Duration::from_secs(30);
+/// Leading byte marking a fragment; wire-encoded epiphanies start with `Q`
+const FRAGMENT_MAGIC: u8 = 0xF7;
+/// Cap on concurrently reassembling messages (oldest is evicted first)
+const MAX_PENDING_MESSAGES: usize = 64;
+/// Cap on concurrently reassembling messages from a single peer
+const MAX_PENDING_PER_SOURCE: usize = 4;
+
+/// A payload that cannot be split into fragments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FragmentError {
+    /// More fragments would be needed than the `u16` count can describe
+    TooManyFragments { needed: usize },
+}
+
+impl fmt::Display for FragmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FragmentError::TooManyFragments { needed } => write!(
+                f,
+                "Payload needs {} fragments, more than {}",
+                needed,
+                u16::MAX
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FragmentError {}
+
+/// One framed piece of a larger payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fragment {
+    /// Identifier shared by every fragment of the same payload
+    pub msg_id: u64,
+    /// Position of this fragment within the payload
+    pub frag_index: u16,
+    /// Total number of fragments in the payload
+    pub frag_count: u16,
+    /// This fragment's slice of the payload
+    pub chunk: Vec<u8>,
+}
+
+impl Fragment {
+    /// Encode the fragment with its reassembly header.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(FRAGMENT_HEADER_LEN + self.chunk.len());
+        out.push(FRAGMENT_MAGIC);
+        out.extend_from_slice(&self.msg_id.to_be_bytes());
+        out.extend_from_slice(&self.frag_index.to_be_bytes());
+        out.extend_from_slice(&self.frag_count.to_be_bytes());
+        out.extend_from_slice(&self.chunk);
+        out
+    }
+
+    /// Decode a framed fragment. Returns `None` for unframed payloads and
+    /// for headers that cannot describe a valid set.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() <= FRAGMENT_HEADER_LEN || data[0] != FRAGMENT_MAGIC {
+            return None;
+        }
+        let msg_id = u64::from_be_bytes(data[1..9].try_into().ok()?);
+        let frag_index = u16::from_be_bytes(data[9..11].try_into().ok()?);
+        let frag_count = u16::from_be_bytes(data[11..13].try_into().ok()?);
+        if frag_count < 2 || frag_index >= frag_count {
+            return None;
+        }
+        Some(Self {
+            msg_id,
+            frag_index,
+            frag_count,
+            chunk: data[FRAGMENT_HEADER_LEN..].to_vec(),
+        })
+    }
+}
+
+/// Split `payload` into wire messages of at most `mtu` bytes each.
+///
+/// A payload that already fits is returned unframed as the only element.
+pub fn fragment(payload: &[u8], mtu: usize) -> Result<Vec<Vec<u8>>, FragmentError> {
+    if payload.len() <= mtu {
+        return Ok(vec![payload.to_vec()]);
+    }
+    let chunk_size = mtu.saturating_sub(FRAGMENT_HEADER_LEN).max(1);
+    let chunks: Vec<&[u8]> = payload.chunks(chunk_size).collect();
+    let frag_count = u16::try_from(chunks.len()).map_err(|_| FragmentError::TooManyFragments {
+        needed: chunks.len(),
+    })?;
+
+    let mut hasher = DefaultHasher::new();
+    payload.hash(&mut hasher);
+    let msg_id = hasher.finish();
+
+    Ok(chunks
+        .into_iter()
+        .enumerate()
+        .map(|(i, chunk)| {
+            Fragment {
+                msg_id,
+                frag_index: i as u16,
+                frag_count,
+                chunk: chunk.to_vec(),
+            }
+            .to_bytes()
+        })
+        .collect())
+}
+
+/// Fragments collected so far for one payload.
+#[derive(Debug)]
+struct PartialPayload {
+    chunks: Vec<Option<Vec<u8>>>,
+    received: usize,
+    bytes: usize,
+    first_seen: Instant,
+}
+
+/// Collects fragments until each payload is complete.
+///
+/// Sets are keyed by `(sending peer, msg_id)`: `msg_id` is a predictable hash
+/// of the payload, so keying by it alone would let any peer pre-claim the
+/// slot of an honest payload.
+#[derive(Debug)]
+pub struct Reassembler {
+    pending: HashMap<(PeerId, u64), PartialPayload>,
+    timeout: Duration,
+    max_payload_size: usize,
+}
+
+impl Reassembler {
+    /// Create a reassembler that refuses payloads above `max_payload_size`.
+    pub fn new(timeout: Duration, max_payload_size: usize) -> Self {
+        Self {
+            pending: HashMap::new(),
+            timeout,
+            max_payload_size,
+        }
+    }
+
+    /// Drop the oldest pending set among those `matches` selects.
+    fn evict_oldest(&mut self, matches: impl Fn(&(PeerId, u64)) -> bool) {
+        let oldest = self
+            .pending
+            .iter()
+            .filter(|(key, _)| matches(key))
+            .min_by_key(|(_, p)| p.first_seen)
+            .map(|(key, _)| *key);
+        if let Some(key) = oldest {
+            self.pending.remove(&key);
+        }
+    }
+
+    /// Add a fragment sent by `source`, received at `now`.
+    ///
+    /// Returns the full payload once its last missing fragment arrives.
+    /// Fragments that disagree with their set's count, or that would push
+    /// the payload past the size limit, discard the whole set. A peer with
+    /// too many incomplete sets loses its own oldest one.
+    pub fn push(&mut self, source: PeerId, fragment: Fragment, now: Instant) -> Option<Vec<u8>> {
+        let count = fragment.frag_count as usize;
+        let key = (source, fragment.msg_id);
+        if !self.pending.contains_key(&key) {
+            let from_source = self.pending.keys().filter(|(s, _)| *s == source).count();
+            if from_source >= MAX_PENDING_PER_SOURCE {
+                self.evict_oldest(|(s, _)| *s == source);
+            } else if self.pending.len() >= MAX_PENDING_MESSAGES {
+                self.evict_oldest(|_| true);
+            }
+        }
+
+        let partial = self.pending.entry(key).or_insert_with(|| PartialPayload {
+            chunks: vec![None; count],
+            received: 0,
+            bytes: 0,
+            first_seen: now,
+        });
+        if partial.chunks.len() != count {
+            self.pending.remove(&key);
+            return None;
+        }
+
+        let slot = &mut partial.chunks[fragment.frag_index as usize];
+        if slot.is_some() {
+            return None;
+        }
+        partial.bytes += fragment.chunk.len();
+        if partial.bytes > self.max_payload_size {
+            self.pending.remove(&key);
+            return None;
+        }
+        *slot = Some(fragment.chunk);
+        partial.received += 1;
+        if partial.received < count {
+            return None;
+        }
+
+        let partial = self.pending.remove(&key)?;
+        Some(partial.chunks.into_iter().flatten().flatten().collect())
+    }
+
+    /// Drop incomplete sets older than the timeout; returns how many.
+    pub fn evict_expired(&mut self, now: Instant) -> usize {
+        let before = self.pending.len();
+        let timeout = self.timeout;
+        self.pending
+            .retain(|_, p| now.saturating_duration_since(p.first_seen) < timeout);
+        before - self.pending.len()
+    }
+
+    /// Number of payloads still waiting for fragments
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 31 % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_large_payload_round_trip() {
+        let data = payload(10_000);
+        let frames = fragment(&data, FRAGMENT_MTU).unwrap();
+        assert_eq!(frames.len(), 8);
+        assert!(frames.iter().all(|f| f.len() <= FRAGMENT_MTU));
+
+        let mut reassembler = Reassembler::new(REASSEMBLY_TIMEOUT, 65536);
+        let now = Instant::now();
+        let source = PeerId::random();
+        let mut out = None;
+        // Deliver out of order: gossip gives no ordering guarantee
+        for frame in frames.iter().rev() {
+            assert!(out.is_none());
+            out = reassembler.push(source, Fragment::parse(frame).unwrap(), now);
+        }
+        assert_eq!(out.unwrap(), data);
+        assert_eq!(reassembler.pending(), 0);
+    }
+
+    #[test]
+    fn test_small_payload_is_unframed() {
+        let data = br#"{"epiphany":1}"#.to_vec();
+        let frames = fragment(&data, FRAGMENT_MTU).unwrap();
+        assert_eq!(frames, vec![data.clone()]);
+        assert!(Fragment::parse(&frames[0]).is_none());
+    }
+
+    #[test]
+    fn test_incomplete_set_expires() {
+        let frames = fragment(&payload(5_000), FRAGMENT_MTU).unwrap();
+        let mut reassembler = Reassembler::new(REASSEMBLY_TIMEOUT, 65536);
+        let start = Instant::now();
+        let source = PeerId::random();
+        for frame in &frames[1..] {
+            assert!(reassembler
+                .push(source, Fragment::parse(frame).unwrap(), start)
+                .is_none());
+        }
+        assert_eq!(reassembler.evict_expired(start + Duration::from_secs(1)), 0);
+        assert_eq!(reassembler.evict_expired(start + REASSEMBLY_TIMEOUT), 1);
+
+        // The late first fragment starts a fresh, incomplete set
+        let late = Fragment::parse(&frames[0]).unwrap();
+        assert!(reassembler
+            .push(source, late, start + REASSEMBLY_TIMEOUT)
+            .is_none());
+        assert_eq!(reassembler.pending(), 1);
+    }
+
+    #[test]
+    fn test_oversized_set_is_dropped() {
+        let frames = fragment(&payload(5_000), FRAGMENT_MTU).unwrap();
+        let mut reassembler = Reassembler::new(REASSEMBLY_TIMEOUT, 2_000);
+        let now = Instant::now();
+        let source = PeerId::random();
+        for frame in &frames {
+            assert!(reassembler
+                .push(source, Fragment::parse(frame).unwrap(), now)
+                .is_none());
+        }
+    }
+
+    #[test]
+    fn test_poisoned_slot_does_not_block_honest_sender() {
+        let data = payload(5_000);
+        let frames = fragment(&data, FRAGMENT_MTU).unwrap();
+        let mut reassembler = Reassembler::new(REASSEMBLY_TIMEOUT, 65536);
+        let now = Instant::now();
+
+        // An attacker races a bogus first chunk under the same msg_id
+        let mut bogus = Fragment::parse(&frames[0]).unwrap();
+        bogus.chunk = vec![0; bogus.chunk.len()];
+        let attacker = PeerId::random();
+        assert!(reassembler.push(attacker, bogus, now).is_none());
+
+        let honest = PeerId::random();
+        let out = frames
+            .iter()
+            .find_map(|f| reassembler.push(honest, Fragment::parse(f).unwrap(), now));
+        assert_eq!(out.unwrap(), data);
+    }
+
+    #[test]
+    fn test_flooding_peer_only_evicts_its_own_sets() {
+        let mut reassembler = Reassembler::new(REASSEMBLY_TIMEOUT, 65536);
+        let start = Instant::now();
+        let honest = PeerId::random();
+        let data = payload(5_000);
+        let frames = fragment(&data, FRAGMENT_MTU).unwrap();
+        assert!(reassembler
+            .push(honest, Fragment::parse(&frames[0]).unwrap(), start)
+            .is_none());
+
+        let attacker = PeerId::random();
+        for i in 0..(MAX_PENDING_MESSAGES as u64 * 2) {
+            let bogus = Fragment {
+                msg_id: i,
+                frag_index: 0,
+                frag_count: 2,
+                chunk: vec![1],
+            };
+            reassembler.push(attacker, bogus, start + Duration::from_millis(i + 1));
+        }
+        assert_eq!(reassembler.pending(), 1 + MAX_PENDING_PER_SOURCE);
+
+        let out = frames[1..]
+            .iter()
+            .find_map(|f| reassembler.push(honest, Fragment::parse(f).unwrap(), start));
+        assert_eq!(out.unwrap(), data);
+    }
+
+    #[test]
+    fn test_oversized_payload_is_an_error() {
+        // A 1-byte chunk per fragment makes u16::MAX + 1 fragments
+        let mtu = FRAGMENT_HEADER_LEN + 1;
+        let data = vec![0u8; u16::MAX as usize + 1];
+        assert_eq!(
+            fragment(&data, mtu),
+            Err(FragmentError::TooManyFragments {
+                needed: u16::MAX as usize + 1
+            })
+        );
+        assert_eq!(fragment(&data[1..], mtu).unwrap().len(), u16::MAX as usize);
+    }
+}
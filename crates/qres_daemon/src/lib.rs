@@ -0,0 +1,24 @@
+pub mod analytics;
+pub mod api;
+pub mod atomic_file;
+pub mod audit_log;
+pub mod bootstrap;
+pub mod brain_aggregator;
+pub mod config;
+pub mod daemon;
+pub mod dreaming;
+pub mod error;
+pub mod fragmentation;
+pub mod living_brain;
+pub mod lz4;
+pub mod peer_keys;
+pub mod pipeline;
+pub mod qes;
+pub mod reciprocity;
+pub mod security;
+pub mod stats;
+pub mod swarm;
+pub mod swarm_p2p;
+pub mod transform;
+pub mod verification_queue;
+pub mod wire;
@@ -1,3 +1,4 @@
+use crate::security::KeyRotation;
 use qres_core::mixer::NUM_MODELS;
 use qres_core::zk_proofs::ProofBundle;
 use serde::{Deserialize, Serialize};
@@ -42,6 +43,9 @@ pub struct SignedEpiphany {
     pub timestamp: u64,      // Replay protection
     pub nonce: u64,          // Replay protection
     pub is_storm_mode: bool, // True if weights are I8F8 quantized
+    /// Endorsement of the signing key by the sender's previous key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_rotation: Option<KeyRotation>,
 }
 
 /// Type alias for forward compatibility with v21.0 terminology migration.
@@ -73,9 +77,16 @@ impl SignedEpiphany {
             timestamp,
             nonce,
             is_storm_mode,
+            key_rotation: None,
         }
     }
 
+    /// Attach the sender's key rotation certificate
+    pub fn with_key_rotation(mut self, key_rotation: Option<KeyRotation>) -> Self {
+        self.key_rotation = key_rotation;
+        self
+    }
+
     /// Serialize just the payload (brain + proof) for signing
     pub fn payload_bytes(&self) -> Vec<u8> {
         // We re-serialize the components to get the canonical bytes for signing
@@ -92,6 +103,27 @@ impl SignedEpiphany {
     }
 }
 
+/// Errors from combining brains
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrainError {
+    /// Confidence vectors have different lengths
+    DimensionMismatch { local: usize, remote: usize },
+}
+
+impl std::fmt::Display for BrainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BrainError::DimensionMismatch { local, remote } => write!(
+                f,
+                "Confidence dimension mismatch (local {}, remote {})",
+                local, remote
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BrainError {}
+
 impl LivingBrain {
     pub fn new() -> Self {
         LivingBrain {
@@ -116,14 +148,27 @@ impl LivingBrain {
         serde_json::to_string(self).unwrap_or("{}".to_string())
     }
 
-    pub fn merge(&mut self, other: &LivingBrain, alpha: f32) {
-        for i in 0..self.confidence.len().min(other.confidence.len()) {
-            self.confidence[i] = self.confidence[i] * (1.0 - alpha) + other.confidence[i] * alpha;
+    /// Blend `other`'s confidence into this brain by `alpha`.
+    ///
+    /// Both brains must have the same confidence dimension; on mismatch
+    /// nothing is merged rather than blending only the shared prefix. An
+    /// `alpha` of 0 leaves the brain untouched, global anchor included.
+    pub fn merge(&mut self, other: &LivingBrain, alpha: f32) -> Result<(), BrainError> {
+        if self.confidence.len() != other.confidence.len() {
+            return Err(BrainError::DimensionMismatch {
+                local: self.confidence.len(),
+                remote: other.confidence.len(),
+            });
+        }
+        for (mine, theirs) in self.confidence.iter_mut().zip(&other.confidence) {
+            *mine = *mine * (1.0 - alpha) + theirs * alpha;
         }
-        // Always derive global anchor from the imported brain (truth)
-        if other.global_confidence.is_some() {
+        // Derive the global anchor from the imported brain (truth) unless
+        // the import is ignored
+        if alpha > 0.0 && other.global_confidence.is_some() {
             self.global_confidence = other.global_confidence.clone();
         }
+        Ok(())
     }
 
     pub fn diff(&self, other: &LivingBrain) -> Option<BrainDelta> {
@@ -164,3 +209,97 @@ impl LivingBrain {
         }
     }
 }
+
+/// Predictor set advertised to peers in the identify agent version.
+///
+/// Brains are only merged between nodes running the same predictor set
+/// version with the same confidence dimension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PredictorCapability {
+    pub version: u8,
+    pub dimension: usize,
+}
+
+impl PredictorCapability {
+    const AGENT_PREFIX: &'static str = "qres-predictors";
+
+    pub fn of(brain: &LivingBrain) -> Self {
+        Self {
+            version: brain.version,
+            dimension: brain.confidence.len(),
+        }
+    }
+
+    /// Encode as an identify agent version, e.g. `qres-predictors/v1/d6`
+    pub fn to_agent_version(&self) -> String {
+        format!(
+            "{}/v{}/d{}",
+            Self::AGENT_PREFIX,
+            self.version,
+            self.dimension
+        )
+    }
+
+    /// Parse an agent version produced by [`to_agent_version`](Self::to_agent_version)
+    pub fn from_agent_version(agent: &str) -> Option<Self> {
+        let mut parts = agent.split('/');
+        if parts.next()? != Self::AGENT_PREFIX {
+            return None;
+        }
+        let version = parts.next()?.strip_prefix('v')?.parse().ok()?;
+        let dimension = parts.next()?.strip_prefix('d')?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self { version, dimension })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_predictor_capability_agent_roundtrip() {
+        let cap = PredictorCapability::of(&LivingBrain::new());
+        assert_eq!(
+            PredictorCapability::from_agent_version(&cap.to_agent_version()),
+            Some(cap)
+        );
+        assert_eq!(
+            PredictorCapability::from_agent_version("rust-libp2p/0.44"),
+            None
+        );
+        assert_eq!(
+            PredictorCapability::from_agent_version("qres-predictors/v1/dx"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_merge_rejects_dimension_mismatch() {
+        let mut local = LivingBrain::new();
+        local.confidence = vec![0.5; 4];
+        let mut remote = LivingBrain::new();
+        remote.confidence = vec![1.0; 6];
+        remote.global_confidence = Some(remote.confidence.clone());
+
+        assert_eq!(
+            local.merge(&remote, 1.0),
+            Err(BrainError::DimensionMismatch {
+                local: 4,
+                remote: 6
+            })
+        );
+        // No partial (prefix) merge and no anchor adoption
+        assert_eq!(local.confidence, vec![0.5; 4]);
+        assert!(local.global_confidence.is_none());
+
+        remote.confidence.truncate(4);
+        local.merge(&remote, 0.0).unwrap();
+        assert_eq!(local.confidence, vec![0.5; 4]);
+        assert!(local.global_confidence.is_none());
+        local.merge(&remote, 0.5).unwrap();
+        assert_eq!(local.confidence, vec![0.75; 4]);
+    }
+}
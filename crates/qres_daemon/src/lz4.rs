@@ -0,0 +1,236 @@
+//! LZ4 Block Codec for the Low-CPU Fallback
+//!
+//! When the core codec would expand a chunk, the daemon stores it with a
+//! general-purpose fallback. Zstd gives the better ratio; this LZ4 path
+//! trades ratio for far less CPU, which matters on energy-harvesting nodes.
+//!
+//! Output is the standard LZ4 *block* format (no frame header or checksum;
+//! the chunk header already records the decoded length), produced by a
+//! single-pass greedy matcher with a 4K-entry hash table.
+
+use std::io;
+
+/// Minimum match length the format can express
+const MIN_MATCH: usize = 4;
+/// The last match must start at least this many bytes before the end
+const MF_LIMIT: usize = 12;
+/// The final bytes of a block are always literals
+const LAST_LITERALS: usize = 5;
+/// Matches may reach back at most this far
+const MAX_OFFSET: usize = u16::MAX as usize;
+const HASH_LOG: u32 = 12;
+
+fn read_u32(data: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap())
+}
+
+fn hash(sequence: u32) -> usize {
+    (sequence.wrapping_mul(2_654_435_761) >> (32 - HASH_LOG)) as usize
+}
+
+/// Append an LZ4 length continuation (bytes of 255, then the remainder).
+fn push_length(out: &mut Vec<u8>, mut len: usize) {
+    while len >= 255 {
+        out.push(255);
+        len -= 255;
+    }
+    out.push(len as u8);
+}
+
+/// Emit one sequence: literals, then an optional `(offset, match_len)`.
+fn push_sequence(out: &mut Vec<u8>, literals: &[u8], matched: Option<(usize, usize)>) {
+    let lit_nibble = literals.len().min(15);
+    let match_nibble = matched.map_or(0, |(_, len)| (len - MIN_MATCH).min(15));
+    out.push(((lit_nibble as u8) << 4) | match_nibble as u8);
+    if literals.len() >= 15 {
+        push_length(out, literals.len() - 15);
+    }
+    out.extend_from_slice(literals);
+    if let Some((offset, len)) = matched {
+        out.extend_from_slice(&(offset as u16).to_le_bytes());
+        if len - MIN_MATCH >= 15 {
+            push_length(out, len - MIN_MATCH - 15);
+        }
+    }
+}
+
+/// Worst-case size of a block compressed from `len` input bytes.
+pub fn max_compressed_len(len: usize) -> usize {
+    len + len / 255 + 16
+}
+
+/// Compress `input` as one LZ4 block.
+pub fn compress(input: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(input.len() / 2 + 16);
+    let mut anchor = 0;
+    if input.len() > MF_LIMIT {
+        let match_limit = input.len() - MF_LIMIT;
+        let end_of_matches = input.len() - LAST_LITERALS;
+        let mut table = vec![usize::MAX; 1 << HASH_LOG];
+        let mut pos = 0;
+        while pos < match_limit {
+            let sequence = read_u32(input, pos);
+            let slot = &mut table[hash(sequence)];
+            let candidate = *slot;
+            *slot = pos;
+
+            if candidate == usize::MAX
+                || pos - candidate > MAX_OFFSET
+                || read_u32(input, candidate) != sequence
+            {
+                pos += 1;
+                continue;
+            }
+
+            let mut len = MIN_MATCH;
+            while pos + len < end_of_matches && input[candidate + len] == input[pos + len] {
+                len += 1;
+            }
+            push_sequence(&mut out, &input[anchor..pos], Some((pos - candidate, len)));
+            pos += len;
+            anchor = pos;
+        }
+    }
+    push_sequence(&mut out, &input[anchor..], None);
+    out
+}
+
+fn corrupt(reason: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("LZ4: {}", reason))
+}
+
+/// Read an LZ4 length continuation starting at `*pos`.
+fn read_length(block: &[u8], pos: &mut usize) -> io::Result<usize> {
+    let mut len = 0usize;
+    loop {
+        let byte = *block.get(*pos).ok_or_else(|| corrupt("truncated length"))?;
+        *pos += 1;
+        len = len
+            .checked_add(byte as usize)
+            .ok_or_else(|| corrupt("length overflow"))?;
+        if byte != 255 {
+            return Ok(len);
+        }
+    }
+}
+
+/// Decompress one LZ4 block that must decode to exactly `decoded_len` bytes.
+pub fn decompress(block: &[u8], decoded_len: usize) -> io::Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(decoded_len);
+    let mut pos = 0;
+    loop {
+        let token = *block.get(pos).ok_or_else(|| corrupt("truncated block"))?;
+        pos += 1;
+
+        let mut lit_len = (token >> 4) as usize;
+        if lit_len == 15 {
+            lit_len += read_length(block, &mut pos)?;
+        }
+        let literals = block
+            .get(pos..pos.saturating_add(lit_len))
+            .ok_or_else(|| corrupt("truncated literals"))?;
+        if out.len() + lit_len > decoded_len {
+            return Err(corrupt("output exceeds decoded length"));
+        }
+        out.extend_from_slice(literals);
+        pos += lit_len;
+        if pos == block.len() {
+            break;
+        }
+
+        let offset = block
+            .get(pos..pos + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]) as usize)
+            .ok_or_else(|| corrupt("truncated offset"))?;
+        pos += 2;
+        if offset == 0 || offset > out.len() {
+            return Err(corrupt("match offset out of range"));
+        }
+        let mut match_len = (token & 0x0F) as usize;
+        if match_len == 15 {
+            match_len += read_length(block, &mut pos)?;
+        }
+        match_len += MIN_MATCH;
+        if out.len() + match_len > decoded_len {
+            return Err(corrupt("output exceeds decoded length"));
+        }
+        // Byte by byte: a match may overlap the bytes it is producing
+        let start = out.len() - offset;
+        for i in 0..match_len {
+            out.push(out[start + i]);
+        }
+    }
+    if out.len() != decoded_len {
+        return Err(corrupt("decoded length mismatch"));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(data: &[u8]) -> Vec<u8> {
+        let block = compress(data);
+        assert_eq!(decompress(&block, data.len()).unwrap(), data);
+        block
+    }
+
+    #[test]
+    fn test_round_trip_shapes() {
+        round_trip(b"");
+        assert!(round_trip(b"abc").len() <= max_compressed_len(3));
+        round_trip(b"exactly twelve bytes");
+
+        // Long runs exercise overlapping matches and length continuations
+        let run = round_trip(&[7u8; 10_000]);
+        assert!(run.len() < 100, "{}", run.len());
+
+        let text: Vec<u8> = b"timestamp,station,temp_c\n"
+            .iter()
+            .chain(b"1700000000,north,20.05\n".repeat(500).iter())
+            .copied()
+            .collect();
+        assert!(round_trip(&text).len() < text.len() / 4);
+
+        let mut state = 0x1234_5678u32;
+        let noise: Vec<u8> = (0..70_000)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                (state >> 16) as u8
+            })
+            .collect();
+        assert!(round_trip(&noise).len() <= max_compressed_len(noise.len()));
+    }
+
+    #[test]
+    fn test_decodes_reference_block() {
+        // Block taken from a frame written by the reference `lz4 -9` CLI
+        // (v1.9); covers a literal length continuation and overlapping matches
+        let expected = b"timestamp,temp\n1700000000,20.5\n1700000001,20.5\n\
+                         1700000002,20.5\n1700000003,20.6\n";
+        let block = [
+            0xf3, 0x03, 0x74, 0x69, 0x6d, 0x65, 0x73, 0x74, 0x61, 0x6d, 0x70, 0x2c, 0x74, 0x65,
+            0x6d, 0x70, 0x0a, 0x31, 0x37, 0x30, 0x01, 0x00, 0x56, 0x2c, 0x32, 0x30, 0x2e, 0x35,
+            0x10, 0x00, 0x1b, 0x31, 0x10, 0x00, 0x1b, 0x32, 0x10, 0x00, 0x70, 0x33, 0x2c, 0x32,
+            0x30, 0x2e, 0x36, 0x0a,
+        ];
+        assert_eq!(decompress(&block, expected.len()).unwrap(), expected);
+        assert_eq!(
+            decompress(&compress(expected), expected.len()).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_corrupt_blocks_are_rejected() {
+        let data = b"abcabcabcabcabcabcabcabcabcabc".repeat(10);
+        let block = compress(&data);
+        assert!(decompress(&block, data.len() + 1).is_err());
+        assert!(decompress(&block, data.len() - 1).is_err());
+        assert!(decompress(&block[..block.len() - 3], data.len()).is_err());
+        // Offset pointing before the start of the output
+        assert!(decompress(&[0x10, b'a', 0x05, 0x00], 5).is_err());
+        assert!(decompress(&[], 0).is_err());
+    }
+}
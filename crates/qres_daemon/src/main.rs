@@ -1,32 +1,54 @@
-pub mod analytics;
-pub mod api;
-pub mod brain_aggregator;
-pub mod config;
-pub mod daemon;
-pub mod dreaming;
-pub mod living_brain;
-pub mod peer_keys;
-pub mod qes;
-pub mod security;
-pub mod stats;
-pub mod swarm;
-pub mod swarm_p2p;
-
-use crate::living_brain::LivingBrain;
 use clap::{Parser, Subcommand};
+use qres_core::config::QresConfig;
 use qres_core::tensor::MpsCompressor;
-use qres_core::{
-    compress_chunk, config::QresConfig, decompress_chunk_with_state, PredictorSet, QresError,
+use qres_daemon::config::SwarmTransport;
+use qres_daemon::error::DaemonError;
+use qres_daemon::living_brain::{BrainError, LivingBrain};
+use qres_daemon::pipeline::{
+    compress_file, decompress_file, decompress_file_with_trace, parse_chunk_size, verify_file,
+    CompressOptions, FallbackCodec, CHUNK_SIZE,
 };
-// use qres_core::QresError;
+use qres_daemon::transform::StructuralTransform;
 use std::fs::{self, File};
-use std::io::{self, Read, Write};
+use std::io::{self, IsTerminal, Read};
 use tracing::{error, info};
 
 const DEFAULT_BRAIN_FILE: &str = "qres_brain.json";
-const CHUNK_SIZE: usize = 64 * 1024; // 64KB chunks
-/// Byte threshold for progress reporting during compression/decompression (1 MiB).
+/// Byte interval between progress log lines when stderr is not a terminal (1 MiB).
 const PROGRESS_THRESHOLD: u64 = 1024 * 1024;
+/// Width of the terminal progress bar, in characters
+const PROGRESS_BAR_WIDTH: usize = 30;
+/// Size of the TMPS tensor file header (magic, rows, cols, dtype width).
+const TMPS_HEADER_LEN: usize = 21;
+/// Largest decoded size a TMPS file may claim, as a multiple of its own size.
+/// Sparse files of smooth matrices legitimately expand a lot; this only stops
+/// a forged header from requesting an arbitrary allocation.
+const TMPS_MAX_EXPANSION: usize = 1 << 20;
+
+/// Element type of a raw matrix file fed to `TensorCompress`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum TensorDtype {
+    F32,
+    F64,
+}
+
+impl TensorDtype {
+    /// Bytes per element; also the dtype byte recorded in the TMPS header.
+    fn width(self) -> usize {
+        match self {
+            TensorDtype::F32 => 4,
+            TensorDtype::F64 => 8,
+        }
+    }
+
+    fn from_width(width: u8) -> Option<Self> {
+        match width {
+            4 => Some(TensorDtype::F32),
+            8 => Some(TensorDtype::F64),
+            _ => None,
+        }
+    }
+}
 
 #[derive(Parser)]
 #[command(name = "qres-cli")]
@@ -35,10 +57,76 @@ struct Cli {
     #[command(flatten)]
     config: QresConfig,
 
+    /// Path to the brain file supplying predictor weights (must match between
+    /// compression and decompression)
+    #[arg(long, global = true, default_value = DEFAULT_BRAIN_FILE)]
+    brain: String,
+
+    /// Write the fully-resolved configuration (CLI + config file + defaults)
+    /// as JSON to this path before running the command
+    #[arg(long, global = true)]
+    dump_config: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
 
+/// Stands in for secrets in `--dump-config` output
+const REDACTED: &str = "<redacted>";
+
+/// Effective configuration of a run, as written by `--dump-config`.
+#[derive(serde::Serialize)]
+struct EffectiveConfig<'a> {
+    codec: &'a QresConfig,
+    brain: &'a str,
+    daemon: qres_daemon::config::Config,
+}
+
+impl Cli {
+    /// Merge the CLI arguments over the loaded config file; CLI overrides win.
+    /// Secrets are redacted, since the result is meant to be written out.
+    fn effective_config(&self, mut daemon: qres_daemon::config::Config) -> EffectiveConfig<'_> {
+        if let Commands::Swarm {
+            port,
+            key,
+            transport,
+            listen,
+            no_mdns,
+        } = &self.command
+        {
+            // The status API always listens on `--port` (which has a default)
+            daemon.api.port = *port;
+            if let Some(key) = key {
+                daemon.security.key_path = Some(key.clone());
+            }
+            if let Some(transport) = transport {
+                daemon.swarm.transport = *transport;
+            }
+            if let Some(listen) = listen {
+                daemon.swarm.listen_addr = Some(listen.clone());
+            }
+            if *no_mdns {
+                daemon.swarm.mdns = false;
+            }
+        }
+        if daemon.security.swarm_secret.is_some() {
+            daemon.security.swarm_secret = Some(REDACTED.to_string());
+        }
+        EffectiveConfig {
+            codec: &self.config,
+            brain: &self.brain,
+            daemon,
+        }
+    }
+}
+
+fn dump_config(path: &str, config: &EffectiveConfig) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(config).map_err(io::Error::other)?;
+    fs::write(path, json)?;
+    info!(path = path, "Dumped effective configuration");
+    Ok(())
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Compress a file
@@ -47,6 +135,21 @@ enum Commands {
         input: String,
         /// Output file path
         output: String,
+        /// Reversible structural transform applied before compression
+        #[arg(long, value_enum, default_value_t = StructuralTransform::None)]
+        transform: StructuralTransform,
+        /// Number of worker threads compressing chunks in parallel
+        #[arg(long, default_value_t = 1)]
+        jobs: usize,
+        /// Pick the starting predictor from the first chunk's contents
+        #[arg(long)]
+        auto_predictor: bool,
+        /// Bytes of input per chunk (4096 to 16777216); recorded in the file header
+        #[arg(long, default_value_t = CHUNK_SIZE, value_parser = parse_chunk_size)]
+        chunk_size: usize,
+        /// Codec for chunks the core codec would expand
+        #[arg(long, value_enum, default_value_t = FallbackCodec::Zstd)]
+        fallback: FallbackCodec,
     },
     /// Decompress a file
     Decompress {
@@ -54,6 +157,14 @@ enum Commands {
         input: String,
         /// Output file path
         output: String,
+        /// Log the predictor id and decoded size of every chunk
+        #[arg(long)]
+        trace: bool,
+    },
+    /// Round-trip a file in memory and check it decodes to the original bytes
+    Verify {
+        /// Input file path
+        input: String,
     },
     /// Export brain to JSON
     ExportBrain {
@@ -64,22 +175,41 @@ enum Commands {
     ImportBrain {
         /// Input JSON file path
         input: String,
+        /// Influence of the imported brain (0.0 ignores it, 1.0 overwrites)
+        #[arg(long, default_value = "1.0")]
+        weight: f32,
+        /// Report the merge impact without writing the local brain
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Compare two brain JSON files
+    DiffBrain {
+        /// First brain JSON file
+        a: String,
+        /// Second brain JSON file
+        b: String,
     },
     /// Run swarm node
     Swarm {
-        /// Path to brain file
-        #[arg(long, default_value = "qres_brain.json")]
-        brain: String,
         /// API Port
         #[arg(long, default_value = "8080")]
         port: u16,
         /// Path to node private key
         #[arg(long)]
         key: Option<String>,
+        /// Swarm transport (overrides the config file)
+        #[arg(long, value_enum)]
+        transport: Option<SwarmTransport>,
+        /// Listen multiaddr, e.g. /ip4/0.0.0.0/tcp/4001 (overrides the config file)
+        #[arg(long)]
+        listen: Option<String>,
+        /// Disable mDNS local peer discovery
+        #[arg(long)]
+        no_mdns: bool,
     },
     /// Compress structured data using Tensor MPS
     TensorCompress {
-        /// Input file path (Raw f64 binary)
+        /// Input file path (Raw little-endian binary)
         input: String,
         /// Output file path
         output: String,
@@ -92,226 +222,176 @@ enum Commands {
         /// Approximation Threshold
         #[arg(long, default_value = "1.0")]
         threshold: f64,
+        /// Element type of the input matrix
+        #[arg(long, value_enum, default_value_t = TensorDtype::F64)]
+        dtype: TensorDtype,
+    },
+    /// Reconstruct a matrix from a TMPS tensor file
+    TensorDecompress {
+        /// Input TMPS file path
+        input: String,
+        /// Output file path (Raw little-endian binary, same dtype as compressed)
+        output: String,
     },
 }
 
-fn compress_file(input: &str, output: &str, config: &QresConfig) -> io::Result<()> {
-    let mut input_file = File::open(input)?;
-    let mut output_file = File::create(output)?;
+/// Size of the file at `path`, or 0 if it cannot be read.
+fn file_len(path: &str) -> u64 {
+    fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
 
-    // Load Living Brain for Initialization
-    let brain = if let Ok(json) = fs::read_to_string(DEFAULT_BRAIN_FILE) {
-        LivingBrain::from_json(&json).unwrap_or_default()
-    } else {
-        LivingBrain::default()
-    };
+/// CLI progress display: a bar on stderr when it is a terminal, otherwise an
+/// `info!` line every `PROGRESS_THRESHOLD` input bytes.
+struct ProgressReporter {
+    label: &'static str,
+    /// Expected final `bytes_in` (0 if unknown)
+    total: u64,
+    interactive: bool,
+    next_log: u64,
+    drawn: bool,
+}
 
-    // Prepare weights buffer (Init + Global)
-    let mut w_bytes = Vec::with_capacity(80);
-    // 1. Initial Weights
-    for &f in &brain.confidence {
-        w_bytes.extend_from_slice(&f.to_le_bytes());
-    }
-    // 2. Global Weights (FedProx)
-    if let Some(g) = &brain.global_confidence {
-        for &f in g {
-            w_bytes.extend_from_slice(&f.to_le_bytes());
+impl ProgressReporter {
+    fn new(label: &'static str, total: u64) -> Self {
+        Self {
+            label,
+            total,
+            interactive: io::stderr().is_terminal(),
+            next_log: PROGRESS_THRESHOLD,
+            drawn: false,
         }
     }
-    let weights_arg = if w_bytes.is_empty() {
-        None
-    } else {
-        Some(w_bytes.as_slice())
-    };
-
-    let mut buffer = vec![0u8; CHUNK_SIZE];
-    let mut total_input = 0u64;
-    let mut total_output = 0u64;
-    let start = std::time::Instant::now();
-
-    loop {
-        let bytes_read = input_file.read(&mut buffer)?;
-        if bytes_read == 0 {
-            break;
-        }
-
-        let chunk = &buffer[..bytes_read];
-        // Allocate buffer (worst case estimate)
-        let mut comp_buffer = vec![0u8; chunk.len() + 4096];
-        let compressed_result =
-            compress_chunk(chunk, 0, weights_arg, Some(config), &mut comp_buffer);
-
-        let compressed = match compressed_result {
-            Ok(len) => comp_buffer[..len].to_vec(),
-            Err(QresError::CompressionError(_)) => {
-                // Core failed (expansion). Use Zstd fallback.
-                let zstd_data = zstd::bulk::compress(chunk, 3)?;
-                let ver = 0x0A;
-                let flag_byte = (ver << 4) | 0x01;
-
-                let mut out = Vec::with_capacity(5 + zstd_data.len());
-                out.push(flag_byte);
-                out.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
-                out.extend_from_slice(&zstd_data);
-                out
-            }
-            Err(e) => return Err(io::Error::other(e.to_string())),
-        };
 
-        // Write chunk size (4 bytes) + compressed data
-        output_file.write_all(&(compressed.len() as u32).to_le_bytes())?;
-        output_file.write_all(&compressed)?;
-
-        total_input += bytes_read as u64;
-        total_output += compressed.len() as u64 + 4;
-
-        // Progress indicator
-        if total_input >= PROGRESS_THRESHOLD && total_input.is_multiple_of(PROGRESS_THRESHOLD) {
-            let ratio = (total_output as f64 / total_input as f64) * 100.0;
+    fn update(&mut self, bytes_in: u64, bytes_out: u64) {
+        let mib = |b: u64| b as f64 / 1024.0 / 1024.0;
+        if self.interactive {
+            let percent = (bytes_in * 100)
+                .checked_div(self.total)
+                .map_or(100, |p| p.min(100) as usize);
+            let filled = percent * PROGRESS_BAR_WIDTH / 100;
+            eprint!(
+                "\r{} [{}{}] {:>3}% {:.1} MiB -> {:.1} MiB",
+                self.label,
+                "#".repeat(filled),
+                " ".repeat(PROGRESS_BAR_WIDTH - filled),
+                percent,
+                mib(bytes_in),
+                mib(bytes_out)
+            );
+            self.drawn = true;
+        } else if bytes_in >= self.next_log {
             info!(
-                current_input_mb = total_input as f64 / 1024.0 / 1024.0,
-                current_output_mb = total_output as f64 / 1024.0 / 1024.0,
-                ratio_percent = ratio,
-                "Compressing..."
+                input_mb = mib(bytes_in),
+                output_mb = mib(bytes_out),
+                "{}...",
+                self.label
             );
+            self.next_log = bytes_in - bytes_in % PROGRESS_THRESHOLD + PROGRESS_THRESHOLD;
         }
     }
 
-    let elapsed = start.elapsed();
-    let ratio = if total_input > 0 {
-        (total_output as f64 / total_input as f64) * 100.0
-    } else {
-        0.0
-    };
-
-    info!(
-        total_input_bytes = total_input,
-        total_output_bytes = total_output,
-        ratio_percent = ratio,
-        duration_secs = elapsed.as_secs_f64(),
-        throughput_mb_s = if elapsed.as_secs_f64() > 0.0 {
-            (total_input as f64 / 1024.0 / 1024.0) / elapsed.as_secs_f64()
-        } else {
-            0.0
-        },
-        "Compression Complete"
-    );
-
-    Ok(())
+    /// End the bar's line so later output starts on a fresh one.
+    fn finish(&self) {
+        if self.drawn {
+            eprintln!();
+        }
+    }
 }
 
-fn decompress_file(input: &str, output: &str) -> io::Result<()> {
-    let mut input_file = File::open(input)?;
-    let mut output_file = File::create(output)?;
-
-    // Load Living Brain for Initialization (Must match Encoder!)
-    let brain = if let Ok(json) = fs::read_to_string(DEFAULT_BRAIN_FILE) {
-        LivingBrain::from_json(&json).unwrap_or_default()
+fn verify_mode(input: &str, brain_path: &str, config: &QresConfig) -> Result<(), DaemonError> {
+    let report = verify_file(input, brain_path, config)?;
+    println!(
+        "{}: {} -> {} bytes ({:.2}%), max abs error {} (tolerance {})",
+        if report.passed { "PASS" } else { "FAIL" },
+        report.input_bytes,
+        report.compressed_bytes,
+        report.ratio_percent(),
+        report.max_abs_error,
+        report.tolerance
+    );
+    if report.passed {
+        Ok(())
     } else {
-        LivingBrain::default()
-    };
-
-    // Prepare weights buffer
-    let mut w_bytes = Vec::with_capacity(80);
-    for &f in &brain.confidence {
-        w_bytes.extend_from_slice(&f.to_le_bytes());
-    }
-    if let Some(g) = &brain.global_confidence {
-        for &f in g {
-            w_bytes.extend_from_slice(&f.to_le_bytes());
-        }
+        Err(DaemonError::RoundTripMismatch)
     }
-    let weights_arg = if w_bytes.is_empty() {
-        None
+}
+
+fn brain_export_to_file(brain_path: &str, output: &str) -> io::Result<()> {
+    let json = if let Ok(content) = fs::read_to_string(brain_path) {
+        content
     } else {
-        Some(w_bytes.as_slice())
+        LivingBrain::new().to_json()
     };
+    fs::write(output, json)?;
+    info!(output_path = output, "Brain exported to file");
+    Ok(())
+}
 
-    let mut total_output = 0u64;
-    let start = std::time::Instant::now();
-
-    // OPTIMIZATION: Allocate PredictorSet ONCE (saves ~22MB allocation per chunk)
-    // The PredictorSet is reset internally by decompress_chunk_with_state before each use
-    let mut predictor_state = PredictorSet::new(None, None);
-
-    loop {
-        // Read chunk size
-        let mut size_buf = [0u8; 4];
-        match input_file.read_exact(&mut size_buf) {
-            Ok(_) => {}
-            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
-            Err(e) => return Err(e),
-        }
-
-        let chunk_size = u32::from_le_bytes(size_buf) as usize;
-
-        // Read compressed chunk
-        let mut compressed = vec![0u8; chunk_size];
-        input_file.read_exact(&mut compressed)?;
-
-        // Decompress using reusable predictor state (eliminates ~22MB alloc/dealloc per chunk)
-        let result = decompress_chunk_with_state(&compressed, 0, weights_arg, &mut predictor_state);
-
-        let decompressed = match result {
-            Ok(d) => d,
-            Err(QresError::CompressionError(s)) if s.contains("Zstd") => {
-                // Fallback for Zstd chunks (0x01) which Core rejected
-                // We need to parse the header manually to extract Zstd payload
-                // Offsets: [Header:1][UncompressedLen:4][Payload...]
-                if compressed.len() < 5 {
-                    return Err(io::Error::new(
-                        io::ErrorKind::InvalidData,
-                        "Chunk too short",
-                    ));
-                }
-                let decomp_len = u32::from_le_bytes(compressed[1..5].try_into().unwrap()) as usize;
-                let payload = &compressed[5..];
-                zstd::bulk::decompress(payload, decomp_len)?
-            }
-            Err(e) => return Err(io::Error::other(e.to_string())),
-        };
-
-        output_file.write_all(&decompressed)?;
-
-        total_output += decompressed.len() as u64;
+/// Effect of merging an imported brain into the local one.
+#[derive(Debug, Clone, PartialEq)]
+struct MergeImpact {
+    /// Per-dimension confidence change (merged - local)
+    confidence_delta: Vec<f32>,
+    global_confidence_changed: bool,
+    /// L2 norm of `confidence_delta`
+    l2_shift: f32,
+}
 
-        // Progress indicator
-        if total_output >= PROGRESS_THRESHOLD && total_output.is_multiple_of(PROGRESS_THRESHOLD) {
-            info!(
-                decompressed_mb = total_output as f64 / 1024.0 / 1024.0,
-                "Decompressing..."
-            );
+impl MergeImpact {
+    fn between(local: &LivingBrain, merged: &LivingBrain) -> Self {
+        let confidence_delta: Vec<f32> = local
+            .confidence
+            .iter()
+            .zip(&merged.confidence)
+            .map(|(a, b)| b - a)
+            .collect();
+        let l2_shift = confidence_delta.iter().map(|d| d * d).sum::<f32>().sqrt();
+        Self {
+            confidence_delta,
+            global_confidence_changed: local.global_confidence != merged.global_confidence,
+            l2_shift,
         }
     }
+}
 
-    let elapsed = start.elapsed();
-    info!(
-        total_output_bytes = total_output,
-        duration_secs = elapsed.as_secs_f64(),
-        throughput_mb_s = if elapsed.as_secs_f64() > 0.0 {
-            (total_output as f64 / 1024.0 / 1024.0) / elapsed.as_secs_f64()
+fn print_merge_impact(file_path: &str, impact: &MergeImpact) {
+    println!(
+        "Dry run: importing {} would shift confidence by L2 {:.4}, global confidence {}",
+        file_path,
+        impact.l2_shift,
+        if impact.global_confidence_changed {
+            "changed"
         } else {
-            0.0
-        },
-        "Decompression Complete"
+            "unchanged"
+        }
     );
-
-    Ok(())
+    for (i, delta) in impact.confidence_delta.iter().enumerate() {
+        println!("  [{}] {:+.4}", i, delta);
+    }
 }
 
-fn brain_export_to_file(output: &str) -> io::Result<()> {
-    let json = if let Ok(content) = fs::read_to_string(DEFAULT_BRAIN_FILE) {
-        content
-    } else {
-        LivingBrain::new().to_json()
-    };
-    fs::write(output, json)?;
-    info!(output_path = output, "Brain exported to file");
-    Ok(())
-}
+fn brain_import(
+    brain_path: &str,
+    file_path: &str,
+    weight: f32,
+    dry_run: bool,
+) -> Result<(), DaemonError> {
+    // A NaN or infinite weight is a typo, not a request to ignore or overwrite
+    if !weight.is_finite() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("import weight must be finite, got {}", weight),
+        )
+        .into());
+    }
+    let effective_weight = weight.clamp(0.0, 1.0);
+    info!(
+        requested_weight = weight,
+        weight = effective_weight,
+        "Importing brain"
+    );
 
-fn brain_import(file_path: &str) -> io::Result<()> {
-    let mut local = if let Ok(json) = fs::read_to_string(DEFAULT_BRAIN_FILE) {
+    let mut local = if let Ok(json) = fs::read_to_string(brain_path) {
         LivingBrain::from_json(&json).unwrap_or_default()
     } else {
         LivingBrain::new()
@@ -319,10 +399,20 @@ fn brain_import(file_path: &str) -> io::Result<()> {
 
     let import_json = fs::read_to_string(file_path)?;
     if let Some(imported) = LivingBrain::from_json(&import_json) {
+        if dry_run {
+            let mut merged = local.clone();
+            merged.merge(&imported, effective_weight)?;
+            print_merge_impact(file_path, &MergeImpact::between(&local, &merged));
+            return Ok(());
+        }
+        if effective_weight == 0.0 {
+            info!("Import weight is 0, local brain left unchanged");
+            return Ok(());
+        }
         // V4: Hive Sync (Python) handles the merging logic (FedProx).
-        // CLI just applies the result (Overwrite confidence, keep stats).
-        local.merge(&imported, 1.0);
-        fs::write(DEFAULT_BRAIN_FILE, local.to_json())?;
+        // CLI just applies the result (blend confidence by weight, keep stats).
+        local.merge(&imported, effective_weight)?;
+        fs::write(brain_path, local.to_json())?;
         info!("Brain merged successfully. Wisdom assimilated.");
     } else {
         error!("Failed to parse imported brain.");
@@ -330,11 +420,110 @@ fn brain_import(file_path: &str) -> io::Result<()> {
     Ok(())
 }
 
-fn swarm_mode(brain: String, port: u16, key_path: Option<String>) -> io::Result<()> {
+/// Confidence comparison between two brains (`b - a`).
+#[derive(Debug, Clone, PartialEq)]
+struct BrainComparison {
+    /// Per-dimension confidence change
+    confidence_delta: Vec<f32>,
+    max_abs_delta: f32,
+    /// Exactly one of the brains has a global confidence anchor
+    global_confidence_presence_differs: bool,
+    /// Cosine similarity of the confidence vectors (0.0 if either is zero)
+    cosine_similarity: f32,
+}
+
+impl BrainComparison {
+    /// Compare two brains of the same confidence dimension; a dimension
+    /// mismatch is an error rather than a comparison of the shared prefix.
+    fn between(a: &LivingBrain, b: &LivingBrain) -> Result<Self, BrainError> {
+        if a.confidence.len() != b.confidence.len() {
+            return Err(BrainError::DimensionMismatch {
+                local: a.confidence.len(),
+                remote: b.confidence.len(),
+            });
+        }
+        let confidence_delta: Vec<f32> = a
+            .confidence
+            .iter()
+            .zip(&b.confidence)
+            .map(|(x, y)| y - x)
+            .collect();
+        let max_abs_delta = confidence_delta.iter().fold(0.0f32, |m, d| m.max(d.abs()));
+
+        let dot: f32 = a
+            .confidence
+            .iter()
+            .zip(&b.confidence)
+            .map(|(x, y)| x * y)
+            .sum();
+        let norm_a = a.confidence.iter().map(|x| x * x).sum::<f32>().sqrt();
+        let norm_b = b.confidence.iter().map(|x| x * x).sum::<f32>().sqrt();
+        let cosine_similarity = if norm_a > 0.0 && norm_b > 0.0 {
+            dot / (norm_a * norm_b)
+        } else {
+            0.0
+        };
+
+        Ok(Self {
+            confidence_delta,
+            max_abs_delta,
+            global_confidence_presence_differs: a.global_confidence.is_some()
+                != b.global_confidence.is_some(),
+            cosine_similarity,
+        })
+    }
+}
+
+fn read_brain_file(path: &str) -> io::Result<LivingBrain> {
+    let json = fs::read_to_string(path)?;
+    LivingBrain::from_json(&json).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Failed to parse brain file {}", path),
+        )
+    })
+}
+
+fn compare_brain_files(a: &str, b: &str) -> Result<BrainComparison, DaemonError> {
+    Ok(BrainComparison::between(
+        &read_brain_file(a)?,
+        &read_brain_file(b)?,
+    )?)
+}
+
+fn brain_diff(a: &str, b: &str) -> Result<(), DaemonError> {
+    let comparison = compare_brain_files(a, b)?;
+    for (i, delta) in comparison.confidence_delta.iter().enumerate() {
+        println!("  [{}] {:+.4}", i, delta);
+    }
+    println!(
+        "max abs delta {:.4}, cosine similarity {:.6}, global confidence presence {}",
+        comparison.max_abs_delta,
+        comparison.cosine_similarity,
+        if comparison.global_confidence_presence_differs {
+            "differs"
+        } else {
+            "matches"
+        }
+    );
+    Ok(())
+}
+
+fn swarm_mode(
+    brain: String,
+    port: u16,
+    key_path: Option<String>,
+    transport: Option<SwarmTransport>,
+    listen: Option<String>,
+    no_mdns: bool,
+) -> io::Result<()> {
     info!(
         brain_file = brain,
         port = port,
         key_path = ?key_path,
+        transport = ?transport,
+        listen = ?listen,
+        no_mdns = no_mdns,
         "Starting QRES P2P Swarm Node (libp2p)..."
     );
 
@@ -342,7 +531,11 @@ fn swarm_mode(brain: String, port: u16, key_path: Option<String>) -> io::Result<
     let rt = tokio::runtime::Runtime::new().map_err(io::Error::other)?;
 
     rt.block_on(async {
-        if let Err(e) = crate::swarm_p2p::start_p2p_node(brain, port, key_path).await {
+        if let Err(e) = qres_daemon::swarm_p2p::start_p2p_node(
+            brain, port, key_path, transport, listen, no_mdns,
+        )
+        .await
+        {
             error!(error = %e, "Swarm crashed");
         }
     });
@@ -356,33 +549,38 @@ fn compress_tensor_file(
     rows: usize,
     cols: usize,
     threshold: f64,
+    dtype: TensorDtype,
 ) -> io::Result<()> {
     let mut file = File::open(input)?;
     let metadata = file.metadata()?;
     let len = metadata.len();
+    let width = dtype.width();
 
-    // Validate size (must be rows*cols*8)
-    if len != (rows * cols * 8) as u64 {
+    // Validate size (must be rows*cols*width)
+    if len != (rows * cols * width) as u64 {
         return Err(io::Error::new(
             io::ErrorKind::InvalidData,
             format!(
-                "File size {} does not match rows*cols*8 ({})",
+                "File size {} does not match rows*cols*{} ({})",
                 len,
-                rows * cols * 8
+                width,
+                rows * cols * width
             ),
         ));
     }
 
-    // Read all data as f64 (unsafe/transmute for speed, or byte-by-byte conversion)
-    // For simplicity: Read bytes, convert to f64 safely.
+    // Read all data, then convert to f64 safely.
     let mut buffer = Vec::with_capacity(len as usize);
     file.read_to_end(&mut buffer)?;
 
     // Convert to Vec<f64>
     // Assuming Little Endian (standard)
     let mut floats = Vec::with_capacity(rows * cols);
-    for chunk in buffer.chunks_exact(8) {
-        let val = f64::from_le_bytes(chunk.try_into().unwrap());
+    for chunk in buffer.chunks_exact(width) {
+        let val = match dtype {
+            TensorDtype::F32 => f32::from_le_bytes(chunk.try_into().unwrap()) as f64,
+            TensorDtype::F64 => f64::from_le_bytes(chunk.try_into().unwrap()),
+        };
         floats.push(val);
     }
 
@@ -391,54 +589,22 @@ fn compress_tensor_file(
     let start = std::time::Instant::now();
 
     let compressor = MpsCompressor::new(10, threshold);
-    let chunks = compressor.compress_matrix(&floats, rows, cols);
+    let pairs = compressor.compress_matrix_sparse(&floats, rows, cols);
 
     // Write Output
-    // Format: [Magic: TMPS] [Rows:8] [Cols:8] [Data...]
-    let mut out_file = File::create(output)?;
-    out_file.write_all(b"TMPS")?;
-    out_file.write_all(&(rows as u64).to_le_bytes())?;
-    out_file.write_all(&(cols as u64).to_le_bytes())?;
-
-    let mut compressed_size = 20; // Header
-
-    if let Some(data) = chunks.first() {
-        // Simple Sparse Serialization: [Index:4, Value:8] or just [Value:8] if dense?
-        // My MpsCompressor returns a dense vector with 0.0s for pruned values.
-        // We should RLE or Sparse-Pack it here to realize gains.
-
-        let mut n_zeros = 0;
-        let mut packed_bytes = Vec::new();
-
-        // Simple RLE for Zero Runs (0x00 flag)
-        for val in data {
-            let val: f64 = *val;
-            if val.abs() < 1e-9 {
-                n_zeros += 1;
-                while n_zeros >= 255 {
-                    packed_bytes.push(0x00);
-                    packed_bytes.push(255);
-                    n_zeros -= 255;
-                }
-            } else {
-                if n_zeros > 0 {
-                    packed_bytes.push(0x00);
-                    packed_bytes.push(n_zeros as u8);
-                    n_zeros = 0;
-                }
-                packed_bytes.push(0x01); // Value flag
-                packed_bytes.extend_from_slice(&val.to_le_bytes());
-            }
-        }
-        // Flush zeros
-        if n_zeros > 0 {
-            packed_bytes.push(0x00);
-            packed_bytes.push(n_zeros as u8);
-        }
-
-        out_file.write_all(&packed_bytes)?;
-        compressed_size += packed_bytes.len();
+    // Format: [Magic: TMPS] [Rows:8] [Cols:8] [Dtype width:1]
+    // then [Index:4][Value:8] per surviving coefficient
+    let mut packed_bytes = Vec::with_capacity(TMPS_HEADER_LEN + pairs.len() * 12);
+    packed_bytes.extend_from_slice(b"TMPS");
+    packed_bytes.extend_from_slice(&(rows as u64).to_le_bytes());
+    packed_bytes.extend_from_slice(&(cols as u64).to_le_bytes());
+    packed_bytes.push(width as u8);
+    for (index, value) in &pairs {
+        packed_bytes.extend_from_slice(&index.to_le_bytes());
+        packed_bytes.extend_from_slice(&value.to_le_bytes());
     }
+    fs::write(output, &packed_bytes)?;
+    let compressed_size = packed_bytes.len();
 
     let elapsed = start.elapsed();
     let ratio = if len > 0 {
@@ -450,6 +616,7 @@ fn compress_tensor_file(
     info!(
         original_bytes = len,
         compressed_bytes = compressed_size,
+        coefficients = pairs.len(),
         ratio_percent = ratio,
         duration_secs = elapsed.as_secs_f64(),
         "Tensor Compression Complete"
@@ -458,6 +625,73 @@ fn compress_tensor_file(
     Ok(())
 }
 
+fn decompress_tensor_file(input: &str, output: &str) -> io::Result<()> {
+    let data = fs::read(input)?;
+    let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+
+    if data.len() < 4 || &data[..4] != b"TMPS" {
+        return Err(invalid(format!("{} is not a TMPS tensor file", input)));
+    }
+    if data.len() < TMPS_HEADER_LEN {
+        return Err(invalid("Truncated TMPS header".to_string()));
+    }
+
+    let rows = u64::from_le_bytes(data[4..12].try_into().unwrap()) as usize;
+    let cols = u64::from_le_bytes(data[12..20].try_into().unwrap()) as usize;
+    let dtype = TensorDtype::from_width(data[20])
+        .ok_or_else(|| invalid(format!("Unknown TMPS dtype width {}", data[20])))?;
+    let too_large = || invalid(format!("Matrix {}x{} is too large", rows, cols));
+    let elements = rows.checked_mul(cols).ok_or_else(too_large)?;
+    let out_len = elements.checked_mul(dtype.width()).ok_or_else(too_large)?;
+    if out_len > data.len().saturating_mul(TMPS_MAX_EXPANSION) {
+        return Err(too_large());
+    }
+
+    // Records: [Index:4][Value:8]
+    let records = &data[TMPS_HEADER_LEN..];
+    if records.len() % 12 != 0 {
+        return Err(invalid("Truncated TMPS coefficient record".to_string()));
+    }
+    let mut pairs = Vec::with_capacity(records.len() / 12);
+    for record in records.chunks_exact(12) {
+        let index = u32::from_le_bytes(record[..4].try_into().unwrap());
+        if index as usize >= elements {
+            return Err(invalid(format!(
+                "Coefficient index {} out of range for {}x{} matrix",
+                index, rows, cols
+            )));
+        }
+        pairs.push((index, f64::from_le_bytes(record[4..].try_into().unwrap())));
+    }
+
+    let matrix = MpsCompressor::new(10, 0.0).decompress_sparse(&pairs, rows, cols);
+    if matrix.len() != elements {
+        return Err(invalid(format!(
+            "Decoded {} elements, expected rows*cols = {}",
+            matrix.len(),
+            elements
+        )));
+    }
+
+    let mut out = Vec::with_capacity(out_len);
+    for val in &matrix {
+        match dtype {
+            TensorDtype::F32 => out.extend_from_slice(&(*val as f32).to_le_bytes()),
+            TensorDtype::F64 => out.extend_from_slice(&val.to_le_bytes()),
+        }
+    }
+    fs::write(output, out)?;
+
+    info!(
+        rows = rows,
+        cols = cols,
+        coefficients = pairs.len(),
+        "Tensor Decompression Complete"
+    );
+
+    Ok(())
+}
+
 fn main() {
     // Initialize structured logging
     let subscriber = tracing_subscriber::fmt()
@@ -477,23 +711,436 @@ fn main() {
         "Starting QRES with Configuration"
     );
 
-    let result = match cli.command {
-        Commands::Compress { input, output } => compress_file(&input, &output, &cli.config),
-        Commands::Decompress { input, output } => decompress_file(&input, &output),
-        Commands::ExportBrain { output } => brain_export_to_file(&output),
-        Commands::ImportBrain { input } => brain_import(&input),
-        Commands::Swarm { brain, port, key } => swarm_mode(brain, port, key),
+    if let Some(path) = &cli.dump_config {
+        let file_config = qres_daemon::config::Config::load().unwrap_or_else(|e| {
+            error!(error = %e, "Could not load config file, dumping defaults");
+            qres_daemon::config::Config::default()
+        });
+        if let Err(e) = dump_config(path, &cli.effective_config(file_config)) {
+            error!(error = %e, "Failed to dump configuration");
+            std::process::exit(1);
+        }
+    }
+
+    let result: Result<(), DaemonError> = match cli.command {
+        Commands::Compress {
+            input,
+            output,
+            transform,
+            jobs,
+            auto_predictor,
+            chunk_size,
+            fallback,
+        } => {
+            let mut bar = ProgressReporter::new("Compressing", file_len(&input));
+            let result = compress_file(
+                &input,
+                &output,
+                &cli.brain,
+                &cli.config,
+                &CompressOptions {
+                    transform,
+                    jobs,
+                    auto_predictor,
+                    chunk_size,
+                    fallback,
+                },
+                Some(&mut |bytes_in, bytes_out| bar.update(bytes_in, bytes_out)),
+            );
+            bar.finish();
+            result.map(|_| ())
+        }
+        Commands::Decompress {
+            input,
+            output,
+            trace,
+        } => {
+            let mut bar = ProgressReporter::new("Decompressing", file_len(&input));
+            let mut on_progress = |bytes_in, bytes_out| bar.update(bytes_in, bytes_out);
+            let result = if trace {
+                decompress_file_with_trace(
+                    &input,
+                    &output,
+                    &cli.brain,
+                    |t| {
+                        info!(
+                            chunk = t.index,
+                            predictor_id = ?t.predictor_id,
+                            decoded_bytes = t.decoded_bytes,
+                            "Chunk decoded"
+                        );
+                    },
+                    Some(&mut on_progress),
+                )
+            } else {
+                decompress_file(&input, &output, &cli.brain, Some(&mut on_progress))
+            };
+            bar.finish();
+            result.map(|_| ())
+        }
+        Commands::Verify { input } => verify_mode(&input, &cli.brain, &cli.config),
+        Commands::ExportBrain { output } => {
+            brain_export_to_file(&cli.brain, &output).map_err(DaemonError::from)
+        }
+        Commands::ImportBrain {
+            input,
+            weight,
+            dry_run,
+        } => brain_import(&cli.brain, &input, weight, dry_run),
+        Commands::DiffBrain { a, b } => brain_diff(&a, &b),
+        Commands::Swarm {
+            port,
+            key,
+            transport,
+            listen,
+            no_mdns,
+        } => swarm_mode(cli.brain.clone(), port, key, transport, listen, no_mdns)
+            .map_err(DaemonError::from),
         Commands::TensorCompress {
             input,
             output,
             rows,
             cols,
             threshold,
-        } => compress_tensor_file(&input, &output, rows, cols, threshold),
+            dtype,
+        } => compress_tensor_file(&input, &output, rows, cols, threshold, dtype)
+            .map_err(DaemonError::from),
+        Commands::TensorDecompress { input, output } => {
+            decompress_tensor_file(&input, &output).map_err(DaemonError::from)
+        }
     };
 
     if let Err(e) = result {
         error!(error = %e, "Fatal Error");
-        std::process::exit(1);
+        std::process::exit(e.exit_code());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qres_daemon::pipeline::load_brain;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("qres_cli_{}_{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_tensor_compress_f32_header_and_size() {
+        let (rows, cols) = (4usize, 8usize);
+        let matrix: Vec<f32> = (0..rows * cols).map(|i| (i / cols) as f32 * 2.0).collect();
+        let input = temp_path("tensor_f32_in");
+        let packed = temp_path("tensor_f32_packed");
+        let raw: Vec<u8> = matrix.iter().flat_map(|v| v.to_le_bytes()).collect();
+        fs::write(&input, &raw).unwrap();
+
+        compress_tensor_file(
+            input.to_str().unwrap(),
+            packed.to_str().unwrap(),
+            rows,
+            cols,
+            0.5,
+            TensorDtype::F32,
+        )
+        .unwrap();
+
+        let out = fs::read(&packed).unwrap();
+        assert_eq!(&out[..4], b"TMPS");
+        assert_eq!(
+            u64::from_le_bytes(out[4..12].try_into().unwrap()),
+            rows as u64
+        );
+        assert_eq!(
+            u64::from_le_bytes(out[12..20].try_into().unwrap()),
+            cols as u64
+        );
+        assert_eq!(out[20], 4);
+
+        let as_f64: Vec<f64> = matrix.iter().map(|&v| v as f64).collect();
+        let expected_pairs =
+            MpsCompressor::new(10, 0.5).compress_matrix_sparse(&as_f64, rows, cols);
+        assert!(!expected_pairs.is_empty());
+        assert_eq!(out.len(), TMPS_HEADER_LEN + expected_pairs.len() * 12);
+
+        // Width is checked against the declared dtype
+        assert!(compress_tensor_file(
+            input.to_str().unwrap(),
+            packed.to_str().unwrap(),
+            rows,
+            cols,
+            0.5,
+            TensorDtype::F64,
+        )
+        .is_err());
+
+        let _ = fs::remove_file(input);
+        let _ = fs::remove_file(packed);
+    }
+
+    #[test]
+    fn test_tensor_roundtrip_and_magic_check() {
+        let (rows, cols) = (8usize, 6usize);
+        let matrix: Vec<f64> = (0..rows * cols)
+            .map(|i| 1.0 + 0.25 * (i / cols) as f64 + 0.1 * (i % cols) as f64)
+            .collect();
+        let input = temp_path("tensor_rt_in");
+        let packed = temp_path("tensor_rt_packed");
+        let restored = temp_path("tensor_rt_out");
+        let raw: Vec<u8> = matrix.iter().flat_map(|v| v.to_le_bytes()).collect();
+        fs::write(&input, &raw).unwrap();
+
+        compress_tensor_file(
+            input.to_str().unwrap(),
+            packed.to_str().unwrap(),
+            rows,
+            cols,
+            0.0,
+            TensorDtype::F64,
+        )
+        .unwrap();
+        decompress_tensor_file(packed.to_str().unwrap(), restored.to_str().unwrap()).unwrap();
+
+        let out = fs::read(&restored).unwrap();
+        assert_eq!(out.len(), rows * cols * 8);
+        for (chunk, expected) in out.chunks_exact(8).zip(&matrix) {
+            let val = f64::from_le_bytes(chunk.try_into().unwrap());
+            assert!((val - expected).abs() < 1e-3, "{} vs {}", val, expected);
+        }
+
+        // Raw input is not a TMPS file
+        let err = decompress_tensor_file(input.to_str().unwrap(), restored.to_str().unwrap())
+            .unwrap_err();
+        assert!(err.to_string().contains("not a TMPS tensor file"));
+
+        // A forged header may not claim more than the file could expand to
+        let mut forged = fs::read(&packed).unwrap();
+        forged[4..12].copy_from_slice(&(1u64 << 20).to_le_bytes());
+        forged[12..20].copy_from_slice(&(1u64 << 20).to_le_bytes());
+        fs::write(&packed, &forged).unwrap();
+        let err = decompress_tensor_file(packed.to_str().unwrap(), restored.to_str().unwrap())
+            .unwrap_err();
+        assert!(err.to_string().contains("too large"));
+        forged[4..12].copy_from_slice(&u64::MAX.to_le_bytes());
+        fs::write(&packed, &forged).unwrap();
+        let err = decompress_tensor_file(packed.to_str().unwrap(), restored.to_str().unwrap())
+            .unwrap_err();
+        assert!(err.to_string().contains("too large"));
+
+        for p in [input, packed, restored] {
+            let _ = fs::remove_file(p);
+        }
+    }
+
+    #[test]
+    fn test_import_brain_dry_run_leaves_local_brain_untouched() {
+        let brain_path = temp_path("dry_run_brain.json");
+        let import_path = temp_path("dry_run_import.json");
+        let local = LivingBrain::new();
+        fs::write(&brain_path, local.to_json()).unwrap();
+        let mut imported = LivingBrain::new();
+        imported.confidence[0] = 0.8;
+        imported.confidence[1] = 0.1;
+        imported.global_confidence = Some(imported.confidence.clone());
+        fs::write(&import_path, imported.to_json()).unwrap();
+
+        let before = fs::read(&brain_path).unwrap();
+        brain_import(
+            brain_path.to_str().unwrap(),
+            import_path.to_str().unwrap(),
+            1.0,
+            true,
+        )
+        .unwrap();
+        assert_eq!(fs::read(&brain_path).unwrap(), before);
+
+        let mut merged = local.clone();
+        merged.merge(&imported, 1.0).unwrap();
+        let impact = MergeImpact::between(&local, &merged);
+        assert!((impact.confidence_delta[0] - 0.3).abs() < 1e-6);
+        assert!((impact.confidence_delta[1] + 0.4).abs() < 1e-6);
+        assert!((impact.l2_shift - 0.5).abs() < 1e-6);
+        assert!(impact.global_confidence_changed);
+
+        let _ = fs::remove_file(brain_path);
+        let _ = fs::remove_file(import_path);
+    }
+
+    #[test]
+    fn test_import_brain_weight_blends_confidence() {
+        let brain_path = temp_path("weighted_brain.json");
+        let import_path = temp_path("weighted_import.json");
+        let mut local = LivingBrain::new();
+        local.confidence = vec![0.2, 0.4, 0.6, 0.8];
+        fs::write(&brain_path, local.to_json()).unwrap();
+        let mut imported = LivingBrain::new();
+        imported.confidence = vec![0.8, 0.0, 1.0, 0.8];
+        imported.global_confidence = Some(imported.confidence.clone());
+        fs::write(&import_path, imported.to_json()).unwrap();
+
+        brain_import(
+            brain_path.to_str().unwrap(),
+            import_path.to_str().unwrap(),
+            0.5,
+            false,
+        )
+        .unwrap();
+        let merged = load_brain(brain_path.to_str().unwrap());
+        for ((m, a), b) in merged
+            .confidence
+            .iter()
+            .zip(&local.confidence)
+            .zip(&imported.confidence)
+        {
+            assert!((m - (a + b) / 2.0).abs() < 1e-6);
+        }
+
+        assert_eq!(merged.global_confidence, imported.global_confidence);
+
+        // Zero and negative weights ignore the import, anchor included
+        for weight in [0.0, -3.0] {
+            fs::write(&brain_path, local.to_json()).unwrap();
+            brain_import(
+                brain_path.to_str().unwrap(),
+                import_path.to_str().unwrap(),
+                weight,
+                false,
+            )
+            .unwrap();
+            let unchanged = load_brain(brain_path.to_str().unwrap());
+            assert_eq!(unchanged.confidence, local.confidence, "weight {}", weight);
+            assert!(unchanged.global_confidence.is_none(), "weight {}", weight);
+        }
+
+        // Non-finite weights are refused outright
+        for weight in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            let err = brain_import(
+                brain_path.to_str().unwrap(),
+                import_path.to_str().unwrap(),
+                weight,
+                false,
+            )
+            .unwrap_err();
+            assert!(
+                matches!(err, DaemonError::Io(ref e) if e.kind() == io::ErrorKind::InvalidInput)
+            );
+        }
+
+        // A brain of another shape is a config error, not an IO failure
+        imported.confidence.push(0.5);
+        fs::write(&import_path, imported.to_json()).unwrap();
+        for dry_run in [true, false] {
+            let err = brain_import(
+                brain_path.to_str().unwrap(),
+                import_path.to_str().unwrap(),
+                0.5,
+                dry_run,
+            )
+            .unwrap_err();
+            assert!(matches!(
+                err,
+                DaemonError::IncompatibleBrain(BrainError::DimensionMismatch {
+                    local: 4,
+                    remote: 5
+                })
+            ));
+            assert_eq!(err.exit_code(), qres_daemon::error::EXIT_CONFIG);
+        }
+
+        let _ = fs::remove_file(brain_path);
+        let _ = fs::remove_file(import_path);
+    }
+
+    #[test]
+    fn test_diff_brain_against_self_and_perturbed_copy() {
+        let a_path = temp_path("diff_a.json");
+        let b_path = temp_path("diff_b.json");
+        let mut a = LivingBrain::new();
+        a.confidence = vec![0.2, 0.4, 0.6, 0.8];
+        fs::write(&a_path, a.to_json()).unwrap();
+
+        let same = compare_brain_files(a_path.to_str().unwrap(), a_path.to_str().unwrap()).unwrap();
+        assert!(same.confidence_delta.iter().all(|&d| d == 0.0));
+        assert_eq!(same.max_abs_delta, 0.0);
+        assert!(!same.global_confidence_presence_differs);
+        assert!((same.cosine_similarity - 1.0).abs() < 1e-6);
+
+        let mut b = a.clone();
+        b.confidence[2] = 0.1;
+        b.global_confidence = Some(b.confidence.clone());
+        fs::write(&b_path, b.to_json()).unwrap();
+        let perturbed =
+            compare_brain_files(a_path.to_str().unwrap(), b_path.to_str().unwrap()).unwrap();
+        assert!((perturbed.confidence_delta[2] + 0.5).abs() < 1e-6);
+        assert!((perturbed.max_abs_delta - 0.5).abs() < 1e-6);
+        assert!(perturbed.global_confidence_presence_differs);
+        assert!(perturbed.cosine_similarity < 1.0);
+
+        // Differing dimensions are reported, not truncated to the shared prefix
+        b.confidence.push(0.5);
+        fs::write(&b_path, b.to_json()).unwrap();
+        let err =
+            compare_brain_files(a_path.to_str().unwrap(), b_path.to_str().unwrap()).unwrap_err();
+        assert!(matches!(
+            err,
+            DaemonError::IncompatibleBrain(BrainError::DimensionMismatch {
+                local: 4,
+                remote: 5
+            })
+        ));
+        assert_eq!(err.exit_code(), qres_daemon::error::EXIT_CONFIG);
+
+        fs::write(&b_path, "not a brain").unwrap();
+        let err =
+            compare_brain_files(a_path.to_str().unwrap(), b_path.to_str().unwrap()).unwrap_err();
+        assert!(matches!(err, DaemonError::Io(ref e) if e.kind() == io::ErrorKind::InvalidData));
+
+        let _ = fs::remove_file(a_path);
+        let _ = fs::remove_file(b_path);
+    }
+
+    #[test]
+    fn test_dump_config_prefers_cli_override_over_file() {
+        let mut file_config: qres_daemon::config::Config = toml::from_str(
+            "[swarm]\ngossip_interval = 30\nwan_mode = true\nmax_peers = 8\ntransport = \"ws\"\n",
+        )
+        .unwrap();
+        file_config.security.swarm_secret = Some("hunter2".to_string());
+        let cli = Cli::try_parse_from([
+            "qres-cli",
+            "--window-size",
+            "64",
+            "swarm",
+            "--transport",
+            "quic",
+            "--no-mdns",
+            "--port",
+            "9191",
+            "--key",
+            "/tmp/cli_node_key",
+        ])
+        .unwrap();
+
+        let path = temp_path("dump_config.json");
+        dump_config(path.to_str().unwrap(), &cli.effective_config(file_config)).unwrap();
+        let dumped: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+
+        // CLI wins over the file; untouched file values and defaults survive
+        assert_eq!(dumped["daemon"]["swarm"]["transport"], "quic");
+        assert_eq!(dumped["daemon"]["swarm"]["mdns"], false);
+        assert_eq!(dumped["daemon"]["swarm"]["max_peers"], 8);
+        assert_eq!(dumped["daemon"]["api"]["port"], 9191);
+        assert_eq!(
+            dumped["daemon"]["security"]["key_path"],
+            "/tmp/cli_node_key"
+        );
+        assert_eq!(dumped["codec"]["window_size"], 64);
+        assert_eq!(dumped["brain"], DEFAULT_BRAIN_FILE);
+        // The swarm secret never reaches the dump file
+        assert_eq!(dumped["daemon"]["security"]["swarm_secret"], REDACTED);
+        assert!(!fs::read_to_string(&path).unwrap().contains("hunter2"));
+
+        let _ = fs::remove_file(path);
     }
 }
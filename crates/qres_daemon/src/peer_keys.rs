@@ -4,11 +4,26 @@
 //! populated via libp2p Identify protocol or manual configuration.
 //! Part of Phase 1 Item 2 of the security roadmap.
 
+use crate::security::KeyRotation;
 use libp2p::identity::PublicKey;
 use libp2p::PeerId;
 use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 use tracing::{info, warn};
 
+/// How the store regards the key that signed an update
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignerStatus {
+    /// A peer's current key
+    Current,
+    /// Replaced by a rotation but still inside its grace period
+    Previous,
+    /// Replaced by a rotation whose grace period has ended
+    Retired,
+    /// Not tracked by the store
+    Unknown,
+}
+
 /// Store for peer public keys, used for identity verification
 pub struct PeerKeyStore {
     /// Map of peer ID to their verified public key
@@ -17,6 +32,8 @@ pub struct PeerKeyStore {
     trusted_peer_ids: HashSet<PeerId>,
     /// Whether to allow any peer or only trusted ones
     whitelist_mode: bool,
+    /// Keys replaced by a rotation, with the end of their grace period
+    retired_keys: HashMap<PeerId, Instant>,
 }
 
 impl PeerKeyStore {
@@ -26,6 +43,7 @@ impl PeerKeyStore {
             keys: HashMap::new(),
             trusted_peer_ids: HashSet::new(),
             whitelist_mode: !trusted_peers.is_empty(),
+            retired_keys: HashMap::new(),
         };
 
         // Parse trusted peer IDs from config
@@ -43,11 +61,7 @@ impl PeerKeyStore {
             if let Ok(pubkey_bytes) = hex::decode(pubkey_hex) {
                 // Try to parse as ed25519 public key (32 bytes)
                 if pubkey_bytes.len() == 32 {
-                    if let Ok(ed_key) =
-                        libp2p::identity::ed25519::PublicKey::try_from_bytes(&pubkey_bytes)
-                    {
-                        let public_key = PublicKey::from(ed_key);
-                        let peer_id = PeerId::from_public_key(&public_key);
+                    if let Some((peer_id, public_key)) = ed25519_peer(pubkey_hex) {
                         store.keys.insert(peer_id, public_key);
                         store.trusted_peer_ids.insert(peer_id);
                         info!(peer_id = %peer_id, "Added trusted pubkey from config");
@@ -89,6 +103,54 @@ impl PeerKeyStore {
         true
     }
 
+    /// Classify the hex-encoded ed25519 key that signed an update.
+    pub fn signer_status(&self, pubkey_hex: &str, now: Instant) -> SignerStatus {
+        let Some((peer_id, _)) = ed25519_peer(pubkey_hex) else {
+            return SignerStatus::Unknown;
+        };
+        if self.keys.contains_key(&peer_id) {
+            return SignerStatus::Current;
+        }
+        match self.retired_keys.get(&peer_id) {
+            Some(expires) if now < *expires => SignerStatus::Previous,
+            Some(_) => SignerStatus::Retired,
+            None => SignerStatus::Unknown,
+        }
+    }
+
+    /// Follow a peer's key rotation: the endorsed key replaces the previous
+    /// one, which stays acceptable for `grace`. Only rotations away from a
+    /// key the store already holds are applied; re-announcing an applied
+    /// rotation is a no-op and does not extend the grace period.
+    pub fn apply_rotation(
+        &mut self,
+        rotation: &KeyRotation,
+        grace: Duration,
+        now: Instant,
+    ) -> bool {
+        let (Some((old_id, _)), Some((new_id, new_key))) = (
+            ed25519_peer(&rotation.previous_pubkey),
+            ed25519_peer(&rotation.current_pubkey),
+        ) else {
+            return false;
+        };
+        if self.retired_keys.contains_key(&old_id) && self.keys.contains_key(&new_id) {
+            return true;
+        }
+        if !self.keys.contains_key(&old_id) || !rotation.verify() {
+            return false;
+        }
+
+        self.keys.remove(&old_id);
+        self.keys.insert(new_id, new_key);
+        if self.trusted_peer_ids.remove(&old_id) {
+            self.trusted_peer_ids.insert(new_id);
+        }
+        self.retired_keys.insert(old_id, now + grace);
+        info!(previous = %old_id, current = %new_id, grace_secs = grace.as_secs(), "Applied peer key rotation");
+        true
+    }
+
     /// Get the public key for a peer
     pub fn get_key(&self, peer_id: &PeerId) -> Option<PublicKey> {
         self.keys.get(peer_id).cloned()
@@ -131,6 +193,14 @@ impl PeerKeyStore {
     }
 }
 
+/// Parse a hex-encoded ed25519 key and derive its PeerId
+fn ed25519_peer(pubkey_hex: &str) -> Option<(PeerId, PublicKey)> {
+    let bytes = hex::decode(pubkey_hex).ok()?;
+    let ed_key = libp2p::identity::ed25519::PublicKey::try_from_bytes(&bytes).ok()?;
+    let public_key = PublicKey::from(ed_key);
+    Some((PeerId::from_public_key(&public_key), public_key))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -184,4 +254,41 @@ mod tests {
         // Try to add keypair2's public key for peer_id1 (should fail)
         assert!(!store.add_peer_key(peer_id1, keypair2.public()));
     }
+
+    #[test]
+    fn test_repeated_rotation_does_not_extend_grace() {
+        let dir = std::env::temp_dir();
+        let old_path = dir.join("test_qres_peer_keys_rotation_old");
+        let new_path = dir.join("test_qres_peer_keys_rotation_new");
+        let _ = std::fs::remove_file(&old_path);
+        let _ = std::fs::remove_file(&new_path);
+
+        let old = crate::security::SecurityManager::new(&old_path, true).unwrap();
+        let mut new = crate::security::SecurityManager::new(&new_path, true).unwrap();
+        new.load_previous_key(&old_path).unwrap();
+        let rotation = new.key_rotation().unwrap();
+
+        let mut store = PeerKeyStore::new(&[], &[old.public_key_hex()]);
+        let grace = Duration::from_secs(60);
+        let start = Instant::now();
+        assert!(store.apply_rotation(&rotation, grace, start));
+        // Every later update re-announces the same rotation
+        assert!(store.apply_rotation(&rotation, grace, start + Duration::from_secs(50)));
+
+        assert_eq!(
+            store.signer_status(&new.public_key_hex(), start + grace),
+            SignerStatus::Current
+        );
+        assert_eq!(
+            store.signer_status(&old.public_key_hex(), start + Duration::from_secs(59)),
+            SignerStatus::Previous
+        );
+        assert_eq!(
+            store.signer_status(&old.public_key_hex(), start + grace),
+            SignerStatus::Retired
+        );
+
+        let _ = std::fs::remove_file(&old_path);
+        let _ = std::fs::remove_file(&new_path);
+    }
 }
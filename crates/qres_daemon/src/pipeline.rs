@@ -0,0 +1,1454 @@
+//! Chunked Compression Pipeline
+//!
+//! The framed file format shared by the `compress`/`decompress` commands and
+//! library callers: a header recording the brain hash, structural transform
+//! and chunk size, followed by length-prefixed chunks. Each chunk goes through
+//! the core codec, or through the Zstd/LZ4 fallback when the core codec would
+//! expand it. [`compress_bytes_to_vec`] and [`decompress_bytes_from_vec`] run
+//! the whole pipeline in memory.
+
+use crate::error::DaemonError;
+use crate::living_brain::LivingBrain;
+use crate::lz4;
+use crate::transform::StructuralTransform;
+use qres_core::predictors::suggest_predictor;
+use qres_core::{
+    compress_chunk,
+    config::{CompressionMode, PredictorType, QresConfig},
+    decompress_chunk_with_state, read_chunk_header, PredictorSet, QresError, PREDICTOR_ID_DEFAULT,
+    PREDICTOR_ID_NEURAL,
+};
+use rayon::prelude::*;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tracing::info;
+
+/// Default chunk size (64KB); `--chunk-size` picks another within the range below
+pub const CHUNK_SIZE: usize = 64 * 1024;
+pub const MIN_CHUNK_SIZE: usize = 4 * 1024;
+pub const MAX_CHUNK_SIZE: usize = qres_core::MAX_RUN_CHUNK_LEN;
+
+/// File header written by `compress_file`.
+/// Format: [Magic: QRHD] [Version:1] [BLAKE3(brain weights):32] [Transform Id:1]
+///         [Chunk Size:4 LE] [Chunks...]
+/// Version 1 headers have no chunk size field and used `CHUNK_SIZE`.
+const FILE_MAGIC: &[u8; 4] = b"QRHD";
+const FILE_HEADER_VERSION: u8 = 2;
+const FILE_HEADER_LEN: usize = 4 + 1 + 32 + 1 + 4;
+const FILE_HEADER_V1_LEN: usize = 4 + 1 + 32 + 1;
+/// Legacy header of transformed streams: [Magic: QRTF] [Transform Id:1] [Chunks...]
+/// Older untransformed streams are bare chunks. Neither carries a brain hash.
+const TRANSFORM_MAGIC: &[u8; 4] = b"QRTF";
+/// Chunk codec mode of chunks stored with the Zstd fallback
+const CODEC_MODE_ZSTD: u8 = 0x01;
+/// Chunk codec mode of chunks stored with the LZ4 fallback (0x02 is the neural mode)
+const CODEC_MODE_LZ4: u8 = 0x05;
+
+/// General-purpose codec storing chunks the core codec would expand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum FallbackCodec {
+    /// Better ratio (level 3)
+    Zstd,
+    /// Far less CPU, for power-constrained nodes where ratio matters less
+    Lz4,
+}
+
+/// Parse `--chunk-size`, rejecting sizes outside `MIN_CHUNK_SIZE..=MAX_CHUNK_SIZE`.
+pub fn parse_chunk_size(arg: &str) -> Result<usize, String> {
+    let size: usize = arg.parse().map_err(|e| format!("{}", e))?;
+    if (MIN_CHUNK_SIZE..=MAX_CHUNK_SIZE).contains(&size) {
+        Ok(size)
+    } else {
+        Err(format!(
+            "chunk size must be between {} and {} bytes",
+            MIN_CHUNK_SIZE, MAX_CHUNK_SIZE
+        ))
+    }
+}
+
+/// Load the brain at `path`, falling back to the default brain when it is absent.
+pub fn load_brain(path: &str) -> LivingBrain {
+    match fs::read_to_string(path) {
+        Ok(json) => LivingBrain::from_json(&json).unwrap_or_default(),
+        Err(e) => {
+            info!(brain_path = path, error = %e, "Brain file not loaded, using default brain");
+            LivingBrain::default()
+        }
+    }
+}
+
+/// Serialize brain weights for `compress_chunk` (Init + Global/FedProx).
+fn brain_weights(brain: &LivingBrain) -> Vec<u8> {
+    let mut w_bytes = Vec::with_capacity(80);
+    // 1. Initial Weights
+    for &f in &brain.confidence {
+        w_bytes.extend_from_slice(&f.to_le_bytes());
+    }
+    // 2. Global Weights (FedProx)
+    if let Some(g) = &brain.global_confidence {
+        for &f in g {
+            w_bytes.extend_from_slice(&f.to_le_bytes());
+        }
+    }
+    w_bytes
+}
+
+/// Weights to compress `chunk` with: with `--predictor auto` (a `probe` is
+/// given) the brain weights are kept only if they win a trial encode. The
+/// chunk header records the choice, so the decoder follows it per chunk.
+fn chunk_weights<'a>(
+    chunk: &[u8],
+    weights: Option<&'a [u8]>,
+    probe: Option<&mut PredictorSet>,
+) -> Option<&'a [u8]> {
+    match probe {
+        Some(probe) => {
+            weights.filter(|w| probe.best_predictor_for(chunk, Some(w)) == PREDICTOR_ID_NEURAL)
+        }
+        None => weights,
+    }
+}
+
+/// Totals reported by the file compression commands.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileStats {
+    /// Bytes read (the compressed stream, when decompressing)
+    pub input_bytes: u64,
+    /// Bytes written
+    pub output_bytes: u64,
+    /// Chunks in the stream
+    pub chunks: usize,
+    /// Chunks stored with the fallback codec because the core codec would expand them
+    pub fallback_chunks: usize,
+}
+
+/// Settings of a `compress` run beyond the codec configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressOptions {
+    /// Reversible structural transform applied before compression
+    pub transform: StructuralTransform,
+    /// Worker threads compressing chunks in parallel
+    pub jobs: usize,
+    /// Pick the starting predictor from the first chunk's contents
+    pub auto_predictor: bool,
+    /// Bytes of input per chunk
+    pub chunk_size: usize,
+    /// Codec for chunks the core codec would expand
+    pub fallback: FallbackCodec,
+}
+
+impl Default for CompressOptions {
+    fn default() -> Self {
+        Self {
+            transform: StructuralTransform::None,
+            jobs: 1,
+            auto_predictor: false,
+            chunk_size: CHUNK_SIZE,
+            fallback: FallbackCodec::Zstd,
+        }
+    }
+}
+
+/// Per-chunk progress callback, given cumulative `(bytes_in, bytes_out)`.
+pub type Progress<'a> = Option<&'a mut dyn FnMut(u64, u64)>;
+
+/// How often parallel compression reports progress while chunks are in flight
+const PARALLEL_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Compress one chunk, falling back to `fallback` when the core codec would expand it.
+fn compress_file_chunk(
+    chunk: &[u8],
+    weights_arg: Option<&[u8]>,
+    config: &QresConfig,
+    fallback: FallbackCodec,
+) -> Result<Vec<u8>, DaemonError> {
+    // Allocate buffer (worst case estimate)
+    let mut comp_buffer = vec![0u8; chunk.len() + 4096];
+    match compress_chunk(chunk, 0, weights_arg, Some(config), &mut comp_buffer) {
+        Ok(len) => Ok(comp_buffer[..len].to_vec()),
+        Err(QresError::CompressionError(_)) => {
+            // Core failed (expansion). Use the fallback codec.
+            let (mode, payload) = match fallback {
+                FallbackCodec::Zstd => (
+                    CODEC_MODE_ZSTD,
+                    zstd::bulk::compress(chunk, 3).map_err(DaemonError::Fallback)?,
+                ),
+                FallbackCodec::Lz4 => (CODEC_MODE_LZ4, lz4::compress(chunk)),
+            };
+            let ver = 0x0A;
+            let flag_byte = (ver << 4) | mode;
+
+            let mut out = Vec::with_capacity(5 + payload.len());
+            out.push(flag_byte);
+            out.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+            out.extend_from_slice(&payload);
+            Ok(out)
+        }
+        Err(e) => Err(DaemonError::Codec(e)),
+    }
+}
+
+/// Compress a file, reporting progress to `progress` after every chunk
+/// (with `jobs > 1`, periodically while chunks compress in parallel).
+pub fn compress_file(
+    input: &str,
+    output: &str,
+    brain_path: &str,
+    config: &QresConfig,
+    options: &CompressOptions,
+    progress: Progress<'_>,
+) -> Result<FileStats, DaemonError> {
+    // Structural transforms work on whole rows, so the input is read up front
+    let input_file: Box<dyn Read> = match options.transform {
+        StructuralTransform::None => Box::new(File::open(input)?),
+        t => Box::new(io::Cursor::new(t.forward(&fs::read(input)?)?)),
+    };
+    let mut output_file = File::create(output)?;
+    compress_stream(
+        input_file,
+        &mut output_file,
+        &load_brain(brain_path),
+        config,
+        options,
+        progress,
+    )
+}
+
+/// Compress already-transformed `input` into the framed file format.
+pub fn compress_stream<R: Read, W: Write>(
+    input: R,
+    output_file: &mut W,
+    brain: &LivingBrain,
+    config: &QresConfig,
+    options: &CompressOptions,
+    mut progress: Progress<'_>,
+) -> Result<FileStats, DaemonError> {
+    let chunk_size = options.chunk_size;
+    // Buffered to exactly one chunk so the first chunk can be inspected up front
+    let mut input_file = BufReader::with_capacity(chunk_size, input);
+
+    let w_bytes = brain_weights(brain);
+
+    // The decoder must rebuild the same weights; record their hash to catch a changed brain
+    output_file.write_all(FILE_MAGIC)?;
+    output_file.write_all(&[FILE_HEADER_VERSION])?;
+    output_file.write_all(blake3::hash(&w_bytes).as_bytes())?;
+    output_file.write_all(&[options.transform.id()])?;
+    output_file.write_all(&(chunk_size as u32).to_le_bytes())?;
+    let mut weights_arg = if w_bytes.is_empty() {
+        None
+    } else {
+        Some(w_bytes.as_slice())
+    };
+    if options.auto_predictor {
+        // Only the neural predictor benefits from the brain weights
+        let predictor = suggest_predictor(input_file.fill_buf()?);
+        info!(predictor_id = predictor, "Auto-selected starting predictor");
+        if predictor != PREDICTOR_ID_NEURAL {
+            weights_arg = None;
+        }
+    }
+
+    // Predictor memory reused by the per-chunk trial encodes
+    let new_probe = || (config.predictor == PredictorType::Auto).then(PredictorSet::default);
+
+    let mut stats = FileStats::default();
+    let start = std::time::Instant::now();
+
+    if options.jobs > 1 {
+        // Chunks are independent, so compress them all at once and write in
+        // order. Workers count finished bytes; the calling thread (which owns
+        // the callback) reports them while it waits.
+        let mut data = Vec::new();
+        input_file.read_to_end(&mut data)?;
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(options.jobs)
+            .build()
+            .map_err(io::Error::other)?;
+        let (done_in, done_out) = (AtomicU64::new(0), AtomicU64::new(0));
+        let caller = std::thread::current();
+        let compressed: Vec<Vec<u8>> = std::thread::scope(|scope| {
+            let worker = scope.spawn(|| {
+                let result = pool.install(|| {
+                    data.par_chunks(chunk_size)
+                        .map_init(new_probe, |probe, chunk| {
+                            let weights = chunk_weights(chunk, weights_arg, probe.as_mut());
+                            let out =
+                                compress_file_chunk(chunk, weights, config, options.fallback)?;
+                            done_in.fetch_add(chunk.len() as u64, Ordering::Relaxed);
+                            done_out.fetch_add(out.len() as u64 + 4, Ordering::Relaxed);
+                            Ok(out)
+                        })
+                        .collect::<Result<_, DaemonError>>()
+                });
+                caller.unpark();
+                result
+            });
+            while !worker.is_finished() {
+                if let Some(progress) = progress.as_mut() {
+                    progress(
+                        done_in.load(Ordering::Relaxed),
+                        done_out.load(Ordering::Relaxed),
+                    );
+                }
+                std::thread::park_timeout(PARALLEL_PROGRESS_INTERVAL);
+            }
+            worker
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+        })?;
+        for (chunk, out) in data.chunks(chunk_size).zip(&compressed) {
+            write_chunk(output_file, &mut stats, chunk.len(), out)?;
+        }
+        if let Some(progress) = progress.as_mut() {
+            progress(stats.input_bytes, stats.output_bytes);
+        }
+    } else {
+        let mut buffer = vec![0u8; chunk_size];
+        let mut probe = new_probe();
+        loop {
+            let bytes_read = input_file.read(&mut buffer)?;
+            if bytes_read == 0 {
+                break;
+            }
+
+            let chunk = &buffer[..bytes_read];
+            let weights = chunk_weights(chunk, weights_arg, probe.as_mut());
+            let compressed = compress_file_chunk(chunk, weights, config, options.fallback)?;
+            write_chunk(output_file, &mut stats, bytes_read, &compressed)?;
+            if let Some(progress) = progress.as_mut() {
+                progress(stats.input_bytes, stats.output_bytes);
+            }
+        }
+    }
+
+    let elapsed = start.elapsed();
+    let (total_input, total_output) = (stats.input_bytes, stats.output_bytes);
+    let ratio = if total_input > 0 {
+        (total_output as f64 / total_input as f64) * 100.0
+    } else {
+        0.0
+    };
+
+    info!(
+        total_input_bytes = total_input,
+        total_output_bytes = total_output,
+        fallback_chunks = stats.fallback_chunks,
+        ratio_percent = ratio,
+        duration_secs = elapsed.as_secs_f64(),
+        throughput_mb_s = if elapsed.as_secs_f64() > 0.0 {
+            (total_input as f64 / 1024.0 / 1024.0) / elapsed.as_secs_f64()
+        } else {
+            0.0
+        },
+        "Compression Complete"
+    );
+
+    Ok(stats)
+}
+
+/// Write one framed chunk (`[len:4][chunk]`) and account for it in `stats`.
+fn write_chunk<W: Write>(
+    output_file: &mut W,
+    stats: &mut FileStats,
+    chunk_len: usize,
+    compressed: &[u8],
+) -> io::Result<()> {
+    output_file.write_all(&(compressed.len() as u32).to_le_bytes())?;
+    output_file.write_all(compressed)?;
+
+    stats.input_bytes += chunk_len as u64;
+    stats.output_bytes += compressed.len() as u64 + 4;
+    stats.chunks += 1;
+    if matches!(compressed[0] & 0x0F, CODEC_MODE_ZSTD | CODEC_MODE_LZ4) {
+        stats.fallback_chunks += 1;
+    }
+    Ok(())
+}
+
+/// Compress `data` in memory through the full chunked pipeline (including
+/// the fallback codec), producing the same bytes `compress_file` would write.
+pub fn compress_bytes_to_vec(
+    data: &[u8],
+    config: &QresConfig,
+    brain: &LivingBrain,
+) -> Result<Vec<u8>, DaemonError> {
+    let mut packed = Vec::with_capacity(FILE_HEADER_LEN + data.len() / 2);
+    compress_stream(
+        data,
+        &mut packed,
+        brain,
+        config,
+        &CompressOptions::default(),
+        None,
+    )?;
+    Ok(packed)
+}
+
+/// Decode bytes produced by `compress_bytes_to_vec` (or read from a compressed file).
+pub fn decompress_bytes_from_vec(data: &[u8], brain: &LivingBrain) -> Result<Vec<u8>, DaemonError> {
+    let mut restored = Vec::new();
+    decompress_stream(&mut &data[..], &mut restored, brain, |_| {}, None)?;
+    Ok(restored)
+}
+
+/// Per-chunk report produced by `decompress_file_with_trace`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkTrace {
+    /// Position of the chunk in the stream
+    pub index: usize,
+    /// Predictor id read from the chunk header (`None` for fallback chunks)
+    pub predictor_id: Option<u8>,
+    /// Number of bytes the chunk decoded to
+    pub decoded_bytes: usize,
+}
+
+/// Decompress a file, reporting progress to `progress` after every chunk.
+pub fn decompress_file(
+    input: &str,
+    output: &str,
+    brain_path: &str,
+    progress: Progress<'_>,
+) -> Result<FileStats, DaemonError> {
+    decompress_file_with_trace(input, output, brain_path, |_| {}, progress)
+}
+
+/// Decompress a file, reporting the predictor used by each chunk to `trace`
+/// and the bytes consumed and produced so far to `progress`.
+///
+/// Pairs with the encoder's per-chunk predictor stamping so that a decode
+/// mismatch can be narrowed down to the chunk (and predictor) that caused it.
+pub fn decompress_file_with_trace<F>(
+    input: &str,
+    output: &str,
+    brain_path: &str,
+    trace: F,
+    progress: Progress<'_>,
+) -> Result<FileStats, DaemonError>
+where
+    F: FnMut(&ChunkTrace),
+{
+    let mut input_file = File::open(input)?;
+    let mut output_file = File::create(output)?;
+    decompress_stream(
+        &mut input_file,
+        &mut output_file,
+        &load_brain(brain_path),
+        trace,
+        progress,
+    )
+}
+
+/// Decode a framed stream produced by `compress_stream`.
+pub fn decompress_stream<R: Read, W: Write, F>(
+    input_file: &mut R,
+    output_file: &mut W,
+    brain: &LivingBrain,
+    mut trace: F,
+    mut progress: Progress<'_>,
+) -> Result<FileStats, DaemonError>
+where
+    F: FnMut(&ChunkTrace),
+{
+    // Must match the brain the encoder used
+    let w_bytes = brain_weights(brain);
+    let weights_arg = if w_bytes.is_empty() {
+        None
+    } else {
+        Some(w_bytes.as_slice())
+    };
+
+    let mut stats = FileStats::default();
+    let start = std::time::Instant::now();
+
+    // OPTIMIZATION: Allocate PredictorSet ONCE (saves ~22MB allocation per chunk)
+    // The PredictorSet is reset internally by decompress_chunk_with_state before each use
+    let mut predictor_state = PredictorSet::new(None, None);
+    let mut chunk_index = 0usize;
+
+    // File header (current or legacy); bare streams start with a chunk size
+    let mut transform = StructuralTransform::None;
+    // Chunk size recorded by the encoder; older streams did not record one
+    let mut chunk_limit = None;
+    let mut pending_size = None;
+    let mut first_word = [0u8; 4];
+    match input_file.read_exact(&mut first_word) {
+        Ok(_) if &first_word == FILE_MAGIC => {
+            let mut version = [0u8; 1];
+            input_file
+                .read_exact(&mut version)
+                .map_err(|e| DaemonError::from_stream_read(e, "file header"))?;
+            let header_len = match version[0] {
+                1 => FILE_HEADER_V1_LEN,
+                FILE_HEADER_VERSION => FILE_HEADER_LEN,
+                v => return Err(DaemonError::UnsupportedVersion(v)),
+            };
+            let mut header = vec![0u8; header_len - 5];
+            input_file
+                .read_exact(&mut header)
+                .map_err(|e| DaemonError::from_stream_read(e, "file header"))?;
+            if header[..32] != *blake3::hash(&w_bytes).as_bytes() {
+                return Err(DaemonError::BrainMismatch);
+            }
+            transform = StructuralTransform::from_id(header[32])
+                .map_err(|e| DaemonError::CorruptStream(e.to_string()))?;
+            if let Some(size) = header.get(33..37) {
+                let size = u32::from_le_bytes(size.try_into().unwrap()) as usize;
+                if !(MIN_CHUNK_SIZE..=MAX_CHUNK_SIZE).contains(&size) {
+                    return Err(DaemonError::CorruptStream(format!(
+                        "invalid chunk size {}",
+                        size
+                    )));
+                }
+                chunk_limit = Some(size);
+            }
+            stats.input_bytes = header_len as u64;
+        }
+        Ok(_) if &first_word == TRANSFORM_MAGIC => {
+            let mut id = [0u8; 1];
+            input_file
+                .read_exact(&mut id)
+                .map_err(|e| DaemonError::from_stream_read(e, "file header"))?;
+            transform = StructuralTransform::from_id(id[0])
+                .map_err(|e| DaemonError::CorruptStream(e.to_string()))?;
+            stats.input_bytes = 5;
+        }
+        Ok(_) => pending_size = Some(first_word),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {}
+        Err(e) => return Err(DaemonError::Io(e)),
+    }
+    let mut structured = Vec::new();
+
+    loop {
+        // Read chunk size
+        let size_buf = match pending_size.take() {
+            Some(buf) => buf,
+            None => {
+                let mut buf = [0u8; 4];
+                match input_file.read_exact(&mut buf) {
+                    Ok(_) => buf,
+                    Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+                    Err(e) => return Err(DaemonError::Io(e)),
+                }
+            }
+        };
+
+        let chunk_size = u32::from_le_bytes(size_buf) as usize;
+        // A stored chunk never exceeds its input by more than the fallback
+        // codecs' framing, so a larger size prefix means corruption
+        if let Some(limit) = chunk_limit {
+            let bound = zstd::zstd_safe::compress_bound(limit).max(lz4::max_compressed_len(limit));
+            if chunk_size > bound + 5 {
+                return Err(DaemonError::CorruptStream(format!(
+                    "chunk {} is {} bytes, too large for {} byte chunks",
+                    chunk_index, chunk_size, limit
+                )));
+            }
+        }
+
+        // Read compressed chunk
+        let mut compressed = vec![0u8; chunk_size];
+        input_file
+            .read_exact(&mut compressed)
+            .map_err(|e| DaemonError::from_stream_read(e, &format!("chunk {}", chunk_index)))?;
+        stats.input_bytes += 4 + chunk_size as u64;
+
+        let header = read_chunk_header(&compressed)
+            .map_err(|e| DaemonError::CorruptStream(e.to_string()))?;
+        if chunk_limit.is_some_and(|limit| header.decoded_len > limit) {
+            return Err(DaemonError::CorruptStream(format!(
+                "chunk {} decodes to {} bytes, more than the recorded chunk size",
+                chunk_index, header.decoded_len
+            )));
+        }
+
+        // Standard-mode chunks are encoded without brain weights (e.g. picked by
+        // --auto-predictor or --predictor auto), so the decoder must not apply them either
+        let chunk_weights = if header.predictor_id == Some(PREDICTOR_ID_DEFAULT) {
+            None
+        } else {
+            weights_arg
+        };
+
+        // Fallback chunks are [Header:1][UncompressedLen:4][Payload...]; the
+        // header parse above already checked the length. Every chunk was
+        // produced by a codec that can decode it, so a failure here means the
+        // stream is corrupt.
+        let decompressed = match header.codec_mode {
+            CODEC_MODE_ZSTD => {
+                stats.fallback_chunks += 1;
+                zstd::bulk::decompress(&compressed[5..], header.decoded_len)
+                    .map_err(|e| corrupt_chunk(chunk_index, e))?
+            }
+            CODEC_MODE_LZ4 => {
+                stats.fallback_chunks += 1;
+                lz4::decompress(&compressed[5..], header.decoded_len)
+                    .map_err(|e| corrupt_chunk(chunk_index, e))?
+            }
+            // Decompress using reusable predictor state (eliminates ~22MB alloc/dealloc per chunk)
+            _ => decompress_chunk_with_state(&compressed, 0, chunk_weights, &mut predictor_state)
+                .map_err(|e| corrupt_chunk(chunk_index, e))?,
+        };
+        if decompressed.len() != header.decoded_len {
+            return Err(corrupt_chunk(
+                chunk_index,
+                format!(
+                    "decoded {} bytes, header says {}",
+                    decompressed.len(),
+                    header.decoded_len
+                ),
+            ));
+        }
+
+        if transform == StructuralTransform::None {
+            output_file.write_all(&decompressed)?;
+        } else {
+            structured.extend_from_slice(&decompressed);
+        }
+
+        trace(&ChunkTrace {
+            index: chunk_index,
+            predictor_id: header.predictor_id,
+            decoded_bytes: decompressed.len(),
+        });
+        chunk_index += 1;
+        stats.chunks += 1;
+
+        stats.output_bytes += decompressed.len() as u64;
+
+        if let Some(progress) = progress.as_mut() {
+            progress(stats.input_bytes, stats.output_bytes);
+        }
+    }
+
+    if transform != StructuralTransform::None {
+        let restored = transform
+            .inverse(&structured)
+            .map_err(|e| DaemonError::CorruptStream(e.to_string()))?;
+        output_file.write_all(&restored)?;
+    }
+
+    let elapsed = start.elapsed();
+    let total_output = stats.output_bytes;
+    info!(
+        total_output_bytes = total_output,
+        duration_secs = elapsed.as_secs_f64(),
+        throughput_mb_s = if elapsed.as_secs_f64() > 0.0 {
+            (total_output as f64 / 1024.0 / 1024.0) / elapsed.as_secs_f64()
+        } else {
+            0.0
+        },
+        "Decompression Complete"
+    );
+
+    Ok(stats)
+}
+
+/// A chunk that its codec could not decode.
+fn corrupt_chunk(index: usize, err: impl std::fmt::Display) -> DaemonError {
+    DaemonError::CorruptStream(format!("chunk {} failed to decode: {}", index, err))
+}
+
+/// Largest byte error lossy mode may introduce: the core quantizes residuals
+/// to multiples of 5, truncating toward zero.
+pub const LOSSY_MAX_ABS_ERROR: u8 = 4;
+
+/// Outcome of `verify_file`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyReport {
+    pub input_bytes: usize,
+    pub compressed_bytes: usize,
+    /// Largest absolute byte difference after the round trip
+    pub max_abs_error: u8,
+    /// Largest `max_abs_error` the mode allows (0 unless lossy)
+    pub tolerance: u8,
+    /// Lengths match and `max_abs_error` is within `tolerance`
+    pub passed: bool,
+}
+
+impl VerifyReport {
+    pub fn ratio_percent(&self) -> f64 {
+        if self.input_bytes > 0 {
+            (self.compressed_bytes as f64 / self.input_bytes as f64) * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Compress `input` to memory with the current brain/config and decode it back.
+pub fn verify_file(
+    input: &str,
+    brain_path: &str,
+    config: &QresConfig,
+) -> Result<VerifyReport, DaemonError> {
+    let data = fs::read(input)?;
+    let brain = load_brain(brain_path);
+
+    let packed = compress_bytes_to_vec(&data, config, &brain)?;
+    let restored = decompress_bytes_from_vec(&packed, &brain)?;
+
+    let max_abs_error = data
+        .iter()
+        .zip(&restored)
+        .map(|(a, b)| a.abs_diff(*b))
+        .max()
+        .unwrap_or(0);
+    let tolerance = if config.mode == CompressionMode::Lossy {
+        LOSSY_MAX_ABS_ERROR
+    } else {
+        0
+    };
+
+    Ok(VerifyReport {
+        input_bytes: data.len(),
+        compressed_bytes: packed.len(),
+        max_abs_error,
+        tolerance,
+        passed: restored.len() == data.len() && max_abs_error <= tolerance,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Brain path that never exists, so tests use the default brain
+    /// regardless of the working directory.
+    const NO_BRAIN: &str = "qres_test_missing_brain.json";
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("qres_pipeline_{}_{}", std::process::id(), name))
+    }
+
+    /// One compressible chunk followed by one pseudo-random (fallback) chunk.
+    fn mixed_input() -> Vec<u8> {
+        let mut data: Vec<u8> = (0..CHUNK_SIZE)
+            .map(|i| ((i % 256) ^ ((i / 256) % 256)) as u8)
+            .collect();
+        let mut rng_state: u32 = 0xDEADBEEF;
+        data.extend((0..CHUNK_SIZE).map(|_| {
+            rng_state = rng_state.wrapping_mul(1103515245).wrapping_add(12345);
+            (rng_state >> 16) as u8
+        }));
+        data
+    }
+
+    #[test]
+    fn test_all_zero_file_compresses_to_run_chunks() {
+        let input = temp_path("zeros_in");
+        let packed = temp_path("zeros_packed");
+        let unpacked = temp_path("zeros_out");
+        let data = vec![0u8; 1024 * 1024];
+        fs::write(&input, &data).unwrap();
+
+        compress_file(
+            input.to_str().unwrap(),
+            packed.to_str().unwrap(),
+            NO_BRAIN,
+            &QresConfig::default(),
+            &CompressOptions::default(),
+            None,
+        )
+        .unwrap();
+        let chunks = data.len().div_ceil(CHUNK_SIZE);
+        let packed_len = fs::metadata(&packed).unwrap().len() as usize;
+        assert!(
+            packed_len <= FILE_HEADER_LEN + chunks * 10,
+            "{} bytes for {} constant chunks",
+            packed_len,
+            chunks
+        );
+
+        decompress_file(
+            packed.to_str().unwrap(),
+            unpacked.to_str().unwrap(),
+            NO_BRAIN,
+            None,
+        )
+        .unwrap();
+        assert_eq!(fs::read(&unpacked).unwrap(), data);
+
+        let _ = fs::remove_file(&input);
+        let _ = fs::remove_file(&packed);
+        let _ = fs::remove_file(&unpacked);
+    }
+
+    #[test]
+    fn test_in_memory_roundtrip_with_fallback_chunk() {
+        let data = mixed_input();
+        let brain = LivingBrain::default();
+        let config = QresConfig::default();
+
+        let packed = compress_bytes_to_vec(&data, &config, &brain).unwrap();
+        assert_eq!(&packed[..4], FILE_MAGIC);
+        // The random second chunk must have taken the Zstd fallback path
+        let first_len = u32::from_le_bytes(
+            packed[FILE_HEADER_LEN..FILE_HEADER_LEN + 4]
+                .try_into()
+                .unwrap(),
+        ) as usize;
+        let second = &packed[FILE_HEADER_LEN + 4 + first_len + 4..];
+        assert_eq!(read_chunk_header(second).unwrap().predictor_id, None);
+
+        assert_eq!(decompress_bytes_from_vec(&packed, &brain).unwrap(), data);
+    }
+
+    /// Compress `data` in memory with the given fallback codec.
+    fn pack_with_fallback(data: &[u8], fallback: FallbackCodec) -> (Vec<u8>, FileStats) {
+        let mut packed = Vec::new();
+        let stats = compress_stream(
+            data,
+            &mut packed,
+            &LivingBrain::default(),
+            &QresConfig::default(),
+            &CompressOptions {
+                fallback,
+                ..CompressOptions::default()
+            },
+            None,
+        )
+        .unwrap();
+        (packed, stats)
+    }
+
+    /// Codec mode of the chunk starting at `pos`, and the offset of the next chunk.
+    fn chunk_mode_at(packed: &[u8], pos: usize) -> (u8, usize) {
+        let len = u32::from_le_bytes(packed[pos..pos + 4].try_into().unwrap()) as usize;
+        (packed[pos + 4] & 0x0F, pos + 4 + len)
+    }
+
+    #[test]
+    fn test_lz4_fallback_round_trip() {
+        let data = mixed_input();
+        let (packed, stats) = pack_with_fallback(&data, FallbackCodec::Lz4);
+        assert_eq!(stats.fallback_chunks, 1);
+
+        let (_, second) = chunk_mode_at(&packed, FILE_HEADER_LEN);
+        assert_eq!(chunk_mode_at(&packed, second).0, CODEC_MODE_LZ4);
+
+        let mut restored = Vec::new();
+        let stats = decompress_stream(
+            &mut &packed[..],
+            &mut restored,
+            &LivingBrain::default(),
+            |_| {},
+            None,
+        )
+        .unwrap();
+        assert_eq!(restored, data);
+        assert_eq!(stats.fallback_chunks, 1);
+    }
+
+    #[test]
+    fn test_mixed_zstd_and_lz4_chunks_decode() {
+        let data = mixed_input();
+        let (mut packed, _) = pack_with_fallback(&data, FallbackCodec::Zstd);
+        let (lz4_packed, _) = pack_with_fallback(&data, FallbackCodec::Lz4);
+
+        // Append the LZ4 stream's fallback chunk after the Zstd stream's chunks
+        let (_, second) = chunk_mode_at(&packed, FILE_HEADER_LEN);
+        assert_eq!(chunk_mode_at(&packed, second).0, CODEC_MODE_ZSTD);
+        let (_, lz4_second) = chunk_mode_at(&lz4_packed, FILE_HEADER_LEN);
+        packed.extend_from_slice(&lz4_packed[lz4_second..]);
+
+        let mut expected = data.clone();
+        expected.extend_from_slice(&data[CHUNK_SIZE..]);
+        let mut restored = Vec::new();
+        let stats = decompress_stream(
+            &mut &packed[..],
+            &mut restored,
+            &LivingBrain::default(),
+            |_| {},
+            None,
+        )
+        .unwrap();
+        assert_eq!(restored, expected);
+        assert_eq!(stats.chunks, 3);
+        assert_eq!(stats.fallback_chunks, 2);
+    }
+
+    #[test]
+    fn test_trace_reports_encoder_predictor_ids() {
+        let input = temp_path("trace_in");
+        let packed = temp_path("trace_packed");
+        let unpacked = temp_path("trace_out");
+        let data = mixed_input();
+        fs::write(&input, &data).unwrap();
+
+        compress_file(
+            input.to_str().unwrap(),
+            packed.to_str().unwrap(),
+            NO_BRAIN,
+            &QresConfig::default(),
+            &CompressOptions::default(),
+            None,
+        )
+        .unwrap();
+
+        // Predictor ids as written by the encoder, read straight off the stream
+        let stream = fs::read(&packed).unwrap();
+        let mut written = Vec::new();
+        let mut pos = FILE_HEADER_LEN;
+        while pos < stream.len() {
+            let len = u32::from_le_bytes(stream[pos..pos + 4].try_into().unwrap()) as usize;
+            pos += 4;
+            written.push(
+                read_chunk_header(&stream[pos..pos + len])
+                    .unwrap()
+                    .predictor_id,
+            );
+            pos += len;
+        }
+        assert_eq!(written, vec![Some(qres_core::PREDICTOR_ID_NEURAL), None]);
+
+        let mut traces = Vec::new();
+        decompress_file_with_trace(
+            packed.to_str().unwrap(),
+            unpacked.to_str().unwrap(),
+            NO_BRAIN,
+            |t| traces.push(t.clone()),
+            None,
+        )
+        .unwrap();
+
+        let reported: Vec<Option<u8>> = traces.iter().map(|t| t.predictor_id).collect();
+        assert_eq!(reported, written);
+        assert!(traces.iter().all(|t| t.decoded_bytes == CHUNK_SIZE));
+        assert_eq!(fs::read(&unpacked).unwrap(), data);
+
+        for p in [input, packed, unpacked] {
+            let _ = fs::remove_file(p);
+        }
+    }
+
+    /// Synthetic sensor log: timestamp, station id, temperature, humidity.
+    fn sensor_csv() -> Vec<u8> {
+        let mut csv = b"timestamp,station,temp_c,humidity\n".to_vec();
+        for i in 0..4000u32 {
+            let line = format!(
+                "{},{},{:.2},{}\n",
+                1_700_000_000 + i * 10,
+                ["north", "south", "east"][(i % 3) as usize],
+                20.0 + ((i % 97) as f32) * 0.05,
+                40 + (i * 7) % 13
+            );
+            csv.extend_from_slice(line.as_bytes());
+        }
+        csv
+    }
+
+    #[test]
+    fn test_csv_transform_improves_ratio_and_roundtrips() {
+        let input = temp_path("csv_in");
+        let data = sensor_csv();
+        fs::write(&input, &data).unwrap();
+
+        let mut sizes = Vec::new();
+        for (name, transform) in [
+            ("csv_none", StructuralTransform::None),
+            ("csv_cols", StructuralTransform::Csv),
+        ] {
+            let packed = temp_path(&format!("{}_packed", name));
+            let unpacked = temp_path(&format!("{}_out", name));
+            compress_file(
+                input.to_str().unwrap(),
+                packed.to_str().unwrap(),
+                NO_BRAIN,
+                &QresConfig::default(),
+                &CompressOptions {
+                    transform,
+                    ..CompressOptions::default()
+                },
+                None,
+            )
+            .unwrap();
+            decompress_file(
+                packed.to_str().unwrap(),
+                unpacked.to_str().unwrap(),
+                NO_BRAIN,
+                None,
+            )
+            .unwrap();
+
+            assert_eq!(fs::read(&unpacked).unwrap(), data);
+            sizes.push(fs::metadata(&packed).unwrap().len());
+            let _ = fs::remove_file(packed);
+            let _ = fs::remove_file(unpacked);
+        }
+        let _ = fs::remove_file(input);
+
+        assert!(
+            sizes[1] < sizes[0],
+            "column transform ({} bytes) should beat plain ({} bytes)",
+            sizes[1],
+            sizes[0]
+        );
+    }
+
+    #[test]
+    fn test_parallel_compression_matches_sequential() {
+        let input = temp_path("jobs_in");
+        let sequential = temp_path("jobs_seq");
+        let parallel = temp_path("jobs_par");
+        let mut data = mixed_input();
+        data.extend(mixed_input());
+        data.extend_from_slice(b"trailing partial chunk");
+        fs::write(&input, &data).unwrap();
+
+        for (out, jobs) in [(&sequential, 1), (&parallel, 4)] {
+            compress_file(
+                input.to_str().unwrap(),
+                out.to_str().unwrap(),
+                NO_BRAIN,
+                &QresConfig::default(),
+                &CompressOptions {
+                    jobs,
+                    ..CompressOptions::default()
+                },
+                None,
+            )
+            .unwrap();
+        }
+        assert_eq!(fs::read(&sequential).unwrap(), fs::read(&parallel).unwrap());
+
+        for p in [input, sequential, parallel] {
+            let _ = fs::remove_file(p);
+        }
+    }
+
+    #[test]
+    fn test_parallel_progress_is_reported_while_compressing() {
+        let data = sensor_csv().repeat(4);
+        let options = CompressOptions {
+            jobs: 4,
+            chunk_size: 16 * 1024,
+            ..CompressOptions::default()
+        };
+
+        let mut calls = Vec::new();
+        let stats = compress_stream(
+            &data[..],
+            &mut Vec::new(),
+            &LivingBrain::default(),
+            &QresConfig::default(),
+            &options,
+            Some(&mut |bytes_in, bytes_out| calls.push((bytes_in, bytes_out))),
+        )
+        .unwrap();
+        assert!(calls
+            .windows(2)
+            .all(|w| w[0].0 <= w[1].0 && w[0].1 <= w[1].1));
+        assert!(calls
+            .iter()
+            .all(|&(bytes_in, _)| bytes_in <= data.len() as u64));
+        assert_eq!(calls.last(), Some(&(stats.input_bytes, stats.output_bytes)));
+    }
+
+    #[test]
+    fn test_explicit_brain_path_drives_weights() {
+        let brain_file = temp_path("brain.json");
+        let input = temp_path("brain_in");
+        let packed = temp_path("brain_packed");
+        let unpacked = temp_path("brain_out");
+
+        let mut brain = LivingBrain::new();
+        brain.confidence = vec![0.9, 0.05, 0.01, 0.01, 0.02, 0.01];
+        fs::write(&brain_file, brain.to_json()).unwrap();
+        let brain_path = brain_file.to_str().unwrap();
+
+        assert_eq!(
+            brain_weights(&load_brain(brain_path)),
+            brain_weights(&brain)
+        );
+        assert_eq!(
+            brain_weights(&load_brain(NO_BRAIN)),
+            brain_weights(&LivingBrain::default())
+        );
+
+        let data = mixed_input();
+        fs::write(&input, &data).unwrap();
+        compress_file(
+            input.to_str().unwrap(),
+            packed.to_str().unwrap(),
+            brain_path,
+            &QresConfig::default(),
+            &CompressOptions::default(),
+            None,
+        )
+        .unwrap();
+        decompress_file(
+            packed.to_str().unwrap(),
+            unpacked.to_str().unwrap(),
+            brain_path,
+            None,
+        )
+        .unwrap();
+        assert_eq!(fs::read(&unpacked).unwrap(), data);
+
+        for p in [brain_file, input, packed, unpacked] {
+            let _ = fs::remove_file(p);
+        }
+    }
+
+    #[test]
+    fn test_chunk_size_is_recorded_and_honored() {
+        let input = temp_path("chunk_size_in");
+        let packed = temp_path("chunk_size_packed");
+        let unpacked = temp_path("chunk_size_out");
+        let data = sensor_csv();
+        fs::write(&input, &data).unwrap();
+
+        for chunk_size in [MIN_CHUNK_SIZE, 256 * 1024] {
+            let stats = compress_file(
+                input.to_str().unwrap(),
+                packed.to_str().unwrap(),
+                NO_BRAIN,
+                &QresConfig::default(),
+                &CompressOptions {
+                    chunk_size,
+                    ..CompressOptions::default()
+                },
+                None,
+            )
+            .unwrap();
+            assert_eq!(stats.chunks, data.len().div_ceil(chunk_size));
+
+            let stream = fs::read(&packed).unwrap();
+            let recorded = &stream[FILE_HEADER_V1_LEN..FILE_HEADER_LEN];
+            assert_eq!(recorded, (chunk_size as u32).to_le_bytes());
+
+            let stats = decompress_file(
+                packed.to_str().unwrap(),
+                unpacked.to_str().unwrap(),
+                NO_BRAIN,
+                None,
+            )
+            .unwrap();
+            assert_eq!(stats.chunks, data.len().div_ceil(chunk_size));
+            assert_eq!(fs::read(&unpacked).unwrap(), data);
+        }
+
+        // Version 1 headers (no chunk size field) still decode
+        let stream = fs::read(&packed).unwrap();
+        let mut legacy = stream[..FILE_HEADER_V1_LEN].to_vec();
+        legacy[4] = 1;
+        legacy.extend_from_slice(&stream[FILE_HEADER_LEN..]);
+        let brain = load_brain(NO_BRAIN);
+        assert_eq!(decompress_bytes_from_vec(&legacy, &brain).unwrap(), data);
+
+        assert!(parse_chunk_size("65536").is_ok());
+        assert!(parse_chunk_size("1024").is_err());
+        assert!(parse_chunk_size("33554432").is_err());
+        assert!(parse_chunk_size("big").is_err());
+
+        for p in [input, packed, unpacked] {
+            let _ = fs::remove_file(p);
+        }
+    }
+
+    #[test]
+    fn test_progress_reported_per_chunk() {
+        let input = temp_path("progress_in");
+        let packed = temp_path("progress_packed");
+        let unpacked = temp_path("progress_out");
+        let data = sensor_csv();
+        fs::write(&input, &data).unwrap();
+        let options = CompressOptions {
+            chunk_size: 16 * 1024,
+            ..CompressOptions::default()
+        };
+        let chunks = data.len().div_ceil(options.chunk_size);
+        assert!(chunks > 1);
+
+        let mut calls = Vec::new();
+        let stats = compress_file(
+            input.to_str().unwrap(),
+            packed.to_str().unwrap(),
+            NO_BRAIN,
+            &QresConfig::default(),
+            &options,
+            Some(&mut |bytes_in, bytes_out| calls.push((bytes_in, bytes_out))),
+        )
+        .unwrap();
+        assert_eq!(calls.len(), chunks);
+        assert!(calls.windows(2).all(|w| w[0].0 < w[1].0 && w[0].1 < w[1].1));
+        assert_eq!(calls.last(), Some(&(stats.input_bytes, stats.output_bytes)));
+
+        let mut count = 0;
+        let stats = decompress_file(
+            packed.to_str().unwrap(),
+            unpacked.to_str().unwrap(),
+            NO_BRAIN,
+            Some(&mut |bytes_in, bytes_out| {
+                count += 1;
+                assert!(bytes_out <= data.len() as u64);
+                assert!(bytes_in <= fs::metadata(&packed).unwrap().len());
+            }),
+        )
+        .unwrap();
+        assert_eq!(count, chunks);
+        assert_eq!(stats.output_bytes, data.len() as u64);
+
+        for p in [input, packed, unpacked] {
+            let _ = fs::remove_file(p);
+        }
+    }
+
+    #[test]
+    fn test_truncated_stream_is_corrupt() {
+        let input = temp_path("trunc_in");
+        let packed = temp_path("trunc_packed");
+        let unpacked = temp_path("trunc_out");
+        fs::write(&input, mixed_input()).unwrap();
+
+        let stats = compress_file(
+            input.to_str().unwrap(),
+            packed.to_str().unwrap(),
+            NO_BRAIN,
+            &QresConfig::default(),
+            &CompressOptions::default(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(stats.chunks, 2);
+        assert_eq!(stats.fallback_chunks, 1);
+
+        let stream = fs::read(&packed).unwrap();
+        assert_eq!(stats.output_bytes as usize + FILE_HEADER_LEN, stream.len());
+        fs::write(&packed, &stream[..stream.len() - 100]).unwrap();
+
+        let err = decompress_file(
+            packed.to_str().unwrap(),
+            unpacked.to_str().unwrap(),
+            NO_BRAIN,
+            None,
+        )
+        .unwrap_err();
+        assert!(matches!(err, DaemonError::CorruptStream(_)), "{:?}", err);
+        assert_eq!(err.exit_code(), crate::error::EXIT_CORRUPT);
+
+        // A missing file is still an IO error
+        let err = decompress_file(
+            input.with_extension("missing").to_str().unwrap(),
+            unpacked.to_str().unwrap(),
+            NO_BRAIN,
+            None,
+        )
+        .unwrap_err();
+        assert!(matches!(err, DaemonError::Io(_)));
+
+        for p in [input, packed, unpacked] {
+            let _ = fs::remove_file(p);
+        }
+    }
+
+    #[test]
+    fn test_bit_flipped_fallback_chunk_is_corrupt() {
+        let brain = LivingBrain::default();
+        for fallback in [FallbackCodec::Zstd, FallbackCodec::Lz4] {
+            let options = CompressOptions {
+                fallback,
+                ..CompressOptions::default()
+            };
+            let mut stream = Vec::new();
+            compress_stream(
+                &mixed_input()[..],
+                &mut stream,
+                &brain,
+                &QresConfig::default(),
+                &options,
+                None,
+            )
+            .unwrap();
+
+            // Locate the fallback chunk's decoded-length field
+            let mut pos = FILE_HEADER_LEN;
+            let len_field = loop {
+                let len = u32::from_le_bytes(stream[pos..pos + 4].try_into().unwrap()) as usize;
+                let chunk = pos + 4;
+                if matches!(stream[chunk] & 0x0F, CODEC_MODE_ZSTD | CODEC_MODE_LZ4) {
+                    break chunk + 1;
+                }
+                pos = chunk + len;
+            };
+
+            // Clear the lowest set bit (a short claim) or set the lowest
+            // clear bit (a long one): single-bit flips either way
+            let field = len_field..len_field + 4;
+            let len = u32::from_le_bytes(stream[field.clone()].try_into().unwrap());
+            for flipped_len in [len & (len - 1), len | (len + 1)] {
+                let mut flipped = stream.clone();
+                flipped[field.clone()].copy_from_slice(&flipped_len.to_le_bytes());
+                let err = decompress_bytes_from_vec(&flipped, &brain).unwrap_err();
+                assert!(matches!(err, DaemonError::CorruptStream(_)), "{:?}", err);
+                assert_eq!(err.exit_code(), crate::error::EXIT_CORRUPT);
+            }
+        }
+    }
+
+    #[test]
+    fn test_per_chunk_auto_predictor_beats_fixed() {
+        // A smooth ramp chunk followed by a CSV text chunk
+        let mut data: Vec<u8> = (0..CHUNK_SIZE)
+            .map(|i| ((i as f32 * 0.02).sin() * 100.0 + 128.0) as u8)
+            .collect();
+        data.extend(sensor_csv().iter().cycle().take(CHUNK_SIZE));
+
+        // Brain weights reach the mixer as raw Q16.16 bits: all trust on the
+        // last-value predictor, which suits the ramp but not the text
+        let brain = LivingBrain {
+            confidence: vec![f32::from_bits(1 << 16), 0.0, 0.0, 0.0, 0.0, 0.0],
+            ..LivingBrain::default()
+        };
+        let no_weights = LivingBrain {
+            confidence: Vec::new(),
+            ..LivingBrain::default()
+        };
+
+        let fixed = QresConfig::default();
+        let auto = QresConfig {
+            predictor: PredictorType::Auto,
+            ..QresConfig::default()
+        };
+        let neural_len = compress_bytes_to_vec(&data, &fixed, &brain).unwrap().len();
+        let default_len = compress_bytes_to_vec(&data, &fixed, &no_weights)
+            .unwrap()
+            .len();
+        let packed = compress_bytes_to_vec(&data, &auto, &brain).unwrap();
+
+        assert!(
+            packed.len() < neural_len && packed.len() < default_len,
+            "auto {} vs neural {} / default {}",
+            packed.len(),
+            neural_len,
+            default_len
+        );
+        let first = read_chunk_header(&packed[FILE_HEADER_LEN + 4..]).unwrap();
+        assert_eq!(first.predictor_id, Some(qres_core::PREDICTOR_ID_NEURAL));
+        assert_eq!(decompress_bytes_from_vec(&packed, &brain).unwrap(), data);
+    }
+
+    #[test]
+    fn test_auto_predictor_seeds_from_first_chunk() {
+        let input = temp_path("auto_in");
+        let packed = temp_path("auto_packed");
+        let unpacked = temp_path("auto_out");
+        let data = sensor_csv();
+        fs::write(&input, &data).unwrap();
+
+        for (auto_predictor, expected) in [
+            (false, qres_core::PREDICTOR_ID_NEURAL),
+            (true, qres_core::PREDICTOR_ID_DEFAULT),
+        ] {
+            compress_file(
+                input.to_str().unwrap(),
+                packed.to_str().unwrap(),
+                NO_BRAIN,
+                &QresConfig::default(),
+                &CompressOptions {
+                    auto_predictor,
+                    ..CompressOptions::default()
+                },
+                None,
+            )
+            .unwrap();
+
+            // Textual input skips the brain-weighted neural predictor
+            let stream = fs::read(&packed).unwrap();
+            let header = read_chunk_header(&stream[FILE_HEADER_LEN + 4..]).unwrap();
+            assert_eq!(header.predictor_id, Some(expected));
+
+            decompress_file(
+                packed.to_str().unwrap(),
+                unpacked.to_str().unwrap(),
+                NO_BRAIN,
+                None,
+            )
+            .unwrap();
+            assert_eq!(fs::read(&unpacked).unwrap(), data);
+        }
+
+        for p in [input, packed, unpacked] {
+            let _ = fs::remove_file(p);
+        }
+    }
+
+    #[test]
+    fn test_decompress_rejects_changed_brain() {
+        let brain_file = temp_path("hash_brain.json");
+        let input = temp_path("hash_in");
+        let packed = temp_path("hash_packed");
+        let unpacked = temp_path("hash_out");
+        fs::write(&input, sensor_csv()).unwrap();
+
+        let mut brain = LivingBrain::new();
+        brain.confidence = vec![0.7, 0.1, 0.1, 0.05, 0.03, 0.02];
+        fs::write(&brain_file, brain.to_json()).unwrap();
+        compress_file(
+            input.to_str().unwrap(),
+            packed.to_str().unwrap(),
+            brain_file.to_str().unwrap(),
+            &QresConfig::default(),
+            &CompressOptions::default(),
+            None,
+        )
+        .unwrap();
+
+        let stream = fs::read(&packed).unwrap();
+        assert_eq!(&stream[..4], FILE_MAGIC);
+        assert_eq!(stream[4], FILE_HEADER_VERSION);
+
+        // The default brain produces different weights than the one used to compress
+        let err = decompress_file(
+            packed.to_str().unwrap(),
+            unpacked.to_str().unwrap(),
+            NO_BRAIN,
+            None,
+        )
+        .unwrap_err();
+        assert!(matches!(err, DaemonError::BrainMismatch));
+        assert!(err.to_string().contains("brain mismatch"));
+
+        decompress_file(
+            packed.to_str().unwrap(),
+            unpacked.to_str().unwrap(),
+            brain_file.to_str().unwrap(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(fs::read(&unpacked).unwrap(), sensor_csv());
+
+        for p in [brain_file, input, packed, unpacked] {
+            let _ = fs::remove_file(p);
+        }
+    }
+
+    #[test]
+    fn test_verify_reports_lossless_and_lossy_roundtrips() {
+        let input = temp_path("verify_in");
+        fs::write(&input, mixed_input()).unwrap();
+
+        let report =
+            verify_file(input.to_str().unwrap(), NO_BRAIN, &QresConfig::default()).unwrap();
+        assert!(report.passed);
+        assert_eq!(report.max_abs_error, 0);
+        assert_eq!(report.input_bytes, 2 * CHUNK_SIZE);
+        assert!(report.compressed_bytes > FILE_HEADER_LEN);
+
+        let lossy = QresConfig {
+            mode: CompressionMode::Lossy,
+            ..QresConfig::default()
+        };
+        let report = verify_file(input.to_str().unwrap(), NO_BRAIN, &lossy).unwrap();
+        // Quantized residuals stay within the lossy tolerance
+        assert!(report.passed, "max abs error {}", report.max_abs_error);
+        assert!(report.max_abs_error > 0);
+        assert_eq!(report.tolerance, LOSSY_MAX_ABS_ERROR);
+
+        let _ = fs::remove_file(input);
+    }
+}
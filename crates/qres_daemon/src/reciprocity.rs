@@ -0,0 +1,123 @@
+//! Tit-for-tat Reciprocity Accounting
+//!
+//! Tracks, per peer, how many of our epiphanies it has been offered (we
+//! published while it was connected) versus how many of its epiphanies we
+//! accepted. Peers that keep consuming while staying silent get their
+//! occasional updates buffered less often, so free-riding on the swarm's
+//! learning stops paying off while reciprocating peers are unaffected.
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Broadcasts a peer must have been offered before it can be judged
+const RECIPROCITY_WARMUP: u64 = 10;
+/// Give/take ratio below which a peer counts as a free-rider
+const FREE_RIDER_RATIO: f32 = 0.1;
+/// Free-riders get one update buffered per this many received
+const FREE_RIDER_BUFFER_STRIDE: u64 = 4;
+
+/// Exchange counters for a single peer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct PeerReciprocity {
+    /// Our epiphanies published while the peer was connected
+    pub taken: u64,
+    /// The peer's epiphanies we accepted
+    pub given: u64,
+}
+
+impl PeerReciprocity {
+    /// Contributions per consumed epiphany (1.0 before anything was consumed)
+    pub fn ratio(&self) -> f32 {
+        if self.taken == 0 {
+            1.0
+        } else {
+            self.given as f32 / self.taken as f32
+        }
+    }
+
+    pub fn is_free_rider(&self) -> bool {
+        self.taken >= RECIPROCITY_WARMUP && self.ratio() < FREE_RIDER_RATIO
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReciprocityLedger {
+    peers: HashMap<String, PeerReciprocity>,
+}
+
+impl ReciprocityLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that we published an epiphany to each of `peers`.
+    pub fn record_published<'a>(&mut self, peers: impl IntoIterator<Item = &'a String>) {
+        for peer in peers {
+            self.peers.entry(peer.clone()).or_default().taken += 1;
+        }
+    }
+
+    /// Record an accepted epiphany from `peer` and decide whether to buffer it.
+    ///
+    /// Reciprocating peers are always buffered; free-riders only every
+    /// `FREE_RIDER_BUFFER_STRIDE`-th update.
+    pub fn record_contribution(&mut self, peer: &str) -> bool {
+        let entry = self.peers.entry(peer.to_string()).or_default();
+        let free_rider = entry.is_free_rider();
+        entry.given += 1;
+        !free_rider || entry.given.is_multiple_of(FREE_RIDER_BUFFER_STRIDE)
+    }
+
+    pub fn get(&self, peer: &str) -> PeerReciprocity {
+        self.peers.get(peer).copied().unwrap_or_default()
+    }
+
+    pub fn peers(&self) -> impl Iterator<Item = (&String, &PeerReciprocity)> {
+        self.peers.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_free_rider_is_deprioritized() {
+        let mut ledger = ReciprocityLedger::new();
+        let leech = "leech".to_string();
+        let fair = "fair".to_string();
+
+        // Both peers consume 20 of our epiphanies; only `fair` answers each one
+        for _ in 0..20 {
+            ledger.record_published([&leech, &fair]);
+            assert!(ledger.record_contribution(&fair));
+        }
+        assert!(!ledger.get(&fair).is_free_rider());
+        assert_eq!(ledger.get(&fair).ratio(), 1.0);
+
+        assert!(ledger.get(&leech).is_free_rider());
+        assert_eq!(ledger.get(&leech).ratio(), 0.0);
+
+        // The free-rider's first updates are mostly dropped
+        let buffered = (0..2)
+            .filter(|_| ledger.record_contribution(&leech))
+            .count();
+        assert_eq!(buffered, 0);
+
+        // Contributing enough restores normal treatment
+        ledger.record_contribution(&leech);
+        ledger.record_contribution(&leech);
+        assert!(!ledger.get(&leech).is_free_rider());
+        assert!(ledger.record_contribution(&leech));
+    }
+
+    #[test]
+    fn test_new_peers_are_not_judged_during_warmup() {
+        let mut ledger = ReciprocityLedger::new();
+        let peer = "newcomer".to_string();
+        for _ in 0..RECIPROCITY_WARMUP - 1 {
+            ledger.record_published([&peer]);
+        }
+        assert!(ledger.record_contribution(&peer));
+    }
+}
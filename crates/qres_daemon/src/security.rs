@@ -3,15 +3,44 @@
 //! Provides ed25519 signing and verification for model updates,
 //! implementing Phase 1 Item 1 of the security roadmap.
 
+use crate::atomic_file::write_file_atomic;
+use crate::peer_keys::{PeerKeyStore, SignerStatus};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
 use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tracing::warn;
 
 /// Size of an ed25519 signature in bytes
 pub const SIGNATURE_SIZE: usize = 64;
 
+/// Size of the ChaCha20-Poly1305 nonce prefixed to encrypted payloads
+pub const PAYLOAD_NONCE_SIZE: usize = 12;
+
+/// Bytes encryption adds to a payload (nonce plus Poly1305 tag)
+pub const PAYLOAD_OVERHEAD: usize = PAYLOAD_NONCE_SIZE + 16;
+
+/// Default accepted difference between a message timestamp and local time
+pub const DEFAULT_MAX_CLOCK_SKEW_SECS: u64 = 300;
+
+/// Cap on how far ahead of local time a message timestamp may be, whatever
+/// the configured skew: future-dated messages stay replayable for longer
+const MAX_FUTURE_SKEW_SECS: u64 = 60;
+
+/// `(signer, nonce)` pairs remembered per signer for replay detection
+const NONCE_QUOTA_PER_SIGNER: usize = 1_024;
+
+/// Domain separator for key rotation certificates
+const KEY_ROTATION_CONTEXT: &[u8] = b"qres-daemon key rotation v1";
+
+/// BLAKE3 key-derivation context for the swarm payload key
+const PAYLOAD_KEY_CONTEXT: &str = "qres-daemon 2024 swarm gossip payload encryption";
+
 /// A signed message containing payload and signature
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignedPayload {
@@ -27,15 +56,49 @@ pub struct SignedPayload {
     pub nonce: u64,
 }
 
+/// Announcement that a node now signs with `current_pubkey`, signed by its
+/// `previous_pubkey` so peers that only know the old key can follow along.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRotation {
+    /// Hex-encoded key being retired
+    pub previous_pubkey: String,
+    /// Hex-encoded key now used for signing
+    pub current_pubkey: String,
+    /// Hex-encoded signature by the previous key over the current one
+    pub signature: String,
+}
+
+impl KeyRotation {
+    fn message(current: &VerifyingKey) -> Vec<u8> {
+        let mut message = KEY_ROTATION_CONTEXT.to_vec();
+        message.extend_from_slice(current.as_bytes());
+        message
+    }
+
+    /// Whether the previous key really endorsed the current one
+    pub fn verify(&self) -> bool {
+        let (Ok(previous), Ok(current)) = (
+            decode_verifying_key(&self.previous_pubkey),
+            decode_verifying_key(&self.current_pubkey),
+        ) else {
+            return false;
+        };
+        decode_signature(&self.signature)
+            .is_ok_and(|sig| previous.verify(&Self::message(&current), &sig).is_ok())
+    }
+}
+
 /// Security manager for handling keys and verification
 pub struct SecurityManager {
     signing_key: SigningKey,
     verifying_key: VerifyingKey,
+    /// Key retired by a rotation, kept to endorse the current one
+    previous_signing_key: Option<SigningKey>,
     require_signatures: bool,
-    /// Set of recently seen nonces to prevent replay
-    seen_nonces: std::collections::HashSet<u64>,
-    /// Maximum age of messages in seconds (for timestamp validation)
-    max_message_age_secs: u64,
+    /// Recently seen `(signer, nonce)` pairs to prevent replay
+    seen_nonces: NonceCache,
+    /// Accepted distance of a message timestamp from local time, in seconds
+    max_clock_skew_secs: u64,
 }
 
 impl SecurityManager {
@@ -45,14 +108,7 @@ impl SecurityManager {
         require_signatures: bool,
     ) -> Result<Self, Box<dyn std::error::Error>> {
         let signing_key = if key_path.exists() {
-            // Load existing key
-            let key_bytes = fs::read(key_path)?;
-            if key_bytes.len() != 32 {
-                return Err("Invalid key file size".into());
-            }
-            let mut arr = [0u8; 32];
-            arr.copy_from_slice(&key_bytes);
-            SigningKey::from_bytes(&arr)
+            load_signing_key(key_path)?
         } else {
             // Generate new key
             let mut csprng = OsRng;
@@ -70,9 +126,36 @@ impl SecurityManager {
         Ok(Self {
             signing_key,
             verifying_key,
+            previous_signing_key: None,
             require_signatures,
-            seen_nonces: std::collections::HashSet::new(),
-            max_message_age_secs: 300, // 5 minutes
+            seen_nonces: NonceCache::new(NONCE_QUOTA_PER_SIGNER),
+            max_clock_skew_secs: DEFAULT_MAX_CLOCK_SKEW_SECS,
+        })
+    }
+
+    /// Accept timestamps at most `secs` old, and at most `secs` (capped at
+    /// 60 s) ahead of local time
+    pub fn with_max_clock_skew(mut self, secs: u64) -> Self {
+        self.max_clock_skew_secs = secs;
+        self
+    }
+
+    /// Load the key this node signed with before its last rotation. Every
+    /// update then carries a [`KeyRotation`] so peers that still list the
+    /// old key accept the new one.
+    pub fn load_previous_key(&mut self, key_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        self.previous_signing_key = Some(load_signing_key(key_path)?);
+        Ok(())
+    }
+
+    /// Certificate endorsing the current key, if a previous key is loaded
+    pub fn key_rotation(&self) -> Option<KeyRotation> {
+        let previous = self.previous_signing_key.as_ref()?;
+        let signature = previous.sign(&KeyRotation::message(&self.verifying_key));
+        Some(KeyRotation {
+            previous_pubkey: hex::encode(previous.verifying_key().to_bytes()),
+            current_pubkey: self.public_key_hex(),
+            signature: hex::encode(signature.to_bytes()),
         })
     }
 
@@ -87,8 +170,12 @@ impl SecurityManager {
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
             .as_secs();
-        let nonce = rand::random::<u64>();
+        self.sign_at(data, timestamp, rand::random::<u64>())
+    }
 
+    /// Sign a payload with a caller-chosen timestamp and nonce, e.g. ones
+    /// already embedded in the signed data
+    pub fn sign_at(&self, data: &[u8], timestamp: u64, nonce: u64) -> SignedPayload {
         // Create message to sign: data + timestamp + nonce
         let mut message = data.to_vec();
         message.extend_from_slice(&timestamp.to_le_bytes());
@@ -105,6 +192,23 @@ impl SecurityManager {
         }
     }
 
+    /// Verify a signed payload whose signer must not be a key that
+    /// `peer_keys` has retired past its rotation grace period. Signers the
+    /// store has never seen are judged by their signature alone.
+    pub fn verify_with_keys(
+        &mut self,
+        payload: &SignedPayload,
+        peer_keys: &PeerKeyStore,
+        now: Instant,
+    ) -> Result<Vec<u8>, SecurityError> {
+        if self.require_signatures
+            && peer_keys.signer_status(&payload.signer_pubkey, now) == SignerStatus::Retired
+        {
+            return Err(SecurityError::RetiredKey);
+        }
+        self.verify(payload)
+    }
+
     /// Verify a signed payload
     /// Returns the original data if valid, or an error
     pub fn verify(&mut self, payload: &SignedPayload) -> Result<Vec<u8>, SecurityError> {
@@ -119,32 +223,27 @@ impl SecurityManager {
             .unwrap()
             .as_secs();
 
-        if payload.timestamp > now + 60 {
+        let max_future_skew = self.max_clock_skew_secs.min(MAX_FUTURE_SKEW_SECS);
+        if payload.timestamp > now.saturating_add(max_future_skew) {
             return Err(SecurityError::FutureTimestamp);
         }
 
-        if now - payload.timestamp > self.max_message_age_secs {
+        if now.saturating_sub(payload.timestamp) > self.max_clock_skew_secs {
             return Err(SecurityError::ExpiredMessage);
         }
 
-        // Check nonce (prevent replay within window)
-        if self.seen_nonces.contains(&payload.nonce) {
+        // Check nonce (prevent replay within window); nonces whose messages
+        // would now fail the age check no longer need remembering
+        self.seen_nonces
+            .evict_before(now.saturating_sub(self.max_clock_skew_secs));
+        if self
+            .seen_nonces
+            .contains(&payload.signer_pubkey, payload.nonce)
+        {
             return Err(SecurityError::ReplayDetected);
         }
 
-        // Decode public key
-        let pubkey_bytes =
-            hex::decode(&payload.signer_pubkey).map_err(|_| SecurityError::InvalidPublicKey)?;
-
-        if pubkey_bytes.len() != 32 {
-            return Err(SecurityError::InvalidPublicKey);
-        }
-
-        let mut pubkey_arr = [0u8; 32];
-        pubkey_arr.copy_from_slice(&pubkey_bytes);
-
-        let verifying_key =
-            VerifyingKey::from_bytes(&pubkey_arr).map_err(|_| SecurityError::InvalidPublicKey)?;
+        let verifying_key = decode_verifying_key(&payload.signer_pubkey)?;
 
         // Reconstruct signed message
         let mut message = payload.data.clone();
@@ -152,27 +251,171 @@ impl SecurityManager {
         message.extend_from_slice(&payload.nonce.to_le_bytes());
 
         // Verify signature
-        let sig_bytes =
-            hex::decode(&payload.signature).map_err(|_| SecurityError::InvalidSignature)?;
-        if sig_bytes.len() != SIGNATURE_SIZE {
-            return Err(SecurityError::InvalidSignature);
-        }
-        let mut sig_arr = [0u8; SIGNATURE_SIZE];
-        sig_arr.copy_from_slice(&sig_bytes);
-        let signature = Signature::from_bytes(&sig_arr);
+        let signature = decode_signature(&payload.signature)?;
         verifying_key
             .verify(&message, &signature)
             .map_err(|_| SecurityError::InvalidSignature)?;
 
         // Record nonce to prevent replay
-        self.seen_nonces.insert(payload.nonce);
+        self.seen_nonces.insert(
+            payload.signer_pubkey.clone(),
+            payload.nonce,
+            payload.timestamp,
+        );
+
+        Ok(payload.data.clone())
+    }
+}
+
+/// Read a raw 32-byte ed25519 secret key file
+fn load_signing_key(key_path: &Path) -> Result<SigningKey, Box<dyn std::error::Error>> {
+    let key_bytes = fs::read(key_path)?;
+    let arr: [u8; 32] = key_bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| "Invalid key file size")?;
+    Ok(SigningKey::from_bytes(&arr))
+}
+
+/// Parse a hex-encoded ed25519 public key
+fn decode_verifying_key(pubkey_hex: &str) -> Result<VerifyingKey, SecurityError> {
+    let pubkey_bytes = hex::decode(pubkey_hex).map_err(|_| SecurityError::InvalidPublicKey)?;
+    let pubkey_arr: [u8; 32] = pubkey_bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| SecurityError::InvalidPublicKey)?;
+    VerifyingKey::from_bytes(&pubkey_arr).map_err(|_| SecurityError::InvalidPublicKey)
+}
+
+/// Parse a hex-encoded ed25519 signature
+fn decode_signature(signature_hex: &str) -> Result<Signature, SecurityError> {
+    let sig_bytes = hex::decode(signature_hex).map_err(|_| SecurityError::InvalidSignature)?;
+    let sig_arr: [u8; SIGNATURE_SIZE] = sig_bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| SecurityError::InvalidSignature)?;
+    Ok(Signature::from_bytes(&sig_arr))
+}
 
-        // Prune old nonces periodically (simple approach: limit size)
-        if self.seen_nonces.len() > 10000 {
-            self.seen_nonces.clear();
+/// Memory of verified `(signer, nonce)` pairs, bounded per signer.
+///
+/// Entries are forgotten once their message timestamp falls out of the skew
+/// window, when the age check rejects a replay on its own. Each signer also
+/// has a quota: past it, that signer's oldest pair is dropped. A signer
+/// flooding fresh nonces can only reopen the window for its own messages,
+/// never crowd out another signer's.
+#[derive(Debug)]
+struct NonceCache {
+    /// Message timestamp of each remembered pair
+    seen: HashMap<(String, u64), u64>,
+    /// The same pairs ordered by timestamp, for expiry
+    by_timestamp: BTreeSet<(u64, String, u64)>,
+    /// Each signer's `(timestamp, nonce)` pairs, oldest first
+    by_signer: HashMap<String, BTreeSet<(u64, u64)>>,
+    quota: usize,
+}
+
+impl NonceCache {
+    fn new(quota: usize) -> Self {
+        Self {
+            seen: HashMap::new(),
+            by_timestamp: BTreeSet::new(),
+            by_signer: HashMap::new(),
+            quota: quota.max(1),
         }
+    }
 
-        Ok(payload.data.clone())
+    fn contains(&self, signer: &str, nonce: u64) -> bool {
+        self.seen.contains_key(&(signer.to_string(), nonce))
+    }
+
+    /// Forget pairs whose message timestamp is before `cutoff`.
+    fn evict_before(&mut self, cutoff: u64) {
+        while let Some((timestamp, signer, nonce)) = self.by_timestamp.pop_first() {
+            if timestamp >= cutoff {
+                self.by_timestamp.insert((timestamp, signer, nonce));
+                break;
+            }
+            if let Some(pairs) = self.by_signer.get_mut(&signer) {
+                pairs.remove(&(timestamp, nonce));
+                if pairs.is_empty() {
+                    self.by_signer.remove(&signer);
+                }
+            }
+            self.seen.remove(&(signer, nonce));
+        }
+    }
+
+    /// Remember a pair, dropping the signer's oldest one if it is at quota.
+    fn insert(&mut self, signer: String, nonce: u64, timestamp: u64) {
+        let key = (signer, nonce);
+        if self.seen.contains_key(&key) {
+            return;
+        }
+        let pairs = self.by_signer.entry(key.0.clone()).or_default();
+        if pairs.len() >= self.quota {
+            if let Some((old_timestamp, old_nonce)) = pairs.pop_first() {
+                self.by_timestamp
+                    .remove(&(old_timestamp, key.0.clone(), old_nonce));
+                self.seen.remove(&(key.0.clone(), old_nonce));
+            }
+        }
+        pairs.insert((timestamp, nonce));
+        self.by_timestamp.insert((timestamp, key.0.clone(), nonce));
+        self.seen.insert(key, timestamp);
+    }
+}
+
+/// Symmetric encryption for gossip payloads in confidential swarms.
+///
+/// The key is derived from a secret shared by all swarm members. Encryption
+/// wraps the already-signed message, so signatures are checked on the
+/// plaintext after decryption. Wire format: `[nonce:12][ciphertext+tag]`.
+#[derive(Clone)]
+pub struct PayloadCipher {
+    key: [u8; 32],
+}
+
+impl PayloadCipher {
+    /// Derive the payload key from the swarm secret
+    pub fn from_secret(secret: &str) -> Self {
+        Self {
+            key: blake3::derive_key(PAYLOAD_KEY_CONTEXT, secret.as_bytes()),
+        }
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(Key::from_slice(&self.key))
+    }
+
+    /// Encrypt `plaintext` under a fresh random nonce
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let nonce: [u8; PAYLOAD_NONCE_SIZE] = rand::random();
+        let ciphertext = self
+            .cipher()
+            .encrypt(Nonce::from_slice(&nonce), plaintext)
+            .expect("ChaCha20-Poly1305 encryption is infallible for in-memory buffers");
+        let mut out = Vec::with_capacity(PAYLOAD_NONCE_SIZE + ciphertext.len());
+        out.extend_from_slice(&nonce);
+        out.extend(ciphertext);
+        out
+    }
+
+    /// Decrypt a payload produced by [`encrypt`](Self::encrypt)
+    pub fn decrypt(&self, payload: &[u8]) -> Result<Vec<u8>, SecurityError> {
+        if payload.len() < PAYLOAD_NONCE_SIZE {
+            return Err(SecurityError::DecryptionFailed);
+        }
+        let (nonce, ciphertext) = payload.split_at(PAYLOAD_NONCE_SIZE);
+        self.cipher()
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| SecurityError::DecryptionFailed)
+    }
+}
+
+impl std::fmt::Debug for PayloadCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PayloadCipher").finish_non_exhaustive()
     }
 }
 
@@ -184,6 +427,8 @@ pub enum SecurityError {
     ExpiredMessage,
     FutureTimestamp,
     ReplayDetected,
+    DecryptionFailed,
+    RetiredKey,
 }
 
 impl std::fmt::Display for SecurityError {
@@ -194,6 +439,8 @@ impl std::fmt::Display for SecurityError {
             SecurityError::ExpiredMessage => write!(f, "Message expired"),
             SecurityError::FutureTimestamp => write!(f, "Future timestamp detected"),
             SecurityError::ReplayDetected => write!(f, "Replay attack detected"),
+            SecurityError::DecryptionFailed => write!(f, "Payload decryption failed"),
+            SecurityError::RetiredKey => write!(f, "Signed with a retired key"),
         }
     }
 }
@@ -208,15 +455,29 @@ pub struct ReputationManager {
     pub peers: std::collections::HashMap<String, f32>,
     /// Path to save reputation DB
     pub db_path: PathBuf,
+    /// EWMA weight of the latest observation (0.0 = cumulative scoring)
+    #[serde(skip)]
+    pub trust_decay: f32,
 }
 
 impl ReputationManager {
     pub fn new(db_path: PathBuf) -> Self {
-        // Try load existing
+        // Try load existing. Saves go through a temp file and a rename, so a
+        // crash leaves either the old or the new DB; an empty or unparsable
+        // file (e.g. from an older non-atomic build) starts fresh.
         if db_path.exists() {
-            if let Ok(content) = fs::read_to_string(&db_path) {
-                if let Ok(loaded) = serde_json::from_str::<Self>(&content) {
-                    return loaded;
+            match fs::read_to_string(&db_path) {
+                Ok(content) if content.is_empty() => {
+                    warn!(path = %db_path.display(), "Reputation DB is empty; starting fresh");
+                }
+                Ok(content) => match serde_json::from_str::<Self>(&content) {
+                    Ok(loaded) => return loaded,
+                    Err(e) => {
+                        warn!(path = %db_path.display(), "Reputation DB is corrupt ({}); starting fresh", e);
+                    }
+                },
+                Err(e) => {
+                    warn!(path = %db_path.display(), "Failed to read reputation DB ({}); starting fresh", e);
                 }
             }
         }
@@ -224,9 +485,33 @@ impl ReputationManager {
         Self {
             peers: std::collections::HashMap::new(),
             db_path,
+            trust_decay: 0.0,
         }
     }
 
+    /// Weight recent behavior over history in `reward`/`punish`.
+    ///
+    /// With a decay `alpha` in (0, 1], trust becomes an exponentially weighted
+    /// average of observations (1.0 for a reward, 0.0 for a punishment), so a
+    /// long-trusted peer that turns malicious loses trust within a few rounds
+    /// instead of spending its accumulated credit. 0.0 keeps cumulative scoring.
+    pub fn set_trust_decay(&mut self, alpha: f32) {
+        self.trust_decay = alpha.clamp(0.0, 1.0);
+    }
+
+    /// Apply one observation: EWMA when decay is enabled, otherwise `delta`.
+    fn observe(&mut self, peer_id: &str, good: bool, delta: f32) {
+        let alpha = self.trust_decay;
+        let entry = self.peers.entry(peer_id.to_string()).or_insert(0.5);
+        *entry = if alpha > 0.0 {
+            let target = if good { 1.0 } else { 0.0 };
+            (1.0 - alpha) * *entry + alpha * target
+        } else {
+            (*entry + delta).clamp(0.0, 1.0)
+        };
+        let _ = self.save();
+    }
+
     /// Get trust score for a peer (default 0.5 for new peers)
     pub fn get_trust(&self, peer_id: &str) -> f32 {
         *self.peers.get(peer_id).unwrap_or(&0.5)
@@ -237,18 +522,14 @@ impl ReputationManager {
         self.get_trust(peer_id) < 0.2
     }
 
-    /// Reward a peer for good contribution (+0.01)
+    /// Reward a peer for good contribution (+0.01, or an EWMA step with trust decay)
     pub fn reward(&mut self, peer_id: &str) {
-        let entry = self.peers.entry(peer_id.to_string()).or_insert(0.5);
-        *entry = (*entry + 0.01).min(1.0);
-        let _ = self.save();
+        self.observe(peer_id, true, 0.01);
     }
 
-    /// Punish a peer for malicious contribution (-0.1)
+    /// Punish a peer for malicious contribution (-0.1, or an EWMA step with trust decay)
     pub fn punish(&mut self, peer_id: &str) {
-        let entry = self.peers.entry(peer_id.to_string()).or_insert(0.5);
-        *entry = (*entry - 0.1).max(0.0);
-        let _ = self.save();
+        self.observe(peer_id, false, -0.1);
     }
 
     // =========================================================================
@@ -273,6 +554,15 @@ impl ReputationManager {
         let _ = self.save();
     }
 
+    /// Lift a banned peer to the ban threshold (0.2) when its ban expires, so
+    /// it re-enters on probation and a single further punishment bans it again.
+    pub fn restore_probation(&mut self, peer_id: &str) {
+        if let Some(score) = self.peers.get_mut(peer_id) {
+            *score = score.max(0.2);
+        }
+        let _ = self.save();
+    }
+
     /// Natural decay of reputation over time (call periodically)
     /// Prevents reputation from being "earned once and forgotten"
     /// Decay rate: -0.001 per call (configurable)
@@ -283,9 +573,10 @@ impl ReputationManager {
         let _ = self.save();
     }
 
-    fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+    /// Persist through [`write_file_atomic`] so a crash never truncates the DB.
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
         let json = serde_json::to_string_pretty(self)?;
-        fs::write(&self.db_path, json)?;
+        write_file_atomic(&self.db_path, json.as_bytes())?;
         Ok(())
     }
 }
@@ -293,6 +584,7 @@ impl ReputationManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::atomic_file::staging_path;
 
     #[test]
     fn test_sign_verify() {
@@ -358,6 +650,169 @@ mod tests {
         let _ = fs::remove_file(&key_path);
     }
 
+    #[test]
+    fn test_nonce_is_scoped_to_signer() {
+        let temp_dir = std::env::temp_dir();
+        let key_a = temp_dir.join("test_qres_key_nonce_a");
+        let key_b = temp_dir.join("test_qres_key_nonce_b");
+        let _ = fs::remove_file(&key_a);
+        let _ = fs::remove_file(&key_b);
+
+        let mut receiver = SecurityManager::new(&key_a, true).unwrap();
+        let other = SecurityManager::new(&key_b, true).unwrap();
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        assert!(receiver.verify(&receiver.sign_at(b"a", now, 7)).is_ok());
+        // Another signer may legitimately pick the same nonce
+        assert!(receiver.verify(&other.sign_at(b"b", now, 7)).is_ok());
+        assert!(matches!(
+            receiver.verify(&other.sign_at(b"b", now, 7)),
+            Err(SecurityError::ReplayDetected)
+        ));
+
+        let _ = fs::remove_file(&key_a);
+        let _ = fs::remove_file(&key_b);
+    }
+
+    #[test]
+    fn test_clock_skew_window() {
+        let key_path = std::env::temp_dir().join("test_qres_key_skew");
+        let _ = fs::remove_file(&key_path);
+
+        let mut manager = SecurityManager::new(&key_path, true)
+            .unwrap()
+            .with_max_clock_skew(30);
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        assert!(manager.verify(&manager.sign_at(b"d", now - 20, 1)).is_ok());
+        assert!(manager.verify(&manager.sign_at(b"d", now + 20, 2)).is_ok());
+        assert!(matches!(
+            manager.verify(&manager.sign_at(b"d", now - 120, 3)),
+            Err(SecurityError::ExpiredMessage)
+        ));
+        assert!(matches!(
+            manager.verify(&manager.sign_at(b"d", now + 120, 4)),
+            Err(SecurityError::FutureTimestamp)
+        ));
+
+        // A wide skew still caps how far in the future a message may be
+        let mut manager = manager.with_max_clock_skew(300);
+        assert!(manager.verify(&manager.sign_at(b"d", now - 200, 5)).is_ok());
+        assert!(manager.verify(&manager.sign_at(b"d", now + 50, 6)).is_ok());
+        assert!(matches!(
+            manager.verify(&manager.sign_at(b"d", now + 120, 7)),
+            Err(SecurityError::FutureTimestamp)
+        ));
+
+        let _ = fs::remove_file(&key_path);
+    }
+
+    #[test]
+    fn test_key_rotation_grace_period() {
+        let temp_dir = std::env::temp_dir();
+        let old_path = temp_dir.join("test_qres_key_rotation_old");
+        let new_path = temp_dir.join("test_qres_key_rotation_new");
+        let receiver_path = temp_dir.join("test_qres_key_rotation_receiver");
+        for path in [&old_path, &new_path, &receiver_path] {
+            let _ = fs::remove_file(path);
+        }
+
+        let old = SecurityManager::new(&old_path, true).unwrap();
+        let mut rotated = SecurityManager::new(&new_path, true).unwrap();
+        rotated.load_previous_key(&old_path).unwrap();
+        let rotation = rotated.key_rotation().unwrap();
+        assert!(rotation.verify());
+        assert_eq!(rotation.previous_pubkey, old.public_key_hex());
+
+        // The receiver's store still only lists the old key
+        let mut receiver = SecurityManager::new(&receiver_path, true).unwrap();
+        let mut store = PeerKeyStore::new(&[], &[old.public_key_hex()]);
+        let start = Instant::now();
+        let grace = std::time::Duration::from_secs(60);
+        assert!(store.apply_rotation(&rotation, grace, start));
+
+        let signed_new = rotated.sign(b"update");
+        assert!(receiver
+            .verify_with_keys(&signed_new, &store, start)
+            .is_ok());
+        // The old key keeps working during the grace window...
+        let signed_old = old.sign(b"update");
+        assert!(receiver
+            .verify_with_keys(&signed_old, &store, start + grace / 2)
+            .is_ok());
+        // ...and is refused once it ends
+        let signed_old = old.sign(b"update");
+        assert!(matches!(
+            receiver.verify_with_keys(&signed_old, &store, start + grace),
+            Err(SecurityError::RetiredKey)
+        ));
+
+        for path in [&old_path, &new_path, &receiver_path] {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    #[test]
+    fn test_forged_key_rotation_rejected() {
+        let temp_dir = std::env::temp_dir();
+        let old_path = temp_dir.join("test_qres_key_forged_old");
+        let new_path = temp_dir.join("test_qres_key_forged_new");
+        let _ = fs::remove_file(&old_path);
+        let _ = fs::remove_file(&new_path);
+
+        let old = SecurityManager::new(&old_path, true).unwrap();
+        let mut attacker = SecurityManager::new(&new_path, true).unwrap();
+        // Endorsed by the attacker's own key, not the victim's
+        attacker.load_previous_key(&new_path).unwrap();
+        let mut rotation = attacker.key_rotation().unwrap();
+        rotation.previous_pubkey = old.public_key_hex();
+        assert!(!rotation.verify());
+
+        let mut store = PeerKeyStore::new(&[], &[old.public_key_hex()]);
+        let now = Instant::now();
+        assert!(!store.apply_rotation(&rotation, std::time::Duration::from_secs(60), now));
+        assert_eq!(
+            store.signer_status(&old.public_key_hex(), now),
+            SignerStatus::Current
+        );
+
+        let _ = fs::remove_file(&old_path);
+        let _ = fs::remove_file(&new_path);
+    }
+
+    #[test]
+    fn test_nonce_cache_quota_is_per_signer() {
+        let mut cache = NonceCache::new(2);
+        cache.insert("honest".to_string(), 1, 100);
+        cache.insert("flood".to_string(), 1, 100);
+        cache.insert("flood".to_string(), 2, 90);
+
+        // A signer at quota loses its own oldest pair, nobody else's
+        cache.evict_before(50);
+        for nonce in 3..100 {
+            cache.insert("flood".to_string(), nonce, 120);
+        }
+        assert!(cache.contains("honest", 1));
+        assert!(!cache.contains("flood", 2));
+        assert!(cache.contains("flood", 99));
+        cache.insert("honest".to_string(), 2, 120);
+        assert!(cache.contains("honest", 1));
+        assert!(cache.contains("honest", 2));
+
+        // The oldest timestamp expires first, whatever the insertion order
+        cache.evict_before(110);
+        assert!(!cache.contains("honest", 1));
+        assert!(cache.contains("honest", 2));
+        assert!(!cache.by_signer["honest"].contains(&(100, 1)));
+        assert_eq!(cache.seen.len(), cache.by_timestamp.len());
+    }
+
     #[test]
     fn test_reputation_scoring() {
         let temp_dir = std::env::temp_dir();
@@ -385,4 +840,120 @@ mod tests {
 
         let _ = fs::remove_file(db_path);
     }
+
+    #[test]
+    fn test_reputation_survives_interrupted_save() {
+        let db_path = std::env::temp_dir().join("reputation_atomic_save.json");
+        let _ = fs::remove_file(&db_path);
+
+        let mut rep = ReputationManager::new(db_path.clone());
+        rep.reward("peer_A");
+        rep.punish("peer_B");
+        assert!(!staging_path(&db_path).exists());
+
+        // A crash mid-save leaves a partial temp file behind
+        fs::write(staging_path(&db_path), b"{\"peers\": {\"peer_A\": 0.").unwrap();
+
+        let reloaded = ReputationManager::new(db_path.clone());
+        assert_eq!(reloaded.get_trust("peer_A"), 0.51);
+        assert!((reloaded.get_trust("peer_B") - 0.4).abs() < 1e-6);
+
+        // The next save replaces the stale temp file
+        let mut reloaded = reloaded;
+        reloaded.reward("peer_B");
+        assert!(!staging_path(&db_path).exists());
+        assert!(ReputationManager::new(db_path.clone()).get_trust("peer_B") > 0.4);
+
+        // A zero-length DB (left by a non-atomic write) starts fresh
+        fs::write(&db_path, b"").unwrap();
+        let fresh = ReputationManager::new(db_path.clone());
+        assert!(fresh.peers.is_empty());
+        assert_eq!(fresh.db_path, db_path);
+
+        let _ = fs::remove_file(db_path);
+    }
+
+    #[test]
+    fn test_trust_decay_bans_compromised_peer_quickly() {
+        let db_path = std::env::temp_dir().join("reputation_trust_decay.json");
+        let _ = fs::remove_file(&db_path);
+
+        let mut cumulative = ReputationManager::new(db_path.clone());
+        let mut decayed = ReputationManager::new(db_path.clone());
+        decayed.set_trust_decay(0.3);
+
+        // A long, spotless history
+        let peer = "veteran";
+        for _ in 0..500 {
+            cumulative.reward(peer);
+            decayed.reward(peer);
+        }
+        assert!(cumulative.get_trust(peer) > 0.99);
+        assert!(decayed.get_trust(peer) > 0.99);
+
+        let rounds_to_ban = |rep: &mut ReputationManager| {
+            (1..=20)
+                .find(|_| {
+                    rep.punish(peer);
+                    rep.is_banned(peer)
+                })
+                .unwrap()
+        };
+        let decayed_rounds = rounds_to_ban(&mut decayed);
+        let cumulative_rounds = rounds_to_ban(&mut cumulative);
+
+        // 0.7^5 < 0.2: banned within five bad rounds
+        assert!(decayed_rounds <= 5, "took {} rounds", decayed_rounds);
+        assert!(decayed_rounds < cumulative_rounds);
+
+        let _ = fs::remove_file(db_path);
+    }
+
+    #[test]
+    fn test_encrypted_epiphany_roundtrip() {
+        use crate::living_brain::{LivingBrain, SignedEpiphany};
+
+        let key_path = std::env::temp_dir().join("test_qres_key_encrypted");
+        let _ = fs::remove_file(&key_path);
+        let mut manager = SecurityManager::new(&key_path, true).unwrap();
+
+        let mut brain = LivingBrain::new();
+        brain.best_engine_weights = Some(b"proprietary-weights".to_vec());
+        let mut epiphany = SignedEpiphany::new(
+            brain,
+            None,
+            String::new(),
+            manager.public_key_hex(),
+            0,
+            7,
+            false,
+        );
+        let signed = manager.sign(&epiphany.payload_bytes());
+        epiphany.signature = signed.signature.clone();
+        let plaintext = serde_json::to_vec(&epiphany).unwrap();
+
+        let cipher = PayloadCipher::from_secret("swarm-secret");
+        let wire = cipher.encrypt(&plaintext);
+
+        // Unreadable to listeners without the key
+        assert!(serde_json::from_slice::<SignedEpiphany>(&wire).is_err());
+        assert!(!wire.windows(4).any(|w| w == b"best"));
+        assert!(matches!(
+            PayloadCipher::from_secret("other-secret").decrypt(&wire),
+            Err(SecurityError::DecryptionFailed)
+        ));
+
+        // Key holders recover the message and its signature still verifies
+        let decrypted = cipher.decrypt(&wire).unwrap();
+        assert_eq!(decrypted, plaintext);
+        let received: SignedEpiphany = serde_json::from_slice(&decrypted).unwrap();
+        let payload = SignedPayload {
+            data: received.payload_bytes(),
+            signature: received.signature.clone(),
+            ..signed
+        };
+        assert!(manager.verify(&payload).is_ok());
+
+        let _ = fs::remove_file(&key_path);
+    }
 }
@@ -1,5 +1,11 @@
+use crate::config::ConvergenceConfig;
+use crate::security::ReputationManager;
+use crate::swarm_p2p::AppState;
+use qres_core::adaptive::regime_detector::Regime;
+use qres_core::resource_management::EnergyPool;
+use qres_core::tensor::{VarianceMonitor, CONVERGENCE_HISTORY};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
@@ -78,6 +84,203 @@ impl SingularityMetrics {
     }
 }
 
+/// Tracks global error across federation rounds and decides when the node
+/// can stop training.
+///
+/// After `patience_rounds` consecutive rounds below `target_error` the node
+/// enters inference-only mode: it keeps serving predictions but no longer
+/// applies federated updates. Any round at or above the target resumes
+/// training. The round history and the window test live in
+/// [`VarianceMonitor::has_converged`].
+#[derive(Debug, Clone)]
+pub struct ConvergenceMonitor {
+    target_error: f32,
+    /// Clamped to `1..=CONVERGENCE_HISTORY` so the window can always fill
+    patience_rounds: usize,
+    history: VarianceMonitor,
+    inference_only: bool,
+}
+
+impl ConvergenceMonitor {
+    pub fn new(config: &ConvergenceConfig) -> Self {
+        Self {
+            target_error: config.target_error,
+            patience_rounds: (config.patience_rounds as usize).clamp(1, CONVERGENCE_HISTORY),
+            history: VarianceMonitor::default(),
+            inference_only: false,
+        }
+    }
+
+    /// Record one round's global error. Returns `true` if the mode changed.
+    pub fn observe(&mut self, global_error: f32) -> bool {
+        let was_inference_only = self.inference_only;
+        self.history.observe_variance(global_error);
+        self.inference_only = self
+            .history
+            .has_converged(self.patience_rounds, self.target_error);
+        was_inference_only != self.inference_only
+    }
+
+    /// Whether training and aggregation are currently suspended
+    pub fn is_inference_only(&self) -> bool {
+        self.inference_only
+    }
+}
+
+/// Point-in-time capture of a node's swarm state for A/B experiments.
+///
+/// Run the same input against two configs (e.g. Krum vs TrimmedMean), take a
+/// snapshot of each, and `compare` them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwarmSnapshot {
+    pub timestamp: u64,
+    /// Per-predictor brain confidence
+    pub confidence: Vec<f32>,
+    /// Regime name as reported by the regime detector
+    pub regime: String,
+    pub energy_ratio: f32,
+    pub total_energy_consumed: u64,
+    /// PeerID -> trust score
+    pub reputation: BTreeMap<String, f32>,
+}
+
+/// Per-field differences between two snapshots (`other - self`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    /// Confidence delta per predictor (over the common prefix; see
+    /// `confidence_len_mismatch`)
+    pub confidence_delta: Vec<f32>,
+    /// `(self, other)` confidence lengths when they differ, in which case the
+    /// deltas cover only the shorter one
+    pub confidence_len_mismatch: Option<(usize, usize)>,
+    /// Largest absolute confidence delta
+    pub max_confidence_delta: f32,
+    /// `(self, other)` regimes when they differ
+    pub regime_change: Option<(String, String)>,
+    pub energy_ratio_delta: f32,
+    pub energy_consumed_delta: i64,
+    /// Trust delta for every peer seen by either snapshot (unknown peers count as 0.5)
+    pub reputation_delta: BTreeMap<String, f32>,
+    pub convergence: ConvergenceSummary,
+}
+
+/// Which side of a comparison converged tighter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConvergenceWinner {
+    Baseline,
+    Candidate,
+    Tie,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvergenceSummary {
+    /// Loss (1 - mean confidence) of the snapshot `compare` was called on
+    pub baseline_loss: f32,
+    /// Loss of the snapshot passed to `compare`
+    pub candidate_loss: f32,
+    pub winner: ConvergenceWinner,
+}
+
+/// Losses closer than this are reported as a tie.
+const CONVERGENCE_TIE_EPSILON: f32 = 1e-6;
+
+impl SwarmSnapshot {
+    pub fn new(
+        confidence: Vec<f32>,
+        regime: Regime,
+        energy_pool: &EnergyPool,
+        reputation: &ReputationManager,
+    ) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            confidence,
+            regime: format!("{:?}", regime),
+            energy_ratio: energy_pool.ratio(),
+            total_energy_consumed: energy_pool.lifetime_consumption(),
+            reputation: reputation
+                .peers
+                .iter()
+                .map(|(k, v)| (k.clone(), *v))
+                .collect(),
+        }
+    }
+
+    /// Capture the live state of a running node.
+    pub fn capture(state: &AppState) -> Self {
+        Self::new(
+            state.brain.confidence.clone(),
+            state.regime_detector.current_regime(),
+            &state.energy_pool,
+            &state.reputation,
+        )
+    }
+
+    /// Local loss, matching `SingularityMetrics::local_loss`.
+    pub fn loss(&self) -> f32 {
+        if self.confidence.is_empty() {
+            return 1.0;
+        }
+        1.0 - self.confidence.iter().sum::<f32>() / self.confidence.len() as f32
+    }
+
+    /// Compare against `other`, treating `self` as the baseline.
+    pub fn compare(&self, other: &SwarmSnapshot) -> SnapshotDiff {
+        let confidence_delta: Vec<f32> = self
+            .confidence
+            .iter()
+            .zip(&other.confidence)
+            .map(|(a, b)| b - a)
+            .collect();
+        let max_confidence_delta = confidence_delta
+            .iter()
+            .fold(0.0f32, |acc, d| acc.max(d.abs()));
+        let confidence_len_mismatch = (self.confidence.len() != other.confidence.len())
+            .then_some((self.confidence.len(), other.confidence.len()));
+
+        let regime_change = if self.regime != other.regime {
+            Some((self.regime.clone(), other.regime.clone()))
+        } else {
+            None
+        };
+
+        let mut reputation_delta = BTreeMap::new();
+        for peer in self.reputation.keys().chain(other.reputation.keys()) {
+            let before = self.reputation.get(peer).copied().unwrap_or(0.5);
+            let after = other.reputation.get(peer).copied().unwrap_or(0.5);
+            reputation_delta.insert(peer.clone(), after - before);
+        }
+
+        let baseline_loss = self.loss();
+        let candidate_loss = other.loss();
+        let winner = if (baseline_loss - candidate_loss).abs() <= CONVERGENCE_TIE_EPSILON {
+            ConvergenceWinner::Tie
+        } else if baseline_loss < candidate_loss {
+            ConvergenceWinner::Baseline
+        } else {
+            ConvergenceWinner::Candidate
+        };
+
+        SnapshotDiff {
+            confidence_delta,
+            confidence_len_mismatch,
+            max_confidence_delta,
+            regime_change,
+            energy_ratio_delta: other.energy_ratio - self.energy_ratio,
+            energy_consumed_delta: other.total_energy_consumed as i64
+                - self.total_energy_consumed as i64,
+            reputation_delta,
+            convergence: ConvergenceSummary {
+                baseline_loss,
+                candidate_loss,
+                winner,
+            },
+        }
+    }
+}
+
 impl Default for CompressionStats {
     fn default() -> Self {
         let mut engines = HashMap::new();
@@ -143,3 +346,107 @@ impl CompressionStats {
 lazy_static::lazy_static! {
     pub static ref GLOBAL_STATS: Arc<Mutex<CompressionStats>> = Arc::new(Mutex::new(CompressionStats::load()));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reputation(peers: &[(&str, f32)]) -> ReputationManager {
+        let mut rep =
+            ReputationManager::new(std::env::temp_dir().join("qres_snapshot_unused.json"));
+        rep.peers = peers.iter().map(|(p, t)| (p.to_string(), *t)).collect();
+        rep
+    }
+
+    #[test]
+    fn test_snapshot_compare_reports_deltas_and_convergence() {
+        let mut pool = EnergyPool::new(1000);
+        let krum = SwarmSnapshot::new(
+            vec![0.90, 0.80, 0.70],
+            Regime::Calm,
+            &pool,
+            &reputation(&[("peer_a", 0.6)]),
+        );
+
+        pool.spend(250);
+        let trimmed = SwarmSnapshot::new(
+            vec![0.95, 0.80, 0.60],
+            Regime::Storm,
+            &pool,
+            &reputation(&[("peer_a", 0.7), ("peer_b", 0.4)]),
+        );
+
+        let diff = krum.compare(&trimmed);
+        let expected = [0.05, 0.0, -0.10];
+        assert_eq!(diff.confidence_delta.len(), 3);
+        for (d, e) in diff.confidence_delta.iter().zip(expected) {
+            assert!((d - e).abs() < 1e-6, "delta {} != {}", d, e);
+        }
+        assert!((diff.max_confidence_delta - 0.10).abs() < 1e-6);
+        assert_eq!(diff.confidence_len_mismatch, None);
+        assert_eq!(
+            diff.regime_change,
+            Some(("Calm".to_string(), "Storm".to_string()))
+        );
+        assert!((diff.energy_ratio_delta + 0.25).abs() < 1e-6);
+        assert_eq!(diff.energy_consumed_delta, 250);
+        assert!((diff.reputation_delta["peer_a"] - 0.1).abs() < 1e-6);
+        assert!((diff.reputation_delta["peer_b"] + 0.1).abs() < 1e-6);
+
+        // Mean confidence 0.80 vs 0.7833: the baseline converged tighter
+        assert_eq!(diff.convergence.winner, ConvergenceWinner::Baseline);
+        assert!(diff.convergence.baseline_loss < diff.convergence.candidate_loss);
+        assert_eq!(
+            trimmed.compare(&krum).convergence.winner,
+            ConvergenceWinner::Candidate
+        );
+        assert_eq!(
+            krum.compare(&krum).convergence.winner,
+            ConvergenceWinner::Tie
+        );
+    }
+
+    #[test]
+    fn test_snapshot_compare_reports_confidence_length_mismatch() {
+        let pool = EnergyPool::new(1000);
+        let short = SwarmSnapshot::new(vec![0.5, 0.5], Regime::Calm, &pool, &reputation(&[]));
+        let long = SwarmSnapshot::new(vec![0.5, 0.6, 0.9], Regime::Calm, &pool, &reputation(&[]));
+
+        let diff = short.compare(&long);
+        assert_eq!(diff.confidence_len_mismatch, Some((2, 3)));
+        assert_eq!(diff.confidence_delta.len(), 2);
+        assert_eq!(long.compare(&short).confidence_len_mismatch, Some((3, 2)));
+    }
+
+    #[test]
+    fn test_convergence_monitor_switches_to_inference_and_back() {
+        let mut monitor = ConvergenceMonitor::new(&ConvergenceConfig {
+            target_error: 0.01,
+            patience_rounds: 3,
+        });
+
+        // A dip that does not last is not convergence
+        assert!(!monitor.observe(0.005));
+        assert!(!monitor.observe(0.02));
+        assert!(!monitor.is_inference_only());
+
+        // Sustained low error switches to inference-only
+        assert!(!monitor.observe(0.005));
+        assert!(!monitor.observe(0.004));
+        assert!(monitor.observe(0.003));
+        assert!(monitor.is_inference_only());
+        assert!(!monitor.observe(0.002));
+
+        // An error spike resumes training
+        assert!(monitor.observe(0.2));
+        assert!(!monitor.is_inference_only());
+
+        // Zero patience still needs one round below target
+        let mut monitor = ConvergenceMonitor::new(&ConvergenceConfig {
+            target_error: 0.01,
+            patience_rounds: 0,
+        });
+        assert!(!monitor.is_inference_only());
+        assert!(monitor.observe(0.005));
+    }
+}
@@ -241,8 +241,12 @@ impl QresSwarm {
                                                        // local_brain.update_weights(3, weights);
                                                   }
                                             }
-                                            local_brain.merge(&remote_brain, 0.05);
-                                            let _ = tokio::fs::write(&brain_path, local_brain.to_json()).await;
+                                            match local_brain.merge(&remote_brain, 0.05) {
+                                                Ok(()) => {
+                                                    let _ = tokio::fs::write(&brain_path, local_brain.to_json()).await;
+                                                }
+                                                Err(e) => println!("🚫 Rejected wisdom from {}: {}", peer_id, e),
+                                            }
                                         }
                                     } else {
                                          println!("🚫 Rejected Malformed Wisdom from {}", peer_id);
@@ -1,37 +1,57 @@
+use crate::atomic_file::write_file_atomic;
+use crate::audit_log::{AuditLog, AuditRecord};
+use crate::bootstrap::{BootstrapRedialer, REDIAL_CHECK_INTERVAL};
 use crate::brain_aggregator::{BrainAggregator, FederatedAverager};
-use crate::config::Config;
-use crate::living_brain::{LivingBrain, SignedEpiphany};
+use crate::config::{Config, SecurityConfig, StormPolicy, SwarmTransport};
+use crate::dreaming::{DreamBuffer, DreamingManager};
+use crate::fragmentation::{
+    self, Fragment, FragmentError, Reassembler, FRAGMENT_HEADER_LEN, FRAGMENT_MTU,
+    REASSEMBLY_TIMEOUT,
+};
+use crate::living_brain::{LivingBrain, PredictorCapability, SignedEpiphany};
 use crate::peer_keys::PeerKeyStore;
-use crate::security::{ReputationManager, SecurityManager, SignedPayload};
-use crate::stats::SingularityMetrics;
-use axum::{extract::State, routing::get, Json, Router};
+use crate::reciprocity::ReciprocityLedger;
+use crate::security::{
+    PayloadCipher, ReputationManager, SecurityError, SecurityManager, SignedPayload,
+    PAYLOAD_OVERHEAD,
+};
+use crate::stats::{ConvergenceMonitor, SingularityMetrics};
+use crate::verification_queue::VerificationQueue;
+use axum::{
+    extract::{Path, State},
+    http::header,
+    routing::{get, post},
+    Json, Router,
+};
 use fixed::types::I16F16;
 use libp2p::futures::StreamExt; // For select_next_some
 use libp2p::gossipsub::IdentTopic; // Added helper
+use libp2p::multiaddr::Protocol;
 use libp2p::{
     gossipsub, identify, identity, mdns, noise,
-    swarm::{NetworkBehaviour, SwarmEvent},
-    tcp, yamux, PeerId, SwarmBuilder,
+    swarm::{behaviour::toggle::Toggle, dial_opts::DialOpts, NetworkBehaviour, SwarmEvent},
+    tcp, yamux, Multiaddr, PeerId, SwarmBuilder,
 };
-use qres_core::adaptive::regime_detector::{Regime, RegimeDetector};
+use qres_core::adaptive::regime_detector::{Regime, RegimeDetector, RegimeSnapshot};
 use qres_core::adaptive::SilenceController;
-use qres_core::consensus::krum::Bfp16Vec; // v19.0 Bfp16Vec
+use qres_core::consensus::krum::{BfpVec, BfpWidth}; // v19.0 block floating point
 use qres_core::privacy::PrivacyAccountant;
+use qres_core::reputation::ReputationTracker;
 use qres_core::resource_management::{energy_costs, EnergyPool};
 use qres_core::tensor::{FixedTensor, I8F8};
 use qres_core::zk_proofs::{ProofBundle, ZkNormProver};
 use rand;
 use serde::{Deserialize, Serialize}; // Added Deserialize
 use std::collections::hash_map::DefaultHasher;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::hash::{Hash, Hasher};
 use std::io;
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::{Duration, SystemTime};
-use tokio::sync::RwLock;
-use tracing::{info, warn};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::{mpsc, RwLock};
+use tracing::{debug, info, warn};
 
 // Topic for brain synchronization
 const BRAIN_TOPIC: &str = "qres-hive-v2";
@@ -42,6 +62,8 @@ const BRAIN_TOPIC: &str = "qres-hive-v2";
 const FEDERATION_BUFFER_SIZE: usize = 50;
 /// Exponential decay half-life in seconds for federation weights.
 const FEDERATION_HALF_LIFE_SECS: f64 = 300.0;
+/// Seconds without accepted gossip before the node starts dreaming.
+const DREAM_IDLE_THRESHOLD_SECS: u64 = 300;
 
 /// Initial differential privacy budget (epsilon) for the accountant.
 const INITIAL_PRIVACY_BUDGET: f64 = 10.0;
@@ -57,6 +79,12 @@ const REGIME_ENTROPY_THRESHOLD: f32 = 0.8;
 /// Throughput threshold in bytes/sec for regime detection (1 MB/s).
 const REGIME_THROUGHPUT_THRESHOLD: f32 = 1_000_000.0;
 
+/// Suffix appended to the brain path for the persisted regime detector state.
+const REGIME_SNAPSHOT_SUFFIX: &str = ".regime.json";
+
+/// Broadcast ticks a silence state is held before it may change (1 minute).
+const SILENCE_MIN_DWELL_TICKS: u64 = 6;
+
 /// Total energy capacity for the daemon's energy pool.
 const ENERGY_POOL_CAPACITY: u32 = 10_000;
 
@@ -73,6 +101,10 @@ const BRAIN_BROADCAST_INTERVAL_SECS: u64 = 10;
 /// Federation epoch interval in seconds.
 const FEDERATION_EPOCH_INTERVAL_SECS: u64 = 5;
 
+/// Federation epochs a peer stays banned from gossip once its trust falls
+/// below the ban threshold.
+const GOSSIP_BAN_EPOCHS: u64 = 12;
+
 /// Gossipsub heartbeat interval in seconds.
 const GOSSIPSUB_HEARTBEAT_SECS: u64 = 1;
 
@@ -81,28 +113,37 @@ const ZK_NORM_THRESHOLD: f32 = 10.0;
 /// Reputation score threshold for accepting proofless updates.
 const REPUTATION_TRUST_THRESHOLD: f32 = 80.0;
 
-/// Elapsed milliseconds passed to regime detector on update.
-const REGIME_UPDATE_INTERVAL_MS: usize = 10_000;
-
-/// Singularity threshold: global error rate below this triggers singularity event.
-const SINGULARITY_ERROR_THRESHOLD: f32 = 0.01;
-
 // v19.0: Summary Gene for Fast Onboarding
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SummaryGene {
     pub round_index: u64,
     pub history_hash: [u8; 32],
-    pub consensus: Bfp16Vec,
-    pub variance: Bfp16Vec,
+    pub consensus: BfpVec,
+    pub variance: BfpVec,
 }
 
 impl SummaryGene {
-    pub fn new(round: u64, hash: [u8; 32], consensus: &[f32], variance: &[f32]) -> Self {
+    pub fn new(
+        round: u64,
+        hash: [u8; 32],
+        consensus: &[f32],
+        variance: &[f32],
+        width: BfpWidth,
+    ) -> Self {
         Self {
             round_index: round,
             history_hash: hash,
-            consensus: Bfp16Vec::from_f32_slice(consensus),
-            variance: Bfp16Vec::from_f32_slice(variance),
+            consensus: BfpVec::from_f32_slice(width, consensus),
+            variance: BfpVec::from_f32_slice(width, variance),
+        }
+    }
+
+    /// Mantissa width for the current regime: Storm halves the payload
+    /// with BFP-8, mirroring the I8F8 downcast on the gossip path.
+    pub fn width_for_regime(regime: Regime) -> BfpWidth {
+        match regime {
+            Regime::Storm => BfpWidth::Bfp8,
+            Regime::PreStorm | Regime::Calm => BfpWidth::Bfp16,
         }
     }
 
@@ -113,14 +154,8 @@ impl SummaryGene {
         bytes.extend_from_slice(&self.history_hash);
 
         // Manual BFP compression using qres_core arithmetic module
-        bytes.extend(qres_core::encoding::arithmetic::compress_bfp(
-            self.consensus.exponent,
-            &self.consensus.mantissas,
-        ));
-        bytes.extend(qres_core::encoding::arithmetic::compress_bfp(
-            self.variance.exponent,
-            &self.variance.mantissas,
-        ));
+        bytes.extend(self.consensus.to_bytes());
+        bytes.extend(self.variance.to_bytes());
 
         bytes
     }
@@ -134,6 +169,14 @@ pub struct SwarmStatus {
     pub brain_confidence: Vec<f32>,
     pub total_energy_consumed: u64,   // calibration metric
     pub energy_efficiency_ratio: f32, // useful work / total energy
+    /// Older audit records were evicted from memory
+    pub audit_history_overflowed: bool,
+    /// Training suspended after convergence
+    pub inference_only: bool,
+    /// Current regime (`Calm`, `PreStorm`, `Storm`)
+    pub regime: String,
+    /// Strategic silence state (`Active`, `Alert`, `DeepSilence`)
+    pub silence_state: String,
 }
 
 pub struct AppState {
@@ -149,17 +192,213 @@ pub struct AppState {
     pub federated_averager: FederatedAverager,
     pub config: Config,
     pub privacy_accountant: PrivacyAccountant,
-    pub zk_prover: ZkNormProver,
+    /// Shared so proofs are generated and checked outside the state lock
+    pub zk_prover: Arc<ZkNormProver>,
     pub regime_detector: RegimeDetector,
+    /// Start of the regime detector's millisecond clock
+    pub regime_clock: Instant,
+    /// Detector time at `regime_clock`, so a restored snapshot's clock keeps running
+    pub regime_clock_offset_ms: u64,
     pub silence_controller: SilenceController,
     pub energy_pool: EnergyPool, // Track energy for calibration
+    /// Predictor capability advertised by each peer via identify
+    pub peer_capabilities: HashMap<String, PredictorCapability>,
+    /// Verification history of accepted updates
+    pub audit_history: AuditLog<AuditRecord>,
+    /// Updates rejected during verification
+    pub rejection_log: AuditLog<AuditRecord>,
+    /// Gossip payload encryption for confidential swarms
+    pub payload_cipher: Option<PayloadCipher>,
+    /// Switches the node to inference-only once the swarm has converged
+    pub convergence: ConvergenceMonitor,
+    /// Per-peer give/take accounting for tit-for-tat buffering
+    pub reciprocity: ReciprocityLedger,
+    /// Verified updates held back during a local Storm (`StormPolicy::Defer`)
+    pub deferred_updates: VecDeque<SignedEpiphany>,
+    /// Completed federated averaging rounds; resumes from the restored dream
+    /// replay so round tags keep increasing across restarts
+    pub federated_rounds: u64,
+    /// Federation epoch ticks since startup; gossip bans expire on this clock
+    pub federation_epochs: u64,
+    /// Timed gossip bans, keyed by [`ban_key`]
+    pub bans: ReputationTracker,
+    /// Peers currently blacklisted from gossipsub, by [`ban_key`]
+    pub gossip_banned: HashMap<[u8; 32], PeerId>,
+    /// Gossipsub author of each signature-verified `sender_id`; reputation is
+    /// keyed by sender, bans are applied to the transport peer
+    pub sender_peers: HashMap<String, PeerId>,
+    /// Partially received fragmented epiphanies
+    pub reassembler: Reassembler,
+    /// Idle-time dreaming and the replay buffer served to waking peers
+    pub dreaming: DreamingManager,
+}
+
+impl AppState {
+    /// Dream one sample if the node has been idle, tagged with the current
+    /// federation round. Returns whether a sample was recorded.
+    pub fn dream_if_idle(&mut self) -> bool {
+        if !self.dreaming.should_dream() {
+            return false;
+        }
+        let mean = if self.brain.confidence.is_empty() {
+            0.5
+        } else {
+            self.brain.confidence.iter().sum::<f32>() / self.brain.confidence.len() as f32
+        };
+        let pattern_mean = (mean.clamp(0.0, 1.0) * 255.0) as u8;
+        self.dreaming
+            .generate_dream_sample(self.federated_rounds, pattern_mean);
+        self.dreaming.reset_idle();
+        true
+    }
+
+    /// Install the replay buffer persisted by a previous run, moving the round
+    /// counter past its entries so new samples sort after them.
+    pub fn restore_dream_replay(&mut self, replay: DreamBuffer) {
+        if let Some(latest) = replay.latest_round() {
+            self.federated_rounds = self.federated_rounds.max(latest);
+        }
+        self.dreaming = DreamingManager::new(DREAM_IDLE_THRESHOLD_SECS).with_replay(replay);
+    }
+
+    /// Whether `peer` is serving a gossip ban this federation epoch.
+    pub fn is_gossip_banned(&self, peer: &PeerId) -> bool {
+        self.gossip_banned.contains_key(&ban_key(peer))
+            && self
+                .bans
+                .is_banned_at(&ban_key(peer), self.federation_epochs)
+    }
+
+    /// Ban the peers behind senders whose trust fell below the threshold and
+    /// lift expired bans.
+    ///
+    /// Senders are mapped to peers through [`sender_peers`](Self::sender_peers);
+    /// a sender never seen with a verified signature cannot be banned. Returns
+    /// `(newly_banned, expired)` for the caller to blacklist from and restore
+    /// to gossipsub. Expired peers' senders re-enter on probation.
+    pub fn update_gossip_bans(&mut self) -> (Vec<PeerId>, Vec<PeerId>) {
+        let epoch = self.federation_epochs;
+        let mut expired = Vec::new();
+        for key in self.bans.expire_bans(epoch) {
+            if let Some(peer) = self.gossip_banned.remove(&key) {
+                for (sender_id, _) in self.sender_peers.iter().filter(|(_, p)| **p == peer) {
+                    self.reputation.restore_probation(sender_id);
+                }
+                expired.push(peer);
+            }
+        }
+
+        let mut distrusted: Vec<PeerId> = Vec::new();
+        for (sender_id, peer) in &self.sender_peers {
+            if self.reputation.is_banned(sender_id)
+                && !self.gossip_banned.contains_key(&ban_key(peer))
+                && !distrusted.contains(peer)
+            {
+                distrusted.push(*peer);
+            }
+        }
+        for peer in &distrusted {
+            let key = ban_key(peer);
+            self.bans.ban(&key, epoch + GOSSIP_BAN_EPOCHS);
+            self.gossip_banned.insert(key, *peer);
+        }
+        (distrusted, expired)
+    }
+
+    /// Capability this node advertises to peers.
+    pub fn local_capability(&self) -> PredictorCapability {
+        PredictorCapability::of(&self.brain)
+    }
+
+    /// Record a rejected update in the rejection audit log.
+    pub fn record_rejection(&mut self, peer_id: &str, reason: &str) {
+        self.rejection_log.push(AuditRecord::now(peer_id, reason));
+    }
+
+    /// Move retained audit records to disk (no-op unless flushing is enabled).
+    pub fn flush_audit_logs(&mut self) {
+        for log in [&mut self.audit_history, &mut self.rejection_log] {
+            if let Err(e) = log.flush() {
+                warn!(error = %e, "Failed to flush audit log");
+            }
+        }
+    }
+
+    /// Whether either audit log has dropped records from memory.
+    pub fn audit_history_overflowed(&self) -> bool {
+        self.audit_history.overflowed() || self.rejection_log.overflowed()
+    }
+
+    /// Monotonic milliseconds on the regime detector's clock.
+    pub fn regime_now_ms(&self) -> u64 {
+        self.regime_clock_offset_ms
+            .saturating_add(self.regime_clock.elapsed().as_millis() as u64)
+    }
+
+    /// Install a restored detector, resuming its clock after the latest
+    /// timestamp it holds.
+    pub fn restore_regime_detector(&mut self, detector: RegimeDetector) {
+        let snapshot = detector.to_snapshot();
+        self.regime_clock_offset_ms = snapshot
+            .recent_transitions_ms
+            .iter()
+            .copied()
+            .fold(snapshot.last_update_ms, u64::max);
+        self.regime_clock = Instant::now();
+        self.regime_detector = detector;
+    }
+
+    /// Feed the regime detector and react to the transition it reports, if any.
+    pub fn update_regime(&mut self, entropy: f32, packet_size: usize, now_ms: u64) {
+        if let Some((old, new)) = self.regime_detector.update(entropy, packet_size, now_ms) {
+            info!(from = ?old, to = ?new, "Regime transition");
+            if new == Regime::Calm {
+                self.apply_deferred_updates();
+            }
+        }
+    }
+
+    /// Whether the local node is in Storm.
+    pub fn in_storm(&self) -> bool {
+        self.regime_detector.current_regime() == Regime::Storm
+    }
+
+    /// Hold a verified update until the Storm subsides, dropping the oldest when full.
+    fn defer_update(&mut self, epiphany: SignedEpiphany) {
+        if self.deferred_updates.len() >= FEDERATION_BUFFER_SIZE {
+            self.deferred_updates.pop_front();
+        }
+        self.deferred_updates.push_back(epiphany);
+    }
+
+    /// Buffer every update deferred during the last Storm for federation.
+    fn apply_deferred_updates(&mut self) {
+        if self.deferred_updates.is_empty() {
+            return;
+        }
+        info!(
+            count = self.deferred_updates.len(),
+            "Applying updates deferred during Storm"
+        );
+        for epiphany in self.deferred_updates.drain(..) {
+            self.federated_averager.add_update(epiphany);
+        }
+    }
+
+    /// Whether a peer's brain may be aggregated with ours. Peers that have not
+    /// completed identify yet are accepted; known mismatches are refused.
+    pub fn is_compatible_peer(&self, peer_id: &str) -> bool {
+        self.peer_capabilities
+            .get(peer_id)
+            .is_none_or(|cap| *cap == self.local_capability())
+    }
 }
 
 // Custom Behavior Struct
 #[derive(NetworkBehaviour)]
 pub struct QresBehavior {
     pub gossipsub: gossipsub::Behaviour,
-    pub mdns: mdns::tokio::Behaviour,
+    pub mdns: Toggle<mdns::tokio::Behaviour>,
     pub identify: identify::Behaviour,
 }
 
@@ -167,27 +406,106 @@ pub async fn start_p2p_node(
     brain_path: String,
     port: u16,
     key_path_override: Option<String>,
+    transport_override: Option<SwarmTransport>,
+    listen_override: Option<String>,
+    no_mdns: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let (id_keys, state) = setup_identity_and_state(key_path_override)?;
+    if let Some(detector) = load_regime_snapshot(&brain_path) {
+        info!(regime = ?detector.current_regime(), "Restored regime detector state");
+        state.write().await.restore_regime_detector(detector);
+    }
+    // Start from the persisted brain so a shutdown flush never writes back a default one
+    if let Some(brain) = fs::read_to_string(&brain_path)
+        .ok()
+        .and_then(|json| LivingBrain::from_json(&json))
+    {
+        state.write().await.brain = brain;
+    }
+    // Catch up on the replay buffer persisted before the node went to sleep
+    let replay = DreamBuffer::load();
+    if !replay.is_empty() {
+        info!(entries = replay.len(), latest_round = ?replay.latest_round(), "Restored dream replay buffer");
+    }
+    state.write().await.restore_dream_replay(replay);
     spawn_status_api(state.clone(), port);
-    let mut swarm = build_swarm(id_keys)?;
-    swarm.listen_on("/ip4/0.0.0.0/tcp/0".parse()?)?;
+
+    // Priority: 1. CLI Override, 2. Config transport / listen address / mDNS
+    let (transport, listen_addr, options) = {
+        let app_state = state.read().await;
+        let swarm_config = &app_state.config.swarm;
+        (
+            transport_override.unwrap_or(swarm_config.transport),
+            listen_override.or(swarm_config.listen_addr.clone()),
+            SwarmOptions {
+                enable_mdns: swarm_config.mdns && !no_mdns,
+                max_message_size: swarm_config.max_message_size,
+                capability: app_state.local_capability(),
+            },
+        )
+    };
+    let listen_addr = resolve_listen_multiaddr(listen_addr.as_deref(), transport)?;
+    info!(transport = ?transport, mdns = options.enable_mdns, "Building swarm transport");
+    if !options.enable_mdns {
+        info!("mDNS disabled; peers must be dialed explicitly");
+    }
+    let mut swarm = build_swarm(id_keys, transport, options).await?;
+    swarm.listen_on(listen_addr)?;
+    let bootstrap_peers = state.read().await.config.swarm.bootstrap_peers.clone();
+    let mut redialer = BootstrapRedialer::default();
+    dial_bootstrap_peers(&mut swarm, &mut redialer, &bootstrap_peers);
 
     let mut broadcast_interval =
         tokio::time::interval(Duration::from_secs(BRAIN_BROADCAST_INTERVAL_SECS));
     let mut federation_epoch =
         tokio::time::interval(Duration::from_secs(FEDERATION_EPOCH_INTERVAL_SECS));
+    let mut redial_check = tokio::time::interval(REDIAL_CHECK_INTERVAL);
+    let verify_queue = {
+        let swarm_config = &state.read().await.config.swarm;
+        VerificationQueue::new(
+            swarm_config.verify_queue_capacity,
+            swarm_config.verify_concurrency,
+        )
+    };
+
+    let (validation_tx, mut validation_rx) = mpsc::unbounded_channel::<ValidationReport>();
+
+    let shutdown = tokio::signal::ctrl_c();
+    tokio::pin!(shutdown);
 
     loop {
         tokio::select! {
+            _ = &mut shutdown => {
+                info!("Shutdown signal received");
+                handle_shutdown(&state, &brain_path).await;
+                return Ok(());
+            }
             _ = broadcast_interval.tick() => {
                 handle_broadcast_tick(&state, &mut swarm, &brain_path).await;
             }
             _ = federation_epoch.tick() => {
                 handle_federation_tick(&state, &brain_path).await;
+                sync_gossip_bans(&state, &mut swarm).await;
+            }
+            _ = redial_check.tick() => {
+                redial_bootstrap_peers(&mut swarm, &mut redialer, Instant::now());
+            }
+            Some((message_id, source, acceptance)) = validation_rx.recv() => {
+                let _ = swarm
+                    .behaviour_mut()
+                    .gossipsub
+                    .report_message_validation_result(&message_id, &source, acceptance);
             }
             event = swarm.select_next_some() => {
-                handle_swarm_event(event, &state, &mut swarm).await;
+                handle_swarm_event(
+                    event,
+                    &state,
+                    &mut swarm,
+                    &mut redialer,
+                    &verify_queue,
+                    &validation_tx,
+                )
+                .await;
             }
         }
     }
@@ -215,6 +533,7 @@ fn setup_identity_and_state(
         let key_path = PathBuf::from(key_path_str);
         match SecurityManager::new(&key_path, config.security.require_signatures) {
             Ok(mgr) => {
+                let mgr = configure_security_manager(mgr, &config.security);
                 info!(pubkey = %mgr.public_key_hex(), path = ?key_path, "Security manager initialized");
                 Some(mgr)
             }
@@ -227,6 +546,7 @@ fn setup_identity_and_state(
         let key_path = crate::config::qres_data_dir().join("node_key");
         match SecurityManager::new(&key_path, true) {
             Ok(mgr) => {
+                let mgr = configure_security_manager(mgr, &config.security);
                 info!(pubkey = %mgr.public_key_hex(), key_path = ?key_path, "Security manager auto-initialized");
                 Some(mgr)
             }
@@ -240,10 +560,50 @@ fn setup_identity_and_state(
     };
 
     let rep_path = crate::config::qres_data_dir().join("reputation.json");
-    let reputation = ReputationManager::new(rep_path);
+    let mut reputation = ReputationManager::new(rep_path);
+    reputation.set_trust_decay(config.security.trust_decay);
+
+    let state = Arc::new(RwLock::new(new_app_state(
+        peer_id.to_string(),
+        config,
+        peer_keys,
+        security,
+        reputation,
+    )));
+
+    Ok((id_keys, state))
+}
 
-    let state = Arc::new(RwLock::new(AppState {
-        local_peer_id: peer_id.to_string(),
+fn new_app_state(
+    local_peer_id: String,
+    config: Config,
+    peer_keys: PeerKeyStore,
+    security: Option<SecurityManager>,
+    reputation: ReputationManager,
+) -> AppState {
+    let reassembler = Reassembler::new(REASSEMBLY_TIMEOUT, config.swarm.max_message_size);
+    let mut audit_history = AuditLog::new(config.audit.history_capacity);
+    let mut rejection_log = AuditLog::new(config.audit.rejection_capacity);
+    if config.audit.flush_to_disk {
+        let data_dir = crate::config::qres_data_dir();
+        audit_history = audit_history.with_flush_path(data_dir.join("audit_history.jsonl"));
+        rejection_log = rejection_log.with_flush_path(data_dir.join("audit_rejections.jsonl"));
+    }
+    let federated_averager =
+        FederatedAverager::new(FEDERATION_BUFFER_SIZE, FEDERATION_HALF_LIFE_SECS)
+            .with_max_update_age(config.aggregation.max_update_age_secs as f64)
+            .with_strategy_config(config.aggregation.clone())
+            .with_proximal_mu(config.aggregation.fedprox_mu);
+
+    let convergence = ConvergenceMonitor::new(&config.convergence);
+    let payload_cipher = config
+        .security
+        .swarm_secret
+        .as_deref()
+        .map(PayloadCipher::from_secret);
+
+    AppState {
+        local_peer_id,
         connected_peers: HashSet::new(),
         known_peers: HashSet::new(),
         brain: LivingBrain::default(),
@@ -252,37 +612,92 @@ fn setup_identity_and_state(
         reputation,
         require_signatures: config.security.require_signatures,
         aggregator: BrainAggregator::new(config.aggregation.clone()),
-        federated_averager: FederatedAverager::new(
-            FEDERATION_BUFFER_SIZE,
-            FEDERATION_HALF_LIFE_SECS,
-        ),
+        federated_averager,
         config,
         privacy_accountant: PrivacyAccountant::new(
             INITIAL_PRIVACY_BUDGET,
             PRIVACY_DELTA,
             PRIVACY_DECAY_COEFFICIENT,
         ),
-        zk_prover: ZkNormProver::new(),
+        zk_prover: Arc::new(ZkNormProver::new()),
         regime_detector: RegimeDetector::new(
             REGIME_WINDOW_SIZE,
             REGIME_ENTROPY_THRESHOLD,
             REGIME_THROUGHPUT_THRESHOLD,
         ),
-        silence_controller: SilenceController::new(),
-        energy_pool: EnergyPool::new(ENERGY_POOL_CAPACITY),
-    }));
+        regime_clock: Instant::now(),
+        regime_clock_offset_ms: 0,
+        silence_controller: {
+            let mut controller = SilenceController::new();
+            controller.set_min_dwell(SILENCE_MIN_DWELL_TICKS);
+            controller
+        },
+        energy_pool: EnergyPool::with_harvest_rate(
+            ENERGY_POOL_CAPACITY,
+            energy_costs::RECHARGE_RATE,
+        ),
+        peer_capabilities: HashMap::new(),
+        audit_history,
+        rejection_log,
+        payload_cipher,
+        convergence,
+        reciprocity: ReciprocityLedger::new(),
+        deferred_updates: VecDeque::new(),
+        federated_rounds: 0,
+        federation_epochs: 0,
+        bans: ReputationTracker::new(),
+        gossip_banned: HashMap::new(),
+        sender_peers: HashMap::new(),
+        reassembler,
+        dreaming: DreamingManager::new(DREAM_IDLE_THRESHOLD_SECS),
+    }
+}
 
-    Ok((id_keys, state))
+/// Apply the configured clock skew and, after a key rotation, load the
+/// previous key so updates carry an endorsement of the current one.
+fn configure_security_manager(mgr: SecurityManager, config: &SecurityConfig) -> SecurityManager {
+    let mut mgr = mgr.with_max_clock_skew(config.max_clock_skew_secs);
+    if let Some(previous_path) = &config.previous_key_path {
+        match mgr.load_previous_key(&PathBuf::from(previous_path)) {
+            Ok(()) => info!(path = %previous_path, "Previous key loaded, announcing key rotation"),
+            Err(e) => {
+                warn!(error = %e, path = %previous_path, "Failed to load previous key, not announcing rotation")
+            }
+        }
+    }
+    mgr
+}
+
+/// Routes served by the P2P status API. `gossip_inject` adds the
+/// `/gossip-inject` debugging route.
+fn status_router(state: Arc<RwLock<AppState>>, gossip_inject: bool) -> Router {
+    let mut router = Router::new()
+        .route("/status", get(get_status))
+        .route("/brain", get(get_brain))
+        .route("/peers", get(get_peers))
+        .route("/health", get(get_health))
+        .route("/metrics", get(get_metrics))
+        .route("/dream-since/:round", get(get_dream_since));
+    if gossip_inject {
+        router = router.route("/gossip-inject", post(post_gossip_inject));
+    }
+    router.with_state(state)
+}
+
+/// Whether an opt-in switch like `QRES_GOSSIP_INJECT` is explicitly on.
+/// Only "1" or "true" count, so `QRES_GOSSIP_INJECT=0` stays disabled.
+fn env_flag_enabled(value: Option<&str>) -> bool {
+    value.is_some_and(|v| v.trim() == "1" || v.trim().eq_ignore_ascii_case("true"))
 }
 
 /// Spawn the P2P status API on the given port.
 fn spawn_status_api(state: Arc<RwLock<AppState>>, port: u16) {
     tokio::spawn(async move {
-        let app = Router::new()
-            .route("/status", get(get_status))
-            .route("/brain", get(get_brain))
-            .route("/health", get(get_health))
-            .with_state(state);
+        let gossip_inject = env_flag_enabled(std::env::var("QRES_GOSSIP_INJECT").ok().as_deref());
+        if gossip_inject {
+            warn!("QRES_GOSSIP_INJECT set: POST /gossip-inject accepts unauthenticated updates");
+        }
+        let app = status_router(state, gossip_inject);
 
         let addr_str = if std::env::var("QRES_PUBLIC").is_ok() {
             format!("0.0.0.0:{}", port)
@@ -304,58 +719,198 @@ fn spawn_status_api(state: Arc<RwLock<AppState>>, port: u16) {
     });
 }
 
-/// Build the libp2p swarm with gossipsub, mDNS, and identify protocols.
-fn build_swarm(
+/// Wildcard listen address for the selected transport (ephemeral port).
+fn listen_multiaddr(transport: SwarmTransport) -> Multiaddr {
+    let addr = match transport {
+        SwarmTransport::Tcp => "/ip4/0.0.0.0/tcp/0",
+        SwarmTransport::Quic => "/ip4/0.0.0.0/udp/0/quic-v1",
+        SwarmTransport::Ws => "/ip4/0.0.0.0/tcp/0/ws",
+    };
+    addr.parse().expect("static multiaddr is valid")
+}
+
+/// Explicit listen address if configured, else an ephemeral one for `transport`.
+fn resolve_listen_multiaddr(
+    explicit: Option<&str>,
+    transport: SwarmTransport,
+) -> Result<Multiaddr, libp2p::multiaddr::Error> {
+    match explicit {
+        Some(addr) => addr.parse(),
+        None => Ok(listen_multiaddr(transport)),
+    }
+}
+
+/// Peer ID of a `/p2p/<peer id>`-terminated address.
+fn multiaddr_peer_id(addr: &Multiaddr) -> Option<PeerId> {
+    addr.iter().find_map(|protocol| match protocol {
+        Protocol::P2p(peer_id) => Some(peer_id),
+        _ => None,
+    })
+}
+
+/// Dial one bootstrap address after waiting `backoff` (`None` on the first
+/// attempt), keeping it as an explicit gossipsub peer when its peer ID is
+/// known. The dial is tracked by `redialer`, so a failure schedules a retry.
+fn dial_bootstrap_peer(
+    swarm: &mut libp2p::Swarm<QresBehavior>,
+    redialer: &mut BootstrapRedialer,
+    addr: &Multiaddr,
+    backoff: Option<Duration>,
+) -> bool {
+    if let Some(peer_id) = multiaddr_peer_id(addr) {
+        swarm.behaviour_mut().gossipsub.add_explicit_peer(&peer_id);
+    }
+    let opts = DialOpts::from(addr.clone());
+    let connection_id = opts.connection_id();
+    redialer.dialed(connection_id, addr.clone(), backoff);
+    match swarm.dial(opts) {
+        Ok(()) => {
+            info!(address = %addr, "Dialing bootstrap peer");
+            true
+        }
+        Err(e) => {
+            let retry_in = redialer.dial_failed(connection_id, Instant::now());
+            warn!(address = %addr, error = %e, retry_in = ?retry_in, "Failed to dial bootstrap peer");
+            false
+        }
+    }
+}
+
+/// Dial every bootstrap address. Returns the number of dials issued.
+fn dial_bootstrap_peers(
+    swarm: &mut libp2p::Swarm<QresBehavior>,
+    redialer: &mut BootstrapRedialer,
+    addrs: &[Multiaddr],
+) -> usize {
+    addrs
+        .iter()
+        .filter(|addr| dial_bootstrap_peer(swarm, redialer, addr, None))
+        .count()
+}
+
+/// Redial the failed or lost bootstrap addresses whose backoff expired by
+/// `now`. Returns the number of dials issued.
+fn redial_bootstrap_peers(
+    swarm: &mut libp2p::Swarm<QresBehavior>,
+    redialer: &mut BootstrapRedialer,
+    now: Instant,
+) -> usize {
+    redialer
+        .take_due(now)
+        .into_iter()
+        .filter(|(addr, backoff)| dial_bootstrap_peer(swarm, redialer, addr, Some(*backoff)))
+        .count()
+}
+
+/// Behaviour settings that do not depend on the transport.
+#[derive(Debug, Clone, Copy)]
+struct SwarmOptions {
+    enable_mdns: bool,
+    /// Gossipsub transmit limit in bytes
+    max_message_size: usize,
+    /// Predictor capability advertised through identify (from the loaded brain)
+    capability: PredictorCapability,
+}
+
+impl Default for SwarmOptions {
+    fn default() -> Self {
+        let swarm_config = crate::config::SwarmConfig::default();
+        Self {
+            enable_mdns: swarm_config.mdns,
+            max_message_size: swarm_config.max_message_size,
+            capability: PredictorCapability::of(&LivingBrain::default()),
+        }
+    }
+}
+
+/// Build the libp2p swarm on the selected transport.
+///
+/// TCP and WebSocket are upgraded with Noise + Yamux; QUIC brings its own
+/// TLS 1.3 handshake and stream multiplexing, so no upgrade is applied.
+async fn build_swarm(
     id_keys: identity::Keypair,
+    transport: SwarmTransport,
+    options: SwarmOptions,
 ) -> Result<libp2p::Swarm<QresBehavior>, Box<dyn std::error::Error>> {
-    let swarm = SwarmBuilder::with_existing_identity(id_keys)
-        .with_tokio()
-        .with_tcp(
-            tcp::Config::default(),
-            noise::Config::new,
-            yamux::Config::default,
-        )?
-        .with_behaviour(|key| {
-            let message_id_fn = |message: &gossipsub::Message| {
-                let mut s = DefaultHasher::new();
-                message.data.hash(&mut s);
-                gossipsub::MessageId::from(s.finish().to_string())
-            };
-            let gossipsub_config = gossipsub::ConfigBuilder::default()
-                .heartbeat_interval(Duration::from_secs(GOSSIPSUB_HEARTBEAT_SECS))
-                .validation_mode(gossipsub::ValidationMode::Permissive)
-                .message_id_fn(message_id_fn)
-                .build()
-                .map_err(io::Error::other)?;
-
-            let mut gossipsub = gossipsub::Behaviour::new(
-                gossipsub::MessageAuthenticity::Signed(key.clone()),
-                gossipsub_config,
-            )
-            .map_err(io::Error::other)?;
+    let build_behaviour = |key: &identity::Keypair| build_behaviour(key, options);
+    let builder = SwarmBuilder::with_existing_identity(id_keys).with_tokio();
+    let swarm = match transport {
+        SwarmTransport::Tcp => builder
+            .with_tcp(
+                tcp::Config::default(),
+                noise::Config::new,
+                yamux::Config::default,
+            )?
+            .with_behaviour(build_behaviour)?
+            .build(),
+        SwarmTransport::Quic => builder.with_quic().with_behaviour(build_behaviour)?.build(),
+        SwarmTransport::Ws => builder
+            .with_websocket(noise::Config::new, yamux::Config::default)
+            .await?
+            .with_behaviour(build_behaviour)?
+            .build(),
+    };
 
-            let topic = gossipsub::IdentTopic::new(BRAIN_TOPIC);
-            gossipsub
-                .subscribe(&topic)
-                .map_err(|e| io::Error::other(format!("{:?}", e)))?;
+    Ok(swarm)
+}
 
-            let mdns =
-                mdns::tokio::Behaviour::new(mdns::Config::default(), PeerId::from(key.public()))?;
+/// Gossipsub, mDNS (unless disabled), and identify protocols shared by every transport.
+///
+/// Gossipsub holds each received message until a validation result has been
+/// reported for it (see [`gossip_precheck`] and [`handle_gossipsub_message`]),
+/// so malformed or oversized payloads are never forwarded.
+fn build_behaviour(
+    key: &identity::Keypair,
+    options: SwarmOptions,
+) -> Result<QresBehavior, Box<dyn std::error::Error + Send + Sync>> {
+    let message_id_fn = |message: &gossipsub::Message| {
+        let mut s = DefaultHasher::new();
+        message.data.hash(&mut s);
+        gossipsub::MessageId::from(s.finish().to_string())
+    };
+    let gossipsub_config = gossipsub::ConfigBuilder::default()
+        .heartbeat_interval(Duration::from_secs(GOSSIPSUB_HEARTBEAT_SECS))
+        .validation_mode(gossipsub::ValidationMode::Strict)
+        .validate_messages()
+        .max_transmit_size(options.max_message_size)
+        .message_id_fn(message_id_fn)
+        .build()
+        .map_err(io::Error::other)?;
+
+    let mut gossipsub = gossipsub::Behaviour::new(
+        gossipsub::MessageAuthenticity::Signed(key.clone()),
+        gossipsub_config,
+    )
+    .map_err(io::Error::other)?;
+
+    let topic = gossipsub::IdentTopic::new(BRAIN_TOPIC);
+    gossipsub
+        .subscribe(&topic)
+        .map_err(|e| io::Error::other(format!("{:?}", e)))?;
+
+    let mdns = if options.enable_mdns {
+        Toggle::from(Some(mdns::tokio::Behaviour::new(
+            mdns::Config::default(),
+            PeerId::from(key.public()),
+        )?))
+    } else {
+        Toggle::from(None)
+    };
 
-            let identify = identify::Behaviour::new(identify::Config::new(
-                "qres/1.0.0".to_string(),
-                key.public(),
-            ));
+    let identify = identify::Behaviour::new(identify_config(key, options.capability));
 
-            Ok(QresBehavior {
-                gossipsub,
-                mdns,
-                identify,
-            })
-        })?
-        .build();
+    Ok(QresBehavior {
+        gossipsub,
+        mdns,
+        identify,
+    })
+}
 
-    Ok(swarm)
+/// Identify config advertising `capability` as the agent version, so peers
+/// can gate merges on the predictor set this node actually runs.
+fn identify_config(key: &identity::Keypair, capability: PredictorCapability) -> identify::Config {
+    identify::Config::new("qres/1.0.0".to_string(), key.public())
+        .with_agent_version(capability.to_agent_version())
 }
 
 /// Handle the periodic brain broadcast tick (privacy, silence, ZK proofs, signing, publishing).
@@ -364,9 +919,15 @@ async fn handle_broadcast_tick(
     swarm: &mut libp2p::Swarm<QresBehavior>,
     brain_file: &str,
 ) {
-    // Privacy accounting: decay budget
+    // Privacy accounting: decay budget; energy harvesting: recharge pool
     {
-        state.write().await.privacy_accountant.decay();
+        let mut app_state = state.write().await;
+        app_state.privacy_accountant.decay();
+        app_state.energy_pool.tick();
+        let expired = app_state.reassembler.evict_expired(Instant::now());
+        if expired > 0 {
+            warn!(expired, "Dropped incomplete fragmented epiphanies");
+        }
     }
 
     let epiphany_cost = EPIPHANY_PRIVACY_COST;
@@ -441,6 +1002,11 @@ async fn handle_broadcast_tick(
                                 })
                                 .collect();
                             let fixed_tensor = FixedTensor::new(i16f16_weights);
+                            info!(
+                                quantization_error =
+                                    fixed_tensor.quantization_error().to_num::<f32>(),
+                                "Storm downcast to I8F8"
+                            );
                             let i8f8_weights = fixed_tensor.quantize_to_i8f8();
                             let quantized_bytes: Vec<u8> =
                                 i8f8_weights.iter().flat_map(|&w| w.to_le_bytes()).collect();
@@ -471,23 +1037,7 @@ async fn handle_broadcast_tick(
                     };
 
                     let proof_bundle = if !is_storm {
-                        let app_state = state.read().await;
-                        if !weights_f32.is_empty() {
-                            if let Some((proof, _)) = app_state
-                                .zk_prover
-                                .generate_proof(&weights_f32, ZK_NORM_THRESHOLD)
-                            {
-                                Some(ProofBundle {
-                                    peer_id: [0u8; 32],
-                                    masked_weights: weights_f32,
-                                    zk_proof: proof,
-                                })
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
-                        }
+                        generate_proof_bundle(state, weights_f32).await
                     } else {
                         None
                     };
@@ -499,27 +1049,28 @@ async fn handle_broadcast_tick(
                         .as_secs();
                     let nonce = rand::random::<u64>();
 
+                    let (sender_id, key_rotation) = match &state.read().await.security {
+                        Some(s) => (s.public_key_hex(), s.key_rotation()),
+                        None => (String::new(), None),
+                    };
                     let mut epiphany = SignedEpiphany {
                         brain: brain.clone(),
                         proof_bundle: proof_bundle.clone(),
                         signature: String::new(),
-                        sender_id: state
-                            .read()
-                            .await
-                            .security
-                            .as_ref()
-                            .map(|s| s.public_key_hex())
-                            .unwrap_or_default(),
+                        sender_id,
                         timestamp,
                         nonce,
                         is_storm_mode: is_storm,
+                        key_rotation,
                     };
 
                     let payload_bytes = epiphany.payload_bytes();
                     let signed_payload = {
                         let app_state = state.read().await;
                         if let Some(sec_mgr) = &app_state.security {
-                            sec_mgr.sign(&payload_bytes)
+                            // Sign over the epiphany's own timestamp and nonce,
+                            // which receivers check for replay
+                            sec_mgr.sign_at(&payload_bytes, timestamp, nonce)
                         } else {
                             SignedPayload {
                                 data: payload_bytes,
@@ -532,26 +1083,65 @@ async fn handle_broadcast_tick(
                     };
                     epiphany.signature = signed_payload.signature;
 
-                    let msg_bytes = serde_json::to_vec(&epiphany).unwrap();
-                    let outgoing_bytes = msg_bytes.len() as u64;
+                    let msg_bytes = match epiphany.to_wire_bytes() {
+                        Ok(bytes) => bytes,
+                        Err(e) => {
+                            tracing::error!(error = %e, "Failed to encode epiphany");
+                            return;
+                        }
+                    };
+                    let frames = match encode_gossip_frames(
+                        &msg_bytes,
+                        state.read().await.payload_cipher.as_ref(),
+                    ) {
+                        Ok(frames) => frames,
+                        Err(e) => {
+                            tracing::error!(error = %e, "Epiphany too large to publish");
+                            return;
+                        }
+                    };
+                    let outgoing_bytes: u64 = frames.iter().map(|f| f.len() as u64).sum();
                     let topic = IdentTopic::new(BRAIN_TOPIC);
-                    if let Err(e) = swarm.behaviour_mut().gossipsub.publish(topic, msg_bytes) {
+                    let published = frames.into_iter().try_for_each(|frame| {
+                        swarm
+                            .behaviour_mut()
+                            .gossipsub
+                            .publish(topic.clone(), frame)
+                            .map(|_| ())
+                    });
+                    if let Err(e) = published {
                         tracing::error!("Publish error: {:?}", e);
                     } else {
                         let mut app_state = state.write().await;
                         let _ = app_state
                             .privacy_accountant
                             .record_consumption(epiphany_cost);
+                        let AppState {
+                            reciprocity,
+                            connected_peers,
+                            ..
+                        } = &mut *app_state;
+                        reciprocity.record_published(connected_peers.iter());
                         let entropy = calculate_brain_entropy(&brain);
-                        app_state.regime_detector.update(
-                            entropy,
-                            REGIME_UPDATE_INTERVAL_MS,
-                            outgoing_bytes,
-                        );
+                        let now_ms = app_state.regime_now_ms();
+                        app_state.update_regime(entropy, outgoing_bytes as usize, now_ms);
                         info!(
+                            wire_bytes = msg_bytes.len(),
                             "Published SignedEpiphany (mode: {})",
                             if is_storm { "Storm" } else { "Calm" }
                         );
+                        // The JSON comparison costs a second serialization, so
+                        // only pay for it when someone is reading debug logs
+                        if tracing::enabled!(tracing::Level::DEBUG) {
+                            let json_bytes = serde_json::to_vec(&epiphany).map_or(0, |b| b.len());
+                            debug!(
+                                wire_bytes = msg_bytes.len(),
+                                json_bytes,
+                                saved_pct = 100.0
+                                    * (1.0 - msg_bytes.len() as f32 / json_bytes.max(1) as f32),
+                                "SignedEpiphany wire size vs JSON"
+                            );
+                        }
                     }
                 }
             }
@@ -564,17 +1154,116 @@ async fn handle_broadcast_tick(
     }
 }
 
+/// Generate the Calm-mode norm proof for a broadcast, paying `ZK_PROVE` first.
+///
+/// Returns `None` when there are no weights, the energy pool cannot afford
+/// the proof, or the weights exceed the norm threshold; the epiphany then
+/// goes out proofless and receivers fall back to reputation trust. The state
+/// lock is held only for the energy debit, not while proving.
+async fn generate_proof_bundle(
+    state: &Arc<RwLock<AppState>>,
+    weights: Vec<f32>,
+) -> Option<ProofBundle> {
+    if weights.is_empty() {
+        return None;
+    }
+    let prover = {
+        let mut app_state = state.write().await;
+        if !app_state.energy_pool.spend(energy_costs::ZK_PROVE) {
+            info!(
+                "Energy low: skipping ZK proof generation (Ratio: {:.2})",
+                app_state.energy_pool.ratio()
+            );
+            return None;
+        }
+        Arc::clone(&app_state.zk_prover)
+    };
+    let (proof, _) = prover.generate_proof(&weights, ZK_NORM_THRESHOLD)?;
+    Some(ProofBundle {
+        peer_id: [0u8; 32],
+        masked_weights: weights,
+        zk_proof: proof,
+    })
+}
+
+/// Key a libp2p peer in the core [`ReputationTracker`], which identifies
+/// peers by 32 bytes.
+fn ban_key(peer: &PeerId) -> [u8; 32] {
+    *blake3::hash(&peer.to_bytes()).as_bytes()
+}
+
+/// Advance the ban clock one federation epoch and apply ban changes to
+/// gossipsub: banned peers are dropped as explicit peers and blacklisted, so
+/// their messages are refused; expired bans are lifted again.
+async fn sync_gossip_bans(state: &Arc<RwLock<AppState>>, swarm: &mut libp2p::Swarm<QresBehavior>) {
+    let (banned, expired, known) = {
+        let mut app_state = state.write().await;
+        app_state.federation_epochs += 1;
+        let (banned, expired) = app_state.update_gossip_bans();
+        let known: Vec<bool> = expired
+            .iter()
+            .map(|peer| app_state.known_peers.contains(&peer.to_string()))
+            .collect();
+        (banned, expired, known)
+    };
+    let gossipsub = &mut swarm.behaviour_mut().gossipsub;
+    for peer in &banned {
+        warn!(peer_id = %peer, epochs = GOSSIP_BAN_EPOCHS, "Banning distrusted peer from gossip");
+        gossipsub.remove_explicit_peer(peer);
+        gossipsub.blacklist_peer(peer);
+    }
+    for (peer, known) in expired.iter().zip(known) {
+        info!(peer_id = %peer, "Gossip ban expired, peer on probation");
+        gossipsub.remove_blacklisted_peer(peer);
+        if known {
+            gossipsub.add_explicit_peer(peer);
+        }
+    }
+}
+
 /// Handle the federated learning aggregation epoch.
 async fn handle_federation_tick(state: &Arc<RwLock<AppState>>, brain_file: &str) {
     let mut app_state = state.write().await;
+
+    // Periodically persist audit records for long-term retention
+    app_state.flush_audit_logs();
+    save_regime_snapshot(&app_state.regime_detector, brain_file);
+    if app_state.dream_if_idle() {
+        if let Err(e) = app_state.dreaming.replay().save() {
+            warn!(error = %e, "Failed to persist dream replay buffer");
+        }
+    }
+
     if !app_state.federated_averager.should_aggregate() {
         return;
     }
 
     let reputation_clone = app_state.reputation.clone();
-    if let Some((aggregated_weights, aggregated_confidence)) =
-        app_state.federated_averager.aggregate(&reputation_clone)
+    let anchor = app_state.brain.global_confidence.clone();
+    if let Some((aggregated_weights, aggregated_confidence)) = app_state
+        .federated_averager
+        .aggregate(&reputation_clone, anchor.as_deref())
     {
+        app_state.federated_rounds += 1;
+        let global_error_rate =
+            1.0 - (aggregated_confidence.iter().sum::<f32>() / aggregated_confidence.len() as f32);
+        if app_state.convergence.observe(global_error_rate) {
+            if app_state.convergence.is_inference_only() {
+                info!(
+                    "🎯 Converged (global error rate: {:.6}). Entering inference-only mode",
+                    global_error_rate
+                );
+            } else {
+                info!(
+                    "Global error rate rose to {:.6}. Resuming training",
+                    global_error_rate
+                );
+            }
+        }
+        if app_state.convergence.is_inference_only() {
+            return;
+        }
+
         if let Ok(local_json) = fs::read_to_string(brain_file) {
             if let Some(mut local_brain) = LivingBrain::from_json(&local_json) {
                 local_brain.best_engine_weights = Some(aggregated_weights);
@@ -588,16 +1277,6 @@ async fn handle_federation_tick(state: &Arc<RwLock<AppState>>, brain_file: &str)
                         + agg_conf * AGGREGATED_CONFIDENCE_WEIGHT;
                 }
 
-                let global_error_rate = 1.0
-                    - (aggregated_confidence.iter().sum::<f32>()
-                        / aggregated_confidence.len() as f32);
-                if global_error_rate < SINGULARITY_ERROR_THRESHOLD {
-                    info!(
-                        "🎯 SINGULARITY ACHIEVED! Global error rate: {:.6}",
-                        global_error_rate
-                    );
-                }
-
                 let local_loss = 1.0
                     - (local_brain.confidence.iter().sum::<f32>()
                         / local_brain.confidence.len() as f32);
@@ -618,7 +1297,11 @@ async fn handle_federation_tick(state: &Arc<RwLock<AppState>>, brain_file: &str)
                     warn!("Failed to export singularity metrics: {}", e);
                 }
 
-                let _ = fs::write(brain_file, local_brain.to_json());
+                if let Err(e) =
+                    write_file_atomic(brain_file.as_ref(), local_brain.to_json().as_bytes())
+                {
+                    warn!(brain_file = brain_file, error = %e, "Failed to save federated brain");
+                }
                 app_state.brain = local_brain;
                 info!(
                     "Applied federated aggregation. Global error rate: {:.4}",
@@ -629,23 +1312,63 @@ async fn handle_federation_tick(state: &Arc<RwLock<AppState>>, brain_file: &str)
     }
 }
 
+/// Persist in-memory state (brain, reputation, audit logs, regime) before exit.
+async fn handle_shutdown(state: &Arc<RwLock<AppState>>, brain_file: &str) {
+    let mut app_state = state.write().await;
+    if let Err(e) = write_file_atomic(brain_file.as_ref(), app_state.brain.to_json().as_bytes()) {
+        warn!(brain_file = brain_file, error = %e, "Failed to flush brain on shutdown");
+    }
+    if let Err(e) = app_state.reputation.save() {
+        warn!(error = %e, "Failed to save reputation on shutdown");
+    }
+    app_state.flush_audit_logs();
+    save_regime_snapshot(&app_state.regime_detector, brain_file);
+    info!(
+        total_energy_consumed = app_state.energy_pool.lifetime_consumption(),
+        energy_ratio = app_state.energy_pool.ratio(),
+        lifetime_harvested = app_state.energy_pool.lifetime_harvested(),
+        federated_rounds = app_state.federated_rounds,
+        "Swarm node stopped"
+    );
+}
+
 /// Dispatch and handle swarm events (connections, mDNS, identify, gossipsub messages).
 async fn handle_swarm_event(
     event: SwarmEvent<QresBehaviorEvent>,
     state: &Arc<RwLock<AppState>>,
     swarm: &mut libp2p::Swarm<QresBehavior>,
+    redialer: &mut BootstrapRedialer,
+    verify_queue: &VerificationQueue,
+    reports: &mpsc::UnboundedSender<ValidationReport>,
 ) {
     match event {
         SwarmEvent::NewListenAddr { address, .. } => {
             info!(address = %address, "Swarm listening");
         }
-        SwarmEvent::ConnectionEstablished { peer_id, .. } => {
+        SwarmEvent::OutgoingConnectionError {
+            connection_id,
+            peer_id,
+            error,
+        } => {
+            if let Some(retry_in) = redialer.dial_failed(connection_id, Instant::now()) {
+                warn!(peer_id = ?peer_id, error = %error, retry_in = ?retry_in, "Bootstrap dial failed");
+            }
+        }
+        SwarmEvent::ConnectionEstablished {
+            peer_id,
+            connection_id,
+            ..
+        } => {
+            redialer.connected(connection_id);
             info!(peer_id = %peer_id, "Connected to peer");
             state
                 .write()
                 .await
                 .connected_peers
                 .insert(peer_id.to_string());
+            if state.read().await.is_gossip_banned(&peer_id) {
+                swarm.behaviour_mut().gossipsub.blacklist_peer(&peer_id);
+            }
 
             // v19.0: Serve Summary Gene (Mid-Flight Join)
             {
@@ -655,7 +1378,9 @@ async fn handle_swarm_event(
                 let consensus = &brain.confidence[..dims];
                 let variance = vec![0.0; dims];
 
-                let summary = SummaryGene::new(1900, [0xAA; 32], consensus, &variance);
+                let width =
+                    SummaryGene::width_for_regime(state_read.regime_detector.current_regime());
+                let summary = SummaryGene::new(1900, [0xAA; 32], consensus, &variance, width);
                 let bytes = summary.to_bytes();
                 info!(
                     peer_id = %peer_id,
@@ -666,19 +1391,43 @@ async fn handle_swarm_event(
                 );
             }
         }
-        SwarmEvent::ConnectionClosed { peer_id, .. } => {
+        SwarmEvent::ConnectionClosed {
+            peer_id,
+            num_established,
+            ..
+        } => {
             info!(peer_id = %peer_id, "Disconnected from peer");
             state
                 .write()
                 .await
                 .connected_peers
                 .remove(&peer_id.to_string());
+            if num_established == 0 {
+                // Keep WAN swarms joined: redial bootstrap peers we lose, with backoff
+                let now = Instant::now();
+                let app_state = state.read().await;
+                for addr in app_state
+                    .config
+                    .swarm
+                    .bootstrap_peers
+                    .iter()
+                    .filter(|addr| multiaddr_peer_id(addr) == Some(peer_id))
+                {
+                    redialer.disconnected(addr.clone(), now);
+                }
+            }
         }
         SwarmEvent::Behaviour(QresBehaviorEvent::Mdns(mdns::Event::Discovered(list))) => {
             for (peer_id, multiaddr) in list {
                 info!(peer_id = %peer_id, "mDNS Discovered");
-                state.write().await.known_peers.insert(peer_id.to_string());
-                swarm.behaviour_mut().gossipsub.add_explicit_peer(&peer_id);
+                let banned = {
+                    let mut app_state = state.write().await;
+                    app_state.known_peers.insert(peer_id.to_string());
+                    app_state.is_gossip_banned(&peer_id)
+                };
+                if !banned {
+                    swarm.behaviour_mut().gossipsub.add_explicit_peer(&peer_id);
+                }
                 let _ = swarm.dial(multiaddr);
             }
         }
@@ -701,6 +1450,19 @@ async fn handle_swarm_event(
             if app_state.peer_keys.add_peer_key(peer_id, info.public_key) {
                 info!(peer_id = %peer_id, known_keys = app_state.peer_keys.peer_count(), "Peer key verified and stored");
             }
+            match PredictorCapability::from_agent_version(&info.agent_version) {
+                Some(capability) => {
+                    if capability != app_state.local_capability() {
+                        warn!(peer_id = %peer_id, remote = ?capability, "Peer predictor set incompatible; its updates will not be aggregated");
+                    }
+                    app_state
+                        .peer_capabilities
+                        .insert(peer_id.to_string(), capability);
+                }
+                None => {
+                    warn!(peer_id = %peer_id, agent = %info.agent_version, "Peer did not advertise a predictor capability");
+                }
+            }
         }
         SwarmEvent::Behaviour(QresBehaviorEvent::Identify(identify::Event::Sent { peer_id })) => {
             info!(peer_id = %peer_id, "Sent Identify to peer");
@@ -712,67 +1474,341 @@ async fn handle_swarm_event(
             warn!(peer_id = %peer_id, error = %error, "Identify error");
         }
         SwarmEvent::Behaviour(QresBehaviorEvent::Gossipsub(gossipsub::Event::Message {
-            propagation_source: _,
-            message_id: _,
+            propagation_source,
+            message_id,
             message,
         })) => {
-            handle_gossipsub_message(&message, state).await;
+            let (max_message_size, cipher, banned) = {
+                let app_state = state.read().await;
+                let author = message.source.unwrap_or(propagation_source);
+                (
+                    app_state.config.swarm.max_message_size,
+                    app_state.payload_cipher.clone(),
+                    app_state.is_gossip_banned(&author)
+                        || app_state.is_gossip_banned(&propagation_source),
+                )
+            };
+            if banned {
+                // Messages already in flight when the ban started
+                let _ = swarm
+                    .behaviour_mut()
+                    .gossipsub
+                    .report_message_validation_result(
+                        &message_id,
+                        &propagation_source,
+                        gossipsub::MessageAcceptance::Reject,
+                    );
+                return;
+            }
+            if gossip_precheck(&message.data, max_message_size, cipher.as_ref()) {
+                state.write().await.dreaming.record_activity();
+                enqueue_gossipsub_message(
+                    message_id,
+                    propagation_source,
+                    message,
+                    state,
+                    verify_queue,
+                    reports,
+                )
+                .await;
+            } else {
+                let _ = swarm
+                    .behaviour_mut()
+                    .gossipsub
+                    .report_message_validation_result(
+                        &message_id,
+                        &propagation_source,
+                        gossipsub::MessageAcceptance::Reject,
+                    );
+                let source = message
+                    .source
+                    .map_or_else(|| propagation_source.to_string(), |p| p.to_string());
+                warn!(peer_id = %source, bytes = message.data.len(), "Rejected malformed or oversized gossip");
+                state
+                    .write()
+                    .await
+                    .record_rejection(&source, "malformed or oversized gossip");
+            }
         }
         _ => {}
     }
 }
 
-/// Process an incoming gossipsub message (verify signature, verify ZK proof, buffer for federation).
-async fn handle_gossipsub_message(message: &gossipsub::Message, state: &Arc<RwLock<AppState>>) {
-    let signed_epiphany = match serde_json::from_slice::<SignedEpiphany>(&message.data) {
-        Ok(e) => e,
-        Err(_) => {
-            warn!("Failed to deserialize SignedEpiphany");
-            return;
-        }
-    };
+/// Checks cheap enough for the swarm task, run before a message is queued
+/// for verification: it must fit in `max_message_size` and carry a
+/// plausible header. Encrypted payloads can only be size-checked here.
+fn gossip_precheck(data: &[u8], max_message_size: usize, cipher: Option<&PayloadCipher>) -> bool {
+    if data.len() > max_message_size {
+        return false;
+    }
+    if cipher.is_some() {
+        return data.len() > PAYLOAD_OVERHEAD;
+    }
+    match Fragment::parse(data) {
+        Some(fragment) => fragment_set_fits(&fragment, max_message_size),
+        None => SignedEpiphany::has_wire_header(data),
+    }
+}
 
-    // Reconstruct and verify signature
-    let payload_to_verify = SignedPayload {
-        data: signed_epiphany.payload_bytes(),
-        signature: signed_epiphany.signature.clone(),
-        signer_pubkey: signed_epiphany.sender_id.clone(),
-        timestamp: signed_epiphany.timestamp,
-        nonce: signed_epiphany.nonce,
-    };
+/// Whether the set `fragment` announces could reassemble within
+/// `max_message_size`. Fragments are checked as a whole once reassembled;
+/// until then only their header can be judged.
+fn fragment_set_fits(fragment: &Fragment, max_message_size: usize) -> bool {
+    let min_chunk = FRAGMENT_MTU - FRAGMENT_HEADER_LEN - PAYLOAD_OVERHEAD;
+    fragment.frag_count as usize <= max_message_size.div_ceil(min_chunk)
+}
 
-    let sig_valid = {
-        let mut app_state = state.write().await;
-        if let Some(security_mgr) = &mut app_state.security {
-            match security_mgr.verify(&payload_to_verify) {
-                Ok(_) => true,
-                Err(e) => {
-                    warn!("Verification Failed: {}", e);
-                    false
+/// Split a serialized epiphany into MTU-sized frames, encrypting each one
+/// when the swarm is confidential.
+fn encode_gossip_frames(
+    payload: &[u8],
+    cipher: Option<&PayloadCipher>,
+) -> Result<Vec<Vec<u8>>, FragmentError> {
+    match cipher {
+        Some(cipher) => Ok(
+            fragmentation::fragment(payload, FRAGMENT_MTU - PAYLOAD_OVERHEAD)?
+                .iter()
+                .map(|frame| cipher.encrypt(frame))
+                .collect(),
+        ),
+        None => fragmentation::fragment(payload, FRAGMENT_MTU),
+    }
+}
+
+/// A verification worker's verdict on a held gossipsub message, reported
+/// back to gossipsub by the swarm task
+pub type ValidationReport = (gossipsub::MessageId, PeerId, gossipsub::MessageAcceptance);
+
+/// Hand a gossipsub message to the verification workers, which send their
+/// verdict on `reports`. When the queue is full the message is shed and
+/// ignored; the overload is ours, so the author is not penalized. Returns
+/// whether it was queued.
+async fn enqueue_gossipsub_message(
+    message_id: gossipsub::MessageId,
+    propagation_source: PeerId,
+    message: gossipsub::Message,
+    state: &Arc<RwLock<AppState>>,
+    verify_queue: &VerificationQueue,
+    reports: &mpsc::UnboundedSender<ValidationReport>,
+) -> bool {
+    let source = message.source.map(|p| p.to_string());
+    let worker_state = state.clone();
+    let worker_reports = reports.clone();
+    let worker_id = message_id.clone();
+    let queued = verify_queue.try_submit(async move {
+        let acceptance = handle_gossipsub_message(&message, &worker_state).await;
+        let _ = worker_reports.send((worker_id, propagation_source, acceptance));
+    });
+    if !queued {
+        let source = source.unwrap_or_else(|| "unknown".to_string());
+        warn!(peer_id = %source, capacity = verify_queue.capacity(), "Verification queue full, shedding message");
+        let _ = reports.send((
+            message_id,
+            propagation_source,
+            gossipsub::MessageAcceptance::Ignore,
+        ));
+        state
+            .write()
+            .await
+            .record_rejection(&source, "verification queue full");
+    }
+    queued
+}
+
+/// Process an incoming gossipsub message (verify signature, verify ZK proof,
+/// buffer for federation) and decide whether gossipsub may forward it.
+///
+/// Messages that are malformed, undecryptable, banned or carry an invalid
+/// signature are rejected; ones refused only by local policy (trust, proof
+/// requirements, predictor set, replays) are ignored rather than held
+/// against the forwarding peer.
+async fn handle_gossipsub_message(
+    message: &gossipsub::Message,
+    state: &Arc<RwLock<AppState>>,
+) -> gossipsub::MessageAcceptance {
+    use gossipsub::MessageAcceptance::{Accept, Ignore, Reject};
+
+    let source = message
+        .source
+        .map(|p| p.to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    // Queued before its author was banned
+    if let Some(author) = message.source {
+        if state.read().await.is_gossip_banned(&author) {
+            state.write().await.record_rejection(&source, "banned peer");
+            return Reject;
+        }
+    }
+    if !state.read().await.is_compatible_peer(&source) {
+        warn!(peer_id = %source, "Ignoring update from peer with incompatible predictor set");
+        state
+            .write()
+            .await
+            .record_rejection(&source, "incompatible predictor set");
+        return Ignore;
+    }
+
+    let (cipher, max_message_size) = {
+        let app_state = state.read().await;
+        (
+            app_state.payload_cipher.clone(),
+            app_state.config.swarm.max_message_size,
+        )
+    };
+    let data = match cipher {
+        Some(cipher) => match cipher.decrypt(&message.data) {
+            Ok(plaintext) => plaintext,
+            Err(e) => {
+                warn!(peer_id = %source, "Failed to decrypt gossip payload: {}", e);
+                state
+                    .write()
+                    .await
+                    .record_rejection(&source, "undecryptable payload");
+                return Reject;
+            }
+        },
+        None => message.data.clone(),
+    };
+    let data = match Fragment::parse(&data) {
+        Some(fragment) => {
+            if !fragment_set_fits(&fragment, max_message_size) {
+                state
+                    .write()
+                    .await
+                    .record_rejection(&source, "oversized fragment set");
+                return Reject;
+            }
+            // Strict validation signs every message, so the author is known
+            let Some(author) = message.source else {
+                return Ignore;
+            };
+            let completed = state
+                .write()
+                .await
+                .reassembler
+                .push(author, fragment, Instant::now());
+            match completed {
+                Some(payload) => payload,
+                // Forward the fragment and wait for the rest of the set
+                None => return Accept,
+            }
+        }
+        None => data,
+    };
+
+    let signed_epiphany = match SignedEpiphany::from_wire_bytes(&data) {
+        Ok(e) => e,
+        Err(e) => {
+            warn!(peer_id = %source, "Failed to decode SignedEpiphany: {}", e);
+            state
+                .write()
+                .await
+                .record_rejection(&source, "malformed SignedEpiphany");
+            return Reject;
+        }
+    };
+
+    // Rejections are already logged and recorded
+    match verify_and_buffer_epiphany(
+        signed_epiphany,
+        &source,
+        message.source,
+        data.len() as u64,
+        state,
+    )
+    .await
+    {
+        Ok(()) => Accept,
+        Err("invalid signature") => Reject,
+        Err(_) => Ignore,
+    }
+}
+
+/// Run a decoded update through signature, proof/reputation and buffering
+/// checks. Returns the rejection reason, which is also recorded in the
+/// rejection log.
+///
+/// Once the signature verifies, the sender is tied to the gossipsub `author`
+/// so that losing trust later bans that peer from gossip.
+async fn verify_and_buffer_epiphany(
+    signed_epiphany: SignedEpiphany,
+    source: &str,
+    author: Option<PeerId>,
+    incoming_bytes: u64,
+    state: &Arc<RwLock<AppState>>,
+) -> Result<(), &'static str> {
+    // Reconstruct and verify signature
+    let payload_to_verify = SignedPayload {
+        data: signed_epiphany.payload_bytes(),
+        signature: signed_epiphany.signature.clone(),
+        signer_pubkey: signed_epiphany.sender_id.clone(),
+        timestamp: signed_epiphany.timestamp,
+        nonce: signed_epiphany.nonce,
+    };
+
+    // Replays are not held against the signer: anyone can capture and resend
+    // a genuine message
+    let (sig_valid, replayed) = {
+        let mut guard = state.write().await;
+        let app_state = &mut *guard;
+        let now = Instant::now();
+        if let Some(rotation) = &signed_epiphany.key_rotation {
+            if rotation.current_pubkey == signed_epiphany.sender_id {
+                let grace = Duration::from_secs(app_state.config.security.key_rotation_grace_secs);
+                app_state.peer_keys.apply_rotation(rotation, grace, now);
+            }
+        }
+        if let Some(security_mgr) = &mut app_state.security {
+            match security_mgr.verify_with_keys(&payload_to_verify, &app_state.peer_keys, now) {
+                Ok(_) => (true, false),
+                Err(e) => {
+                    warn!("Verification Failed: {}", e);
+                    (false, matches!(e, SecurityError::ReplayDetected))
                 }
             }
         } else {
-            !app_state.require_signatures
+            (!app_state.require_signatures, false)
         }
     };
 
+    if replayed {
+        state
+            .write()
+            .await
+            .record_rejection(&signed_epiphany.sender_id, "replayed message");
+        return Err("replayed message");
+    }
     if !sig_valid {
         warn!("Invalid Signature from {}", signed_epiphany.sender_id);
+        let mut app_state = state.write().await;
+        app_state.reputation.punish(&signed_epiphany.sender_id);
+        app_state.record_rejection(&signed_epiphany.sender_id, "invalid signature");
+        return Err("invalid signature");
+    }
+    // Unsigned updates all share the empty sender and cannot be attributed
+    if let Some(author) = author.filter(|_| !signed_epiphany.sender_id.is_empty()) {
         state
             .write()
             .await
-            .reputation
-            .punish(&signed_epiphany.sender_id);
-        return;
+            .sender_peers
+            .insert(signed_epiphany.sender_id.clone(), author);
     }
 
     // Verify ZK proof or trust high-reputation peers
-    let proof_valid = {
+    let proof_valid = if let Some(bundle) = &signed_epiphany.proof_bundle {
+        let prover = {
+            let mut app_state = state.write().await;
+            // Charged for accounting only: skipping verification to save
+            // energy would mean accepting unverified proofs
+            app_state.energy_pool.spend(energy_costs::ZK_VERIFY);
+            Arc::clone(&app_state.zk_prover)
+        };
+        prover.verify_proof(&bundle.zk_proof, ZK_NORM_THRESHOLD)
+    } else {
         let app_state = state.read().await;
-        if let Some(bundle) = &signed_epiphany.proof_bundle {
-            app_state
-                .zk_prover
-                .verify_proof(&bundle.zk_proof, ZK_NORM_THRESHOLD)
+        if app_state.config.swarm.storm_policy == StormPolicy::Scrutinize && app_state.in_storm() {
+            // Under scrutiny, neither storm mode nor reputation stands in for a proof
+            false
         } else {
             let reputation_score = app_state.reputation.get_trust(&signed_epiphany.sender_id);
             signed_epiphany.is_storm_mode || reputation_score > REPUTATION_TRUST_THRESHOLD
@@ -784,12 +1820,10 @@ async fn handle_gossipsub_message(message: &gossipsub::Message, state: &Arc<RwLo
             "Rejected SignedEpiphany from {}: missing/invalid proof and low reputation",
             signed_epiphany.sender_id
         );
-        state
-            .write()
-            .await
-            .reputation
-            .punish(&signed_epiphany.sender_id);
-        return;
+        let mut app_state = state.write().await;
+        app_state.reputation.punish(&signed_epiphany.sender_id);
+        app_state.record_rejection(&signed_epiphany.sender_id, "missing or invalid proof");
+        return Err("missing or invalid proof");
     }
 
     // Handle Storm Mode upcasting (I8F8 -> I16F16)
@@ -817,19 +1851,35 @@ async fn handle_gossipsub_message(message: &gossipsub::Message, state: &Arc<RwLo
         }
     }
 
-    // Buffer for federated learning
+    // Buffer for federated learning, deprioritizing peers that never reciprocate
     let mut app_state = state.write().await;
-    app_state
-        .federated_averager
-        .add_update(signed_epiphany.clone());
+    if !app_state.reciprocity.record_contribution(source) {
+        info!(peer_id = %source, "Skipping update from free-riding peer");
+        app_state.record_rejection(source, "free-rider (low give/take ratio)");
+        return Err("free-rider (low give/take ratio)");
+    }
+    if app_state.config.swarm.storm_policy == StormPolicy::Defer && app_state.in_storm() {
+        info!(peer_id = %source, "Deferring update received during local Storm");
+        app_state.defer_update(signed_epiphany.clone());
+    } else {
+        app_state
+            .federated_averager
+            .add_update(signed_epiphany.clone());
+    }
 
-    let incoming_bytes = message.data.len() as u64;
     let entropy = calculate_brain_entropy(&processed_brain);
-    app_state
-        .regime_detector
-        .update(entropy, REGIME_UPDATE_INTERVAL_MS, incoming_bytes);
+    let now_ms = app_state.regime_now_ms();
+    app_state.update_regime(entropy, incoming_bytes as usize, now_ms);
 
     app_state.reputation.reward(&signed_epiphany.sender_id);
+    app_state.audit_history.push(AuditRecord::now(
+        &signed_epiphany.sender_id,
+        if signed_epiphany.is_storm_mode {
+            "accepted (storm)"
+        } else {
+            "accepted (calm)"
+        },
+    ));
     info!(
         "Buffered SignedEpiphany (mode: {}) for federated averaging",
         if signed_epiphany.is_storm_mode {
@@ -838,6 +1888,43 @@ async fn handle_gossipsub_message(message: &gossipsub::Message, state: &Arc<RwLo
             "Calm"
         }
     );
+    Ok(())
+}
+
+/// Verdict returned by `/gossip-inject`
+#[derive(Clone, Serialize)]
+pub struct InjectVerdict {
+    /// `accepted` or `rejected`
+    pub verdict: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<&'static str>,
+}
+
+/// Feed a `SignedEpiphany` through the gossip receive pipeline as if it
+/// arrived from its sender. Only routed when `QRES_GOSSIP_INJECT` is "1" or
+/// "true".
+async fn post_gossip_inject(
+    State(state): State<Arc<RwLock<AppState>>>,
+    Json(signed_epiphany): Json<SignedEpiphany>,
+) -> Json<InjectVerdict> {
+    let source = signed_epiphany.sender_id.clone();
+    let incoming_bytes = signed_epiphany
+        .to_wire_bytes()
+        .map_or(0, |bytes| bytes.len() as u64);
+    Json(
+        match verify_and_buffer_epiphany(signed_epiphany, &source, None, incoming_bytes, &state)
+            .await
+        {
+            Ok(()) => InjectVerdict {
+                verdict: "accepted",
+                reason: None,
+            },
+            Err(reason) => InjectVerdict {
+                verdict: "rejected",
+                reason: Some(reason),
+            },
+        },
+    )
 }
 
 // Handlers
@@ -850,14 +1937,138 @@ async fn get_status(State(state): State<Arc<RwLock<AppState>>>) -> Json<SwarmSta
         brain_confidence: s.brain.confidence.to_vec(),
         total_energy_consumed: s.energy_pool.lifetime_consumption(),
         energy_efficiency_ratio: s.energy_pool.ratio(), // Re-purposing ratio for now as 'current charge %'
+        audit_history_overflowed: s.audit_history_overflowed(),
+        inference_only: s.convergence.is_inference_only(),
+        regime: format!("{:?}", s.regime_detector.current_regime()),
+        silence_state: format!("{:?}", s.silence_controller.state()),
     })
 }
 
+/// Per-peer view served by `/peers`
+#[derive(Clone, Serialize)]
+pub struct PeerStatus {
+    pub peer_id: String,
+    pub connected: bool,
+    /// Our epiphanies published while the peer was connected
+    pub epiphanies_taken: u64,
+    /// The peer's epiphanies we accepted
+    pub epiphanies_given: u64,
+    /// Give/take ratio (1.0 until the peer has consumed anything)
+    pub give_take_ratio: f32,
+}
+
+async fn get_peers(State(state): State<Arc<RwLock<AppState>>>) -> Json<Vec<PeerStatus>> {
+    let s = state.read().await;
+    let mut peer_ids: Vec<&String> = s
+        .known_peers
+        .iter()
+        .chain(&s.connected_peers)
+        .chain(s.reciprocity.peers().map(|(p, _)| p))
+        .collect();
+    peer_ids.sort();
+    peer_ids.dedup();
+    Json(
+        peer_ids
+            .into_iter()
+            .map(|peer_id| {
+                let rec = s.reciprocity.get(peer_id);
+                PeerStatus {
+                    peer_id: peer_id.clone(),
+                    connected: s.connected_peers.contains(peer_id),
+                    epiphanies_taken: rec.taken,
+                    epiphanies_given: rec.given,
+                    give_take_ratio: rec.ratio(),
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Dream replay entries recorded after `round`, for a peer waking from deep sleep
+async fn get_dream_since(
+    State(state): State<Arc<RwLock<AppState>>>,
+    Path(round): Path<u64>,
+) -> Vec<u8> {
+    state.read().await.dreaming.replay().export_since(round)
+}
+
 async fn get_brain(State(state): State<Arc<RwLock<AppState>>>) -> Json<LivingBrain> {
     let s = state.read().await;
     Json(s.brain.clone())
 }
 
+/// Prometheus text exposition of the node's gauges and counters.
+async fn get_metrics(
+    State(state): State<Arc<RwLock<AppState>>>,
+) -> ([(header::HeaderName, &'static str); 1], String) {
+    use std::fmt::Write;
+
+    let s = state.read().await;
+    let mut out = String::new();
+    let mut metric = |name: &str, kind: &str, help: &str, samples: &[(String, f64)]| {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} {}", name, kind);
+        for (labels, value) in samples {
+            let _ = writeln!(out, "{}{} {}", name, labels, value);
+        }
+    };
+
+    metric(
+        "qres_connected_peers",
+        "gauge",
+        "Peers with an open connection",
+        &[(String::new(), s.connected_peers.len() as f64)],
+    );
+    metric(
+        "qres_known_peers",
+        "gauge",
+        "Peers discovered so far",
+        &[(String::new(), s.known_peers.len() as f64)],
+    );
+    metric(
+        "qres_energy_ratio",
+        "gauge",
+        "Energy pool charge as a fraction of capacity",
+        &[(String::new(), s.energy_pool.ratio() as f64)],
+    );
+    metric(
+        "qres_total_energy_consumed",
+        "counter",
+        "Energy units consumed since startup",
+        &[(String::new(), s.energy_pool.lifetime_consumption() as f64)],
+    );
+    let current = s.regime_detector.current_regime();
+    metric(
+        "qres_regime",
+        "gauge",
+        "Current regime (1 for the active regime label)",
+        &[Regime::Calm, Regime::PreStorm, Regime::Storm].map(|regime| {
+            (
+                format!("{{regime=\"{:?}\"}}", regime),
+                (regime == current) as u8 as f64,
+            )
+        }),
+    );
+    let privacy = &s.privacy_accountant;
+    metric(
+        "qres_privacy_budget_remaining",
+        "gauge",
+        "Epsilon left in the privacy budget",
+        &[(
+            String::new(),
+            (privacy.total_epsilon - privacy.spent_epsilon(privacy.target_delta) as f64).max(0.0),
+        )],
+    );
+    metric(
+        "qres_federated_rounds_total",
+        "counter",
+        "Completed federated averaging rounds",
+        &[(String::new(), s.federated_rounds as f64)],
+    );
+
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], out)
+}
+
 async fn get_health() -> Json<serde_json::Value> {
     Json(serde_json::json!({
         "status": "ok",
@@ -875,3 +2086,999 @@ fn calculate_brain_entropy(brain: &LivingBrain) -> f32 {
     }
     entropy
 }
+
+fn regime_snapshot_path(brain_file: &str) -> PathBuf {
+    PathBuf::from(format!("{}{}", brain_file, REGIME_SNAPSHOT_SUFFIX))
+}
+
+/// Persist the regime detector next to the brain so restarts resume warm.
+fn save_regime_snapshot(detector: &RegimeDetector, brain_file: &str) {
+    let path = regime_snapshot_path(brain_file);
+    match serde_json::to_string(&detector.to_snapshot()) {
+        Ok(json) => {
+            if let Err(e) = write_file_atomic(&path, json.as_bytes()) {
+                warn!(error = %e, path = ?path, "Failed to persist regime snapshot");
+            }
+        }
+        Err(e) => warn!(error = %e, "Failed to serialize regime snapshot"),
+    }
+}
+
+/// Restore a detector saved by [`save_regime_snapshot`], if one exists.
+fn load_regime_snapshot(brain_file: &str) -> Option<RegimeDetector> {
+    let path = regime_snapshot_path(brain_file);
+    let json = fs::read_to_string(&path).ok()?;
+    match serde_json::from_str::<RegimeSnapshot>(&json) {
+        Ok(snapshot) => Some(RegimeDetector::from_snapshot(snapshot)),
+        Err(e) => {
+            warn!(error = %e, path = ?path, "Ignoring unreadable regime snapshot");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reputation DB path private to one test
+    fn test_reputation_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "qres_{}_{}_reputation.json",
+            name,
+            std::process::id()
+        ))
+    }
+
+    /// Node state with the default config, no signing key and an empty
+    /// reputation DB named after the test
+    fn test_app_state(name: &str) -> AppState {
+        let reputation_path = test_reputation_path(name);
+        let _ = fs::remove_file(&reputation_path);
+        new_app_state(
+            PeerId::random().to_string(),
+            Config::default(),
+            PeerKeyStore::new(&[], &[]),
+            None,
+            ReputationManager::new(reputation_path),
+        )
+    }
+
+    #[test]
+    fn test_storm_summary_gene_uses_bfp8() {
+        let consensus = [0.5; 8];
+        let variance = [0.01; 8];
+        let gene = |regime| {
+            let width = SummaryGene::width_for_regime(regime);
+            SummaryGene::new(1, [0; 32], &consensus, &variance, width).to_bytes()
+        };
+
+        // [Round: 8] + [Hash: 32] + 2 × ([Exponent: 1] + 8 mantissas + [CRC: 1])
+        assert_eq!(gene(Regime::Calm).len(), 40 + 2 * 18);
+        assert_eq!(gene(Regime::PreStorm).len(), 40 + 2 * 18);
+        assert_eq!(gene(Regime::Storm).len(), 40 + 2 * 10);
+    }
+
+    #[test]
+    fn test_listen_multiaddr_per_transport() {
+        let protocols = |t| -> Vec<String> {
+            listen_multiaddr(t)
+                .iter()
+                .map(|p| p.tag().to_string())
+                .collect()
+        };
+        assert_eq!(protocols(SwarmTransport::Tcp), vec!["ip4", "tcp"]);
+        assert_eq!(
+            protocols(SwarmTransport::Quic),
+            vec!["ip4", "udp", "quic-v1"]
+        );
+        assert_eq!(protocols(SwarmTransport::Ws), vec!["ip4", "tcp", "ws"]);
+    }
+
+    #[tokio::test]
+    async fn test_swarm_without_mdns_honors_explicit_listen_addr() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let explicit = format!("/ip4/127.0.0.1/tcp/{}", port);
+        let listen_addr = resolve_listen_multiaddr(Some(&explicit), SwarmTransport::Tcp).unwrap();
+        assert_eq!(listen_addr.to_string(), explicit);
+        assert!(resolve_listen_multiaddr(Some("not-a-multiaddr"), SwarmTransport::Tcp).is_err());
+
+        let mut swarm = build_swarm(
+            identity::Keypair::generate_ed25519(),
+            SwarmTransport::Tcp,
+            SwarmOptions {
+                enable_mdns: false,
+                ..SwarmOptions::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert!(!swarm.behaviour().mdns.is_enabled());
+        swarm.listen_on(listen_addr.clone()).unwrap();
+
+        let address = loop {
+            if let SwarmEvent::NewListenAddr { address, .. } = swarm.select_next_some().await {
+                break address;
+            }
+        };
+        assert_eq!(address, listen_addr);
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_peers_are_dialed_on_start() {
+        let peers: Vec<PeerId> = (0..2).map(|_| PeerId::random()).collect();
+        // Closed ports: dials are issued and then fail
+        let addrs: Vec<Multiaddr> = peers
+            .iter()
+            .map(|peer_id| {
+                format!("/ip4/127.0.0.1/tcp/1/p2p/{}", peer_id)
+                    .parse()
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(multiaddr_peer_id(&addrs[0]), Some(peers[0]));
+
+        let mut swarm = build_swarm(
+            identity::Keypair::generate_ed25519(),
+            SwarmTransport::Tcp,
+            SwarmOptions {
+                enable_mdns: false,
+                ..SwarmOptions::default()
+            },
+        )
+        .await
+        .unwrap();
+        let mut redialer = BootstrapRedialer::default();
+        assert_eq!(dial_bootstrap_peers(&mut swarm, &mut redialer, &addrs), 2);
+
+        let mut dialed = HashSet::new();
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while dialed.len() < peers.len() {
+                match swarm.select_next_some().await {
+                    SwarmEvent::Dialing {
+                        peer_id: Some(peer_id),
+                        ..
+                    }
+                    | SwarmEvent::OutgoingConnectionError {
+                        peer_id: Some(peer_id),
+                        ..
+                    } => {
+                        dialed.insert(peer_id);
+                    }
+                    _ => {}
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(dialed, peers.into_iter().collect());
+    }
+
+    #[tokio::test]
+    async fn test_failed_bootstrap_dial_is_retried_with_backoff() {
+        // Closed port: every dial fails
+        let addr: Multiaddr = format!("/ip4/127.0.0.1/tcp/1/p2p/{}", PeerId::random())
+            .parse()
+            .unwrap();
+        let mut swarm = build_swarm(
+            identity::Keypair::generate_ed25519(),
+            SwarmTransport::Tcp,
+            SwarmOptions {
+                enable_mdns: false,
+                ..SwarmOptions::default()
+            },
+        )
+        .await
+        .unwrap();
+        let initial = Duration::from_millis(20);
+        let mut redialer = BootstrapRedialer::new(initial, Duration::from_secs(1));
+        assert_eq!(
+            dial_bootstrap_peers(&mut swarm, &mut redialer, std::slice::from_ref(&addr)),
+            1
+        );
+
+        let mut waits = Vec::new();
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while waits.len() < 2 {
+                if let SwarmEvent::OutgoingConnectionError { connection_id, .. } =
+                    swarm.select_next_some().await
+                {
+                    let wait = redialer
+                        .dial_failed(connection_id, Instant::now())
+                        .expect("bootstrap dial is tracked");
+                    waits.push(wait);
+                    tokio::time::sleep(wait).await;
+                    assert_eq!(
+                        redial_bootstrap_peers(&mut swarm, &mut redialer, Instant::now()),
+                        1
+                    );
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(waits, vec![initial, initial * 2]);
+    }
+
+    #[test]
+    fn test_gossip_precheck_rejects_garbage_and_oversized() {
+        let valid = SignedEpiphany::new(
+            LivingBrain::new(),
+            None,
+            String::new(),
+            String::new(),
+            0,
+            0,
+            false,
+        )
+        .to_wire_bytes()
+        .unwrap();
+        let accepts = gossip_precheck;
+        assert!(accepts(&valid, valid.len(), None));
+        assert!(!accepts(&valid, valid.len() - 1, None));
+        assert!(!accepts(b"not an epiphany", 1024, None));
+
+        let cipher = PayloadCipher::from_secret("swarm secret");
+        let encrypted = cipher.encrypt(&valid);
+        assert!(accepts(&encrypted, 65536, Some(&cipher)));
+        // Ciphertext is opaque until a worker decrypts it; only its length is checked
+        assert!(!accepts(
+            &encrypted[..PAYLOAD_OVERHEAD],
+            65536,
+            Some(&cipher)
+        ));
+    }
+
+    #[test]
+    fn test_fragmented_frames_pass_validation() {
+        let payload = vec![b'x'; 5_000];
+        let frames = encode_gossip_frames(&payload, None).unwrap();
+        assert!(frames.len() > 1);
+        assert!(frames.iter().all(|f| f.len() <= FRAGMENT_MTU));
+        for frame in &frames {
+            assert!(gossip_precheck(frame, 65536, None));
+            // A set announcing more fragments than the size limit allows
+            assert!(!gossip_precheck(frame, 1024, None));
+        }
+
+        let cipher = PayloadCipher::from_secret("swarm secret");
+        let encrypted = encode_gossip_frames(&payload, Some(&cipher)).unwrap();
+        assert!(encrypted.iter().all(|f| f.len() <= FRAGMENT_MTU));
+        let mut reassembler = Reassembler::new(REASSEMBLY_TIMEOUT, 65536);
+        let now = Instant::now();
+        let source = PeerId::random();
+        let rebuilt = encrypted.iter().find_map(|frame| {
+            let plaintext = cipher.decrypt(frame).unwrap();
+            reassembler.push(source, Fragment::parse(&plaintext).unwrap(), now)
+        });
+        assert_eq!(rebuilt.unwrap(), payload);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_gossip_is_not_repropagated() {
+        let options = SwarmOptions {
+            enable_mdns: false,
+            ..SwarmOptions::default()
+        };
+        let mut nodes = Vec::new();
+        for _ in 0..3 {
+            let mut swarm = build_swarm(
+                identity::Keypair::generate_ed25519(),
+                SwarmTransport::Tcp,
+                options,
+            )
+            .await
+            .unwrap();
+            swarm
+                .listen_on("/ip4/127.0.0.1/tcp/0".parse().unwrap())
+                .unwrap();
+            let address = loop {
+                if let SwarmEvent::NewListenAddr { address, .. } = swarm.select_next_some().await {
+                    break address;
+                }
+            };
+            nodes.push((swarm, address));
+        }
+        let (mut c, _) = nodes.pop().unwrap();
+        let (mut b, b_addr) = nodes.pop().unwrap();
+        let (mut a, _) = nodes.pop().unwrap();
+
+        // Chain A - B - C: anything C sees was forwarded by B
+        let (a_id, b_id, c_id) = (*a.local_peer_id(), *b.local_peer_id(), *c.local_peer_id());
+        a.behaviour_mut().gossipsub.add_explicit_peer(&b_id);
+        b.behaviour_mut().gossipsub.add_explicit_peer(&a_id);
+        b.behaviour_mut().gossipsub.add_explicit_peer(&c_id);
+        c.behaviour_mut().gossipsub.add_explicit_peer(&b_id);
+        a.dial(b_addr.clone()).unwrap();
+        c.dial(b_addr).unwrap();
+
+        let mut b_state = test_app_state("repropagate");
+        b_state.require_signatures = false;
+        let b_state = Arc::new(RwLock::new(b_state));
+
+        let garbage = b"not an epiphany".to_vec();
+        let valid = SignedEpiphany::new(
+            LivingBrain::new(),
+            None,
+            String::new(),
+            a_id.to_string(),
+            SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            0,
+            true,
+        )
+        .to_wire_bytes()
+        .unwrap();
+        // Passes the inline header check, fails the worker's full decode
+        let truncated = valid[..valid.len() / 2].to_vec();
+        let mut pending = vec![valid.clone(), truncated.clone(), garbage.clone()];
+        let mut received_by_c = Vec::new();
+        let mut publish_tick = tokio::time::interval(Duration::from_millis(200));
+
+        tokio::time::timeout(Duration::from_secs(30), async {
+            while !received_by_c.contains(&valid) {
+                tokio::select! {
+                    _ = publish_tick.tick() => {
+                        // Invalid messages first; publishing fails until A sees B's subscription
+                        if let Some(data) = pending.last() {
+                            if a.behaviour_mut()
+                                .gossipsub
+                                .publish(IdentTopic::new(BRAIN_TOPIC), data.clone())
+                                .is_ok()
+                            {
+                                pending.pop();
+                            }
+                        }
+                    }
+                    _ = a.select_next_some() => {}
+                    event = b.select_next_some() => {
+                        if let SwarmEvent::Behaviour(QresBehaviorEvent::Gossipsub(
+                            gossipsub::Event::Message { propagation_source, message_id, message },
+                        )) = event
+                        {
+                            let acceptance =
+                                if gossip_precheck(&message.data, options.max_message_size, None) {
+                                    handle_gossipsub_message(&message, &b_state).await
+                                } else {
+                                    gossipsub::MessageAcceptance::Reject
+                                };
+                            let _ = b.behaviour_mut().gossipsub.report_message_validation_result(
+                                &message_id,
+                                &propagation_source,
+                                acceptance,
+                            );
+                        }
+                    }
+                    event = c.select_next_some() => {
+                        if let SwarmEvent::Behaviour(QresBehaviorEvent::Gossipsub(
+                            gossipsub::Event::Message { message, .. },
+                        )) = event
+                        {
+                            received_by_c.push(message.data);
+                        }
+                    }
+                }
+            }
+        })
+        .await
+        .unwrap();
+
+        // B forwards in order, so the earlier invalid messages would have arrived first
+        assert!(!received_by_c.contains(&garbage));
+        assert!(!received_by_c.contains(&truncated));
+        assert_eq!(b_state.read().await.federated_averager.buffer_len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_swarm_listens_on_selected_transport() {
+        for transport in [
+            SwarmTransport::Tcp,
+            SwarmTransport::Quic,
+            SwarmTransport::Ws,
+        ] {
+            let mut swarm = build_swarm(
+                identity::Keypair::generate_ed25519(),
+                transport,
+                SwarmOptions::default(),
+            )
+            .await
+            .unwrap();
+            swarm.listen_on(listen_multiaddr(transport)).unwrap();
+
+            let address = loop {
+                if let SwarmEvent::NewListenAddr { address, .. } = swarm.select_next_some().await {
+                    break address;
+                }
+            };
+            let last = address.iter().last().unwrap();
+            match transport {
+                SwarmTransport::Tcp => assert!(matches!(last, Protocol::Tcp(_))),
+                SwarmTransport::Quic => assert!(matches!(last, Protocol::QuicV1)),
+                SwarmTransport::Ws => assert!(matches!(last, Protocol::Ws(_))),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_depleted_energy_skips_proof_generation() {
+        let state = Arc::new(RwLock::new(test_app_state("zk_energy")));
+        let weights = vec![0.1, -0.2, 0.3];
+
+        state
+            .write()
+            .await
+            .energy_pool
+            .set_energy(energy_costs::ZK_PROVE - 1);
+        assert!(generate_proof_bundle(&state, weights.clone())
+            .await
+            .is_none());
+        // Nothing was spent on the skipped proof
+        assert_eq!(
+            state.read().await.energy_pool.current(),
+            energy_costs::ZK_PROVE - 1
+        );
+
+        state
+            .write()
+            .await
+            .energy_pool
+            .set_energy(energy_costs::ZK_PROVE);
+        let bundle = generate_proof_bundle(&state, weights)
+            .await
+            .expect("affordable proof");
+        let app_state = state.read().await;
+        assert_eq!(app_state.energy_pool.current(), 0);
+        assert!(app_state
+            .zk_prover
+            .verify_proof(&bundle.zk_proof, ZK_NORM_THRESHOLD));
+    }
+
+    #[test]
+    fn test_identify_advertises_loaded_brain_capability() {
+        let mut brain = LivingBrain::new();
+        brain.version = 3;
+        brain.confidence = vec![0.5; 9];
+        let capability = PredictorCapability::of(&brain);
+        assert_ne!(capability, PredictorCapability::of(&LivingBrain::default()));
+
+        let config = identify_config(&identity::Keypair::generate_ed25519(), capability);
+        assert_eq!(
+            PredictorCapability::from_agent_version(&config.agent_version),
+            Some(capability)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_incompatible_predictor_versions_are_not_buffered() {
+        let node = |remote: PeerId, remote_version: u8| {
+            let mut app_state = test_app_state("caps");
+            app_state.require_signatures = false;
+            let mut capability = app_state.local_capability();
+            capability.version = remote_version;
+            app_state
+                .peer_capabilities
+                .insert(remote.to_string(), capability);
+            Arc::new(RwLock::new(app_state))
+        };
+        let message = |source: PeerId| {
+            let epiphany = SignedEpiphany::new(
+                LivingBrain::new(),
+                None,
+                String::new(),
+                source.to_string(),
+                SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs(),
+                0,
+                true,
+            );
+            gossipsub::Message {
+                source: Some(source),
+                data: epiphany.to_wire_bytes().unwrap(),
+                sequence_number: None,
+                topic: IdentTopic::new(BRAIN_TOPIC).hash(),
+            }
+        };
+
+        let (peer_a, peer_b) = (PeerId::random(), PeerId::random());
+        let local_version = LivingBrain::new().version;
+
+        // Node A sees B advertising a newer predictor set, and vice versa
+        let node_a = node(peer_b, local_version + 1);
+        let node_b = node(peer_a, local_version + 1);
+        handle_gossipsub_message(&message(peer_b), &node_a).await;
+        handle_gossipsub_message(&message(peer_a), &node_b).await;
+        assert_eq!(node_a.read().await.federated_averager.buffer_len(), 0);
+        assert_eq!(node_b.read().await.federated_averager.buffer_len(), 0);
+        assert_eq!(node_a.read().await.rejection_log.len(), 1);
+
+        // Matching capabilities are buffered as before
+        let peer_c = PeerId::random();
+        let node_c = node(peer_c, local_version);
+        handle_gossipsub_message(&message(peer_c), &node_c).await;
+        assert_eq!(node_c.read().await.federated_averager.buffer_len(), 1);
+        assert_eq!(node_c.read().await.audit_history.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_route_serves_prometheus_text() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let app_state = test_app_state("metrics");
+        let state = Arc::new(RwLock::new(app_state));
+        state.write().await.federated_rounds = 3;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, status_router(state, false)).await;
+        });
+
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.contains("text/plain; version=0.0.4"));
+        for name in [
+            "qres_connected_peers",
+            "qres_known_peers",
+            "qres_energy_ratio",
+            "qres_privacy_budget_remaining",
+        ] {
+            assert!(response.contains(&format!("# TYPE {} gauge", name)));
+        }
+        assert!(response.contains("# TYPE qres_total_energy_consumed counter"));
+        assert!(response.contains("qres_regime{regime=\"Calm\"} 1"));
+        assert!(response.contains("qres_regime{regime=\"Storm\"} 0"));
+        assert!(response.contains("qres_federated_rounds_total 3"));
+    }
+
+    #[test]
+    fn test_gossip_inject_needs_explicit_opt_in() {
+        assert!(env_flag_enabled(Some("1")));
+        assert!(env_flag_enabled(Some("true")));
+        assert!(env_flag_enabled(Some("TRUE")));
+        assert!(!env_flag_enabled(None));
+        assert!(!env_flag_enabled(Some("")));
+        assert!(!env_flag_enabled(Some("0")));
+        assert!(!env_flag_enabled(Some("false")));
+    }
+
+    #[tokio::test]
+    async fn test_gossip_inject_verdicts() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let key_path = |name: &str| {
+            std::env::temp_dir().join(format!("qres_inject_{}_{}", std::process::id(), name))
+        };
+        let sender = SecurityManager::new(&key_path("sender_key"), true).unwrap();
+        let receiver = SecurityManager::new(&key_path("receiver_key"), true).unwrap();
+        let mut app_state = test_app_state("inject");
+        app_state.security = Some(receiver);
+        let state = Arc::new(RwLock::new(app_state));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = status_router(state.clone(), true);
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, router).await;
+        });
+
+        let signed = |nonce: u64| {
+            let timestamp = SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+            // Storm mode stands in for a proof for an unknown sender
+            let mut epiphany = SignedEpiphany::new(
+                LivingBrain::new(),
+                None,
+                String::new(),
+                sender.public_key_hex(),
+                timestamp,
+                nonce,
+                true,
+            );
+            epiphany.signature = sender
+                .sign_at(&epiphany.payload_bytes(), timestamp, nonce)
+                .signature;
+            epiphany
+        };
+        let inject = |epiphany: SignedEpiphany| async move {
+            let body = serde_json::to_string(&epiphany).unwrap();
+            let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+            let request = format!(
+                "POST /gossip-inject HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(request.as_bytes()).await.unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            let json = response.split("\r\n\r\n").nth(1).unwrap();
+            serde_json::from_str::<serde_json::Value>(json).unwrap()
+        };
+
+        let verdict = inject(signed(1)).await;
+        assert_eq!(verdict["verdict"], "accepted");
+        assert!(verdict.get("reason").is_none());
+
+        // A replay is refused without costing the genuine signer trust
+        let trust = state
+            .read()
+            .await
+            .reputation
+            .get_trust(&sender.public_key_hex());
+        let verdict = inject(signed(1)).await;
+        assert_eq!(verdict["verdict"], "rejected");
+        assert_eq!(verdict["reason"], "replayed message");
+        assert_eq!(
+            state
+                .read()
+                .await
+                .reputation
+                .get_trust(&sender.public_key_hex()),
+            trust
+        );
+
+        let mut forged = signed(2);
+        forged.brain.confidence[0] = 1.0;
+        let verdict = inject(forged).await;
+        assert_eq!(verdict["verdict"], "rejected");
+        assert_eq!(verdict["reason"], "invalid signature");
+
+        let _ = fs::remove_file(key_path("sender_key"));
+        let _ = fs::remove_file(key_path("receiver_key"));
+    }
+
+    #[tokio::test]
+    async fn test_idle_node_dreams_and_serves_replay_delta() {
+        let mut app_state = test_app_state("dream");
+        assert!(!app_state.dream_if_idle());
+
+        app_state.dreaming = DreamingManager::new(0);
+        for round in 1..=3 {
+            app_state.federated_rounds = round;
+            assert!(app_state.dream_if_idle());
+        }
+        let state = Arc::new(RwLock::new(app_state));
+
+        let delta = get_dream_since(State(state.clone()), Path(1)).await;
+        let mut woken = DreamBuffer::default();
+        assert_eq!(woken.import(&delta), Some(2));
+        let rounds: Vec<u64> = woken.entries().map(|e| e.round).collect();
+        assert_eq!(rounds, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_restored_replay_keeps_round_tags_increasing() {
+        let mut previous_run = DreamBuffer::default();
+        for round in 5..=7 {
+            previous_run.record(round, vec![round as u8]);
+        }
+        let mut app_state = test_app_state("dream_restore");
+        app_state.restore_dream_replay(previous_run);
+        assert_eq!(app_state.federated_rounds, 7);
+
+        // A round later, a fresh sample is served to peers that saw round 7
+        app_state.federated_rounds += 1;
+        let replay = app_state.dreaming.replay().clone();
+        app_state.dreaming = DreamingManager::new(0).with_replay(replay);
+        assert!(app_state.dream_if_idle());
+        let replay = app_state.dreaming.replay();
+        assert_eq!(replay.latest_round(), Some(8));
+        let mut peer = DreamBuffer::default();
+        assert_eq!(peer.import(&replay.export_since(7)), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_regime_and_silence_state() {
+        let mut app_state = test_app_state("status");
+        let mut snapshot = app_state.regime_detector.to_snapshot();
+        snapshot.current_regime = Regime::PreStorm;
+        app_state.regime_detector = RegimeDetector::from_snapshot(snapshot);
+        app_state
+            .silence_controller
+            .set_state(qres_core::adaptive::SilenceState::Alert);
+        let state = Arc::new(RwLock::new(app_state));
+
+        let Json(status) = get_status(State(state)).await;
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["regime"], "PreStorm");
+        assert_eq!(json["silence_state"], "Alert");
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_flushes_in_memory_state() {
+        let path = |name: &str| {
+            std::env::temp_dir().join(format!("qres_shutdown_{}_{}", std::process::id(), name))
+        };
+        let brain_file = path("brain.json");
+        let reputation_file = test_reputation_path("shutdown");
+        fs::write(&brain_file, LivingBrain::new().to_json()).unwrap();
+
+        let mut app_state = test_app_state("shutdown");
+        // Latest aggregate only held in RAM
+        app_state.brain.confidence = vec![0.9, 0.1, 0.7, 0.3];
+        let state = Arc::new(RwLock::new(app_state));
+
+        handle_shutdown(&state, brain_file.to_str().unwrap()).await;
+
+        let flushed = LivingBrain::from_json(&fs::read_to_string(&brain_file).unwrap()).unwrap();
+        assert_eq!(flushed.confidence, vec![0.9, 0.1, 0.7, 0.3]);
+        assert!(!crate::atomic_file::staging_path(&brain_file).exists());
+        assert!(reputation_file.exists());
+
+        let _ = fs::remove_file(&brain_file);
+        let _ = fs::remove_file(regime_snapshot_path(brain_file.to_str().unwrap()));
+        let _ = fs::remove_file(reputation_file);
+    }
+
+    #[tokio::test]
+    async fn test_distrusted_peer_is_banned_until_expiry() {
+        let mut app_state = test_app_state("ban");
+        app_state.require_signatures = false;
+        let state = Arc::new(RwLock::new(app_state));
+
+        // Reputation is keyed by the signer, bans by the libp2p author
+        let source = PeerId::random();
+        let sender_id = "ab".repeat(32);
+        let message = |is_storm_mode: bool| {
+            let epiphany = SignedEpiphany::new(
+                LivingBrain::new(),
+                None,
+                String::new(),
+                sender_id.clone(),
+                SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs(),
+                0,
+                is_storm_mode,
+            );
+            gossipsub::Message {
+                source: Some(source),
+                data: epiphany.to_wire_bytes().unwrap(),
+                sequence_number: None,
+                topic: IdentTopic::new(BRAIN_TOPIC).hash(),
+            }
+        };
+
+        // Calm updates without a proof are rejected and punish the sender
+        for _ in 0..4 {
+            handle_gossipsub_message(&message(false), &state).await;
+        }
+        {
+            let mut app_state = state.write().await;
+            assert!(app_state.reputation.is_banned(&sender_id));
+            assert!(!app_state.is_gossip_banned(&source));
+            assert_eq!(app_state.update_gossip_bans(), (vec![source], vec![]));
+            assert!(app_state.is_gossip_banned(&source));
+        }
+
+        // Its messages are refused while the ban lasts, even acceptable ones
+        handle_gossipsub_message(&message(true), &state).await;
+
+        let mut app_state = state.write().await;
+        assert_eq!(app_state.federated_averager.buffer_len(), 0);
+        assert_eq!(app_state.rejection_log.len(), 5);
+        app_state.federation_epochs += GOSSIP_BAN_EPOCHS - 1;
+        assert_eq!(app_state.update_gossip_bans(), (vec![], vec![]));
+        assert!(app_state.is_gossip_banned(&source));
+
+        // Past the expiry the peer is back, its sender on probation
+        app_state.federation_epochs += 1;
+        assert_eq!(app_state.update_gossip_bans(), (vec![], vec![source]));
+        assert!(!app_state.is_gossip_banned(&source));
+        assert!(!app_state.reputation.is_banned(&sender_id));
+        drop(app_state);
+        handle_gossipsub_message(&message(false), &state).await;
+        assert_eq!(
+            state.write().await.update_gossip_bans(),
+            (vec![source], vec![])
+        );
+        let _ = fs::remove_file(test_reputation_path("ban"));
+    }
+
+    #[tokio::test]
+    async fn test_regime_detector_sees_bytes_and_clock_time() {
+        let mut app_state = test_app_state("regime_clock");
+        app_state.require_signatures = false;
+        // A restored detector's clock resumes after its last update
+        let mut snapshot = app_state.regime_detector.to_snapshot();
+        snapshot.last_update_ms = 5_000_000;
+        app_state.restore_regime_detector(RegimeDetector::from_snapshot(snapshot));
+        assert!(app_state.regime_now_ms() >= 5_000_000);
+        let state = Arc::new(RwLock::new(app_state));
+
+        let source = PeerId::random();
+        let epiphany = SignedEpiphany::new(
+            LivingBrain::new(),
+            None,
+            String::new(),
+            source.to_string(),
+            SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            0,
+            true,
+        );
+        let data = epiphany.to_wire_bytes().unwrap();
+        let message = gossipsub::Message {
+            source: Some(source),
+            data: data.clone(),
+            sequence_number: None,
+            topic: IdentTopic::new(BRAIN_TOPIC).hash(),
+        };
+        handle_gossipsub_message(&message, &state).await;
+        {
+            let app_state = state.read().await;
+            assert_eq!(app_state.federated_averager.buffer_len(), 1);
+            let snapshot = app_state.regime_detector.to_snapshot();
+            // Bytes are counted as bytes, not as time
+            assert_eq!(snapshot.accumulated_bytes, data.len() as u64);
+            assert_eq!(snapshot.last_update_ms, 5_000_000);
+        }
+
+        // Two seconds later the throughput window closes on the clock's time
+        state.write().await.regime_clock_offset_ms += 2_000;
+        let now_ms = state.read().await.regime_now_ms();
+        handle_gossipsub_message(&message, &state).await;
+        let snapshot = state.read().await.regime_detector.to_snapshot();
+        assert!(snapshot.last_update_ms >= now_ms);
+        assert_eq!(snapshot.accumulated_bytes, 0);
+        assert!(snapshot.current_throughput > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_defer_policy_holds_updates_until_calm() {
+        let mut app_state = test_app_state("defer");
+        app_state.config.swarm.storm_policy = StormPolicy::Defer;
+        app_state.require_signatures = false;
+        let mut snapshot = app_state.regime_detector.to_snapshot();
+        snapshot.current_regime = Regime::Storm;
+        app_state.regime_detector = RegimeDetector::from_snapshot(snapshot);
+        let state = Arc::new(RwLock::new(app_state));
+
+        let source = PeerId::random();
+        let epiphany = SignedEpiphany::new(
+            LivingBrain::new(),
+            None,
+            String::new(),
+            source.to_string(),
+            SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            0,
+            true,
+        );
+        let message = gossipsub::Message {
+            source: Some(source),
+            data: epiphany.to_wire_bytes().unwrap(),
+            sequence_number: None,
+            topic: IdentTopic::new(BRAIN_TOPIC).hash(),
+        };
+        handle_gossipsub_message(&message, &state).await;
+
+        let mut app_state = state.write().await;
+        assert!(app_state.in_storm());
+        assert_eq!(app_state.federated_averager.buffer_len(), 0);
+        assert_eq!(app_state.deferred_updates.len(), 1);
+
+        // Quiet observations bring the node back to Calm, releasing the update
+        let mut now = 1_000_000;
+        while app_state.in_storm() {
+            assert_eq!(app_state.federated_averager.buffer_len(), 0);
+            now += 1000;
+            app_state.update_regime(0.0, 0, now);
+        }
+        assert_eq!(app_state.regime_detector.current_regime(), Regime::Calm);
+        assert_eq!(app_state.federated_averager.buffer_len(), 1);
+        assert!(app_state.deferred_updates.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_full_verification_queue_sheds_without_penalizing_sender() {
+        let mut app_state = test_app_state("shed");
+        app_state.require_signatures = false;
+        let state = Arc::new(RwLock::new(app_state));
+
+        let source = PeerId::random();
+        let epiphany = SignedEpiphany::new(
+            LivingBrain::new(),
+            None,
+            String::new(),
+            source.to_string(),
+            SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            0,
+            true,
+        );
+        let message = gossipsub::Message {
+            source: Some(source),
+            data: epiphany.to_wire_bytes().unwrap(),
+            sequence_number: None,
+            topic: IdentTopic::new(BRAIN_TOPIC).hash(),
+        };
+
+        // Occupy the only slot with a verification that has not finished yet
+        let queue = VerificationQueue::new(1, 1);
+        let (release, blocked) = tokio::sync::oneshot::channel::<()>();
+        assert!(queue.try_submit(async move {
+            let _ = blocked.await;
+        }));
+
+        let (reports, mut verdicts) = mpsc::unbounded_channel();
+        let enqueue = |message| {
+            enqueue_gossipsub_message(
+                gossipsub::MessageId::from("shed"),
+                source,
+                message,
+                &state,
+                &queue,
+                &reports,
+            )
+        };
+        assert!(!enqueue(message.clone()).await);
+        assert!(matches!(
+            verdicts.try_recv().unwrap().2,
+            gossipsub::MessageAcceptance::Ignore
+        ));
+        {
+            let app_state = state.read().await;
+            assert_eq!(app_state.rejection_log.len(), 1);
+            assert!(app_state.reputation.peers.is_empty());
+            assert_eq!(app_state.federated_averager.buffer_len(), 0);
+        }
+
+        // Once the slot frees up, messages are verified off the event loop
+        release.send(()).unwrap();
+        while queue.pending() > 0 {
+            tokio::task::yield_now().await;
+        }
+        assert!(enqueue(message).await);
+        let verdict = verdicts.recv().await.unwrap();
+        assert!(matches!(verdict.2, gossipsub::MessageAcceptance::Accept));
+        assert_eq!(state.read().await.federated_averager.buffer_len(), 1);
+    }
+
+    #[test]
+    fn test_regime_snapshot_persists_next_to_brain() {
+        let brain_file = std::env::temp_dir()
+            .join(format!("qres_regime_brain_{}.json", std::process::id()))
+            .to_string_lossy()
+            .into_owned();
+        let _ = fs::remove_file(regime_snapshot_path(&brain_file));
+        assert!(load_regime_snapshot(&brain_file).is_none());
+
+        let mut detector = RegimeDetector::new(
+            REGIME_WINDOW_SIZE,
+            REGIME_ENTROPY_THRESHOLD,
+            REGIME_THROUGHPUT_THRESHOLD,
+        );
+        detector.update(0.9, 100, 1000);
+        save_regime_snapshot(&detector, &brain_file);
+
+        let restored = load_regime_snapshot(&brain_file).unwrap();
+        assert_eq!(restored.to_snapshot(), detector.to_snapshot());
+        assert_eq!(restored.pending_regime(), Some(Regime::Storm));
+
+        let _ = fs::remove_file(regime_snapshot_path(&brain_file));
+    }
+}
@@ -0,0 +1,128 @@
+//! Bounded Verification Queue
+//!
+//! Signature and ZK-proof checks for incoming gossip are CPU-heavy. Running
+//! them inline on the swarm task lets a burst of messages stall every other
+//! peer, so they are handed to this queue instead: at most `concurrency` jobs
+//! run at once on the Tokio worker pool, at most `capacity` are admitted
+//! (queued + running), and anything beyond that is refused immediately so
+//! the caller can shed it.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+#[derive(Debug, Clone)]
+pub struct VerificationQueue {
+    capacity: usize,
+    pending: Arc<AtomicUsize>,
+    permits: Arc<Semaphore>,
+}
+
+/// Releases a queue slot when its job finishes (or panics).
+struct PendingSlot(Arc<AtomicUsize>);
+
+impl Drop for PendingSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+impl VerificationQueue {
+    /// Admit up to `capacity` jobs, running at most `concurrency` at a time.
+    pub fn new(capacity: usize, concurrency: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            pending: Arc::new(AtomicUsize::new(0)),
+            permits: Arc::new(Semaphore::new(concurrency.max(1))),
+        }
+    }
+
+    /// Spawn `job` onto the worker pool without waiting for it.
+    ///
+    /// Returns `false` (and drops the job) when the queue is full.
+    pub fn try_submit<F>(&self, job: F) -> bool
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let admitted = self
+            .pending
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < self.capacity).then_some(n + 1)
+            })
+            .is_ok();
+        if !admitted {
+            return false;
+        }
+
+        let slot = PendingSlot(self.pending.clone());
+        let permits = self.permits.clone();
+        tokio::spawn(async move {
+            let _slot = slot;
+            // The semaphore is never closed, so acquiring only fails on shutdown
+            if let Ok(_permit) = permits.acquire_owned().await {
+                job.await;
+            }
+        });
+        true
+    }
+
+    /// Jobs admitted but not yet finished.
+    pub fn pending(&self) -> usize {
+        self.pending.load(Ordering::Acquire)
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+    use tokio::sync::Notify;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_burst_beyond_capacity_is_shed_without_blocking() {
+        let queue = VerificationQueue::new(4, 2);
+        let gate = Arc::new(Notify::new());
+        let completed = Arc::new(AtomicUsize::new(0));
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+
+        // Every job blocks until released, so the burst cannot drain early
+        let start = Instant::now();
+        let accepted = (0..32)
+            .filter(|_| {
+                let (gate, completed) = (gate.clone(), completed.clone());
+                let (running, max_running) = (running.clone(), max_running.clone());
+                queue.try_submit(async move {
+                    let now = running.fetch_add(1, Ordering::AcqRel) + 1;
+                    max_running.fetch_max(now, Ordering::AcqRel);
+                    gate.notified().await;
+                    running.fetch_sub(1, Ordering::AcqRel);
+                    completed.fetch_add(1, Ordering::AcqRel);
+                })
+            })
+            .count();
+
+        // Submission never waits on verification
+        assert!(start.elapsed() < Duration::from_millis(500));
+        assert_eq!(accepted, 4);
+        assert_eq!(queue.pending(), 4);
+
+        // Release jobs until the queue drains
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while queue.pending() > 0 {
+            assert!(Instant::now() < deadline, "queue did not drain");
+            gate.notify_waiters();
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert_eq!(completed.load(Ordering::Acquire), 4);
+        assert!(max_running.load(Ordering::Acquire) <= 2);
+
+        // Freed slots admit new work
+        assert!(queue.try_submit(async {}));
+    }
+}
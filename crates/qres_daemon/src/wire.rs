@@ -0,0 +1,414 @@
+//! Compact Binary Codec for Gossip Updates
+//!
+//! JSON spells every confidence weight and curve point out as text, which
+//! roughly triples the size of a `SignedEpiphany` and pushes it over the MTU
+//! far sooner than necessary. The gossip path uses this fixed-layout binary
+//! encoding instead; JSON stays in use for brain files and the CLI export.
+//!
+//! Layout (integers little-endian, `vec` = `u32` count then items):
+//!
+//! ```text
+//! magic "QE" | version u8 | flags u8 | timestamp u64 | nonce u64
+//! signature (u16 len + bytes) | sender_id (u16 len + utf8)
+//! brain: version u8 | predictors (u16 count, each u8 len + utf8)
+//!        stats (u32 len + compact JSON) | confidence vec<f32>
+//!        [global_confidence vec<f32>] | [best_engine_weights vec<u8>]
+//! [proof: peer_id [u8; 32] | masked_weights vec<f32> | NormProof (104 bytes)]
+//! [key rotation: previous key [u8; 32] | current key [u8; 32] | signature [u8; 64]]
+//! ```
+
+use crate::living_brain::{LivingBrain, SignedEpiphany};
+use crate::security::{KeyRotation, SIGNATURE_SIZE};
+use qres_core::zk_proofs::{NormProof, ProofBundle};
+
+/// Leading bytes of an encoded update (distinct from fragment framing)
+const WIRE_MAGIC: [u8; 2] = *b"QE";
+/// Current layout version
+const WIRE_VERSION: u8 = 1;
+
+const FLAG_STORM: u8 = 1 << 0;
+const FLAG_PROOF: u8 = 1 << 1;
+const FLAG_GLOBAL_CONFIDENCE: u8 = 1 << 2;
+const FLAG_ENGINE_WEIGHTS: u8 = 1 << 3;
+/// Signature was lowercase hex and is carried as raw bytes
+const FLAG_SIGNATURE_HEX: u8 = 1 << 4;
+const FLAG_KEY_ROTATION: u8 = 1 << 5;
+
+/// Failure to decode a binary update.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WireError {
+    /// Input ended before the layout was complete
+    Truncated,
+    /// Missing magic bytes or an unknown layout version
+    UnknownFormat,
+    /// A field held an invalid value
+    InvalidField(&'static str),
+    /// Bytes remained after the last field
+    TrailingBytes(usize),
+    /// A field is too long for its length prefix and cannot be encoded
+    FieldTooLong(&'static str),
+}
+
+impl std::fmt::Display for WireError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WireError::Truncated => write!(f, "Truncated wire update"),
+            WireError::UnknownFormat => write!(f, "Unknown wire format"),
+            WireError::InvalidField(field) => write!(f, "Invalid wire field: {}", field),
+            WireError::TrailingBytes(n) => write!(f, "{} trailing bytes after wire update", n),
+            WireError::FieldTooLong(field) => write!(f, "Wire field too long: {}", field),
+        }
+    }
+}
+
+impl std::error::Error for WireError {}
+
+impl SignedEpiphany {
+    /// Encode for gossip using the compact binary layout.
+    ///
+    /// Fails with [`WireError::FieldTooLong`] rather than truncating a field
+    /// that overflows its length prefix (which would break the signature).
+    pub fn to_wire_bytes(&self) -> Result<Vec<u8>, WireError> {
+        let signature_hex = hex::decode(&self.signature)
+            .ok()
+            .filter(|raw| hex::encode(raw) == self.signature);
+        let brain = &self.brain;
+        // Certificates are fixed-size once decoded; a malformed one could
+        // never verify, so it is left out rather than encoded
+        let key_rotation = self.key_rotation.as_ref().and_then(|r| {
+            let mut raw = hex::decode(&r.previous_pubkey).ok()?;
+            raw.extend(hex::decode(&r.current_pubkey).ok()?);
+            raw.extend(hex::decode(&r.signature).ok()?);
+            (raw.len() == 64 + SIGNATURE_SIZE).then_some(raw)
+        });
+
+        let mut flags = 0;
+        if self.is_storm_mode {
+            flags |= FLAG_STORM;
+        }
+        if self.proof_bundle.is_some() {
+            flags |= FLAG_PROOF;
+        }
+        if brain.global_confidence.is_some() {
+            flags |= FLAG_GLOBAL_CONFIDENCE;
+        }
+        if brain.best_engine_weights.is_some() {
+            flags |= FLAG_ENGINE_WEIGHTS;
+        }
+        if signature_hex.is_some() {
+            flags |= FLAG_SIGNATURE_HEX;
+        }
+        if key_rotation.is_some() {
+            flags |= FLAG_KEY_ROTATION;
+        }
+
+        let mut out = Vec::with_capacity(256 + brain.confidence.len() * 4);
+        out.extend_from_slice(&WIRE_MAGIC);
+        out.push(WIRE_VERSION);
+        out.push(flags);
+        out.extend_from_slice(&self.timestamp.to_le_bytes());
+        out.extend_from_slice(&self.nonce.to_le_bytes());
+        match &signature_hex {
+            Some(raw) => put_short_bytes(&mut out, raw, "signature")?,
+            None => put_short_bytes(&mut out, self.signature.as_bytes(), "signature")?,
+        }
+        put_short_bytes(&mut out, self.sender_id.as_bytes(), "sender_id")?;
+
+        out.push(brain.version);
+        let predictor_count = u16::try_from(brain.predictors.len())
+            .map_err(|_| WireError::FieldTooLong("predictors"))?;
+        out.extend_from_slice(&predictor_count.to_le_bytes());
+        for name in &brain.predictors {
+            let len = u8::try_from(name.len()).map_err(|_| WireError::FieldTooLong("predictor"))?;
+            out.push(len);
+            out.extend_from_slice(name.as_bytes());
+        }
+        let stats = serde_json::to_vec(&brain.stats).unwrap_or_default();
+        put_bytes(&mut out, &stats, "stats")?;
+        put_f32s(&mut out, &brain.confidence, "confidence")?;
+        if let Some(global) = &brain.global_confidence {
+            put_f32s(&mut out, global, "global_confidence")?;
+        }
+        if let Some(weights) = &brain.best_engine_weights {
+            put_bytes(&mut out, weights, "best_engine_weights")?;
+        }
+
+        if let Some(bundle) = &self.proof_bundle {
+            out.extend_from_slice(&bundle.peer_id);
+            put_f32s(&mut out, &bundle.masked_weights, "masked_weights")?;
+            out.extend_from_slice(&bundle.zk_proof.to_bytes());
+        }
+        if let Some(raw) = key_rotation {
+            out.extend(raw);
+        }
+        Ok(out)
+    }
+
+    /// Whether `data` starts like a [`to_wire_bytes`](Self::to_wire_bytes)
+    /// encoding; a cheap filter ahead of the full decode.
+    pub fn has_wire_header(data: &[u8]) -> bool {
+        data.len() > 3 && data[..2] == WIRE_MAGIC && data[2] == WIRE_VERSION
+    }
+
+    /// Decode an update produced by [`to_wire_bytes`](Self::to_wire_bytes).
+    pub fn from_wire_bytes(data: &[u8]) -> Result<Self, WireError> {
+        let mut r = Reader { data };
+        if r.take(2)? != WIRE_MAGIC || r.u8()? != WIRE_VERSION {
+            return Err(WireError::UnknownFormat);
+        }
+        let flags = r.u8()?;
+        let timestamp = r.u64()?;
+        let nonce = r.u64()?;
+        let signature = r.short_bytes()?;
+        let signature = if flags & FLAG_SIGNATURE_HEX != 0 {
+            hex::encode(signature)
+        } else {
+            utf8(signature, "signature")?
+        };
+        let sender_id = utf8(r.short_bytes()?, "sender_id")?;
+
+        let version = r.u8()?;
+        let predictor_count = r.u16()? as usize;
+        let mut predictors = Vec::with_capacity(predictor_count.min(r.data.len()));
+        for _ in 0..predictor_count {
+            let len = r.u8()? as usize;
+            predictors.push(utf8(r.take(len)?, "predictor")?);
+        }
+        let stats =
+            serde_json::from_slice(r.bytes()?).map_err(|_| WireError::InvalidField("stats"))?;
+        let confidence = r.f32s()?;
+        let global_confidence = if flags & FLAG_GLOBAL_CONFIDENCE != 0 {
+            Some(r.f32s()?)
+        } else {
+            None
+        };
+        let best_engine_weights = if flags & FLAG_ENGINE_WEIGHTS != 0 {
+            Some(r.bytes()?.to_vec())
+        } else {
+            None
+        };
+
+        let proof_bundle = if flags & FLAG_PROOF != 0 {
+            let peer_id = r.take(32)?.try_into().expect("took 32 bytes");
+            let masked_weights = r.f32s()?;
+            let zk_proof = NormProof::from_bytes(r.take(NormProof::WIRE_SIZE)?)
+                .ok_or(WireError::InvalidField("zk_proof"))?;
+            Some(ProofBundle {
+                peer_id,
+                masked_weights,
+                zk_proof,
+            })
+        } else {
+            None
+        };
+        let key_rotation = if flags & FLAG_KEY_ROTATION != 0 {
+            Some(KeyRotation {
+                previous_pubkey: hex::encode(r.take(32)?),
+                current_pubkey: hex::encode(r.take(32)?),
+                signature: hex::encode(r.take(SIGNATURE_SIZE)?),
+            })
+        } else {
+            None
+        };
+        if !r.data.is_empty() {
+            return Err(WireError::TrailingBytes(r.data.len()));
+        }
+
+        Ok(SignedEpiphany::new(
+            LivingBrain {
+                version,
+                predictors,
+                stats,
+                confidence,
+                global_confidence,
+                best_engine_weights,
+            },
+            proof_bundle,
+            signature,
+            sender_id,
+            timestamp,
+            nonce,
+            flags & FLAG_STORM != 0,
+        )
+        .with_key_rotation(key_rotation))
+    }
+}
+
+fn put_short_bytes(out: &mut Vec<u8>, bytes: &[u8], field: &'static str) -> Result<(), WireError> {
+    let len = u16::try_from(bytes.len()).map_err(|_| WireError::FieldTooLong(field))?;
+    out.extend_from_slice(&len.to_le_bytes());
+    out.extend_from_slice(bytes);
+    Ok(())
+}
+
+fn put_bytes(out: &mut Vec<u8>, bytes: &[u8], field: &'static str) -> Result<(), WireError> {
+    let len = u32::try_from(bytes.len()).map_err(|_| WireError::FieldTooLong(field))?;
+    out.extend_from_slice(&len.to_le_bytes());
+    out.extend_from_slice(bytes);
+    Ok(())
+}
+
+fn put_f32s(out: &mut Vec<u8>, values: &[f32], field: &'static str) -> Result<(), WireError> {
+    let len = u32::try_from(values.len()).map_err(|_| WireError::FieldTooLong(field))?;
+    out.extend_from_slice(&len.to_le_bytes());
+    for v in values {
+        out.extend_from_slice(&v.to_le_bytes());
+    }
+    Ok(())
+}
+
+fn utf8(bytes: &[u8], field: &'static str) -> Result<String, WireError> {
+    String::from_utf8(bytes.to_vec()).map_err(|_| WireError::InvalidField(field))
+}
+
+/// Cursor over the remaining input.
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], WireError> {
+        if self.data.len() < n {
+            return Err(WireError::Truncated);
+        }
+        let (head, rest) = self.data.split_at(n);
+        self.data = rest;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, WireError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, WireError> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, WireError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64, WireError> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn short_bytes(&mut self) -> Result<&'a [u8], WireError> {
+        let len = self.u16()? as usize;
+        self.take(len)
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], WireError> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+
+    fn f32s(&mut self) -> Result<Vec<f32>, WireError> {
+        let len = self.u32()? as usize;
+        let raw = self.take(len.checked_mul(4).ok_or(WireError::Truncated)?)?;
+        Ok(raw
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qres_core::zk_proofs::ZkNormProver;
+
+    fn sample_epiphany() -> SignedEpiphany {
+        let mut brain = LivingBrain::new();
+        brain.confidence = (0..64).map(|i| 0.1 + i as f32 * 0.0137).collect();
+        brain.global_confidence = Some(brain.confidence.iter().map(|c| c * 0.9).collect());
+        brain.best_engine_weights = Some(vec![3, 1, 4, 1, 5]);
+
+        let prover = ZkNormProver::new();
+        let (zk_proof, _) = prover.generate_proof(&brain.confidence, 100.0).unwrap();
+        let bundle = ProofBundle {
+            peer_id: [7u8; 32],
+            masked_weights: brain.confidence.clone(),
+            zk_proof,
+        };
+
+        SignedEpiphany::new(
+            brain,
+            Some(bundle),
+            hex::encode([0xa5u8; 64]),
+            "12D3KooWExamplePeer".to_string(),
+            1_700_000_000,
+            42,
+            true,
+        )
+        .with_key_rotation(Some(KeyRotation {
+            previous_pubkey: hex::encode([1u8; 32]),
+            current_pubkey: hex::encode([2u8; 32]),
+            signature: hex::encode([3u8; 64]),
+        }))
+    }
+
+    #[test]
+    fn test_wire_round_trip_is_compact() {
+        let epiphany = sample_epiphany();
+        let wire = epiphany.to_wire_bytes().unwrap();
+        let json = serde_json::to_vec(&epiphany).unwrap();
+        assert!(
+            wire.len() * 2 < json.len(),
+            "wire {} bytes vs json {} bytes",
+            wire.len(),
+            json.len()
+        );
+
+        let decoded = SignedEpiphany::from_wire_bytes(&wire).unwrap();
+        assert_eq!(serde_json::to_vec(&decoded).unwrap(), json);
+        assert_eq!(decoded.to_wire_bytes().unwrap(), wire);
+    }
+
+    #[test]
+    fn test_wire_keeps_non_hex_signature() {
+        let mut epiphany = sample_epiphany();
+        epiphany.signature = "ABCD-not-lowercase".to_string();
+        epiphany.proof_bundle = None;
+        let decoded = SignedEpiphany::from_wire_bytes(&epiphany.to_wire_bytes().unwrap()).unwrap();
+        assert_eq!(decoded.signature, epiphany.signature);
+        assert!(decoded.proof_bundle.is_none());
+    }
+
+    #[test]
+    fn test_wire_rejects_malformed_input() {
+        let wire = sample_epiphany().to_wire_bytes().unwrap();
+        assert_eq!(
+            SignedEpiphany::from_wire_bytes(&wire[..wire.len() - 1]).unwrap_err(),
+            WireError::Truncated
+        );
+        let mut extended = wire.clone();
+        extended.push(0);
+        assert_eq!(
+            SignedEpiphany::from_wire_bytes(&extended).unwrap_err(),
+            WireError::TrailingBytes(1)
+        );
+        assert_eq!(
+            SignedEpiphany::from_wire_bytes(b"{\"brain\":{}}").unwrap_err(),
+            WireError::UnknownFormat
+        );
+    }
+
+    #[test]
+    fn test_wire_refuses_to_truncate_long_fields() {
+        let mut epiphany = sample_epiphany();
+        epiphany.sender_id = "x".repeat(u16::MAX as usize);
+        assert!(epiphany.to_wire_bytes().is_ok());
+        epiphany.sender_id.push('x');
+        assert_eq!(
+            epiphany.to_wire_bytes().unwrap_err(),
+            WireError::FieldTooLong("sender_id")
+        );
+
+        let mut epiphany = sample_epiphany();
+        epiphany.brain.predictors.push("p".repeat(256));
+        assert_eq!(
+            epiphany.to_wire_bytes().unwrap_err(),
+            WireError::FieldTooLong("predictor")
+        );
+    }
+}
@@ -1,11 +1,47 @@
-use qres_core::{compress_chunk, decompress_chunk};
+use qres_core::{compress_chunk, decompress_chunk, read_chunk_header, QresError};
 use wasm_bindgen::prelude::*;
 
+/// Codec mode nibble of a Zstd fallback chunk (matches the daemon).
+const ZSTD_FALLBACK_MODE: u8 = 0x01;
+/// Codec mode nibble of the daemon's LZ4 fallback chunks, which are not decoded here.
+const LZ4_FALLBACK_MODE: u8 = 0x05;
+/// Headers of daemon-written files (`QRHD`, and the legacy transformed `QRTF`).
+const DAEMON_FILE_MAGICS: [&[u8; 4]; 2] = [b"QRHD", b"QRTF"];
+/// Reported as `predictor_id` when no core predictor produced the output.
+const NO_PREDICTOR: u8 = 0xFF;
+/// Streaming chunk size, matching the daemon's file framing.
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
 #[wasm_bindgen]
 pub fn init_hooks() {
     console_error_panic_hook::set_once();
 }
 
+/// Output of `compress_bytes_detailed`, with enough metadata to render
+/// ratio and codec path without re-measuring.
+#[wasm_bindgen(getter_with_clone)]
+pub struct CompressResult {
+    /// Compressed chunk (header + payload)
+    pub output: Vec<u8>,
+    /// Length of the input before compression
+    pub original_len: u32,
+    /// True when the core expanded the data and Zstd was used instead
+    pub used_zstd_fallback: bool,
+    /// Predictor stamped into the chunk header (0xFF for Zstd fallback)
+    pub predictor_id: u8,
+}
+
+#[wasm_bindgen]
+impl CompressResult {
+    /// Compressed size as a fraction of the original (0.0 for empty input).
+    pub fn ratio(&self) -> f64 {
+        if self.original_len == 0 {
+            return 0.0;
+        }
+        self.output.len() as f64 / self.original_len as f64
+    }
+}
+
 #[wasm_bindgen]
 pub fn compress_bytes(data: &[u8]) -> Result<Vec<u8>, JsValue> {
     // Bridges JS Uint8Array <-> Rust Vec<u8>
@@ -20,9 +56,281 @@ pub fn compress_bytes(data: &[u8]) -> Result<Vec<u8>, JsValue> {
     Ok(buffer[..len].to_vec())
 }
 
+#[wasm_bindgen]
+pub fn compress_bytes_detailed(data: &[u8]) -> Result<CompressResult, JsValue> {
+    compress_with_fallback(data)
+        .map_err(|e| JsValue::from_str(&format!("Compression failed: {:?}", e)))
+}
+
+/// Compress one chunk, falling back to Zstd when the core would expand it.
+fn compress_with_fallback(data: &[u8]) -> Result<CompressResult, QresError> {
+    let capacity = data.len() + 4096;
+    let mut buffer = vec![0u8; capacity];
+
+    match compress_chunk(data, 0, None, None, &mut buffer) {
+        Ok(len) => {
+            let header = read_chunk_header(&buffer[..len])?;
+            buffer.truncate(len);
+            Ok(CompressResult {
+                output: buffer,
+                original_len: data.len() as u32,
+                used_zstd_fallback: false,
+                predictor_id: header.predictor_id.unwrap_or(NO_PREDICTOR),
+            })
+        }
+        Err(QresError::CompressionError(_)) => {
+            // Core failed (expansion). Use Zstd fallback, framed like the daemon.
+            let zstd_data = ruzstd::encoding::compress_to_vec(
+                data,
+                ruzstd::encoding::CompressionLevel::Fastest,
+            );
+            let ver = 0x0A;
+            let mut output = Vec::with_capacity(5 + zstd_data.len());
+            output.push((ver << 4) | ZSTD_FALLBACK_MODE);
+            output.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            output.extend_from_slice(&zstd_data);
+            Ok(CompressResult {
+                output,
+                original_len: data.len() as u32,
+                used_zstd_fallback: true,
+                predictor_id: NO_PREDICTOR,
+            })
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Incremental compressor for large inputs.
+///
+/// Input is fed in arbitrary slices and emitted as 64KB chunks using the
+/// daemon's file framing (`[chunk_len:4][chunk]` repeated), so a web worker
+/// can stream a File without holding it (or its output) in memory at once.
+#[wasm_bindgen]
+pub struct WasmStreamCompressor {
+    pending: Vec<u8>,
+}
+
+impl Default for WasmStreamCompressor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+impl WasmStreamCompressor {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::with_capacity(STREAM_CHUNK_SIZE),
+        }
+    }
+
+    /// Buffer `chunk` and return the framed output for every full 64KB chunk.
+    pub fn push(&mut self, chunk: &[u8]) -> Result<Vec<u8>, JsValue> {
+        self.pending.extend_from_slice(chunk);
+
+        let mut out = Vec::new();
+        let full = self.pending.len() / STREAM_CHUNK_SIZE * STREAM_CHUNK_SIZE;
+        for block in self.pending[..full].chunks(STREAM_CHUNK_SIZE) {
+            write_framed(block, &mut out)?;
+        }
+        self.pending.drain(..full);
+        Ok(out)
+    }
+
+    /// Flush the remaining partial chunk (if any).
+    pub fn finish(&mut self) -> Result<Vec<u8>, JsValue> {
+        let mut out = Vec::new();
+        if !self.pending.is_empty() {
+            write_framed(&self.pending, &mut out)?;
+            self.pending.clear();
+        }
+        Ok(out)
+    }
+}
+
+fn write_framed(block: &[u8], out: &mut Vec<u8>) -> Result<(), JsValue> {
+    let compressed = compress_with_fallback(block)
+        .map_err(|e| JsValue::from_str(&format!("Compression failed: {:?}", e)))?
+        .output;
+    out.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+    out.extend_from_slice(&compressed);
+    Ok(())
+}
+
+/// Decompress a framed stream produced by `WasmStreamCompressor`.
+///
+/// Files written by the daemon are a different format: their header binds
+/// them to a brain and a byte transform, and they may hold LZ4 fallback
+/// chunks. Those are rejected with an error; decompress them with `qres-cli`.
+#[wasm_bindgen]
+pub fn decompress_stream(data: &[u8]) -> Result<Vec<u8>, JsValue> {
+    if is_daemon_file(data) {
+        return Err(JsValue::from_str(
+            "Decompression failed: daemon file streams are not supported, decompress them with qres-cli",
+        ));
+    }
+    let mut out = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let len_bytes = data
+            .get(pos..pos + 4)
+            .ok_or_else(|| JsValue::from_str("Decompression failed: truncated frame header"))?;
+        let len = u32::from_le_bytes(len_bytes.try_into().unwrap()) as usize;
+        pos += 4;
+        let chunk = data
+            .get(pos..pos + len)
+            .ok_or_else(|| JsValue::from_str("Decompression failed: truncated chunk"))?;
+        out.extend_from_slice(&decompress_bytes(chunk)?);
+        pos += len;
+    }
+    Ok(out)
+}
+
 #[wasm_bindgen]
 pub fn decompress_bytes(data: &[u8]) -> Result<Vec<u8>, JsValue> {
+    if is_lz4_fallback(data) {
+        return Err(JsValue::from_str(
+            "Decompression failed: LZ4 fallback chunks are not supported, decompress them with qres-cli",
+        ));
+    }
+    // Zstd fallback chunks are handled here, as the daemon does
+    if data.len() >= 5 && data[0] & 0x0F == ZSTD_FALLBACK_MODE {
+        return decompress_zstd_fallback(data)
+            .map_err(|e| JsValue::from_str(&format!("Decompression failed: {}", e)));
+    }
+
     // Using default args: predictor_id=0, weights=None
     decompress_chunk(data, 0, None)
         .map_err(|e| JsValue::from_str(&format!("Decompression failed: {:?}", e)))
 }
+
+/// Whether `data` is a file written by the daemon rather than a framed stream.
+fn is_daemon_file(data: &[u8]) -> bool {
+    DAEMON_FILE_MAGICS
+        .iter()
+        .any(|magic| data.starts_with(*magic))
+}
+
+/// Whether `data` is a chunk stored with the daemon's LZ4 fallback.
+fn is_lz4_fallback(data: &[u8]) -> bool {
+    data.first().map(|b| b & 0x0F) == Some(LZ4_FALLBACK_MODE)
+}
+
+fn decompress_zstd_fallback(data: &[u8]) -> Result<Vec<u8>, String> {
+    use std::io::Read;
+
+    let header = read_chunk_header(data).map_err(|e| e.to_string())?;
+    let decoder = ruzstd::decoding::StreamingDecoder::new(&data[5..]).map_err(|e| e.to_string())?;
+    // The header is untrusted: reserve at most one stream chunk up front and
+    // stop decoding one byte past the claimed length
+    let mut out = Vec::with_capacity(header.decoded_len.min(STREAM_CHUNK_SIZE));
+    decoder
+        .take(header.decoded_len as u64 + 1)
+        .read_to_end(&mut out)
+        .map_err(|e| e.to_string())?;
+    if out.len() != header.decoded_len {
+        return Err(String::from("Zstd fallback length mismatch"));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_matches_one_shot() {
+        // ~2.5 chunks of compressible data, fed in awkward slice sizes
+        let data: Vec<u8> = (0..STREAM_CHUNK_SIZE * 5 / 2)
+            .map(|i| ((i % 251) ^ (i / 1024)) as u8)
+            .collect();
+
+        let mut stream = WasmStreamCompressor::new();
+        let mut framed = Vec::new();
+        for slice in data.chunks(10_007) {
+            framed.extend(stream.push(slice).unwrap());
+        }
+        framed.extend(stream.finish().unwrap());
+        assert!(stream.finish().unwrap().is_empty());
+
+        let one_shot = decompress_bytes(&compress_bytes(&data).unwrap()).unwrap();
+        let streamed = decompress_stream(&framed).unwrap();
+        assert_eq!(streamed, one_shot);
+        assert_eq!(streamed, data);
+    }
+
+    #[test]
+    fn test_zstd_fallback_rejects_malformed_length() {
+        let data: Vec<u8> = (0..4096u32).map(|i| ((i * 7919) >> 3) as u8).collect();
+        let mut chunk = vec![(0x0A << 4) | ZSTD_FALLBACK_MODE];
+        chunk.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        chunk.extend_from_slice(&ruzstd::encoding::compress_to_vec(
+            data.as_slice(),
+            ruzstd::encoding::CompressionLevel::Fastest,
+        ));
+        assert_eq!(decompress_zstd_fallback(&chunk).unwrap(), data);
+
+        // A header claiming 4GB must not be trusted for the allocation
+        chunk[1..5].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(decompress_zstd_fallback(&chunk).is_err());
+        // Nor may a short claim let the payload decode past it
+        chunk[1..5].copy_from_slice(&16u32.to_le_bytes());
+        assert!(decompress_zstd_fallback(&chunk).is_err());
+    }
+
+    #[test]
+    fn test_compress_bytes_detailed_reports_codec_path() {
+        // xorshift noise: the core codec would expand it
+        let mut x = 0x9E37_79B9u32;
+        let noise: Vec<u8> = (0..8192)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 17;
+                x ^= x << 5;
+                x as u8
+            })
+            .collect();
+        let result = compress_bytes_detailed(&noise).unwrap();
+        assert!(result.used_zstd_fallback);
+        assert_eq!(result.predictor_id, NO_PREDICTOR);
+        assert_eq!(result.original_len, noise.len() as u32);
+        assert_eq!(result.output[0] & 0x0F, ZSTD_FALLBACK_MODE);
+        assert!((result.ratio() - result.output.len() as f64 / noise.len() as f64).abs() < 1e-12);
+        assert_eq!(decompress_bytes(&result.output).unwrap(), noise);
+
+        let text = b"the quick brown fox jumps over the lazy dog ".repeat(200);
+        let result = compress_bytes_detailed(&text).unwrap();
+        assert!(!result.used_zstd_fallback);
+        assert_ne!(result.predictor_id, NO_PREDICTOR);
+        assert_eq!(result.original_len, text.len() as u32);
+        assert!(result.ratio() < 1.0);
+        assert_eq!(decompress_bytes(&result.output).unwrap(), text);
+    }
+
+    #[test]
+    fn test_daemon_only_formats_are_recognized() {
+        let mut file = b"QRHD".to_vec();
+        file.push(2);
+        file.extend_from_slice(&[0; 32 + 1 + 4]);
+        assert!(is_daemon_file(&file));
+        assert!(is_daemon_file(b"QRTF\x00"));
+        assert!(is_lz4_fallback(&[
+            (0x0A << 4) | LZ4_FALLBACK_MODE,
+            0,
+            0,
+            0,
+            0
+        ]));
+
+        // Streams and chunks of our own never match
+        let data = vec![7u8; 1024];
+        let mut stream = WasmStreamCompressor::new();
+        let mut framed = stream.push(&data).unwrap();
+        framed.extend(stream.finish().unwrap());
+        assert!(!is_daemon_file(&framed));
+        let chunk = compress_bytes(&data).unwrap();
+        assert!(!is_daemon_file(&chunk));
+        assert!(!is_lz4_fallback(&chunk));
+    }
+}
@@ -4,15 +4,18 @@
 use bevy::core_pipeline::bloom::BloomSettings;
 use bevy::core_pipeline::tonemapping::Tonemapping;
 use bevy::prelude::*;
-use qres_core::cortex::{GeneStorage, LinearNeuron, Regime};
+use qres_core::cortex::{
+    prefers_gene, DiskGeneStorage, GeneStorage, LinearNeuron, ModelPersistence, PersistenceError,
+    Regime, RetryPolicy,
+};
 use rand::Rng;
 use std::collections::HashMap;
-use std::fs;
 
 // --- CONFIGURATION ---
 const MTU_LIMIT: usize = 1400;
 const BASE_DROP_RATE: f64 = 0.02;
 const GENE_SIZE_BYTES: usize = 1600; // Large gene triggers MTU fragmentation!
+const SIM_GENE_FITNESS: f32 = 0.0; // Cures carry no fitness: every gene ties
 const NODE_COUNT: u32 = 150; // Total nodes in swarm (denser brain)
 
 // --- BRAIN STRUCTURE CONFIGURATION ---
@@ -52,6 +55,7 @@ fn main() {
         })
         .insert_resource(SwarmMetrics::default())
         .insert_resource(SynapseRegistry::default())
+        .insert_resource(PendingGeneSaves::default())
         .insert_resource(CameraController {
             yaw: 0.0,
             pitch: 0.3,
@@ -131,52 +135,19 @@ struct SynapseRegistry {
     connections: HashMap<(u32, u32), f32>,
 }
 
-/// Disk-based gene storage for persistent evolution
-struct DiskGeneStorage {
-    storage_dir: String,
+/// Gene saves that failed and are waiting out their backoff. Retries run on
+/// later frames so a flaky disk never stalls the render loop.
+#[derive(Resource, Default)]
+struct PendingGeneSaves {
+    retries: Vec<GeneRetry>,
 }
 
-impl DiskGeneStorage {
-    fn new(dir: &str) -> Self {
-        // Create directory if it doesn't exist
-        if let Err(e) = fs::create_dir_all(dir) {
-            eprintln!("⚠️ WARNING: Failed to initialize persistence layer: {}", e);
-        }
-        DiskGeneStorage {
-            storage_dir: dir.to_string(),
-        }
-    }
-
-    fn gene_path(&self, id: u32) -> String {
-        format!("{}/gene_{}.bin", self.storage_dir, id)
-    }
-}
-
-impl GeneStorage for DiskGeneStorage {
-    fn save_gene(&mut self, id: u32, gene: &[u8]) -> bool {
-        let path = self.gene_path(id);
-        match fs::write(&path, gene) {
-            Ok(_) => {
-                println!("💾 Gene saved for node {}: {}", id, path);
-                true
-            }
-            Err(e) => {
-                eprintln!("Failed to save gene {}: {}", id, e);
-                false
-            }
-        }
-    }
-
-    fn load_gene(&self, id: u32) -> Option<Vec<u8>> {
-        let path = self.gene_path(id);
-        match fs::read(&path) {
-            Ok(gene) => {
-                println!("📖 Gene loaded for node {}: {} bytes", id, gene.len());
-                Some(gene)
-            }
-            Err(_) => None, // File doesn't exist yet
-        }
-    }
+struct GeneRetry {
+    node_id: u32,
+    gene: Vec<u8>,
+    attempts: u32,
+    wait_secs: f32,
+    next_backoff_ms: u64,
 }
 
 // --- COMPONENTS ---
@@ -331,6 +302,7 @@ fn setup_swarm(
 
         // Check if this node has a saved gene from a previous session
         let neuron_type = if let Some(gene) = storage.load_gene(id) {
+            println!("📖 Gene loaded for node {}: {} bytes", id, gene.len());
             NeuronType::Evolved(gene)
         } else {
             NeuronType::Linear(LinearNeuron::new(32))
@@ -537,8 +509,17 @@ fn process_incoming_packets(
                         }
                     }
                     PacketType::GenePayload(gene) => {
-                        // INSTALL THE CURE
-                        cortex.neuron_type = NeuronType::Evolved(gene.clone());
+                        // INSTALL THE CURE (ties go to the lowest gene hash, so
+                        // arrival order doesn't decide which cure sticks)
+                        let installed = match &cortex.neuron_type {
+                            NeuronType::Evolved(current) => {
+                                Some((current.as_slice(), SIM_GENE_FITNESS))
+                            }
+                            NeuronType::Linear(_) => None,
+                        };
+                        if prefers_gene((gene, SIM_GENE_FITNESS), installed) {
+                            cortex.neuron_type = NeuronType::Evolved(gene.clone());
+                        }
                     }
                     _ => {}
                 }
@@ -549,8 +530,49 @@ fn process_incoming_packets(
 }
 
 /// 7. Persistence: Save evolved genes to disk (The Hippocampus)
-fn persist_evolved_genes(time: Res<Time>, mut query: Query<(&IoTNode, &mut Cortex)>) {
+fn persist_evolved_genes(
+    time: Res<Time>,
+    mut pending: ResMut<PendingGeneSaves>,
+    mut query: Query<(&IoTNode, &mut Cortex)>,
+) {
     let mut storage = DiskGeneStorage::new("./swarms_memory");
+    let policy = RetryPolicy::default();
+
+    // Retry transient disk errors on later frames so the evolved gene isn't lost
+    pending.retries.retain_mut(|retry| {
+        retry.wait_secs -= time.delta_seconds();
+        if retry.wait_secs > 0.0 {
+            return true;
+        }
+        retry.attempts += 1;
+        match storage.try_save_gene(retry.node_id, &retry.gene) {
+            Ok(()) => {
+                println!(
+                    "💾 Gene saved for node {} after {} attempts: {}",
+                    retry.node_id,
+                    retry.attempts,
+                    storage.gene_path(retry.node_id).display()
+                );
+                false
+            }
+            Err(_) if retry.attempts >= policy.max_attempts => {
+                let err = PersistenceError::RetriesExhausted {
+                    id: retry.node_id,
+                    attempts: retry.attempts,
+                };
+                eprintln!("⚠️ {}", err);
+                false
+            }
+            Err(_) => {
+                retry.wait_secs = retry.next_backoff_ms as f32 / 1000.0;
+                retry.next_backoff_ms = retry
+                    .next_backoff_ms
+                    .saturating_mul(2)
+                    .min(policy.max_backoff_ms);
+                true
+            }
+        }
+    });
 
     for (node, mut cortex) in query.iter_mut() {
         cortex.persistence_timer += time.delta_seconds();
@@ -561,7 +583,28 @@ fn persist_evolved_genes(time: Res<Time>, mut query: Query<(&IoTNode, &mut Corte
 
             if cortex.regime == Regime::Calm {
                 if let NeuronType::Evolved(ref gene) = cortex.neuron_type {
-                    let _ = storage.save_gene(node.id, gene);
+                    match storage.try_save_gene(node.id, gene) {
+                        Ok(()) => println!(
+                            "💾 Gene saved for node {}: {}",
+                            node.id,
+                            storage.gene_path(node.id).display()
+                        ),
+                        Err(_) if policy.max_attempts > 1 => {
+                            // A newer gene supersedes one still waiting to retry
+                            pending.retries.retain(|retry| retry.node_id != node.id);
+                            pending.retries.push(GeneRetry {
+                                node_id: node.id,
+                                gene: gene.clone(),
+                                attempts: 1,
+                                wait_secs: policy.initial_backoff_ms as f32 / 1000.0,
+                                next_backoff_ms: policy
+                                    .initial_backoff_ms
+                                    .saturating_mul(2)
+                                    .min(policy.max_backoff_ms),
+                            });
+                        }
+                        Err(e) => eprintln!("⚠️ {}", e),
+                    }
                 }
             }
         }
@@ -1009,17 +1052,9 @@ fn reset_simulation(
         println!("🔄 RESET: Clearing all evolved genes and restarting simulation...");
 
         // Clear saved gene files
-        if let Ok(entries) = fs::read_dir("./swarms_memory") {
-            for entry in entries.flatten() {
-                if entry
-                    .path()
-                    .extension()
-                    .map(|e| e == "bin")
-                    .unwrap_or(false)
-                {
-                    let _ = fs::remove_file(entry.path());
-                }
-            }
+        let mut storage = DiskGeneStorage::new("./swarms_memory");
+        for id in storage.list_models() {
+            storage.delete_model(id);
         }
 
         // Reset all nodes to Linear (unevolved) state